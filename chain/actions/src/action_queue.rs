@@ -288,20 +288,18 @@ where
         let confirmation = self.action_state.register_expectation(expectation).await?.fuse();
         let timeout = futures_timer::Delay::new(self.cfg.max_action_confirmation_wait).fuse();
 
-        pin_mut!(confirmation, timeout);
-
+        // The TX hash can be shared with other batched actions, so the expectation is not
+        // unregistered by its TX hash here. Only the expectations with dropped resolvers are discarded.
         match futures::future::select(confirmation, timeout).await {
             Either::Left((Ok(chain_event), _)) => Ok(ActionConfirmation {
                 tx_hash: chain_event.tx_hash,
                 event: Some(chain_event.event_type),
                 action,
             }),
-            Either::Left((Err(_), _)) => {
-                self.action_state.unregister_expectation(tx_hash).await;
-                Err(InvalidState("action expectation was removed before resolving".into()))
-            }
-            Either::Right(_) => {
-                self.action_state.unregister_expectation(tx_hash).await;
+            Either::Left((Err(_), _)) => Err(InvalidState("action expectation was removed before resolving".into())),
+            Either::Right((_, confirmation)) => {
+                drop(confirmation);
+                self.action_state.discard_canceled(tx_hash).await;
                 Err(Timeout)
             }
         }
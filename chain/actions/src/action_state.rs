@@ -47,8 +47,16 @@ pub trait ActionState {
     /// Registers new [IndexerExpectation].
     async fn register_expectation(&self, exp: IndexerExpectation) -> Result<ExpectationResolver>;
 
-    /// Manually unregisters `IndexerExpectation` given its TX hash.
+    /// Manually unregisters all `IndexerExpectation`s given their TX hash.
     async fn unregister_expectation(&self, tx_hash: Hash);
+
+    /// Unregisters the `IndexerExpectation`s of the given TX hash whose resolvers have been dropped.
+    async fn discard_canceled(&self, tx_hash: Hash);
+
+    /// Moves all `IndexerExpectation`s registered on the `old_tx_hash` to the `new_tx_hash`.
+    ///
+    /// This is used when a transaction has been replaced by another transaction (e.g. with bumped fees).
+    async fn replace_tx_hash(&self, old_tx_hash: Hash, new_tx_hash: Hash);
 }
 
 /// Expectation on a chain event within a TX indexed by the Indexer.
@@ -84,10 +92,14 @@ impl IndexerExpectation {
     }
 }
 
-type ExpectationTable = HashMap<Hash, (IndexerExpectation, channel::oneshot::Sender<SignificantChainEvent>)>;
+type ExpectationTable = HashMap<Hash, Vec<(IndexerExpectation, channel::oneshot::Sender<SignificantChainEvent>)>>;
 
 /// Implements [action state](ActionState) tracking using a non-persistent in-memory hash table of
 /// assumed [IndexerExpectations](IndexerExpectation).
+///
+/// Multiple expectations can be registered for the same TX hash (e.g. when several actions
+/// were batched into a single transaction). Each event resolves at most one of them, in the order
+/// of their registration. Expectations whose resolvers have been dropped are discarded.
 #[derive(Debug, Clone)]
 pub struct IndexerActionTracker {
     expectations: Arc<RwLock<ExpectationTable>>,
@@ -105,62 +117,88 @@ impl Default for IndexerActionTracker {
 impl ActionState for IndexerActionTracker {
     #[tracing::instrument(level = "debug", skip(self))]
     async fn match_and_resolve(&self, event: &SignificantChainEvent) -> Vec<IndexerExpectation> {
-        let matched_keys = self
+        let has_match = self
             .expectations
             .read()
             .await
-            .iter()
-            .filter_map(|(k, (e, _))| e.test(event).then_some(*k))
-            .collect::<Vec<_>>();
+            .get(&event.tx_hash)
+            .is_some_and(|exps| exps.iter().any(|(e, _)| e.test(event)));
 
-        debug!(count = matched_keys.len(), ?event, "found expectations to match event",);
+        debug!(has_match, ?event, "looked up expectations to match event");
 
-        if matched_keys.is_empty() {
+        if !has_match {
             return Vec::new();
         }
+
         let mut db = self.expectations.write().await;
-        matched_keys
-            .into_iter()
-            .filter_map(|key| {
-                db.remove(&key)
-                    .and_then(|(exp, sender)| match sender.send(event.clone()) {
-                        Ok(_) => {
-                            debug!(%event, "expectation resolved ");
-                            Some(exp)
-                        }
-                        Err(_) => {
-                            error!(
-                                %event, "failed to resolve actions, because the action confirmation already timed out",
-                            );
-                            None
-                        }
-                    })
-            })
-            .collect()
+        let Entry::Occupied(mut entry) = db.entry(event.tx_hash) else {
+            return Vec::new();
+        };
+
+        // Discard expectations which are no longer awaited
+        entry.get_mut().retain(|(_, sender)| !sender.is_canceled());
+
+        let resolved = entry.get().iter().position(|(e, _)| e.test(event)).and_then(|idx| {
+            let (exp, sender) = entry.get_mut().remove(idx);
+            match sender.send(event.clone()) {
+                Ok(_) => {
+                    debug!(%event, "expectation resolved ");
+                    Some(exp)
+                }
+                Err(_) => {
+                    error!(
+                        %event, "failed to resolve actions, because the action confirmation already timed out",
+                    );
+                    None
+                }
+            }
+        });
+
+        if entry.get().is_empty() {
+            entry.remove();
+        }
+
+        resolved.into_iter().collect()
     }
 
     #[tracing::instrument(level = "debug", skip(self))]
     async fn register_expectation(&self, exp: IndexerExpectation) -> Result<ExpectationResolver> {
-        match self.expectations.write().await.entry(exp.tx_hash) {
-            Entry::Occupied(_) => {
-                // TODO: currently cannot register multiple expectations for the same TX hash
-                return Err(ChainActionsError::InvalidState(format!(
-                    "expectation for tx {} already present",
-                    exp.tx_hash
-                )));
-            }
-            Entry::Vacant(e) => {
-                let (tx, rx) = channel::oneshot::channel();
-                e.insert((exp, tx));
-                Ok(rx.map_err(|_| ChainActionsError::ExpectationUnregistered).boxed())
-            }
-        }
+        let (tx, rx) = channel::oneshot::channel();
+
+        let mut db = self.expectations.write().await;
+        let exps = db.entry(exp.tx_hash).or_default();
+        exps.retain(|(_, sender)| !sender.is_canceled());
+        exps.push((exp, tx));
+
+        Ok(rx.map_err(|_| ChainActionsError::ExpectationUnregistered).boxed())
     }
 
     #[tracing::instrument(level = "debug", skip(self))]
     async fn unregister_expectation(&self, tx_hash: Hash) {
         self.expectations.write().await.remove(&tx_hash);
     }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn discard_canceled(&self, tx_hash: Hash) {
+        let mut db = self.expectations.write().await;
+        if let Entry::Occupied(mut entry) = db.entry(tx_hash) {
+            entry.get_mut().retain(|(_, sender)| !sender.is_canceled());
+            if entry.get().is_empty() {
+                entry.remove();
+            }
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn replace_tx_hash(&self, old_tx_hash: Hash, new_tx_hash: Hash) {
+        let mut db = self.expectations.write().await;
+        if let Some(mut exps) = db.remove(&old_tx_hash) {
+            exps.retain(|(_, sender)| !sender.is_canceled());
+            exps.iter_mut().for_each(|(exp, _)| exp.tx_hash = new_tx_hash);
+            debug!(count = exps.len(), "moved expectations to the replacement transaction");
+            db.entry(new_tx_hash).or_default().extend(exps);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -355,4 +393,125 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_expectations_with_the_same_tx_hash_should_resolve_independently() -> anyhow::Result<()> {
+        let tx_hash = Hash::from(random_bytes::<{ Hash::SIZE }>());
+        let sample_events = vec![
+            SignificantChainEvent {
+                tx_hash,
+                event_type: ChainEventType::NetworkRegistryUpdate(*RANDY, NetworkRegistryStatus::Allowed),
+            },
+            SignificantChainEvent {
+                tx_hash,
+                event_type: ChainEventType::NodeSafeRegistered(*RANDY),
+            },
+        ];
+
+        let exp = Arc::new(IndexerActionTracker::default());
+
+        let safe_registered = exp
+            .register_expectation(IndexerExpectation::new(tx_hash, move |e| {
+                matches!(e, ChainEventType::NodeSafeRegistered(_))
+            }))
+            .await
+            .context("should register 1")?;
+
+        // The resolver of this expectation is dropped, so it must never consume any event
+        drop(
+            exp.register_expectation(IndexerExpectation::new(tx_hash, move |e| {
+                matches!(e, ChainEventType::NetworkRegistryUpdate(..))
+            }))
+            .await
+            .context("should register 2")?,
+        );
+
+        let registry_updated = exp
+            .register_expectation(IndexerExpectation::new(tx_hash, move |e| {
+                matches!(e, ChainEventType::NetworkRegistryUpdate(..))
+            }))
+            .await
+            .context("should register 3")?;
+
+        for sample_event in &sample_events {
+            assert_eq!(
+                1,
+                exp.match_and_resolve(sample_event).await.len(),
+                "each event must resolve exactly one expectation"
+            );
+        }
+
+        let resolutions = timeout(
+            Duration::from_secs(5),
+            futures::future::try_join(safe_registered, registry_updated),
+        )
+        .await?
+        .context("no resolver can cancel")?;
+
+        assert_eq!(sample_events[1], resolutions.0, "resolving event 1 must be equal");
+        assert_eq!(sample_events[0], resolutions.1, "resolving event 2 must be equal");
+        assert!(
+            exp.match_and_resolve(&sample_events[0]).await.is_empty(),
+            "all expectations must be unregistered once resolved"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_expectation_should_resolve_on_replaced_tx_hash() -> anyhow::Result<()> {
+        let old_tx_hash = Hash::from(random_bytes::<{ Hash::SIZE }>());
+        let new_tx_hash = Hash::from(random_bytes::<{ Hash::SIZE }>());
+        let sample_event = SignificantChainEvent {
+            tx_hash: new_tx_hash,
+            event_type: ChainEventType::NodeSafeRegistered(*RANDY),
+        };
+
+        let exp = Arc::new(IndexerActionTracker::default());
+
+        let resolver = exp
+            .register_expectation(IndexerExpectation::new(old_tx_hash, move |e| {
+                matches!(e, ChainEventType::NodeSafeRegistered(_))
+            }))
+            .await?;
+
+        exp.replace_tx_hash(old_tx_hash, new_tx_hash).await;
+
+        let resolved = exp.match_and_resolve(&sample_event).await;
+        assert_eq!(1, resolved.len(), "expectation must be matched on the new tx hash");
+        assert_eq!(
+            new_tx_hash, resolved[0].tx_hash,
+            "expectation must have the new tx hash"
+        );
+
+        let resolution = timeout(Duration::from_secs(5), resolver)
+            .await?
+            .context("resolver must not be cancelled")?;
+        assert_eq!(sample_event, resolution, "resolving event must be equal");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_canceled_expectations_should_be_discarded() -> anyhow::Result<()> {
+        let tx_hash = Hash::from(random_bytes::<{ Hash::SIZE }>());
+        let exp = IndexerActionTracker::default();
+
+        let canceled = exp
+            .register_expectation(IndexerExpectation::new(tx_hash, |_| true))
+            .await?;
+        let awaited = exp
+            .register_expectation(IndexerExpectation::new(tx_hash, |_| true))
+            .await?;
+
+        drop(canceled);
+        exp.discard_canceled(tx_hash).await;
+        assert_eq!(1, exp.expectations.read().await.get(&tx_hash).map_or(0, |e| e.len()));
+
+        drop(awaited);
+        exp.discard_canceled(tx_hash).await;
+        assert!(exp.expectations.read().await.get(&tx_hash).is_none());
+
+        Ok(())
+    }
 }
@@ -9,6 +9,9 @@
 //!   used by a HOPR node.
 //! - [SafePayloadGenerator] which implements generation of a payload that embeds the transaction data into the SAFE
 //!   transaction. This is currently the main mode of HOPR node operation.
+//!
+//! Payloads generated by the [SafePayloadGenerator] can also be batched into a single transaction
+//! using [PayloadGenerator::multisend], which executes them via the Safe `MultiSend` library.
 
 use alloy::{
     network::TransactionBuilder,
@@ -36,7 +39,11 @@ use hopr_bindings::{
     hoprnodesaferegistry::HoprNodeSafeRegistry::{deregisterNodeBySafeCall, registerSafeByNodeCall},
     hoprtoken::HoprToken::{approveCall, transferCall},
 };
-use hopr_chain_types::ContractAddresses;
+use hopr_chain_types::{
+    ContractAddresses,
+    constants::SAFE_MULTISEND_ADDRESS,
+    multisend::{MultisendTransaction, SafeTxOperation, multiSendCall},
+};
 use hopr_crypto_types::prelude::*;
 use hopr_internal_types::prelude::*;
 use hopr_primitive_types::prelude::*;
@@ -50,7 +57,7 @@ use crate::errors::{
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 enum Operation {
     Call = 0,
-    // Future use: DelegateCall = 1,
+    DelegateCall = 1,
 }
 
 /// Trait for various implementations of generators of common on-chain transaction payloads.
@@ -91,6 +98,12 @@ pub trait PayloadGenerator<T: Into<TransactionRequest>> {
     /// Creates a transaction payload to remove the Safe instance. Once succeeded,
     /// the funds are no longer managed by the node.
     fn deregister_node_by_safe(&self) -> Result<T>;
+
+    /// Batches the given transaction payloads, previously created by this generator,
+    /// into a single transaction payload that executes all of them in the given order.
+    ///
+    /// If any of the batched calls reverts, the whole batch reverts.
+    fn multisend(&self, txs: Vec<T>) -> Result<T>;
 }
 
 fn channels_payload(hopr_channels: Address, call_data: Vec<u8>) -> Vec<u8> {
@@ -286,6 +299,10 @@ impl PayloadGenerator<TransactionRequest> for BasicPayloadGenerator {
             "Can only deregister an address if Safe is activated".into(),
         ))
     }
+
+    fn multisend(&self, _txs: Vec<TransactionRequest>) -> Result<TransactionRequest> {
+        Err(InvalidState("Can only batch transactions if Safe is activated".into()))
+    }
 }
 
 /// Payload generator that generates Safe-compliant ABI
@@ -294,6 +311,7 @@ pub struct SafePayloadGenerator {
     me: Address,
     contract_addrs: ContractAddresses,
     module: Address,
+    multisend: Address,
 }
 
 pub const DEFAULT_TX_GAS: u64 = 400_000;
@@ -304,8 +322,17 @@ impl SafePayloadGenerator {
             me: chain_keypair.into(),
             contract_addrs,
             module,
+            multisend: SAFE_MULTISEND_ADDRESS.into(),
         }
     }
+
+    /// Overrides the address of the Safe `MultiSend` library used to batch transactions.
+    ///
+    /// The library must be the one configured in the node management module.
+    pub fn with_multisend(mut self, multisend: Address) -> Self {
+        self.multisend = multisend;
+        self
+    }
 }
 
 impl PayloadGenerator<TransactionRequest> for SafePayloadGenerator {
@@ -502,6 +529,61 @@ impl PayloadGenerator<TransactionRequest> for SafePayloadGenerator {
 
         Ok(tx)
     }
+
+    fn multisend(&self, txs: Vec<TransactionRequest>) -> Result<TransactionRequest> {
+        if txs.is_empty() {
+            return Err(InvalidArguments("Cannot batch an empty list of transactions".into()));
+        }
+
+        let mut gas_limit = 0_u64;
+        let mut batch = Vec::with_capacity(txs.len());
+        for tx in txs {
+            // Only calls executed through the node management module can be batched
+            if tx.to.and_then(|to| to.to().copied()) != Some(self.module.into()) {
+                return Err(InvalidArguments(
+                    "Can only batch transactions sent via the module".into(),
+                ));
+            }
+
+            let call = tx
+                .input
+                .input()
+                .and_then(|data| execTransactionFromModuleCall::abi_decode(data).ok())
+                .ok_or_else(|| InvalidArguments("Can only batch transactions executed from the module".into()))?;
+
+            if call.operation != Operation::Call as u8 {
+                return Err(InvalidArguments("Cannot batch delegate calls".into()));
+            }
+
+            gas_limit = gas_limit.saturating_add(tx.gas.unwrap_or(DEFAULT_TX_GAS));
+            batch.push(MultisendTransaction {
+                encoded_data: call.data,
+                tx_operation: SafeTxOperation::Call,
+                to: call.to,
+                value: call.value,
+            });
+        }
+
+        let call_data = multiSendCall {
+            transactions: MultisendTransaction::build_multisend_tx(batch).into(),
+        }
+        .abi_encode();
+
+        let tx = TransactionRequest::default()
+            .with_input(
+                execTransactionFromModuleCall {
+                    to: self.multisend.into(),
+                    value: U256::ZERO,
+                    data: call_data.into(),
+                    operation: Operation::DelegateCall as u8,
+                }
+                .abi_encode(),
+            )
+            .with_to(self.module.into())
+            .with_gas_limit(gas_limit);
+
+        Ok(tx)
+    }
 }
 
 /// Converts off-chain representation of VRF parameters into a representation
@@ -585,7 +667,7 @@ mod tests {
     use hopr_primitive_types::prelude::HoprBalance;
     use multiaddr::Multiaddr;
 
-    use super::{BasicPayloadGenerator, PayloadGenerator};
+    use super::{BasicPayloadGenerator, PayloadGenerator, SafePayloadGenerator};
 
     const PRIVATE_KEY: [u8; 32] = hex!("c14b8faa0a9b8a5fa4453664996f23a7e7de606d42297d723fc4a794f375e260");
     const RESPONSE_TO_CHALLENGE: [u8; 32] = hex!("b58f99c83ae0e7dd6a69f755305b38c7610c7687d2931ff3f70103f8f92b90bb");
@@ -739,4 +821,69 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn safe_payload_generator_should_batch_module_transactions_into_multisend() -> anyhow::Result<()> {
+        use alloy::sol_types::SolCall;
+        use hopr_bindings::hoprnodemanagementmodule::HoprNodeManagementModule::execTransactionFromModuleCall;
+        use hopr_chain_types::{
+            ContractAddresses,
+            multisend::{MultisendTransaction, SafeTxOperation, multiSendCall},
+        };
+        use hopr_primitive_types::prelude::Address;
+
+        let me = ChainKeypair::random();
+        let module = Address::new(&hex!("1111111111111111111111111111111111111111"));
+        let multisend = Address::new(&hex!("2222222222222222222222222222222222222222"));
+        let contract_addrs = ContractAddresses {
+            channels: Address::new(&hex!("3333333333333333333333333333333333333333")),
+            ..Default::default()
+        };
+
+        let generator = SafePayloadGenerator::new(&me, contract_addrs, module).with_multisend(multisend);
+
+        let txs = vec![
+            generator.initiate_outgoing_channel_closure(ChainKeypair::random().public().to_address())?,
+            generator.fund_channel(ChainKeypair::random().public().to_address(), HoprBalance::from(10))?,
+        ];
+
+        let expected_inner = txs
+            .iter()
+            .map(|tx| {
+                let call = execTransactionFromModuleCall::abi_decode(tx.input.input().unwrap())?;
+                Ok(MultisendTransaction {
+                    encoded_data: call.data,
+                    tx_operation: SafeTxOperation::Call,
+                    to: call.to,
+                    value: call.value,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let batch = generator.multisend(txs)?;
+        assert_eq!(batch.to, Some(alloy::primitives::TxKind::Call(module.into())));
+        assert_eq!(batch.gas, Some(2 * super::DEFAULT_TX_GAS));
+
+        let outer = execTransactionFromModuleCall::abi_decode(batch.input.input().unwrap())?;
+        assert_eq!(outer.to, alloy::primitives::Address::from(multisend));
+        assert_eq!(outer.operation, 1, "multisend must be delegate-called");
+
+        let inner = multiSendCall::abi_decode(&outer.data)?;
+        assert_eq!(
+            inner.transactions.to_vec(),
+            MultisendTransaction::build_multisend_tx(expected_inner)
+        );
+
+        // Payloads not executed through the module cannot be batched
+        assert!(
+            generator
+                .multisend(vec![
+                    generator.register_safe_by_node(ChainKeypair::random().public().to_address())?
+                ])
+                .is_err()
+        );
+        assert!(generator.multisend(vec![]).is_err());
+
+        Ok(())
+    }
 }
@@ -1,5 +1,6 @@
 use std::str::FromStr;
 
+use hopr_chain_rpc::{pool::ProviderPoolConfig, tx_manager::TransactionManagerConfig};
 use hopr_chain_types::ContractAddresses;
 use hopr_primitive_types::primitives::Address;
 use semver::{Version, VersionReq};
@@ -7,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use serde_with::{DisplayFromStr, serde_as};
 use validator::Validate;
 
-use crate::errors::HoprChainError;
+use crate::{errors::HoprChainError, executors::TransactionBatchingConfig};
 
 /// Types of HOPR network environments.
 #[derive(Debug, Copy, Clone, Deserialize, Serialize, Eq, PartialEq, strum::Display, strum::EnumString)]
@@ -156,6 +157,18 @@ pub struct ChainNetworkConfig {
     pub max_block_range: u64,
     /// maximum number of RPC requests per second
     pub max_requests_per_sec: Option<u32>,
+    /// additional RPC endpoints to fail over to from the chain's default provider
+    #[serde(default)]
+    pub fallback_providers: Vec<String>,
+    /// configuration of the RPC provider pool, used only if there are any fallback providers
+    #[serde(default)]
+    pub provider_pool: ProviderPoolConfig,
+    /// configuration of the batching of on-chain transactions
+    #[serde(default)]
+    pub tx_batching: TransactionBatchingConfig,
+    /// configuration of the tracking of the sent on-chain transactions
+    #[serde(default)]
+    pub tx_manager: TransactionManagerConfig,
 }
 
 /// Check whether the version is allowed
@@ -172,11 +185,16 @@ fn satisfies(version: &str, allowed_versions: &str) -> crate::errors::Result<boo
 
 impl ChainNetworkConfig {
     /// Returns the network details, returns an error if network is not supported
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: &str,
         version: &str,
         maybe_custom_provider: Option<&str>,
         max_rpc_requests_per_sec: Option<u32>,
+        fallback_providers: &[String],
+        provider_pool: ProviderPoolConfig,
+        tx_batching: TransactionBatchingConfig,
+        tx_manager: TransactionManagerConfig,
         protocol_config: &mut ProtocolsConfig,
     ) -> Result<Self, String> {
         let network = protocol_config
@@ -213,6 +231,10 @@ impl ChainNetworkConfig {
                 tx_polling_interval: network.tx_polling_interval,
                 max_block_range: network.max_block_range,
                 max_requests_per_sec: max_rpc_requests_per_sec.or(chain.max_rpc_requests_per_sec),
+                fallback_providers: fallback_providers.to_vec(),
+                provider_pool,
+                tx_batching,
+                tx_manager,
             }),
             Ok(false) => Err(format!(
                 "network {id} is not supported, supported networks {:?}",
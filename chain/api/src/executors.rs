@@ -1,15 +1,22 @@
-use std::{marker::PhantomData, time::Duration};
+use std::{collections::HashSet, marker::PhantomData, time::Duration};
 
-use alloy::{providers::PendingTransaction, rpc::types::TransactionRequest};
+use alloy::rpc::types::TransactionRequest;
+use async_channel::{Receiver, Sender};
 use async_trait::async_trait;
-use futures::{FutureExt, future::Either, pin_mut};
-use hopr_async_runtime::prelude::sleep;
-use hopr_chain_actions::{action_queue::TransactionExecutor, payload::PayloadGenerator};
-use hopr_chain_rpc::{HoprRpcOperations, errors::RpcError};
+use futures::{FutureExt, channel::oneshot, future::Either, pin_mut};
+use hopr_async_runtime::prelude::{sleep, spawn};
+use hopr_chain_actions::{
+    action_queue::TransactionExecutor, errors::ChainActionsError::TransactionSubmissionFailed,
+    payload::PayloadGenerator,
+};
+use hopr_chain_rpc::{HoprRpcOperations, PendingTransaction, errors::RpcError};
 use hopr_crypto_types::types::Hash;
 use hopr_internal_types::prelude::*;
 use hopr_primitive_types::prelude::*;
 use serde::{Deserialize, Serialize};
+use serde_with::{DurationMilliSeconds, serde_as};
+use tracing::{debug, error, warn};
+use validator::Validate;
 
 /// Represents an abstract client that is capable of submitting
 /// an Ethereum transaction-like object to the blockchain.
@@ -25,6 +32,10 @@ pub trait EthereumClient<T: Into<TransactionRequest>> {
     ///
     /// Returns the TX hash.
     async fn post_transaction_and_await_confirmation(&self, tx: T) -> hopr_chain_rpc::errors::Result<Hash>;
+
+    /// Sends transaction to the blockchain and returns it as pending,
+    /// so that its confirmation can be awaited later.
+    async fn post_pending_transaction(&self, tx: T) -> hopr_chain_rpc::errors::Result<PendingTransaction>;
 }
 
 #[derive(Clone, Debug, PartialEq, smart_default::SmartDefault, Serialize, Deserialize)]
@@ -69,7 +80,7 @@ impl<Rpc: HoprRpcOperations> RpcEthereumClient<Rpc> {
 #[async_trait]
 impl<Rpc: HoprRpcOperations + Send + Sync> EthereumClient<TransactionRequest> for RpcEthereumClient<Rpc> {
     async fn post_transaction(&self, tx: TransactionRequest) -> hopr_chain_rpc::errors::Result<Hash> {
-        self.post_tx_with_timeout(tx).await.map(|t| t.tx_hash().0.into())
+        self.post_tx_with_timeout(tx).await.map(|t| t.tx_hash())
     }
 
     /// Post a transaction and wait for its completion.
@@ -79,8 +90,57 @@ impl<Rpc: HoprRpcOperations + Send + Sync> EthereumClient<TransactionRequest> fo
         &self,
         tx: TransactionRequest,
     ) -> hopr_chain_rpc::errors::Result<Hash> {
-        Ok(self.post_tx_with_timeout(tx).await?.await?.0.into())
+        self.post_tx_with_timeout(tx).await?.await
     }
+
+    async fn post_pending_transaction(
+        &self,
+        tx: TransactionRequest,
+    ) -> hopr_chain_rpc::errors::Result<PendingTransaction> {
+        self.post_tx_with_timeout(tx).await
+    }
+}
+
+/// Configuration of the batching of transactions submitted by the [`EthereumTransactionExecutor`].
+///
+/// When enabled, transactions of compatible actions (ticket redemptions, channel operations and announcements)
+/// are collected over a short time window and submitted as a single Safe `MultiSend` transaction.
+///
+/// Since the `MultiSend` transaction succeeds or reverts as a whole, ticket redemptions in the same channel
+/// are never put into the same batch, and all the actions of a reverted batch fail.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, smart_default::SmartDefault, Serialize, Deserialize, Validate)]
+pub struct TransactionBatchingConfig {
+    /// Enables batching of the compatible transactions.
+    ///
+    /// Defaults to false.
+    #[default = false]
+    pub enabled: bool,
+
+    /// Time window, counted from the arrival of the first transaction of a batch,
+    /// during which more transactions are collected into the same batch.
+    ///
+    /// Defaults to 2 seconds.
+    #[serde_as(as = "DurationMilliSeconds<u64>")]
+    #[default(Duration::from_secs(2))]
+    pub window: Duration,
+
+    /// Maximum number of transactions in a single batch.
+    /// The batch is submitted immediately once this number is reached.
+    ///
+    /// Defaults to 16.
+    #[validate(range(min = 1))]
+    #[default = 16]
+    pub max_batch_size: usize,
+}
+
+/// Transaction waiting to be batched.
+struct BatchedTransaction<T> {
+    payload: T,
+    /// Channel in which the transaction redeems a ticket, if any.
+    redeemed_channel: Option<Hash>,
+    /// Notifies the submitter with the resulting TX hash.
+    notifier: oneshot::Sender<Result<Hash, String>>,
 }
 
 /// Implementation of [`TransactionExecutor`] using the given [`EthereumClient`] and corresponding
@@ -94,6 +154,7 @@ where
 {
     client: C,
     payload_generator: PGen,
+    batch_sender: Option<Sender<BatchedTransaction<T>>>,
     _data: PhantomData<T>,
 }
 
@@ -103,15 +164,183 @@ where
     C: EthereumClient<T> + Clone,
     PGen: PayloadGenerator<T> + Clone,
 {
+    /// Maximum number of transactions waiting to be batched.
+    pub const BATCH_QUEUE_SIZE: usize = 1024;
+
     pub fn new(client: C, payload_generator: PGen) -> Self {
         Self {
             client,
             payload_generator,
+            batch_sender: None,
             _data: PhantomData,
         }
     }
 }
 
+impl<T, C, PGen> EthereumTransactionExecutor<T, C, PGen>
+where
+    T: Into<TransactionRequest> + Clone + Send + Sync + 'static,
+    C: EthereumClient<T> + Clone + Send + Sync + 'static,
+    PGen: PayloadGenerator<T> + Clone + Send + Sync + 'static,
+{
+    /// Enables batching of the compatible transactions into a single transaction
+    /// (see [`PayloadGenerator::multisend`]), if it is enabled in the given `cfg`.
+    ///
+    /// The batches are collected and submitted by a background task.
+    /// Hashes of the batch transactions that reverted are sent to `reverted_batches`,
+    /// so that the actions awaiting them can be failed.
+    pub fn with_batching(mut self, cfg: TransactionBatchingConfig, reverted_batches: Sender<Hash>) -> Self {
+        if cfg.enabled {
+            let (batch_sender, batch_receiver) = async_channel::bounded(Self::BATCH_QUEUE_SIZE);
+            spawn(submit_batches(
+                self.client.clone(),
+                self.payload_generator.clone(),
+                batch_receiver,
+                reverted_batches,
+                cfg,
+            ));
+            self.batch_sender = Some(batch_sender);
+        }
+        self
+    }
+}
+
+impl<T, C, PGen> EthereumTransactionExecutor<T, C, PGen>
+where
+    T: Into<TransactionRequest> + Sync + Send,
+    C: EthereumClient<T> + Clone + Sync + Send,
+    PGen: PayloadGenerator<T> + Clone + Sync + Send,
+{
+    /// Posts the transaction either directly or as a part of the next batch, if batching is enabled.
+    ///
+    /// If the transaction redeems a ticket, the `redeemed_channel` must be given.
+    async fn post_batchable_transaction(
+        &self,
+        payload: T,
+        redeemed_channel: Option<Hash>,
+    ) -> hopr_chain_actions::errors::Result<Hash> {
+        match &self.batch_sender {
+            Some(batch_sender) => {
+                let (notifier, tx_hash) = oneshot::channel();
+                batch_sender
+                    .send(BatchedTransaction {
+                        payload,
+                        redeemed_channel,
+                        notifier,
+                    })
+                    .await
+                    .map_err(|_| TransactionSubmissionFailed("transaction batching has been stopped".into()))?;
+
+                tx_hash
+                    .await
+                    .map_err(|_| TransactionSubmissionFailed("transaction batch has been dropped".into()))?
+                    .map_err(TransactionSubmissionFailed)
+            }
+            None => Ok(self.client.post_transaction(payload).await?),
+        }
+    }
+}
+
+/// Collects transactions into batches and submits each batch as a single transaction.
+///
+/// Every submitter of a transaction in the batch is notified with the hash of the batch transaction.
+/// If the payload generator cannot batch the transactions, they are submitted one by one.
+/// The batch transactions are then awaited, and the hashes of those that reverted are sent to `reverted_batches`.
+async fn submit_batches<T, C, PGen>(
+    client: C,
+    payload_generator: PGen,
+    batch_receiver: Receiver<BatchedTransaction<T>>,
+    reverted_batches: Sender<Hash>,
+    cfg: TransactionBatchingConfig,
+) where
+    T: Into<TransactionRequest> + Clone,
+    C: EthereumClient<T>,
+    PGen: PayloadGenerator<T>,
+{
+    let max_batch_size = cfg.max_batch_size.max(1);
+    let mut next_first = None;
+    loop {
+        let first = match next_first.take() {
+            Some(first) => first,
+            None => match batch_receiver.recv().await {
+                Ok(first) => first,
+                Err(_) => break,
+            },
+        };
+
+        let mut redeemed_channels = HashSet::new();
+        redeemed_channels.extend(first.redeemed_channel);
+        let mut batch = vec![first];
+
+        let window = sleep(cfg.window).fuse();
+        pin_mut!(window);
+        while batch.len() < max_batch_size {
+            let next = batch_receiver.recv().fuse();
+            pin_mut!(next);
+            match futures::future::select(next, window.as_mut()).await {
+                Either::Left((Ok(tx), _)) => {
+                    // Tickets of the same channel must be redeemed in separate transactions
+                    if tx
+                        .redeemed_channel
+                        .is_some_and(|channel| !redeemed_channels.insert(channel))
+                    {
+                        next_first = Some(tx);
+                        break;
+                    }
+                    batch.push(tx);
+                }
+                // The window has elapsed or there will be no more transactions
+                _ => break,
+            }
+        }
+
+        let (payloads, notifiers): (Vec<_>, Vec<_>) = batch.into_iter().map(|tx| (tx.payload, tx.notifier)).unzip();
+        if payloads.len() == 1 {
+            let result = client.post_transaction(payloads[0].clone()).await;
+            notify_batch(notifiers, result);
+            continue;
+        }
+
+        match payload_generator.multisend(payloads.clone()) {
+            Ok(multisend) => {
+                debug!(size = payloads.len(), "submitting batch of transactions");
+                match client.post_pending_transaction(multisend).await {
+                    Ok(pending) => {
+                        notify_batch(notifiers, Ok(pending.tx_hash()));
+
+                        let reverted_batches = reverted_batches.clone();
+                        spawn(async move {
+                            if let Err(RpcError::TransactionReverted(tx_hash)) = pending.await {
+                                error!(%tx_hash, "batch of transactions reverted, failing all its actions");
+                                let _ = reverted_batches.send(tx_hash).await;
+                            }
+                        });
+                    }
+                    Err(error) => notify_batch(notifiers, Err(error)),
+                }
+            }
+            Err(error) => {
+                warn!(%error, "cannot batch transactions, submitting them one by one");
+                for (payload, notifier) in payloads.into_iter().zip(notifiers) {
+                    let result = client.post_transaction(payload).await;
+                    notify_batch(vec![notifier], result);
+                }
+            }
+        }
+    }
+    debug!("transaction batching done");
+}
+
+fn notify_batch(notifiers: Vec<oneshot::Sender<Result<Hash, String>>>, result: hopr_chain_rpc::errors::Result<Hash>) {
+    let result = result.map_err(|error| {
+        error!(%error, size = notifiers.len(), "failed to submit batch of transactions");
+        error.to_string()
+    });
+    for notifier in notifiers {
+        let _ = notifier.send(result.clone());
+    }
+}
+
 #[async_trait]
 impl<T, C, PGen> TransactionExecutor for EthereumTransactionExecutor<T, C, PGen>
 where
@@ -120,8 +349,9 @@ where
     PGen: PayloadGenerator<T> + Clone + Sync + Send,
 {
     async fn redeem_ticket(&self, acked_ticket: RedeemableTicket) -> hopr_chain_actions::errors::Result<Hash> {
+        let channel_id = acked_ticket.verified_ticket().channel_id;
         let payload = self.payload_generator.redeem_ticket(acked_ticket)?;
-        Ok(self.client.post_transaction(payload).await?)
+        self.post_batchable_transaction(payload, Some(channel_id)).await
     }
 
     async fn fund_channel(
@@ -130,22 +360,22 @@ where
         balance: HoprBalance,
     ) -> hopr_chain_actions::errors::Result<Hash> {
         let payload = self.payload_generator.fund_channel(destination, balance)?;
-        Ok(self.client.post_transaction(payload).await?)
+        self.post_batchable_transaction(payload, None).await
     }
 
     async fn initiate_outgoing_channel_closure(&self, dst: Address) -> hopr_chain_actions::errors::Result<Hash> {
         let payload = self.payload_generator.initiate_outgoing_channel_closure(dst)?;
-        Ok(self.client.post_transaction(payload).await?)
+        self.post_batchable_transaction(payload, None).await
     }
 
     async fn finalize_outgoing_channel_closure(&self, dst: Address) -> hopr_chain_actions::errors::Result<Hash> {
         let payload = self.payload_generator.finalize_outgoing_channel_closure(dst)?;
-        Ok(self.client.post_transaction(payload).await?)
+        self.post_batchable_transaction(payload, None).await
     }
 
     async fn close_incoming_channel(&self, src: Address) -> hopr_chain_actions::errors::Result<Hash> {
         let payload = self.payload_generator.close_incoming_channel(src)?;
-        Ok(self.client.post_transaction(payload).await?)
+        self.post_batchable_transaction(payload, None).await
     }
 
     async fn withdraw<Cr: Currency + Send>(
@@ -162,7 +392,7 @@ where
 
     async fn announce(&self, data: AnnouncementData) -> hopr_chain_actions::errors::Result<Hash> {
         let payload = self.payload_generator.announce(data)?;
-        Ok(self.client.post_transaction(payload).await?)
+        self.post_batchable_transaction(payload, None).await
     }
 
     async fn register_safe(&self, safe_address: Address) -> hopr_chain_actions::errors::Result<Hash> {
@@ -170,3 +400,248 @@ where
         Ok(self.client.post_transaction(payload).await?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use alloy::{rpc::types::TransactionRequest, sol_types::SolCall};
+    use async_trait::async_trait;
+    use hex_literal::hex;
+    use hopr_bindings::hoprnodemanagementmodule::HoprNodeManagementModule::execTransactionFromModuleCall;
+    use hopr_chain_actions::{
+        action_queue::TransactionExecutor,
+        payload::{PayloadGenerator, SafePayloadGenerator},
+    };
+    use hopr_chain_rpc::{PendingTransaction, errors::RpcError};
+    use hopr_chain_types::{ContractAddresses, constants::SAFE_MULTISEND_ADDRESS};
+    use hopr_crypto_types::prelude::*;
+    use hopr_primitive_types::prelude::*;
+
+    use super::{EthereumClient, EthereumTransactionExecutor, TransactionBatchingConfig};
+
+    const MODULE: [u8; 20] = hex!("1111111111111111111111111111111111111111");
+
+    /// Client that only records the posted transactions
+    #[derive(Clone, Default)]
+    struct RecordingClient {
+        posted: Arc<Mutex<Vec<TransactionRequest>>>,
+        reverting: bool,
+    }
+
+    impl RecordingClient {
+        fn reverting() -> Self {
+            Self {
+                reverting: true,
+                ..Default::default()
+            }
+        }
+
+        fn posted(&self) -> Vec<TransactionRequest> {
+            self.posted.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl EthereumClient<TransactionRequest> for RecordingClient {
+        async fn post_transaction(&self, tx: TransactionRequest) -> hopr_chain_rpc::errors::Result<Hash> {
+            let mut posted = self.posted.lock().unwrap();
+            posted.push(tx);
+            Ok(Hash::create(&[&posted.len().to_be_bytes()]))
+        }
+
+        async fn post_transaction_and_await_confirmation(
+            &self,
+            tx: TransactionRequest,
+        ) -> hopr_chain_rpc::errors::Result<Hash> {
+            self.post_pending_transaction(tx).await?.await
+        }
+
+        async fn post_pending_transaction(
+            &self,
+            tx: TransactionRequest,
+        ) -> hopr_chain_rpc::errors::Result<PendingTransaction> {
+            let tx_hash = self.post_transaction(tx).await?;
+            let reverting = self.reverting;
+            Ok(PendingTransaction::new(tx_hash, async move {
+                if reverting {
+                    Err(RpcError::TransactionReverted(tx_hash))
+                } else {
+                    Ok(tx_hash)
+                }
+            }))
+        }
+    }
+
+    fn executor(
+        client: RecordingClient,
+        cfg: TransactionBatchingConfig,
+    ) -> (
+        EthereumTransactionExecutor<TransactionRequest, RecordingClient, SafePayloadGenerator>,
+        async_channel::Receiver<Hash>,
+    ) {
+        let payload_generator =
+            SafePayloadGenerator::new(&ChainKeypair::random(), ContractAddresses::default(), MODULE.into());
+        let (reverted_tx, reverted_rx) = async_channel::unbounded();
+        (
+            EthereumTransactionExecutor::new(client, payload_generator).with_batching(cfg, reverted_tx),
+            reverted_rx,
+        )
+    }
+
+    fn random_address() -> Address {
+        ChainKeypair::random().public().to_address()
+    }
+
+    #[tokio::test]
+    async fn test_executor_should_batch_transactions_within_window() -> anyhow::Result<()> {
+        let client = RecordingClient::default();
+        let (executor, _) = executor(
+            client.clone(),
+            TransactionBatchingConfig {
+                enabled: true,
+                window: Duration::from_millis(200),
+                max_batch_size: 16,
+            },
+        );
+
+        let tx_hashes = futures::future::try_join_all([
+            executor.initiate_outgoing_channel_closure(random_address()),
+            executor.finalize_outgoing_channel_closure(random_address()),
+            executor.close_incoming_channel(random_address()),
+        ])
+        .await?;
+
+        assert!(
+            tx_hashes.iter().all(|h| h == &tx_hashes[0]),
+            "all actions must share the tx"
+        );
+
+        let posted = client.posted();
+        assert_eq!(1, posted.len(), "only a single transaction must be posted");
+        let outer = execTransactionFromModuleCall::abi_decode(posted[0].input.input().unwrap())?;
+        assert_eq!(outer.to, SAFE_MULTISEND_ADDRESS);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_should_split_batches_by_max_size() -> anyhow::Result<()> {
+        let client = RecordingClient::default();
+        let (executor, _) = executor(
+            client.clone(),
+            TransactionBatchingConfig {
+                enabled: true,
+                window: Duration::from_secs(5),
+                max_batch_size: 2,
+            },
+        );
+
+        let tx_hashes = tokio::time::timeout(
+            Duration::from_secs(10),
+            futures::future::try_join_all([
+                executor.initiate_outgoing_channel_closure(random_address()),
+                executor.initiate_outgoing_channel_closure(random_address()),
+                executor.initiate_outgoing_channel_closure(random_address()),
+                executor.initiate_outgoing_channel_closure(random_address()),
+            ]),
+        )
+        .await??;
+
+        assert_eq!(tx_hashes[0], tx_hashes[1]);
+        assert_eq!(tx_hashes[2], tx_hashes[3]);
+        assert_ne!(tx_hashes[0], tx_hashes[2]);
+        assert_eq!(2, client.posted().len());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_should_not_batch_when_disabled() -> anyhow::Result<()> {
+        let client = RecordingClient::default();
+        let (executor, _) = executor(client.clone(), TransactionBatchingConfig::default());
+
+        let tx_hashes = futures::future::try_join_all([
+            executor.initiate_outgoing_channel_closure(random_address()),
+            executor.initiate_outgoing_channel_closure(random_address()),
+        ])
+        .await?;
+
+        assert_ne!(tx_hashes[0], tx_hashes[1]);
+
+        let posted = client.posted();
+        assert_eq!(2, posted.len());
+        for tx in posted {
+            let call = execTransactionFromModuleCall::abi_decode(tx.input.input().unwrap())?;
+            assert_eq!(
+                call.to,
+                alloy::primitives::Address::from(ContractAddresses::default().channels)
+            );
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_should_not_batch_redemptions_in_the_same_channel() -> anyhow::Result<()> {
+        let client = RecordingClient::default();
+        let (executor, _) = executor(
+            client.clone(),
+            TransactionBatchingConfig {
+                enabled: true,
+                window: Duration::from_millis(200),
+                max_batch_size: 16,
+            },
+        );
+
+        let (channel_a, channel_b) = (Hash::create(&[b"a"]), Hash::create(&[b"b"]));
+        let payload = || {
+            executor
+                .payload_generator
+                .initiate_outgoing_channel_closure(random_address())
+        };
+
+        let tx_hashes = futures::future::try_join_all([
+            executor.post_batchable_transaction(payload()?, Some(channel_a)),
+            executor.post_batchable_transaction(payload()?, Some(channel_b)),
+            executor.post_batchable_transaction(payload()?, Some(channel_a)),
+        ])
+        .await?;
+
+        assert_eq!(tx_hashes[0], tx_hashes[1]);
+        assert_ne!(
+            tx_hashes[0], tx_hashes[2],
+            "redemptions in the same channel must not be batched"
+        );
+        assert_eq!(2, client.posted().len());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_executor_should_report_reverted_batches() -> anyhow::Result<()> {
+        let client = RecordingClient::reverting();
+        let (executor, reverted_batches) = executor(
+            client.clone(),
+            TransactionBatchingConfig {
+                enabled: true,
+                window: Duration::from_millis(200),
+                max_batch_size: 16,
+            },
+        );
+
+        let tx_hashes = futures::future::try_join_all([
+            executor.initiate_outgoing_channel_closure(random_address()),
+            executor.initiate_outgoing_channel_closure(random_address()),
+        ])
+        .await?;
+
+        let reverted = tokio::time::timeout(Duration::from_secs(5), reverted_batches.recv()).await??;
+        assert_eq!(tx_hashes[0], reverted, "the batch tx must be reported as reverted");
+
+        Ok(())
+    }
+}
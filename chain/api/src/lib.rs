@@ -15,17 +15,20 @@ use alloy::{
 };
 use config::ChainNetworkConfig;
 use executors::{EthereumTransactionExecutor, RpcEthereumClient, RpcEthereumClientConfig};
+use futures::StreamExt;
 use hopr_async_runtime::prelude::{JoinHandle, sleep, spawn};
 use hopr_chain_actions::{
     ChainActions,
     action_queue::{ActionQueue, ActionQueueConfig},
-    action_state::IndexerActionTracker,
+    action_state::{ActionState, IndexerActionTracker},
     payload::SafePayloadGenerator,
 };
+pub use hopr_chain_indexer::block::IndexerSyncProgress;
 use hopr_chain_indexer::{IndexerConfig, block::Indexer, handlers::ContractEventHandlers};
 use hopr_chain_rpc::{
     HoprRpcOperations,
     client::DefaultRetryPolicy,
+    pool::{ProviderPool, ProviderPoolConfig},
     rpc::{RpcOperations, RpcOperationsConfig},
     transport::ReqwestClient,
 };
@@ -110,10 +113,24 @@ fn build_transport_client(url: &str) -> Result<Http<ReqwestClient>> {
     Ok(ReqwestTransport::new(parsed_url))
 }
 
+fn build_provider_pool<'a>(
+    urls: impl Iterator<Item = &'a String>,
+    cfg: ProviderPoolConfig,
+) -> Result<ProviderPool<Http<ReqwestClient>>> {
+    let urls = urls
+        .map(|url| {
+            url::Url::parse(url).map_err(|e| HoprChainError::Configuration(format!("invalid RPC provider URL: {e}")))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    ProviderPool::http(urls, cfg).map_err(|e| HoprChainError::Configuration(e.to_string()))
+}
+
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
 pub enum HoprChainProcess {
     Indexer,
     OutgoingOnchainActionQueue,
+    TransactionManager,
 }
 
 type ActionQueueType<T> = ActionQueue<
@@ -139,10 +156,12 @@ pub struct HoprChain<T: HoprDbAllOperations + Send + Sync + Clone + std::fmt::De
     contract_addresses: ContractAddresses,
     indexer_cfg: IndexerConfig,
     indexer_events_tx: async_channel::Sender<SignificantChainEvent>,
+    indexer_sync_progress_tx: Option<async_channel::Sender<IndexerSyncProgress>>,
     db: T,
     hopr_chain_actions: ChainActions<T>,
     action_queue: ActionQueueType<T>,
     action_state: Arc<IndexerActionTracker>,
+    reverted_batches_rx: async_channel::Receiver<Hash>,
     rpc_operations: RpcOperations<DefaultHttpRequestor>,
 }
 
@@ -191,23 +210,37 @@ impl<T: HoprDbAllOperations + Send + Sync + Clone + std::fmt::Debug + 'static> H
 
         // --- Configs done ---
 
-        let transport_client = build_transport_client(&chain_config.chain.default_provider)?;
-
-        let rpc_client = ClientBuilder::default()
-            .layer(RetryBackoffLayer::new_with_policy(2, 100, 100, rpc_http_retry_policy))
-            .transport(transport_client.clone(), transport_client.guess_local());
+        let rpc_client = if chain_config.fallback_providers.is_empty() {
+            let transport_client = build_transport_client(&chain_config.chain.default_provider)?;
+
+            ClientBuilder::default()
+                .layer(RetryBackoffLayer::new_with_policy(2, 100, 100, rpc_http_retry_policy))
+                .transport(transport_client.clone(), transport_client.guess_local())
+        } else {
+            let provider_pool = build_provider_pool(
+                std::iter::once(&chain_config.chain.default_provider).chain(&chain_config.fallback_providers),
+                chain_config.provider_pool.clone(),
+            )?;
+
+            ClientBuilder::default()
+                .layer(RetryBackoffLayer::new_with_policy(2, 100, 100, rpc_http_retry_policy))
+                .transport(provider_pool.clone(), provider_pool.guess_local())
+        };
 
         let requestor = DefaultHttpRequestor::new();
 
         // Build RPC operations
-        let rpc_operations =
-            RpcOperations::new(rpc_client, requestor, &me_onchain, rpc_cfg).expect("failed to initialize RPC");
+        let rpc_operations = RpcOperations::new(rpc_client, requestor, &me_onchain, rpc_cfg)
+            .expect("failed to initialize RPC")
+            .with_transaction_manager(chain_config.tx_manager.clone());
 
         // Build the Ethereum Transaction Executor that uses RpcOperations as backend
+        let (reverted_batches_tx, reverted_batches_rx) = async_channel::unbounded();
         let ethereum_tx_executor = EthereumTransactionExecutor::new(
             RpcEthereumClient::new(rpc_operations.clone(), rpc_client_cfg),
             SafePayloadGenerator::new(&me_onchain, contract_addresses, module_address),
-        );
+        )
+        .with_batching(chain_config.tx_batching.clone(), reverted_batches_tx);
 
         // Build the Action Queue
         let action_queue = ActionQueue::new(
@@ -229,42 +262,86 @@ impl<T: HoprDbAllOperations + Send + Sync + Clone + std::fmt::Debug + 'static> H
             contract_addresses,
             indexer_cfg,
             indexer_events_tx,
+            indexer_sync_progress_tx: None,
             db,
             hopr_chain_actions,
             action_queue,
             action_state,
+            reverted_batches_rx,
             rpc_operations,
         })
     }
 
+    /// Sets the channel notified about the progress of the initial Indexer synchronization.
+    pub fn with_indexer_sync_progress(mut self, tx: async_channel::Sender<IndexerSyncProgress>) -> Self {
+        self.indexer_sync_progress_tx = Some(tx);
+        self
+    }
+
     /// Execute all processes of the [`HoprChain`] object.
     ///
     /// This method will spawn the [`HoprChainProcess::Indexer`] and [`HoprChainProcess::OutgoingOnchainActionQueue`]
     /// processes and return join handles to the calling function.
+    /// If enabled, the [`HoprChainProcess::TransactionManager`] process is spawned as well.
     pub async fn start(&self) -> errors::Result<HashMap<HoprChainProcess, JoinHandle<()>>> {
         let mut processes: HashMap<HoprChainProcess, JoinHandle<()>> = HashMap::new();
 
+        // Actions whose batch transaction reverted will never be confirmed by the Indexer, so fail them
+        let action_queue = self.action_queue.clone();
+        let reverted_batches_rx = self.reverted_batches_rx.clone();
+        let action_state = self.action_state.clone();
         processes.insert(
             HoprChainProcess::OutgoingOnchainActionQueue,
-            spawn(self.action_queue.clone().start()),
+            spawn(async move {
+                futures::future::join(
+                    action_queue.start(),
+                    reverted_batches_rx.for_each(|tx_hash| {
+                        let action_state = action_state.clone();
+                        async move { action_state.unregister_expectation(tx_hash).await }
+                    }),
+                )
+                .await;
+            }),
         );
-        processes.insert(
-            HoprChainProcess::Indexer,
-            Indexer::new(
-                self.rpc_operations.clone(),
-                ContractEventHandlers::new(
-                    self.contract_addresses,
-                    self.safe_address,
-                    self.me_onchain.clone(),
-                    self.db.clone(),
-                ),
+        let mut indexer = Indexer::new(
+            self.rpc_operations.clone(),
+            ContractEventHandlers::new(
+                self.contract_addresses,
+                self.safe_address,
+                self.me_onchain.clone(),
                 self.db.clone(),
-                self.indexer_cfg,
-                self.indexer_events_tx.clone(),
-            )
-            .start()
-            .await?,
+            ),
+            self.db.clone(),
+            self.indexer_cfg,
+            self.indexer_events_tx.clone(),
         );
+        if let Some(tx) = self.indexer_sync_progress_tx.clone() {
+            indexer = indexer.with_sync_progress_notifier(tx);
+        }
+
+        processes.insert(HoprChainProcess::Indexer, indexer.start().await?);
+
+        if let Some(tx_manager) = self.rpc_operations.transaction_manager().cloned() {
+            let (replacements_tx, replacements_rx) = futures::channel::mpsc::unbounded();
+            let action_state = self.action_state.clone();
+            processes.insert(
+                HoprChainProcess::TransactionManager,
+                spawn(async move {
+                    futures::future::join(
+                        tx_manager.run(replacements_tx),
+                        replacements_rx.for_each(|replacement| {
+                            let action_state = action_state.clone();
+                            async move {
+                                action_state
+                                    .replace_tx_hash(replacement.old_tx_hash, replacement.new_tx_hash)
+                                    .await
+                            }
+                        }),
+                    )
+                    .await;
+                }),
+            );
+        }
 
         Ok(processes)
     }
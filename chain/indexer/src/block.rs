@@ -1,18 +1,30 @@
-use std::sync::{
-    Arc,
-    atomic::{AtomicBool, AtomicU64, Ordering},
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    time::Duration,
 };
 
-use futures::{FutureExt, StreamExt, stream};
-use hopr_async_runtime::prelude::{JoinHandle, spawn};
+use futures::StreamExt;
+use hopr_async_runtime::prelude::{JoinHandle, sleep, spawn};
 use hopr_chain_rpc::{BlockWithLogs, HoprIndexerRpcOperations, LogFilter};
-use hopr_chain_types::chain_events::SignificantChainEvent;
+use hopr_chain_types::chain_events::{ChainEventType, SignificantChainEvent};
 use hopr_crypto_types::types::Hash;
-use hopr_db_api::logs::HoprDbLogOperations;
-use hopr_db_sql::{HoprDbGeneralModelOperations, info::HoprDbInfoOperations};
+use hopr_db_api::logs::{BlockHashes, HoprDbLogOperations};
+use hopr_db_sql::{
+    HoprDbGeneralModelOperations, accounts::HoprDbAccountOperations, channels::HoprDbChannelOperations,
+    info::HoprDbInfoOperations, registry::HoprDbRegistryOperations,
+};
+use hopr_internal_types::{
+    account::{AccountEntry, AccountType},
+    channels::{ChannelEntry, ChannelStatus},
+};
 #[cfg(all(feature = "prometheus", not(test)))]
 use hopr_primitive_types::prelude::ToHex;
-use tracing::{debug, error, info, trace};
+use hopr_primitive_types::prelude::{Address, SerializableLog};
+use tracing::{debug, error, info, trace, warn};
 
 use crate::{
     IndexerConfig,
@@ -43,7 +55,44 @@ lazy_static::lazy_static! {
             "Current data source of the Indexer",
             &["source"],
     ).unwrap();
+    static ref METRIC_INDEXER_CHAIN_REORGS: hopr_metrics::metrics::SimpleCounter =
+        hopr_metrics::metrics::SimpleCounter::new(
+            "hopr_indexer_chain_reorgs",
+            "Number of chain reorganizations detected by the indexer",
+    ).unwrap();
+}
+
+/// Delay before the block continuity is verified again after an RPC failure.
+const BLOCK_CONTINUITY_RETRY_DELAY: Duration = Duration::from_secs(1);
 
+/// Maximum number of stored blocks whose logs are compared with the chain before a fast sync from scratch.
+const MAX_FAST_SYNC_VERIFIED_BLOCKS: usize = 16;
+
+/// Result of the verification whether a block extends the chain of the previously processed blocks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BlockContinuity {
+    /// The block extends the chain, its hashes are stored once it is processed.
+    Continues(BlockHashes),
+    /// The chain was reorganized, the given block is the last processed block which is still part of the chain.
+    Reorganized(u64),
+}
+
+/// Progress of the initial synchronization of the [`Indexer`] with the chain.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IndexerSyncProgress {
+    /// Last processed block number.
+    pub block_number: u64,
+    /// Last known chain head block number.
+    pub head: u64,
+    /// Synchronization progress in the range `[0.0, 1.0]`.
+    pub progress: f64,
+}
+
+impl IndexerSyncProgress {
+    /// Indicates whether the indexer has caught up with the chain head.
+    pub fn is_synced(&self) -> bool {
+        self.block_number >= self.head
+    }
 }
 
 /// Indexer
@@ -64,13 +113,22 @@ pub struct Indexer<T, U, Db>
 where
     T: HoprIndexerRpcOperations + Send + 'static,
     U: ChainLogHandler + Send + 'static,
-    Db: HoprDbGeneralModelOperations + HoprDbInfoOperations + HoprDbLogOperations + Clone + Send + Sync + 'static,
+    Db: HoprDbGeneralModelOperations
+        + HoprDbInfoOperations
+        + HoprDbLogOperations
+        + HoprDbChannelOperations
+        + HoprDbAccountOperations
+        + Clone
+        + Send
+        + Sync
+        + 'static,
 {
     rpc: Option<T>,
     db_processor: Option<U>,
     db: Db,
     cfg: IndexerConfig,
     egress: async_channel::Sender<SignificantChainEvent>,
+    sync_progress: Option<async_channel::Sender<IndexerSyncProgress>>,
     // If true (default), the indexer will panic if the event stream is terminated.
     // Setting it to false is useful for testing.
     panic_on_completion: bool,
@@ -80,7 +138,15 @@ impl<T, U, Db> Indexer<T, U, Db>
 where
     T: HoprIndexerRpcOperations + Sync + Send + 'static,
     U: ChainLogHandler + Send + Sync + 'static,
-    Db: HoprDbGeneralModelOperations + HoprDbInfoOperations + HoprDbLogOperations + Clone + Send + Sync + 'static,
+    Db: HoprDbGeneralModelOperations
+        + HoprDbInfoOperations
+        + HoprDbLogOperations
+        + HoprDbChannelOperations
+        + HoprDbAccountOperations
+        + Clone
+        + Send
+        + Sync
+        + 'static,
 {
     pub fn new(
         rpc: T,
@@ -95,6 +161,7 @@ where
             db,
             cfg,
             egress,
+            sync_progress: None,
             panic_on_completion: true,
         }
     }
@@ -105,11 +172,28 @@ where
         self
     }
 
+    /// Sets the channel notified about the progress of the initial synchronization.
+    ///
+    /// The notifications stop once the indexer is synced with the chain head.
+    pub fn with_sync_progress_notifier(mut self, notifier: async_channel::Sender<IndexerSyncProgress>) -> Self {
+        self.sync_progress = Some(notifier);
+        self
+    }
+
     pub async fn start(mut self) -> Result<JoinHandle<()>>
     where
         T: HoprIndexerRpcOperations + 'static,
         U: ChainLogHandler + 'static,
-        Db: HoprDbGeneralModelOperations + HoprDbInfoOperations + HoprDbLogOperations + Clone + Send + Sync + 'static,
+        Db: HoprDbGeneralModelOperations
+            + HoprDbInfoOperations
+            + HoprDbLogOperations
+            + HoprDbChannelOperations
+            + HoprDbAccountOperations
+            + HoprDbRegistryOperations
+            + Clone
+            + Send
+            + Sync
+            + 'static,
     {
         if self.rpc.is_none() || self.db_processor.is_none() {
             return Err(CoreEthereumIndexerError::ProcessError(
@@ -123,14 +207,20 @@ where
         let logs_handler = Arc::new(self.db_processor.take().expect("db_processor should be present"));
         let db = self.db.clone();
         let tx_significant_events = self.egress.clone();
+        let tx_sync_progress = self.sync_progress.clone();
         let panic_on_completion = self.panic_on_completion;
+        let max_reorg_depth = self.cfg.max_reorg_depth;
 
         // we skip on addresses which have no topics
         let mut addresses = vec![];
         let mut topics = vec![];
         let mut address_topics = vec![];
+        let mut safe_registry = None;
         logs_handler.contract_addresses().iter().for_each(|address| {
             let contract_topics = logs_handler.contract_address_topics(*address);
+            if contract_topics == crate::constants::topics::node_safe_registry() {
+                safe_registry = Some(*address);
+            }
             if !contract_topics.is_empty() {
                 addresses.push(*address);
                 for topic in contract_topics {
@@ -201,6 +291,10 @@ where
             }
         };
 
+        if let Some(safe_registry) = safe_registry.filter(|_| !index_empty) {
+            Self::restore_node_safe_registry(&db, &logs_handler, safe_registry).await?;
+        }
+
         let (tx, mut rx) = futures::channel::mpsc::channel::<()>(1);
 
         // Perform the fast-sync if requested
@@ -218,6 +312,10 @@ where
             }
 
             let log_block_numbers = self.db.get_logs_block_numbers(None, None, processed).await?;
+            if FastSyncMode::FromScratch == will_perform_fast_sync {
+                Self::verify_logs_with_chain(&rpc, &db, &log_filter, &log_block_numbers).await?;
+            }
+
             let _first_log_block_number = log_block_numbers.first().copied().unwrap_or(0);
             let _head = chain_head.load(Ordering::Relaxed);
             for block_number in log_block_numbers {
@@ -270,10 +368,13 @@ where
                 METRIC_INDEXER_SYNC_SOURCE.set(&["rpc"], 1.0);
             }
 
-            let event_stream = rpc
-                .try_stream_logs(next_block_to_process, log_filter)
-                .expect("block stream should be constructible")
-                .then(|block| {
+            let mut next_block = next_block_to_process;
+            'indexing: loop {
+                let mut block_stream = rpc
+                    .try_stream_logs(next_block, log_filter.clone())
+                    .expect("block stream should be constructible");
+
+                while let Some(block) = block_stream.next().await {
                     Self::calculate_sync_process(
                         block.block_id,
                         &rpc,
@@ -281,49 +382,102 @@ where
                         is_synced.clone(),
                         next_block_to_process,
                         tx.clone(),
+                        tx_sync_progress.clone(),
                     )
-                    .map(|_| block)
-                })
-                .filter_map(|block| {
-                    let db = db.clone();
-
-                    async move {
-                        debug!(%block, "storing logs from block");
-                        let logs = block.logs.clone();
-                        let logs_vec = logs.into_iter().collect();
-                        match db.store_logs(logs_vec).await {
-                            Ok(store_results) => {
-                                if let Some(error) = store_results
-                                    .into_iter()
-                                    .filter(|r| r.is_err())
-                                    .map(|r| r.unwrap_err())
-                                    .next()
-                                {
-                                    error!(%block, %error, "failed to processed stored logs from block");
-                                    None
-                                } else {
-                                    Some(block)
+                    .await;
+
+                    let block_hashes = if block.is_empty() {
+                        None
+                    } else {
+                        match Self::verify_block_continuity(&rpc, &db, &block, max_reorg_depth).await {
+                            Ok(BlockContinuity::Continues(hashes)) => Some(hashes),
+                            Ok(BlockContinuity::Reorganized(last_valid_block)) => {
+                                #[cfg(all(feature = "prometheus", not(test)))]
+                                METRIC_INDEXER_CHAIN_REORGS.increment();
+
+                                match Self::rollback_to_block(&db, &logs_handler, last_valid_block).await {
+                                    Ok(corrections) => {
+                                        if is_synced.load(Ordering::Relaxed) {
+                                            for event in corrections {
+                                                trace!(%event, "passing a correction of the rolled back state");
+                                                if let Err(error) = tx_significant_events.try_send(event) {
+                                                    error!(%error, "failed to pass a significant chain event further");
+                                                }
+                                            }
+                                        }
+                                    }
+                                    Err(error) => {
+                                        error!(last_valid_block, %error, "failed to roll back the reorganized blocks");
+                                        panic!(
+                                            "Failed to roll back the reorganized blocks, cannot continue indexing due \
+                                             to {error}"
+                                        )
+                                    }
                                 }
+
+                                info!(last_valid_block, "Restarting indexing after a chain reorganization");
+                                next_block = last_valid_block + 1;
+                                continue 'indexing;
+                            }
+                            Err(CoreEthereumIndexerError::ChainReorgTooDeep(depth)) => {
+                                error!(%block, depth, "chain reorganization is deeper than the indexer can handle");
+                                panic!(
+                                    "Chain reorganization deeper than {depth} blocks detected at block #{}. Possible \
+                                     solutions: increase the maximum reorganization depth, reinitialize the DB",
+                                    block.block_id
+                                )
                             }
                             Err(error) => {
-                                error!(%block, %error, "failed to store logs from block");
-                                None
+                                error!(%block, %error, "failed to verify the block continuity, retrying");
+                                sleep(BLOCK_CONTINUITY_RETRY_DELAY).await;
+                                next_block = block.block_id;
+                                continue 'indexing;
+                            }
+                        }
+                    };
+
+                    debug!(%block, "storing logs from block");
+                    let logs_vec = block.logs.iter().cloned().collect();
+                    match db.store_logs(logs_vec).await {
+                        Ok(store_results) => {
+                            if let Some(error) = store_results.into_iter().find_map(|r| r.err()) {
+                                error!(%block, %error, "failed to processed stored logs from block");
+                                continue;
                             }
                         }
+                        Err(error) => {
+                            error!(%block, %error, "failed to store logs from block");
+                            continue;
+                        }
                     }
-                })
-                .filter_map(|block| Self::process_block(&db, &logs_handler, block, false))
-                .flat_map(stream::iter);
-
-            futures::pin_mut!(event_stream);
-            while let Some(event) = event_stream.next().await {
-                trace!(%event, "processing on-chain event");
-                // Pass the events further only once we're fully synced
-                if is_synced.load(Ordering::Relaxed) {
-                    if let Err(error) = tx_significant_events.try_send(event) {
-                        error!(%error, "failed to pass a significant chain event further");
+
+                    if let Some(hashes) = block_hashes {
+                        if let Err(error) = db.store_block_hashes(hashes).await {
+                            error!(%block, %error, "failed to store block hashes");
+                        }
+                        if let Err(error) = db
+                            .prune_block_hashes(hashes.block_number.saturating_sub(max_reorg_depth))
+                            .await
+                        {
+                            error!(%block, %error, "failed to prune block hashes");
+                        }
+                    }
+
+                    for event in Self::process_block(&db, &logs_handler, block, false)
+                        .await
+                        .unwrap_or_default()
+                    {
+                        trace!(%event, "processing on-chain event");
+                        // Pass the events further only once we're fully synced
+                        if is_synced.load(Ordering::Relaxed) {
+                            if let Err(error) = tx_significant_events.try_send(event) {
+                                error!(%error, "failed to pass a significant chain event further");
+                            }
+                        }
                     }
                 }
+
+                break;
             }
 
             if panic_on_completion {
@@ -388,6 +542,93 @@ where
         Ok(Self::process_block(db, logs_handler, block, true).await)
     }
 
+    /// Restores the node safe registry of an existing index from the stored logs.
+    ///
+    /// The node-to-Safe assignments were not indexed by older versions, so they are rebuilt by
+    /// replaying the already processed logs of the node safe registry contract. The remaining
+    /// logs are processed by the regular sync. Events of the replayed logs are not emitted again.
+    async fn restore_node_safe_registry(db: &Db, logs_handler: &U, safe_registry: Address) -> Result<()>
+    where
+        U: ChainLogHandler + 'static,
+        Db: HoprDbLogOperations + HoprDbRegistryOperations + 'static,
+    {
+        if !db.is_node_safe_registry_empty(None).await? {
+            return Ok(());
+        }
+
+        let logs = db.get_contract_logs(safe_registry, Some(true)).await?;
+        let count = logs.len();
+
+        for block in group_logs_by_block(logs) {
+            logs_handler.collect_block_events(block).await?;
+        }
+
+        if count > 0 {
+            info!(count, "Restored the node safe registry from the stored logs");
+        }
+
+        Ok(())
+    }
+
+    /// Verifies the stored logs of a sample of the given blocks against the logs retrieved over RPC.
+    ///
+    /// The checksums of the stored logs cover only the block hashes, transaction hashes and log indices,
+    /// so logs with altered contents (e.g. in an imported snapshot) would otherwise be processed.
+    /// The sample contains the first and the last block and at most [`MAX_FAST_SYNC_VERIFIED_BLOCKS`] blocks
+    /// spread evenly in between.
+    async fn verify_logs_with_chain(rpc: &T, db: &Db, filter: &LogFilter, block_numbers: &[u64]) -> Result<()>
+    where
+        T: HoprIndexerRpcOperations + 'static,
+        Db: HoprDbLogOperations + 'static,
+    {
+        let sample = if block_numbers.len() <= MAX_FAST_SYNC_VERIFIED_BLOCKS {
+            block_numbers.to_vec()
+        } else {
+            (0..MAX_FAST_SYNC_VERIFIED_BLOCKS)
+                .map(|i| block_numbers[i * (block_numbers.len() - 1) / (MAX_FAST_SYNC_VERIFIED_BLOCKS - 1)])
+                .collect()
+        };
+
+        // Only the fields coming from the chain are compared
+        let log_contents = |log: SerializableLog| {
+            (
+                log.log_index,
+                log.tx_hash,
+                log.block_hash,
+                log.address,
+                log.topics,
+                log.data,
+            )
+        };
+
+        for block_number in sample {
+            let mut stored = db
+                .get_logs(Some(block_number), Some(0))
+                .await?
+                .into_iter()
+                .map(log_contents)
+                .collect::<Vec<_>>();
+            let mut retrieved = rpc
+                .block_logs(block_number, filter.clone())
+                .await?
+                .into_iter()
+                .map(|log| log_contents(log.into()))
+                .collect::<Vec<_>>();
+
+            stored.sort();
+            retrieved.sort();
+
+            if stored != retrieved {
+                error!(block_number, "stored logs do not match the logs on chain");
+                return Err(CoreEthereumIndexerError::ValidationError(format!(
+                    "stored logs of block {block_number} do not match the logs on chain"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Processes a block and its logs.
     ///
     /// This function collects events from the block logs and updates the database with the processed logs.
@@ -483,6 +724,146 @@ where
         }
     }
 
+    /// Verifies that the given block extends the chain of the previously processed blocks.
+    ///
+    /// The logs of the block must belong to the block currently on the chain, and the block
+    /// must descend from the last processed block. Otherwise, the chain was reorganized and
+    /// the last processed block which is still part of the chain is looked up.
+    async fn verify_block_continuity(
+        rpc: &T,
+        db: &Db,
+        block: &BlockWithLogs,
+        max_reorg_depth: u64,
+    ) -> Result<BlockContinuity>
+    where
+        T: HoprIndexerRpcOperations + 'static,
+        Db: HoprDbLogOperations + 'static,
+    {
+        let header = rpc.block_header(block.block_id).await?.ok_or_else(|| {
+            CoreEthereumIndexerError::ProcessError(format!("block #{} not found in the RPC provider", block.block_id))
+        })?;
+
+        if block.logs.iter().any(|log| Hash::from(log.block_hash) != header.hash) {
+            warn!(%block, block_hash = %header.hash, "logs do not belong to the block on the chain");
+            return Self::find_last_valid_block(rpc, db, block.block_id, max_reorg_depth)
+                .await
+                .map(BlockContinuity::Reorganized);
+        }
+
+        if let Some(last) = db.get_latest_block_hashes(1).await?.into_iter().next() {
+            if last.block_number < block.block_id {
+                let continues = if last.block_number + 1 == block.block_id {
+                    last.hash == header.parent_hash
+                } else {
+                    rpc.block_header(last.block_number)
+                        .await?
+                        .is_some_and(|last_header| last_header.hash == last.hash)
+                };
+
+                if !continues {
+                    warn!(%block, last_block = last.block_number, "block does not descend from the last processed block");
+                    return Self::find_last_valid_block(rpc, db, block.block_id, max_reorg_depth)
+                        .await
+                        .map(BlockContinuity::Reorganized);
+                }
+            }
+        }
+
+        Ok(BlockContinuity::Continues(BlockHashes {
+            block_number: header.number,
+            hash: header.hash,
+            parent_hash: header.parent_hash,
+        }))
+    }
+
+    /// Finds the last processed block before `block_number` which is still part of the chain,
+    /// by comparing the stored block hashes with the ones on the chain.
+    ///
+    /// Fails if none of the stored blocks within `max_reorg_depth` is part of the chain.
+    async fn find_last_valid_block(rpc: &T, db: &Db, block_number: u64, max_reorg_depth: u64) -> Result<u64>
+    where
+        T: HoprIndexerRpcOperations + 'static,
+        Db: HoprDbLogOperations + 'static,
+    {
+        let mut verified_any = false;
+        for stored in db.get_latest_block_hashes(max_reorg_depth).await? {
+            if stored.block_number >= block_number {
+                continue;
+            }
+            if stored.block_number < block_number.saturating_sub(max_reorg_depth) {
+                break;
+            }
+
+            if rpc
+                .block_header(stored.block_number)
+                .await?
+                .is_some_and(|header| header.hash == stored.hash)
+            {
+                info!(
+                    block_number,
+                    last_valid_block = stored.block_number,
+                    "Found the last valid block after a chain reorganization"
+                );
+                return Ok(stored.block_number);
+            }
+            verified_any = true;
+        }
+
+        if verified_any {
+            Err(CoreEthereumIndexerError::ChainReorgTooDeep(max_reorg_depth))
+        } else {
+            // No processed blocks to verify, so only the given block is affected
+            Ok(block_number.saturating_sub(1))
+        }
+    }
+
+    /// Removes all the logs after the given block and rebuilds the index from the remaining logs.
+    ///
+    /// The derived channel and account state cannot be reverted per log, so it is rebuilt by
+    /// replaying all the logs since the start block (see [`IndexerConfig::max_reorg_depth`]) within
+    /// a single transaction. Events of the replayed blocks are not emitted, instead the returned
+    /// events correct the channel and account state announced before the rollback.
+    async fn rollback_to_block(db: &Db, logs_handler: &U, block_number: u64) -> Result<Vec<SignificantChainEvent>>
+    where
+        U: ChainLogHandler + 'static,
+        Db: HoprDbLogOperations + HoprDbChannelOperations + HoprDbAccountOperations + 'static,
+    {
+        let removed = db.rollback_logs(block_number).await?;
+        info!(block_number, removed, "Rolled back logs after a chain reorganization");
+
+        if removed == 0 {
+            return Ok(vec![]);
+        }
+
+        let channels_before = db.get_all_channels(None).await?;
+        let accounts_before = db.get_accounts(None, false).await?;
+
+        // The remaining logs were all processed before, so their processing state and checksums stay valid
+        logs_handler
+            .rebuild_index(group_logs_by_block(db.get_logs(None, None).await?))
+            .await?;
+
+        let corrections = rollback_correction_events(
+            channels_before,
+            db.get_all_channels(None).await?,
+            accounts_before,
+            db.get_accounts(None, false).await?,
+        );
+        info!(
+            block_number,
+            count = corrections.len(),
+            "Rebuilt the index after a chain reorganization"
+        );
+
+        Ok(corrections
+            .into_iter()
+            .map(|event_type| SignificantChainEvent {
+                tx_hash: Hash::default(),
+                event_type,
+            })
+            .collect())
+    }
+
     async fn update_chain_head(rpc: &T, chain_head: Arc<AtomicU64>) -> u64
     where
         T: HoprIndexerRpcOperations + 'static,
@@ -513,6 +894,7 @@ where
     /// * `is_synced` - A boolean indicating whether the indexer is synced.
     /// * `start_block` - The first block number to process.
     /// * `tx` - A sender channel for synchronization notifications.
+    /// * `tx_progress` - An optional sender channel for synchronization progress notifications.
     ///
     /// # Returns
     ///
@@ -524,6 +906,7 @@ where
         is_synced: Arc<AtomicBool>,
         start_block: u64,
         mut tx: futures::channel::mpsc::Sender<()>,
+        tx_progress: Option<async_channel::Sender<IndexerSyncProgress>>,
     ) where
         T: HoprIndexerRpcOperations + 'static,
     {
@@ -563,6 +946,17 @@ where
             #[cfg(all(feature = "prometheus", not(test)))]
             METRIC_INDEXER_SYNC_PROGRESS.set(progress);
 
+            if let Some(tx_progress) = tx_progress {
+                let sync_progress = IndexerSyncProgress {
+                    block_number: current_block,
+                    head,
+                    progress: progress.min(1.0),
+                };
+                if let Err(error) = tx_progress.try_send(sync_progress) {
+                    trace!(%error, "failed to notify about the indexer sync progress");
+                }
+            }
+
             if current_block >= head {
                 info!("indexer sync completed successfully");
                 is_synced.store(true, Ordering::Relaxed);
@@ -574,6 +968,92 @@ where
     }
 }
 
+/// Groups the logs ordered by their position on the chain into blocks.
+fn group_logs_by_block(logs: Vec<SerializableLog>) -> Vec<BlockWithLogs> {
+    let mut blocks = BTreeMap::<u64, BlockWithLogs>::new();
+    for log in logs {
+        blocks
+            .entry(log.block_number)
+            .or_insert_with(|| BlockWithLogs {
+                block_id: log.block_number,
+                ..Default::default()
+            })
+            .logs
+            .insert(log);
+    }
+    blocks.into_values().collect()
+}
+
+/// Computes the events which bring the consumers of the previously emitted events
+/// from the state before a rollback to the state after it.
+///
+/// Channels which no longer exist are reported as closed. Accounts are only reported when their
+/// announcement changed, because there is no event for a withdrawn announcement.
+fn rollback_correction_events(
+    channels_before: Vec<ChannelEntry>,
+    channels_after: Vec<ChannelEntry>,
+    accounts_before: Vec<AccountEntry>,
+    accounts_after: Vec<AccountEntry>,
+) -> Vec<ChainEventType> {
+    let mut events = Vec::new();
+
+    let mut channels_before = channels_before
+        .into_iter()
+        .map(|channel| (channel.get_id(), channel))
+        .collect::<HashMap<_, _>>();
+    for channel in channels_after {
+        let before = channels_before.remove(&channel.get_id());
+        let status_changed = before.is_none_or(|before| before.status != channel.status);
+
+        if status_changed {
+            events.push(match channel.status {
+                ChannelStatus::Open => ChainEventType::ChannelOpened(channel),
+                ChannelStatus::PendingToClose(_) => ChainEventType::ChannelClosureInitiated(channel),
+                ChannelStatus::Closed => ChainEventType::ChannelClosed(channel),
+            });
+        } else if let Some(before) = before {
+            if channel.balance > before.balance {
+                events.push(ChainEventType::ChannelBalanceIncreased(
+                    channel,
+                    channel.balance - before.balance,
+                ));
+            } else if channel.balance < before.balance {
+                events.push(ChainEventType::ChannelBalanceDecreased(
+                    channel,
+                    before.balance - channel.balance,
+                ));
+            }
+        }
+    }
+    events.extend(
+        channels_before
+            .into_values()
+            .filter(|channel| channel.status != ChannelStatus::Closed)
+            .map(|mut channel| {
+                channel.status = ChannelStatus::Closed;
+                ChainEventType::ChannelClosed(channel)
+            }),
+    );
+
+    let accounts_before = accounts_before
+        .into_iter()
+        .map(|account| (account.chain_addr, account.entry_type))
+        .collect::<HashMap<_, _>>();
+    for account in accounts_after {
+        if let AccountType::Announced { multiaddr, .. } = &account.entry_type {
+            if accounts_before.get(&account.chain_addr) != Some(&account.entry_type) {
+                events.push(ChainEventType::Announcement {
+                    peer: account.public_key.into(),
+                    address: account.chain_addr,
+                    multiaddresses: vec![multiaddr.clone()],
+                });
+            }
+        }
+    }
+
+    events
+}
+
 #[cfg(test)]
 mod tests {
     use std::{collections::BTreeSet, pin::Pin};
@@ -586,7 +1066,7 @@ mod tests {
     use async_trait::async_trait;
     use futures::{Stream, join};
     use hex_literal::hex;
-    use hopr_chain_rpc::BlockWithLogs;
+    use hopr_chain_rpc::{BlockHeader, BlockWithLogs};
     use hopr_chain_types::chain_events::ChainEventType;
     use hopr_crypto_types::{
         keypairs::{Keypair, OffchainKeypair},
@@ -650,6 +1130,24 @@ mod tests {
         Ok(logs)
     }
 
+    /// Builds the header of a block on a test chain, where the blocks starting at `fork_block`
+    /// were replaced by a chain reorganization.
+    fn build_block_header(block_number: u64, fork_block: u64) -> BlockHeader {
+        let block_hash = |n: u64| {
+            if n >= fork_block {
+                Hash::create(&[format!("reorged block hash {n}").as_bytes()])
+            } else {
+                Hash::create(&[format!("my block hash {n}").as_bytes()])
+            }
+        };
+
+        BlockHeader {
+            number: block_number,
+            hash: block_hash(block_number),
+            parent_hash: block_hash(block_number.saturating_sub(1)),
+        }
+    }
+
     mock! {
         HoprIndexerOps {}     // Name of the mock struct, less the "Mock" prefix
 
@@ -657,6 +1155,10 @@ mod tests {
         impl HoprIndexerRpcOperations for HoprIndexerOps {
             async fn block_number(&self) -> hopr_chain_rpc::errors::Result<u64>;
 
+            async fn block_header(&self, block_number: u64) -> hopr_chain_rpc::errors::Result<Option<BlockHeader>>;
+
+            async fn block_logs(&self, block_number: u64, filter: LogFilter) -> hopr_chain_rpc::errors::Result<Vec<hopr_chain_rpc::Log>>;
+
             fn try_stream_logs<'a>(
                 &'a self,
                 start_block_number: u64,
@@ -797,6 +1299,8 @@ mod tests {
 
         let head_block = 1000;
         rpc.expect_block_number().returning(move || Ok(head_block));
+        rpc.expect_block_header()
+            .returning(|block_number| Ok(Some(build_block_header(block_number, u64::MAX))));
 
         let finalized_block = BlockWithLogs {
             block_id: head_block - 1,
@@ -826,6 +1330,70 @@ mod tests {
         Ok(())
     }
 
+    #[test_log::test(tokio::test)]
+    async fn test_indexer_fast_sync_should_fail_when_stored_logs_do_not_match_the_chain() -> anyhow::Result<()> {
+        let db = HoprDb::new_in_memory(ChainKeypair::random()).await?;
+
+        let addr = Address::new(b"my address 123456789");
+        let topic = Hash::create(&[b"my topic"]);
+
+        let logs = vec![
+            build_announcement_logs(*BOB, 1, 1, 1)?,
+            build_announcement_logs(*BOB, 1, 2, 1)?,
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+        assert!(db.ensure_logs_origin(vec![(addr, topic)]).await.is_ok());
+
+        // The stored log of block 2 has been tampered with, its checksum stays valid
+        for mut log in logs.clone() {
+            if log.block_number == 2 {
+                log.data = build_announcement_logs(*ALICE, 1, 2, 1)?[0].data.clone();
+            }
+            assert!(db.store_log(log).await.is_ok());
+        }
+        assert!(db.update_logs_checksums().await.is_ok());
+
+        let (tx_events, _) = async_channel::unbounded();
+
+        let mut rpc = MockHoprIndexerOps::new();
+        rpc.expect_block_number().returning(move || Ok(5));
+        rpc.expect_block_logs().returning(move |block_number, _| {
+            Ok(logs
+                .iter()
+                .filter(|log| log.block_number == block_number)
+                .cloned()
+                .map(hopr_chain_rpc::Log::from)
+                .collect())
+        });
+        rpc.expect_try_stream_logs().never();
+
+        let mut handlers = MockChainLogHandler::new();
+        handlers.expect_contract_addresses().return_const(vec![addr]);
+        handlers
+            .expect_contract_address_topics()
+            .withf(move |x| x == &addr)
+            .return_const(vec![B256::from_slice(topic.as_ref())]);
+        handlers.expect_collect_block_events().never();
+
+        let indexer_cfg = IndexerConfig {
+            start_block_number: 0,
+            fast_sync: true,
+            ..Default::default()
+        };
+        let indexer = Indexer::new(rpc, handlers, db.clone(), indexer_cfg, tx_events).without_panic_on_completion();
+
+        assert!(matches!(
+            indexer.start().await,
+            Err(CoreEthereumIndexerError::ValidationError(_))
+        ));
+        assert_eq!(db.get_logs_block_numbers(None, None, Some(true)).await?.len(), 0);
+
+        Ok(())
+    }
+
     #[test_log::test(tokio::test)]
     async fn test_indexer_fast_sync_full_with_resume() -> anyhow::Result<()> {
         let db = HoprDb::new_in_memory(ChainKeypair::random()).await?;
@@ -845,7 +1413,7 @@ mod tests {
 
             assert!(db.ensure_logs_origin(vec![(addr, topic)]).await.is_ok());
 
-            for log in logs {
+            for log in logs.clone() {
                 assert!(db.store_log(log).await.is_ok());
             }
             assert!(db.update_logs_checksums().await.is_ok());
@@ -858,6 +1426,14 @@ mod tests {
             let head_block = 5;
             let mut rpc = MockHoprIndexerOps::new();
             rpc.expect_block_number().returning(move || Ok(head_block));
+            rpc.expect_block_logs().times(2).returning(move |block_number, _| {
+                Ok(logs
+                    .iter()
+                    .filter(|log| log.block_number == block_number)
+                    .cloned()
+                    .map(hopr_chain_rpc::Log::from)
+                    .collect())
+            });
             rpc.expect_try_stream_logs()
                 .times(1)
                 .withf(move |x: &u64, _y: &hopr_chain_rpc::LogFilter| *x == 3)
@@ -878,6 +1454,7 @@ mod tests {
             let indexer_cfg = IndexerConfig {
                 start_block_number: 0,
                 fast_sync: true,
+                ..Default::default()
             };
             let indexer = Indexer::new(rpc, handlers, db.clone(), indexer_cfg, tx_events).without_panic_on_completion();
             let (indexing, _) = join!(indexer.start(), async move {
@@ -959,6 +1536,7 @@ mod tests {
             let indexer_cfg = IndexerConfig {
                 start_block_number: 0,
                 fast_sync: true,
+                ..Default::default()
             };
             let indexer = Indexer::new(rpc, handlers, db.clone(), indexer_cfg, tx_events).without_panic_on_completion();
             let (indexing, _) = join!(indexer.start(), async move {
@@ -1011,6 +1589,8 @@ mod tests {
         for _ in 0..(blocks.len() as u64) {
             rpc.expect_block_number().returning(move || Ok(head_block));
         }
+        rpc.expect_block_header()
+            .returning(|block_number| Ok(Some(build_block_header(block_number, u64::MAX))));
 
         for block in blocks.iter() {
             assert!(tx.start_send(block.clone()).is_ok());
@@ -1083,6 +1663,8 @@ mod tests {
         rpc.expect_block_number()
             .times(3)
             .returning(move || Ok(last_processed_block + 1));
+        rpc.expect_block_header()
+            .returning(|block_number| Ok(Some(build_block_header(block_number, u64::MAX))));
 
         let block = BlockWithLogs {
             block_id: last_processed_block + 1,
@@ -1101,6 +1683,7 @@ mod tests {
         let indexer_cfg = IndexerConfig {
             start_block_number: 0,
             fast_sync: false,
+            ..Default::default()
         };
 
         let (tx_events, _) = async_channel::unbounded();
@@ -1109,4 +1692,230 @@ mod tests {
 
         Ok(())
     }
+
+    #[test_log::test(tokio::test)]
+    async fn test_indexer_should_roll_back_reorganized_blocks_and_reemit_their_events() -> anyhow::Result<()> {
+        let db = HoprDb::new_in_memory(ChainKeypair::random()).await?;
+
+        let addr = Address::new(b"my address 123456789");
+        let topic = Hash::create(&[b"my topic"]);
+
+        let fork_block = Arc::new(AtomicU64::new(u64::MAX));
+        let build_block = |block_number: u64, fork_block: u64| -> anyhow::Result<BlockWithLogs> {
+            let block_hash = build_block_header(block_number, fork_block).hash;
+            Ok(BlockWithLogs {
+                block_id: block_number,
+                logs: build_announcement_logs(*ALICE, 1, block_number, 23)?
+                    .into_iter()
+                    .map(|log| SerializableLog {
+                        block_hash: block_hash.into(),
+                        ..log
+                    })
+                    .collect(),
+            })
+        };
+
+        let head_block = 10;
+        let mut rpc = MockHoprIndexerOps::new();
+        rpc.expect_block_number().returning(move || Ok(head_block));
+        let fork_block_clone = fork_block.clone();
+        rpc.expect_block_header().returning(move |block_number| {
+            Ok(Some(build_block_header(
+                block_number,
+                fork_block_clone.load(Ordering::SeqCst),
+            )))
+        });
+
+        let (mut tx_original, rx_original) = futures::channel::mpsc::unbounded::<BlockWithLogs>();
+        rpc.expect_try_stream_logs()
+            .once()
+            .withf(move |x: &u64, _y: &hopr_chain_rpc::LogFilter| *x == 0)
+            .return_once(move |_, _| Ok(Box::pin(rx_original)));
+
+        // After the reorganization is detected, indexing restarts after the last valid block
+        let (mut tx_reorged, rx_reorged) = futures::channel::mpsc::unbounded::<BlockWithLogs>();
+        rpc.expect_try_stream_logs()
+            .once()
+            .withf(move |x: &u64, _y: &hopr_chain_rpc::LogFilter| *x == 11)
+            .return_once(move |_, _| Ok(Box::pin(rx_reorged)));
+
+        let mut handlers = MockChainLogHandler::new();
+        handlers.expect_contract_addresses().return_const(vec![addr]);
+        handlers
+            .expect_contract_address_topics()
+            .withf(move |x| x == &addr)
+            .return_const(vec![B256::from_slice(topic.as_ref())]);
+        // Blocks 10, 11, and the reorganized 11 and 12
+        handlers.expect_collect_block_events().times(4).returning(|b| {
+            let block_hash = b.logs.first().map(|log| log.block_hash).unwrap_or_default();
+            Ok(vec![SignificantChainEvent {
+                tx_hash: Hash::from(block_hash),
+                event_type: RANDOM_ANNOUNCEMENT_CHAIN_EVENT.clone(),
+            }])
+        });
+
+        // The rollback rebuilds the index from block 10 only
+        handlers
+            .expect_rebuild_index()
+            .once()
+            .withf(|blocks| blocks.iter().map(|b| b.block_id).eq([10]))
+            .returning(|_| Ok(()));
+
+        tx_original.start_send(build_block(10, u64::MAX)?)?;
+        tx_original.start_send(build_block(11, u64::MAX)?)?;
+
+        let indexer_cfg = IndexerConfig {
+            start_block_number: 0,
+            fast_sync: false,
+            ..Default::default()
+        };
+        let (tx_events, rx_events) = async_channel::unbounded();
+        let indexer = Indexer::new(rpc, handlers, db.clone(), indexer_cfg, tx_events).without_panic_on_completion();
+        indexer.start().await?;
+
+        let recv_event = move || {
+            let rx_events = rx_events.clone();
+            async move {
+                tokio::time::timeout(std::time::Duration::from_secs(5), rx_events.recv())
+                    .await
+                    .map(|event| event.map(|e| e.tx_hash))
+            }
+        };
+
+        assert_eq!(build_block_header(10, u64::MAX).hash, recv_event().await??);
+        assert_eq!(build_block_header(11, u64::MAX).hash, recv_event().await??);
+
+        // Block 11 gets replaced, so the next block does not descend from the processed block 11
+        fork_block.store(11, Ordering::SeqCst);
+        tx_original.start_send(build_block(12, 11)?)?;
+        tx_reorged.start_send(build_block(11, 11)?)?;
+        tx_reorged.start_send(build_block(12, 11)?)?;
+
+        assert_eq!(build_block_header(11, 11).hash, recv_event().await??);
+        assert_eq!(build_block_header(12, 11).hash, recv_event().await??);
+
+        let logs = db.get_logs(None, None).await?;
+        assert_eq!(
+            vec![
+                build_block_header(10, 11).hash,
+                build_block_header(11, 11).hash,
+                build_block_header(12, 11).hash
+            ],
+            logs.iter().map(|log| Hash::from(log.block_hash)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![12, 11, 10],
+            db.get_latest_block_hashes(10)
+                .await?
+                .into_iter()
+                .map(|h| h.block_number)
+                .collect::<Vec<_>>()
+        );
+
+        tx_original.close_channel();
+        tx_reorged.close_channel();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rollback_correction_events_should_revert_the_channel_and_account_state() -> anyhow::Result<()> {
+        let channel = |destination: Address, balance: u32, status: ChannelStatus| {
+            ChannelEntry::new(*ALICE, destination, balance.into(), 0.into(), status, 1.into())
+        };
+        let account = |multiaddr: &str| -> anyhow::Result<AccountEntry> {
+            Ok(AccountEntry {
+                public_key: *ALICE_OKP.public(),
+                chain_addr: *ALICE,
+                entry_type: AccountType::Announced {
+                    multiaddr: multiaddr.parse()?,
+                    updated_block: 1,
+                },
+                published_at: 1,
+            })
+        };
+
+        let events = rollback_correction_events(
+            vec![
+                channel(*BOB, 10, ChannelStatus::Open),
+                channel(*CHRIS, 10, ChannelStatus::Open),
+            ],
+            vec![channel(*BOB, 5, ChannelStatus::Open)],
+            vec![account("/ip4/1.2.3.4/tcp/1")?],
+            vec![account("/ip4/1.2.3.4/tcp/2")?],
+        );
+
+        assert_eq!(
+            vec![
+                ChainEventType::ChannelBalanceDecreased(channel(*BOB, 5, ChannelStatus::Open), 5.into()),
+                ChainEventType::ChannelClosed(channel(*CHRIS, 10, ChannelStatus::Closed)),
+                ChainEventType::Announcement {
+                    peer: (*ALICE_OKP.public()).into(),
+                    address: *ALICE,
+                    multiaddresses: vec!["/ip4/1.2.3.4/tcp/2".parse()?],
+                },
+            ],
+            events
+        );
+
+        let unchanged = vec![channel(*BOB, 5, ChannelStatus::Open)];
+        assert!(
+            rollback_correction_events(
+                unchanged.clone(),
+                unchanged,
+                vec![account("/ip4/1.2.3.4/tcp/2")?],
+                vec![account("/ip4/1.2.3.4/tcp/2")?]
+            )
+            .is_empty(),
+            "unchanged state must not be corrected"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_indexer_should_restore_the_node_safe_registry_from_the_processed_logs() -> anyhow::Result<()> {
+        let db = HoprDb::new_in_memory(ChainKeypair::random()).await?;
+        let safe_registry = Address::new(b"my address 123456789");
+
+        for (address, block_number, processed) in [
+            (safe_registry, 1, true),
+            (*BOB, 2, true),
+            (safe_registry, 3, true),
+            (safe_registry, 4, false),
+        ] {
+            for log in build_announcement_logs(address, 1, block_number, 1)? {
+                db.store_log(SerializableLog {
+                    processed: Some(processed),
+                    ..log
+                })
+                .await?;
+            }
+        }
+
+        // Only the processed logs of the registry contract are replayed, and only while the registry is empty
+        let mut handlers = MockChainLogHandler::new();
+        handlers
+            .expect_collect_block_events()
+            .times(2)
+            .withf(move |b| b.block_id != 2 && b.logs.iter().all(|log| log.address == safe_registry))
+            .returning(|_| Ok(vec![]));
+
+        Indexer::<MockHoprIndexerOps, MockChainLogHandler, HoprDb>::restore_node_safe_registry(
+            &db,
+            &handlers,
+            safe_registry,
+        )
+        .await?;
+
+        db.set_node_safe(None, *ALICE, Some(*CHRIS)).await?;
+        Indexer::<MockHoprIndexerOps, MockChainLogHandler, HoprDb>::restore_node_safe_registry(
+            &db,
+            &handlers,
+            safe_registry,
+        )
+        .await?;
+
+        Ok(())
+    }
 }
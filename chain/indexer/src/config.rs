@@ -18,6 +18,20 @@ pub struct IndexerConfig {
     /// Default is `true`.
     #[default(true)]
     pub fast_sync: bool,
+
+    /// The maximum depth of a chain reorganization the indexer is able to recover from.
+    ///
+    /// Hashes of this many most recent processed blocks are kept to find the last block
+    /// that is still part of the chain after a reorganization. A deeper reorganization
+    /// is an unrecoverable error.
+    ///
+    /// Note that the depth only bounds the number of logs removed by a rollback. The channel
+    /// and account state is then rebuilt by replaying all the remaining logs, so the cost of
+    /// handling a reorganization grows with the number of indexed logs, like the fast sync does.
+    ///
+    /// Default is `128`.
+    #[default(128)]
+    pub max_reorg_depth: u64,
 }
 
 impl IndexerConfig {
@@ -35,6 +49,7 @@ impl IndexerConfig {
         Self {
             start_block_number,
             fast_sync,
+            ..Default::default()
         }
     }
 }
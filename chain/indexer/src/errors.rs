@@ -49,6 +49,9 @@ pub enum CoreEthereumIndexerError {
     #[error("Cannot deregister non-existent MFA module")]
     MFAModuleDoesNotExist,
 
+    #[error("Chain reorganization is deeper than {0} blocks")]
+    ChainReorgTooDeep(u64),
+
     #[error("Unknown smart contract. Received event from {0}")]
     UnknownContract(Address),
 
@@ -653,16 +653,25 @@ where
 
         match event {
             HoprNodeSafeRegistryEvents::RegisteredNodeSafe(registered) => {
-                if self.chain_key.public().to_address() == registered.nodeAddress.into() {
-                    info!(safe_address = %registered.safeAddress, "Node safe registered", );
-                    // NOTE: we don't store this state in the DB
-                    return Ok(Some(ChainEventType::NodeSafeRegistered(registered.safeAddress.into())));
+                let node_address: Address = registered.nodeAddress.into();
+                let safe_address: Address = registered.safeAddress.into();
+
+                // Keep track of node-to-Safe assignments so that path selection can limit relays per Safe
+                self.db
+                    .set_node_safe(Some(tx), node_address, Some(safe_address))
+                    .await?;
+
+                if self.chain_key.public().to_address() == node_address {
+                    info!(%safe_address, "Node safe registered");
+                    return Ok(Some(ChainEventType::NodeSafeRegistered(safe_address)));
                 }
             }
             HoprNodeSafeRegistryEvents::DergisteredNodeSafe(deregistered) => {
-                if self.chain_key.public().to_address() == deregistered.nodeAddress.into() {
+                let node_address: Address = deregistered.nodeAddress.into();
+                self.db.set_node_safe(Some(tx), node_address, None).await?;
+
+                if self.chain_key.public().to_address() == node_address {
                     info!("Node safe unregistered");
-                    // NOTE: we don't store this state in the DB
                 }
             }
             HoprNodeSafeRegistryEvents::DomainSeparatorUpdated(domain_separator_updated) => {
@@ -908,6 +917,39 @@ where
             })
             .await
     }
+
+    async fn rebuild_index(&self, blocks: Vec<BlockWithLogs>) -> Result<()> {
+        let myself = self.clone();
+        self.db
+            .begin_transaction()
+            .await?
+            .perform(|tx| {
+                Box::pin(async move {
+                    myself.db.clear_index_db(Some(tx)).await?;
+
+                    let mut last_block_id = None;
+                    for block in blocks {
+                        for log in block.logs {
+                            let tx_hash = Hash::from(log.tx_hash);
+                            let log_id = log.log_index;
+                            let block_id = log.block_number;
+
+                            if let Err(error) = myself.process_log_event(tx, log).await {
+                                error!(block_id, %tx_hash, log_id, %error, "error processing log in tx");
+                            }
+                        }
+                        last_block_id = Some(block.block_id);
+                    }
+
+                    if let Some(block_id) = last_block_id {
+                        myself.db.set_indexer_state_info(Some(tx), block_id as u32).await?;
+                    }
+
+                    Ok(())
+                })
+            })
+            .await
+    }
 }
 
 #[cfg(test)]
@@ -924,6 +966,7 @@ mod tests {
     };
     use anyhow::{Context, anyhow};
     use hex_literal::hex;
+    use hopr_chain_rpc::BlockWithLogs;
     use hopr_chain_types::{
         ContractAddresses,
         chain_events::{ChainEventType, NetworkRegistryStatus},
@@ -945,6 +988,7 @@ mod tests {
     use primitive_types::H256;
 
     use super::ContractEventHandlers;
+    use crate::traits::ChainLogHandler;
 
     lazy_static::lazy_static! {
         static ref SELF_PRIV_KEY: OffchainKeypair = OffchainKeypair::from_secret(&hex!("492057cf93e99b31d2a85bc5e98a9c3aa0021feec52c227cc8170e8f7d047775")).expect("lazy static keypair should be constructible");
@@ -2624,6 +2668,43 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn rebuild_index_should_replace_the_index_with_the_replayed_logs() -> anyhow::Result<()> {
+        let db = HoprDb::new_in_memory(SELF_CHAIN_KEY.clone()).await?;
+
+        let handlers = init_handlers(db.clone());
+
+        db.set_node_safe(None, *STAKE_ADDRESS, Some(*SAFE_INSTANCE_ADDR))
+            .await?;
+
+        let safe_registered_log = SerializableLog {
+            address: handlers.addresses.safe_registry,
+            topics: vec![
+                hopr_bindings::hoprnodesaferegistry::HoprNodeSafeRegistry::RegisteredNodeSafe::SIGNATURE_HASH.into(),
+                H256::from_slice(&SAFE_INSTANCE_ADDR.to_bytes32()).into(),
+                H256::from_slice(&SELF_CHAIN_ADDRESS.to_bytes32()).into(),
+            ],
+            data: ().abi_encode(),
+            block_number: 5,
+            ..test_log()
+        };
+
+        handlers
+            .rebuild_index(vec![BlockWithLogs {
+                block_id: 5,
+                logs: [safe_registered_log].into(),
+            }])
+            .await?;
+
+        assert_eq!(None, db.get_node_safe(None, *STAKE_ADDRESS).await?);
+        assert_eq!(
+            Some(*SAFE_INSTANCE_ADDR),
+            db.get_node_safe(None, *SELF_CHAIN_ADDRESS).await?
+        );
+        assert_eq!(5, db.get_indexer_state_info(None).await?.latest_block_number);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn on_node_safe_registry_registered() -> anyhow::Result<()> {
         let db = HoprDb::new_in_memory(SELF_CHAIN_KEY.clone()).await?;
@@ -2652,7 +2733,11 @@ mod tests {
 
         assert!(matches!(event_type, Some(ChainEventType::NodeSafeRegistered(addr)) if addr == *SAFE_INSTANCE_ADDR));
 
-        // Nothing to check in the DB here, since we do not track this
+        assert_eq!(
+            Some(*SAFE_INSTANCE_ADDR),
+            db.get_node_safe(None, *SELF_CHAIN_ADDRESS).await?,
+            "node safe must be tracked"
+        );
         Ok(())
     }
 
@@ -2662,7 +2747,8 @@ mod tests {
 
         let handlers = init_handlers(db.clone());
 
-        // Nothing to write to the DB here, since we do not track this
+        db.set_node_safe(None, *SELF_CHAIN_ADDRESS, Some(*SAFE_INSTANCE_ADDR))
+            .await?;
 
         let encoded_data = ().abi_encode();
 
@@ -2689,7 +2775,11 @@ mod tests {
             "there's no associated chain event type with safe deregistration"
         );
 
-        // Nothing to check in the DB here, since we do not track this
+        assert_eq!(
+            None,
+            db.get_node_safe(None, *SELF_CHAIN_ADDRESS).await?,
+            "node safe must be removed"
+        );
         Ok(())
     }
 
@@ -13,6 +13,10 @@ pub trait ChainLogHandler {
     fn contract_address_topics(&self, contract: Address) -> Vec<B256>;
 
     async fn collect_block_events(&self, block_with_logs: BlockWithLogs) -> Result<Vec<SignificantChainEvent>>;
+
+    /// Clears the index and rebuilds it from the given blocks within a single transaction,
+    /// so that the partially rebuilt index is never observed. No events are collected.
+    async fn rebuild_index(&self, blocks: Vec<BlockWithLogs>) -> Result<()>;
 }
 
 #[cfg(test)]
@@ -40,5 +44,6 @@ mock! {
         fn contract_addresses(&self) -> Vec<Address>;
         fn contract_address_topics(&self, contract: Address) -> Vec<B256>;
         async fn collect_block_events(&self, block_with_logs: BlockWithLogs) -> Result<Vec<SignificantChainEvent>>;
+        async fn rebuild_index(&self, blocks: Vec<BlockWithLogs>) -> Result<()>;
     }
 }
@@ -3,6 +3,7 @@ use alloy::{
     providers::{MulticallError, PendingTransactionError},
     transports::{RpcError as AlloyRpcError, TransportErrorKind},
 };
+use hopr_crypto_types::types::Hash;
 /// Errors produced by this crate and other error-related types.
 use thiserror::Error;
 
@@ -30,6 +31,9 @@ pub enum RpcError {
     #[error(transparent)]
     PendingTransactionError(#[from] PendingTransactionError),
 
+    #[error("transaction {0} reverted")]
+    TransactionReverted(Hash),
+
     #[error("filter does not contain any criteria")]
     FilterIsEmpty,
 
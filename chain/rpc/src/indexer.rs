@@ -12,13 +12,14 @@ use std::pin::Pin;
 use alloy::{providers::Provider, rpc::types::Filter};
 use async_stream::stream;
 use async_trait::async_trait;
-use futures::{Stream, StreamExt, stream::BoxStream};
+use futures::{Stream, StreamExt, TryStreamExt, stream::BoxStream};
+use hopr_crypto_types::types::Hash;
 #[cfg(all(feature = "prometheus", not(test)))]
 use hopr_metrics::metrics::SimpleGauge;
 use tracing::{debug, error, trace, warn};
 
 use crate::{
-    BlockWithLogs, HoprIndexerRpcOperations, Log, LogFilter,
+    BlockHeader, BlockWithLogs, HoprIndexerRpcOperations, Log, LogFilter,
     errors::{Result, RpcError, RpcError::FilterIsEmpty},
     rpc::RpcOperations,
     transport::HttpRequestor,
@@ -103,6 +104,26 @@ impl<R: HttpRequestor + 'static + Clone> HoprIndexerRpcOperations for RpcOperati
         self.get_block_number().await
     }
 
+    async fn block_header(&self, block_number: u64) -> Result<Option<BlockHeader>> {
+        Ok(self
+            .provider
+            .get_block_by_number(block_number.into())
+            .await?
+            .map(|block| BlockHeader {
+                number: block.header.number,
+                hash: Hash::from(block.header.hash.0),
+                parent_hash: Hash::from(block.header.parent_hash.0),
+            }))
+    }
+
+    async fn block_logs(&self, block_number: u64, filter: LogFilter) -> Result<Vec<Log>> {
+        if filter.is_empty() {
+            return Err(FilterIsEmpty);
+        }
+
+        self.stream_logs(filter, block_number, block_number).try_collect().await
+    }
+
     fn try_stream_logs<'a>(
         &'a self,
         start_block_number: u64,
@@ -338,6 +359,45 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_should_get_block_header() -> anyhow::Result<()> {
+        let expected_block_time = Duration::from_secs(1);
+        let anvil = hopr_chain_types::utils::create_anvil(Some(expected_block_time));
+        let chain_key_0 = ChainKeypair::from_secret(anvil.keys()[0].to_bytes().as_ref())?;
+
+        let transport_client = ReqwestTransport::new(anvil.endpoint_url());
+
+        let rpc_client = ClientBuilder::default()
+            .layer(RetryBackoffLayer::new(2, 100, 100))
+            .transport(transport_client.clone(), transport_client.guess_local());
+
+        let cfg = RpcOperationsConfig {
+            finality: 2,
+            expected_block_time,
+            gas_oracle_url: None,
+            ..RpcOperationsConfig::default()
+        };
+
+        sleep((1 + cfg.finality) * expected_block_time).await;
+
+        let rpc = RpcOperations::new(rpc_client, transport_client.client().clone(), &chain_key_0, cfg)?;
+
+        let b1 = rpc.block_number().await?;
+        let header_1 = rpc.block_header(b1).await?.context("block should exist")?;
+        let header_2 = rpc.block_header(b1 + 1).await?.context("block should exist")?;
+
+        assert_eq!(b1, header_1.number);
+        assert_eq!(b1 + 1, header_2.number);
+        assert_eq!(
+            header_1.hash, header_2.parent_hash,
+            "blocks should be linked by their hashes"
+        );
+
+        assert!(rpc.block_header(u32::MAX as u64).await?.is_none());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_try_stream_logs_should_contain_all_logs_when_opening_channel() -> anyhow::Result<()> {
         let _ = env_logger::builder().is_test(true).try_init();
@@ -13,15 +13,16 @@ extern crate core;
 use std::{
     cmp::Ordering,
     collections::BTreeSet,
-    fmt::{Display, Formatter},
+    fmt::{Debug, Display, Formatter},
+    future::{Future, IntoFuture},
     pin::Pin,
     time::Duration,
 };
 
-use alloy::{primitives::B256, providers::PendingTransaction, rpc::types::TransactionRequest};
+use alloy::{primitives::B256, rpc::types::TransactionRequest};
 use async_trait::async_trait;
 use errors::LogConversionError;
-use futures::Stream;
+use futures::{FutureExt, Stream, future::BoxFuture};
 use hopr_crypto_types::types::Hash;
 use hopr_internal_types::prelude::WinningProbability;
 use hopr_primitive_types::prelude::*;
@@ -32,8 +33,10 @@ use crate::{RetryAction::NoRetry, errors::Result};
 pub mod client;
 pub mod errors;
 pub mod indexer;
+pub mod pool;
 pub mod rpc;
 pub mod transport;
+pub mod tx_manager;
 
 pub use crate::transport::ReqwestClient;
 
@@ -277,6 +280,52 @@ impl NodeSafeModuleStatus {
     }
 }
 
+/// Transaction sent to the RPC provider via [`HoprRpcOperations::send_transaction`].
+///
+/// Awaiting it resolves to the hash of the transaction once it has the required number of confirmations.
+/// If the transaction has been replaced meanwhile (see [`TransactionManager`](tx_manager::TransactionManager)),
+/// the hash of the replacement that got mined is returned instead.
+/// Fails with [`RpcError::TransactionReverted`](errors::RpcError::TransactionReverted) if the transaction reverted.
+pub struct PendingTransaction {
+    tx_hash: Hash,
+    confirmation: BoxFuture<'static, Result<Hash>>,
+}
+
+impl PendingTransaction {
+    /// Creates a pending transaction with the given hash, confirmed once the `confirmation` future resolves.
+    pub fn new<F>(tx_hash: Hash, confirmation: F) -> Self
+    where
+        F: Future<Output = Result<Hash>> + Send + 'static,
+    {
+        Self {
+            tx_hash,
+            confirmation: confirmation.boxed(),
+        }
+    }
+
+    /// Hash of the transaction as it was sent.
+    pub fn tx_hash(&self) -> Hash {
+        self.tx_hash
+    }
+}
+
+impl Debug for PendingTransaction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PendingTransaction")
+            .field("tx_hash", &self.tx_hash)
+            .finish_non_exhaustive()
+    }
+}
+
+impl IntoFuture for PendingTransaction {
+    type IntoFuture = BoxFuture<'static, Result<Hash>>;
+    type Output = Result<Hash>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        self.confirmation
+    }
+}
+
 /// Trait defining a general set of operations an RPC provider
 /// must provide to the HOPR node.
 #[async_trait]
@@ -344,6 +393,17 @@ impl BlockWithLogs {
     }
 }
 
+/// Header fields of a block, which are used to verify the continuity of the indexed chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockHeader {
+    /// Block number
+    pub number: u64,
+    /// Hash of the block
+    pub hash: Hash,
+    /// Hash of the parent block
+    pub parent_hash: Hash,
+}
+
 /// Trait with RPC provider functionality required by the Indexer.
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
@@ -351,6 +411,16 @@ pub trait HoprIndexerRpcOperations {
     /// Retrieves the latest block number.
     async fn block_number(&self) -> Result<u64>;
 
+    /// Retrieves the header of the block with the given number, or `None` if no such block exists.
+    ///
+    /// Unlike [`block_number`](HoprIndexerRpcOperations::block_number), the given block number
+    /// is not adjusted by the finality.
+    async fn block_header(&self, block_number: u64) -> Result<Option<BlockHeader>>;
+
+    /// Retrieves the logs of the block with the given number matching the given `filter`.
+    /// The function fails if the filter is empty.
+    async fn block_logs(&self, block_number: u64, filter: LogFilter) -> Result<Vec<Log>>;
+
     /// Starts streaming logs from the given `start_block_number`.
     /// If no `start_block_number` is given, the stream starts from the latest block.
     /// The given `filter` are applied to retrieve the logs, the function fails if the filter is empty.
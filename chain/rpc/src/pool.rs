@@ -0,0 +1,664 @@
+//! Pool of RPC providers, which is used as a transport of the RPC client instead of a single endpoint.
+//!
+//! The [`ProviderPool`] tracks the health, latency and the head block of each endpoint.
+//! Requests are sent to the best available endpoint and are failed over to the next ones
+//! on transport errors or throttling. Endpoints failing repeatedly are avoided for a while,
+//! as are endpoints whose head block lags behind the others.
+//!
+//! Optionally, the pool can require a quorum of endpoints to agree on the results of
+//! `eth_blockNumber` and `eth_getLogs` requests (see [`ProviderPoolConfig::quorum`]).
+//!
+//! Transactions (`eth_sendRawTransaction`) are never failed over, because a failed request might
+//! have still been accepted by the endpoint, and a resubmission would then fail.
+use std::{
+    sync::{Arc, Mutex, PoisonError},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use alloy::{
+    primitives::U64,
+    rpc::json_rpc::{RequestPacket, ResponsePacket, ResponsePayload},
+    transports::{TransportError, TransportErrorKind, TransportFut, http::Http},
+};
+use futures::{FutureExt, StreamExt, future::Either, stream::FuturesUnordered};
+use serde::{Deserialize, Serialize};
+use tower::{Service, ServiceExt};
+use tracing::{debug, warn};
+use url::Url;
+use validator::Validate;
+
+use crate::{
+    errors::{Result, RpcError},
+    transport::ReqwestClient,
+};
+
+#[cfg(all(feature = "prometheus", not(test)))]
+lazy_static::lazy_static! {
+    static ref METRIC_ENDPOINT_REQUESTS: hopr_metrics::metrics::MultiCounter = hopr_metrics::metrics::MultiCounter::new(
+        "hopr_rpc_endpoint_requests",
+        "Number of requests sent to each RPC endpoint of the provider pool and their result",
+        &["endpoint", "result"]
+    )
+    .unwrap();
+    static ref METRIC_ENDPOINT_HEAD_BLOCK: hopr_metrics::metrics::MultiGauge = hopr_metrics::metrics::MultiGauge::new(
+        "hopr_rpc_endpoint_head_block",
+        "Latest block number reported by each RPC endpoint of the provider pool",
+        &["endpoint"]
+    )
+    .unwrap();
+}
+
+/// JSON-RPC methods whose results are subject to the quorum.
+const QUORUM_METHODS: [&str; 2] = ["eth_blockNumber", "eth_getLogs"];
+
+/// JSON-RPC methods which are sent only to the best endpoint, without failing over.
+const NO_FAILOVER_METHODS: [&str; 1] = ["eth_sendRawTransaction"];
+
+/// Weight of a new latency sample in the smoothed latency of an endpoint.
+const LATENCY_SAMPLE_WEIGHT: f64 = 0.2;
+
+/// Configuration of the [`ProviderPool`].
+#[derive(Clone, Debug, PartialEq, Eq, smart_default::SmartDefault, Serialize, Deserialize, Validate)]
+#[serde(default)]
+pub struct ProviderPoolConfig {
+    /// Number of endpoints that must agree on the result of `eth_blockNumber` and `eth_getLogs` requests.
+    ///
+    /// The request is sent to all the endpoints and the result is returned as soon as this many endpoints
+    /// agree on it. For `eth_blockNumber`, the result is the lowest block number among the first responses
+    /// of this many endpoints. If set to 1, these requests are failed over like any other request.
+    ///
+    /// Default is 1.
+    #[validate(range(min = 1))]
+    #[default = 1]
+    pub quorum: usize,
+
+    /// Number of consecutive failed requests after which an endpoint is considered unhealthy.
+    ///
+    /// Default is 3.
+    #[validate(range(min = 1))]
+    #[default = 3]
+    pub max_consecutive_failures: u32,
+
+    /// Time for which an unhealthy endpoint is used only as a last resort.
+    ///
+    /// Default is 30 seconds.
+    #[default(Duration::from_secs(30))]
+    pub unhealthy_cooldown: Duration,
+
+    /// Maximum number of blocks the head of an endpoint can lag behind the highest head
+    /// reported by any endpoint, before the endpoint is considered out of sync.
+    ///
+    /// Default is 10.
+    #[default = 10]
+    pub max_head_lag: u64,
+
+    /// Timeout of a request to a single endpoint, after which the request is failed over.
+    ///
+    /// Default is 30 seconds.
+    #[default(Duration::from_secs(30))]
+    pub request_timeout: Duration,
+
+    /// List of JSON RPC errors that indicate throttling of the endpoint and are failed over.
+    ///
+    /// Default is \[429, -32005, -32016\]
+    #[default(_code = "vec![-32005, -32016, 429]")]
+    pub throttling_json_rpc_errors: Vec<i64>,
+}
+
+#[derive(Debug, Default)]
+struct EndpointHealth {
+    consecutive_failures: u32,
+    unhealthy_since: Option<Instant>,
+    latency: Option<Duration>,
+    head_block: Option<u64>,
+    requests: u64,
+    failures: u64,
+}
+
+/// Status of an endpoint of the [`ProviderPool`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EndpointStatus {
+    /// Index of the endpoint in the pool.
+    pub index: usize,
+    /// Indicates whether the endpoint is currently considered healthy.
+    pub healthy: bool,
+    /// Smoothed latency of the successful requests.
+    pub latency: Option<Duration>,
+    /// Latest block number reported by the endpoint.
+    pub head_block: Option<u64>,
+    /// Total number of requests sent to the endpoint.
+    pub requests: u64,
+    /// Total number of failed requests sent to the endpoint.
+    pub failures: u64,
+}
+
+#[derive(Debug)]
+struct Endpoint<S> {
+    transport: S,
+    health: Mutex<EndpointHealth>,
+}
+
+/// Transport of the RPC client that distributes the requests among multiple RPC endpoints.
+///
+/// The endpoints are ordered by their availability and latency, and a request is sent to the next endpoint
+/// whenever the previous one fails or is throttled.
+#[derive(Debug)]
+pub struct ProviderPool<S> {
+    endpoints: Arc<Vec<Endpoint<S>>>,
+    cfg: ProviderPoolConfig,
+}
+
+impl<S> Clone for ProviderPool<S> {
+    fn clone(&self) -> Self {
+        Self {
+            endpoints: self.endpoints.clone(),
+            cfg: self.cfg.clone(),
+        }
+    }
+}
+
+impl ProviderPool<Http<ReqwestClient>> {
+    /// Creates a pool of HTTP transports to the given RPC endpoint URLs.
+    pub fn http(urls: impl IntoIterator<Item = Url>, cfg: ProviderPoolConfig) -> Result<Self> {
+        Self::new(urls.into_iter().map(Http::new).collect(), cfg)
+    }
+
+    /// Indicates whether all the endpoints of the pool are local, based on their hostnames.
+    pub fn guess_local(&self) -> bool {
+        self.endpoints.iter().all(|e| e.transport.guess_local())
+    }
+}
+
+impl<S> ProviderPool<S> {
+    /// Creates a pool of the given transports, each representing a single RPC endpoint.
+    ///
+    /// The order of the transports determines their preference until their latencies are known.
+    pub fn new(transports: Vec<S>, cfg: ProviderPoolConfig) -> Result<Self> {
+        cfg.validate().map_err(|e| RpcError::Other(e.to_string()))?;
+
+        if transports.is_empty() {
+            return Err(RpcError::Other(
+                "provider pool must contain at least one endpoint".into(),
+            ));
+        }
+
+        if cfg.quorum > transports.len() {
+            return Err(RpcError::Other(format!(
+                "quorum of {} cannot be reached with {} endpoints",
+                cfg.quorum,
+                transports.len()
+            )));
+        }
+
+        Ok(Self {
+            endpoints: Arc::new(
+                transports
+                    .into_iter()
+                    .map(|transport| Endpoint {
+                        transport,
+                        health: Default::default(),
+                    })
+                    .collect(),
+            ),
+            cfg,
+        })
+    }
+
+    /// Returns the current status of all the endpoints in the pool.
+    pub fn status(&self) -> Vec<EndpointStatus> {
+        self.endpoints
+            .iter()
+            .enumerate()
+            .map(|(index, endpoint)| {
+                let health = endpoint.health.lock().unwrap_or_else(PoisonError::into_inner);
+                EndpointStatus {
+                    index,
+                    healthy: !self.is_unhealthy(&health),
+                    latency: health.latency,
+                    head_block: health.head_block,
+                    requests: health.requests,
+                    failures: health.failures,
+                }
+            })
+            .collect()
+    }
+
+    fn is_unhealthy(&self, health: &EndpointHealth) -> bool {
+        health
+            .unhealthy_since
+            .is_some_and(|since| since.elapsed() < self.cfg.unhealthy_cooldown)
+    }
+
+    /// Indices of the endpoints in the order they should be tried.
+    ///
+    /// Unhealthy and out-of-sync endpoints go last, otherwise the endpoints with lower latency go first.
+    fn ordered_endpoints(&self) -> Vec<usize> {
+        let healths = self
+            .endpoints
+            .iter()
+            .map(|e| {
+                let health = e.health.lock().unwrap_or_else(PoisonError::into_inner);
+                (self.is_unhealthy(&health), health.head_block, health.latency)
+            })
+            .collect::<Vec<_>>();
+
+        let best_head = healths.iter().filter_map(|(_, head, _)| *head).max();
+
+        let mut indices = (0..self.endpoints.len()).collect::<Vec<_>>();
+        indices.sort_by_key(|i| {
+            let (unhealthy, head, latency) = healths[*i];
+            let out_of_sync = head
+                .zip(best_head)
+                .is_some_and(|(head, best)| best.saturating_sub(head) > self.cfg.max_head_lag);
+            (unhealthy || out_of_sync, latency.unwrap_or_default())
+        });
+        indices
+    }
+
+    fn record_success(&self, index: usize, latency: Duration, head_block: Option<u64>) {
+        let mut health = self.endpoints[index]
+            .health
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        health.requests += 1;
+        health.consecutive_failures = 0;
+        if health.unhealthy_since.take().is_some() {
+            debug!(endpoint = index, "RPC endpoint is healthy again");
+        }
+
+        health.latency = Some(match health.latency {
+            Some(current) => current.mul_f64(1.0 - LATENCY_SAMPLE_WEIGHT) + latency.mul_f64(LATENCY_SAMPLE_WEIGHT),
+            None => latency,
+        });
+
+        if let Some(head_block) = head_block {
+            health.head_block = Some(head_block);
+
+            #[cfg(all(feature = "prometheus", not(test)))]
+            METRIC_ENDPOINT_HEAD_BLOCK.set(&[&index.to_string()], head_block as f64);
+        }
+
+        #[cfg(all(feature = "prometheus", not(test)))]
+        METRIC_ENDPOINT_REQUESTS.increment(&[&index.to_string(), "success"]);
+    }
+
+    fn record_failure(&self, index: usize) {
+        let mut health = self.endpoints[index]
+            .health
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        health.requests += 1;
+        health.failures += 1;
+        health.consecutive_failures += 1;
+        if health.consecutive_failures >= self.cfg.max_consecutive_failures {
+            if !self.is_unhealthy(&health) {
+                warn!(
+                    endpoint = index,
+                    failures = health.consecutive_failures,
+                    "RPC endpoint is unhealthy"
+                );
+            }
+            health.unhealthy_since = Some(Instant::now());
+        }
+
+        #[cfg(all(feature = "prometheus", not(test)))]
+        METRIC_ENDPOINT_REQUESTS.increment(&[&index.to_string(), "failure"]);
+    }
+
+    fn is_throttled(&self, response: &ResponsePacket) -> bool {
+        match response {
+            ResponsePacket::Single(response) => match &response.payload {
+                ResponsePayload::Failure(error) => self.cfg.throttling_json_rpc_errors.contains(&error.code),
+                ResponsePayload::Success(_) => false,
+            },
+            ResponsePacket::Batch(_) => false,
+        }
+    }
+}
+
+/// Extracts the block number from a successful response to `eth_blockNumber`.
+fn head_block(method: Option<&str>, response: &ResponsePacket) -> Option<u64> {
+    match (method, response) {
+        (Some("eth_blockNumber"), ResponsePacket::Single(response)) => match &response.payload {
+            ResponsePayload::Success(result) => serde_json::from_str::<U64>(result.get()).ok().map(|n| n.to()),
+            ResponsePayload::Failure(_) => None,
+        },
+        _ => None,
+    }
+}
+
+fn request_method(request: &RequestPacket) -> Option<&str> {
+    match request {
+        RequestPacket::Single(request) => Some(request.method()),
+        RequestPacket::Batch(_) => None,
+    }
+}
+
+/// Result of a request, comparable across the endpoints when looking for a quorum.
+#[derive(Debug, PartialEq)]
+enum QuorumVote {
+    BlockNumber(u64),
+    Logs(Vec<alloy::rpc::types::Log>),
+}
+
+impl QuorumVote {
+    fn new(method: &str, response: &ResponsePacket) -> Option<Self> {
+        let ResponsePacket::Single(response) = response else {
+            return None;
+        };
+        let ResponsePayload::Success(result) = &response.payload else {
+            return None;
+        };
+
+        match method {
+            "eth_blockNumber" => serde_json::from_str::<U64>(result.get())
+                .ok()
+                .map(|n| Self::BlockNumber(n.to())),
+            "eth_getLogs" => serde_json::from_str(result.get()).ok().map(Self::Logs),
+            _ => None,
+        }
+    }
+}
+
+impl<S> ProviderPool<S>
+where
+    S: Service<RequestPacket, Response = ResponsePacket, Error = TransportError, Future = TransportFut<'static>>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+{
+    /// Sends the request to a single endpoint, subject to the request timeout.
+    async fn send_to(
+        &self,
+        index: usize,
+        request: RequestPacket,
+    ) -> std::result::Result<ResponsePacket, TransportError> {
+        let response = self.endpoints[index].transport.clone().oneshot(request);
+        let timeout = futures_timer::Delay::new(self.cfg.request_timeout);
+        futures::pin_mut!(response);
+
+        match futures::future::select(response, timeout).await {
+            Either::Left((response, _)) => response,
+            Either::Right(_) => Err(TransportErrorKind::custom_str("RPC endpoint request timed out")),
+        }
+    }
+
+    async fn failover_call(self, request: RequestPacket) -> std::result::Result<ResponsePacket, TransportError> {
+        let method = request_method(&request).map(str::to_owned);
+        let mut last_response = None;
+        let mut last_error = None;
+
+        let mut endpoints = self.ordered_endpoints();
+        if method.as_deref().is_some_and(|m| NO_FAILOVER_METHODS.contains(&m)) {
+            endpoints.truncate(1);
+        }
+
+        for index in endpoints {
+            let start = Instant::now();
+            match self.send_to(index, request.clone()).await {
+                Ok(response) if self.is_throttled(&response) => {
+                    warn!(endpoint = index, ?method, "RPC endpoint is throttling, failing over");
+                    self.record_failure(index);
+                    last_response = Some(response);
+                }
+                Ok(response) => {
+                    self.record_success(index, start.elapsed(), head_block(method.as_deref(), &response));
+                    return Ok(response);
+                }
+                Err(error) => {
+                    warn!(endpoint = index, ?method, %error, "RPC endpoint request failed, failing over");
+                    self.record_failure(index);
+                    last_error = Some(error);
+                }
+            }
+        }
+
+        match (last_response, last_error) {
+            (Some(response), _) => Ok(response),
+            (None, Some(error)) => Err(error),
+            (None, None) => Err(TransportErrorKind::custom_str("no RPC endpoints available")),
+        }
+    }
+
+    async fn quorum_call(
+        self,
+        method: String,
+        request: RequestPacket,
+    ) -> std::result::Result<ResponsePacket, TransportError> {
+        let mut responses = self
+            .ordered_endpoints()
+            .into_iter()
+            .map(|index| {
+                let request = request.clone();
+                let pool = self.clone();
+                async move {
+                    let start = Instant::now();
+                    let response = pool.send_to(index, request).await;
+                    (index, start.elapsed(), response)
+                }
+            })
+            .collect::<FuturesUnordered<_>>();
+
+        // Responses are evaluated as they arrive, the remaining requests are dropped once the quorum is reached
+        let mut votes: Vec<(QuorumVote, ResponsePacket)> = Vec::new();
+        while let Some((index, latency, response)) = responses.next().await {
+            match response {
+                Ok(response) if self.is_throttled(&response) => self.record_failure(index),
+                Ok(response) => {
+                    self.record_success(index, latency, head_block(Some(method.as_str()), &response));
+                    if let Some(vote) = QuorumVote::new(&method, &response) {
+                        votes.push((vote, response));
+                    }
+                }
+                Err(error) => {
+                    warn!(endpoint = index, %method, %error, "RPC endpoint request failed");
+                    self.record_failure(index);
+                }
+            }
+
+            if let Some(agreed) = self.agreed_vote(&method, &mut votes) {
+                return Ok(agreed);
+            }
+        }
+
+        Err(TransportErrorKind::custom_str(&format!(
+            "{method} did not reach the quorum of {} RPC endpoints",
+            self.cfg.quorum
+        )))
+    }
+
+    /// Takes the response agreed on by a quorum of the votes received so far, if any.
+    ///
+    /// For `eth_blockNumber`, this is the block number reached by a quorum of endpoints,
+    /// i.e. the lowest of the first quorum of block numbers received.
+    fn agreed_vote(&self, method: &str, votes: &mut Vec<(QuorumVote, ResponsePacket)>) -> Option<ResponsePacket> {
+        if method == "eth_blockNumber" {
+            if votes.len() < self.cfg.quorum {
+                return None;
+            }
+            votes
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, (vote, _))| match vote {
+                    QuorumVote::BlockNumber(n) => *n,
+                    _ => u64::MAX,
+                })
+                .map(|(i, _)| i)
+                .map(|i| votes.swap_remove(i).1)
+        } else {
+            votes
+                .iter()
+                .position(|(vote, _)| votes.iter().filter(|(other, _)| other == vote).count() >= self.cfg.quorum)
+                .map(|i| votes.swap_remove(i).1)
+        }
+    }
+}
+
+impl<S> Service<RequestPacket> for ProviderPool<S>
+where
+    S: Service<RequestPacket, Response = ResponsePacket, Error = TransportError, Future = TransportFut<'static>>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+{
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+    type Response = ResponsePacket;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<std::result::Result<(), Self::Error>> {
+        // The readiness of each endpoint is awaited when the request is sent to it
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let pool = self.clone();
+        let quorum_method = request_method(&request)
+            .filter(|method| pool.cfg.quorum > 1 && QUORUM_METHODS.contains(method))
+            .map(str::to_owned);
+
+        match quorum_method {
+            Some(method) => pool.quorum_call(method, request).boxed(),
+            None => pool.failover_call(request).boxed(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        providers::{Provider, ProviderBuilder},
+        rpc::{client::ClientBuilder, types::Filter},
+    };
+    use hopr_chain_types::utils::create_anvil;
+
+    use super::*;
+
+    const UNREACHABLE_ENDPOINT: &str = "http://127.0.0.1:1";
+
+    #[tokio::test]
+    async fn provider_pool_should_fail_over_to_reachable_endpoint() -> anyhow::Result<()> {
+        let anvil = create_anvil(None);
+
+        let pool = ProviderPool::http(
+            [UNREACHABLE_ENDPOINT.parse()?, anvil.endpoint_url()],
+            ProviderPoolConfig {
+                max_consecutive_failures: 1,
+                ..Default::default()
+            },
+        )?;
+        let provider = ProviderBuilder::new().connect_client(ClientBuilder::default().transport(pool.clone(), true));
+
+        assert_eq!(0, provider.get_block_number().await?);
+
+        let status = pool.status();
+        assert!(!status[0].healthy);
+        assert_eq!(1, status[0].failures);
+        assert!(status[1].healthy);
+        assert_eq!(Some(0), status[1].head_block);
+
+        // The unhealthy endpoint is no longer tried first
+        assert_eq!(0, provider.get_block_number().await?);
+        assert_eq!(1, pool.status()[0].requests);
+        assert_eq!(2, pool.status()[1].requests);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn provider_pool_should_require_quorum_on_block_number_and_logs() -> anyhow::Result<()> {
+        let anvils = [create_anvil(None), create_anvil(None), create_anvil(None)];
+
+        let pool = ProviderPool::http(
+            anvils.iter().map(|anvil| anvil.endpoint_url()),
+            ProviderPoolConfig {
+                quorum: 2,
+                ..Default::default()
+            },
+        )?;
+        let provider = ProviderBuilder::new().connect_client(ClientBuilder::default().transport(pool.clone(), true));
+
+        assert_eq!(0, provider.get_block_number().await?);
+        assert!(
+            provider
+                .get_logs(&Filter::new().from_block(0).to_block(0))
+                .await?
+                .is_empty()
+        );
+        assert!(pool.status().iter().all(|s| s.failures == 0));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn provider_pool_should_fail_without_quorum() -> anyhow::Result<()> {
+        let anvil = create_anvil(None);
+
+        let pool = ProviderPool::http(
+            [UNREACHABLE_ENDPOINT.parse()?, anvil.endpoint_url()],
+            ProviderPoolConfig {
+                quorum: 2,
+                ..Default::default()
+            },
+        )?;
+        let provider = ProviderBuilder::new().connect_client(ClientBuilder::default().transport(pool, true));
+
+        assert!(provider.get_block_number().await.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn provider_pool_should_not_fail_over_transactions() -> anyhow::Result<()> {
+        let anvil = create_anvil(None);
+
+        let pool = ProviderPool::http(
+            [UNREACHABLE_ENDPOINT.parse()?, anvil.endpoint_url()],
+            Default::default(),
+        )?;
+        let provider = ProviderBuilder::new().connect_client(ClientBuilder::default().transport(pool.clone(), true));
+
+        assert!(provider.send_raw_transaction(&[0x00]).await.is_err());
+        assert_eq!(1, pool.status()[0].requests);
+        assert_eq!(0, pool.status()[1].requests);
+
+        Ok(())
+    }
+
+    #[test]
+    fn provider_pool_config_should_deserialize_with_defaults() -> anyhow::Result<()> {
+        let cfg: ProviderPoolConfig = serde_json::from_str(r#"{"quorum": 2}"#)?;
+
+        assert_eq!(
+            ProviderPoolConfig {
+                quorum: 2,
+                ..Default::default()
+            },
+            cfg
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn provider_pool_should_not_be_created_with_unreachable_quorum() -> anyhow::Result<()> {
+        let urls: Vec<Url> = vec![UNREACHABLE_ENDPOINT.parse()?];
+
+        assert!(ProviderPool::http(urls.clone(), Default::default()).is_ok());
+        assert!(ProviderPool::http(Vec::<Url>::new(), Default::default()).is_err());
+        assert!(
+            ProviderPool::http(
+                urls,
+                ProviderPoolConfig {
+                    quorum: 2,
+                    ..Default::default()
+                }
+            )
+            .is_err()
+        );
+
+        Ok(())
+    }
+}
@@ -7,8 +7,9 @@ use std::{sync::Arc, time::Duration};
 use SafeSingleton::SafeSingletonInstance;
 use alloy::{
     network::EthereumWallet,
+    primitives::B256,
     providers::{
-        CallItemBuilder, Identity, PendingTransaction, Provider, ProviderBuilder, RootProvider,
+        CallItemBuilder, Identity, Provider, ProviderBuilder, RootProvider,
         fillers::{
             BlobGasFiller, CachedNonceManager, ChainIdFiller, FillProvider, GasFiller, JoinFill, NonceFiller,
             WalletFiller,
@@ -22,9 +23,13 @@ use alloy::{
     sol,
 };
 use async_trait::async_trait;
+use hopr_async_runtime::prelude::sleep;
 use hopr_bindings::hoprnodemanagementmodule::HoprNodeManagementModule::{self, HoprNodeManagementModuleInstance};
 use hopr_chain_types::{ContractAddresses, ContractInstances, NetworkRegistryProxy};
-use hopr_crypto_types::keypairs::{ChainKeypair, Keypair};
+use hopr_crypto_types::{
+    keypairs::{ChainKeypair, Keypair},
+    types::Hash,
+};
 use hopr_internal_types::prelude::{EncodedWinProb, WinningProbability};
 use hopr_primitive_types::prelude::*;
 use primitive_types::U256;
@@ -35,10 +40,11 @@ use validator::Validate;
 
 // use crate::middleware::GnosisScan;
 use crate::{
-    HoprRpcOperations, NodeSafeModuleStatus,
+    HoprRpcOperations, NodeSafeModuleStatus, PendingTransaction,
     client::GasOracleFiller,
     errors::{Result, RpcError},
     transport::HttpRequestor,
+    tx_manager::{TransactionManager, TransactionManagerConfig},
 };
 
 // define basic safe abi
@@ -133,6 +139,8 @@ pub struct RpcOperations<R: HttpRequestor + 'static + Clone> {
     contract_instances: Arc<ContractInstances<HoprProvider<R>>>,
     node_module: HoprNodeManagementModuleInstance<HoprProvider<R>>,
     node_safe: SafeSingletonInstance<HoprProvider<R>>,
+    me: Address,
+    tx_manager: Option<TransactionManager<R>>,
 }
 
 impl<R: HttpRequestor + 'static + Clone> RpcOperations<R> {
@@ -165,11 +173,27 @@ impl<R: HttpRequestor + 'static + Clone> RpcOperations<R> {
             )),
             node_module: HoprNodeManagementModule::new(cfg.module_address.into(), provider.clone()),
             node_safe: SafeSingleton::new(cfg.safe_address.into(), provider.clone()),
+            me: chain_key.public().to_address(),
+            tx_manager: None,
             cfg,
             provider: Arc::new(provider),
         })
     }
 
+    /// Sends all transactions through a [`TransactionManager`] with the given configuration,
+    /// unless it is disabled.
+    pub fn with_transaction_manager(mut self, cfg: TransactionManagerConfig) -> Self {
+        self.tx_manager = cfg
+            .enabled
+            .then(|| TransactionManager::new(self.provider.clone(), self.me, cfg));
+        self
+    }
+
+    /// Returns the [`TransactionManager`] if enabled.
+    pub fn transaction_manager(&self) -> Option<&TransactionManager<R>> {
+        self.tx_manager.as_ref()
+    }
+
     pub(crate) async fn get_block_number(&self) -> Result<u64> {
         Ok(self
             .provider
@@ -383,15 +407,63 @@ impl<R: HttpRequestor + 'static + Clone> HoprRpcOperations for RpcOperations<R>
     }
 
     async fn send_transaction(&self, tx: TransactionRequest) -> Result<PendingTransaction> {
-        let sent_tx = self.provider.send_transaction(tx).await?;
-
-        let receipt = sent_tx
-            .with_required_confirmations(self.cfg.finality as u64)
-            .register()
-            .await
-            .map_err(RpcError::PendingTransactionError)?;
+        let provider = self.provider.clone();
+        let finality = self.cfg.finality as u64;
+        let polling_interval = self.cfg.tx_polling_interval;
+
+        match &self.tx_manager {
+            // The transaction can get replaced, so whichever broadcast of its nonce gets mined is awaited
+            Some(tx_manager) => {
+                let (tx_hash, mined) = tx_manager.send(tx).await?;
+                Ok(PendingTransaction::new(tx_hash, async move {
+                    let mined_tx_hash = mined
+                        .await
+                        .map_err(|_| RpcError::Other(format!("transaction {tx_hash} is no longer tracked")))?;
+                    await_confirmations(&provider, mined_tx_hash, finality, polling_interval).await
+                }))
+            }
+            None => {
+                let sent_tx = self.provider.send_transaction(tx).await?;
+                let tx_hash = Hash::from(sent_tx.tx_hash().0);
+                let confirmation = sent_tx
+                    .with_required_confirmations(finality)
+                    .register()
+                    .await
+                    .map_err(RpcError::PendingTransactionError)?;
+
+                Ok(PendingTransaction::new(tx_hash, async move {
+                    confirmation.await.map_err(RpcError::PendingTransactionError)?;
+                    await_confirmations(&provider, tx_hash, finality, polling_interval).await
+                }))
+            }
+        }
+    }
+}
 
-        Ok(receipt)
+/// Polls the receipt of the mined transaction until the transaction has the given number of confirmations.
+///
+/// Fails if the transaction reverted.
+async fn await_confirmations<R: HttpRequestor + 'static + Clone>(
+    provider: &HoprProvider<R>,
+    tx_hash: Hash,
+    confirmations: u64,
+    polling_interval: Duration,
+) -> Result<Hash> {
+    loop {
+        if let Some(receipt) = provider
+            .get_transaction_receipt(B256::from_slice(tx_hash.as_ref()))
+            .await?
+        {
+            let mined_at = receipt.block_number.unwrap_or_default();
+            if provider.get_block_number().await? + 1 >= mined_at + confirmations {
+                return if receipt.status() {
+                    Ok(tx_hash)
+                } else {
+                    Err(RpcError::TransactionReverted(tx_hash))
+                };
+            }
+        }
+        sleep(polling_interval).await;
     }
 }
 
@@ -430,7 +502,7 @@ mod tests {
     // 0.1 (anvil) ETH
 
     pub async fn wait_until_tx(pending: PendingTransaction, timeout: Duration) {
-        let tx_hash = *pending.tx_hash();
+        let tx_hash = pending.tx_hash();
         sleep(timeout).await;
         pending
             .await
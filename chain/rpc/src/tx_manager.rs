@@ -0,0 +1,682 @@
+//! Lifecycle management of the transactions sent by the node.
+//!
+//! The [`TransactionManager`] assigns nonces to the outgoing transactions and tracks each of them until it is mined:
+//! - transactions pending for longer than [`TransactionManagerConfig::fee_bump_after`] are re-broadcast with bumped
+//!   fees,
+//! - transactions dropped by the RPC provider are re-broadcast,
+//! - the local nonce is re-synchronized with the chain whenever it gets out of sync.
+//!
+//! Every replacement of a transaction is reported as [`TransactionReplacement`], so that anything
+//! awaiting the original transaction can follow its replacement instead.
+//! The [`PendingTransaction`](crate::PendingTransaction) of a sent transaction follows its nonce,
+//! so it resolves even if the transaction has been replaced.
+//! The in-flight transactions can be persisted to a file (see [`TransactionManagerConfig::store_path`]),
+//! so they are tracked again after a restart.
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::Duration,
+};
+
+use alloy::{
+    consensus::Transaction,
+    network::Ethereum,
+    primitives::B256,
+    providers::{PendingTransactionBuilder, Provider, SendableTx},
+    rpc::types::TransactionRequest,
+};
+use futures::{
+    channel::{mpsc::UnboundedSender, oneshot},
+    lock::Mutex,
+};
+use hopr_async_runtime::prelude::sleep;
+use hopr_crypto_types::types::Hash;
+use hopr_platform::{
+    file::native::{read_file, write},
+    time::native::current_time,
+};
+use hopr_primitive_types::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_with::{DurationMilliSeconds, serde_as};
+use tracing::{debug, error, info, warn};
+use validator::Validate;
+
+use crate::{
+    errors::{Result, RpcError},
+    rpc::HoprProvider,
+    transport::HttpRequestor,
+};
+
+#[cfg(all(feature = "prometheus", not(test)))]
+lazy_static::lazy_static! {
+    static ref METRIC_IN_FLIGHT_TXS: hopr_metrics::metrics::SimpleGauge = hopr_metrics::metrics::SimpleGauge::new(
+        "hopr_rpc_in_flight_transactions",
+        "Number of sent transactions which are not mined yet"
+    )
+    .unwrap();
+    static ref METRIC_TX_REBROADCASTS: hopr_metrics::metrics::MultiCounter = hopr_metrics::metrics::MultiCounter::new(
+        "hopr_rpc_transaction_rebroadcasts",
+        "Number of re-broadcast transactions and the reason of the re-broadcast",
+        &["reason"]
+    )
+    .unwrap();
+}
+
+/// Configuration of the [`TransactionManager`].
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, smart_default::SmartDefault, Serialize, Deserialize, Validate)]
+pub struct TransactionManagerConfig {
+    /// Enables the tracking of the sent transactions.
+    ///
+    /// Defaults to false.
+    #[default = false]
+    pub enabled: bool,
+
+    /// Time after which a transaction that is still pending is re-broadcast with bumped fees.
+    ///
+    /// Defaults to 60 seconds.
+    #[serde_as(as = "DurationMilliSeconds<u64>")]
+    #[default(Duration::from_secs(60))]
+    pub fee_bump_after: Duration,
+
+    /// Percentage by which the fees are bumped on each re-broadcast.
+    ///
+    /// Most nodes do not accept a replacement transaction with fees bumped by less than 10%.
+    ///
+    /// Defaults to 20.
+    #[validate(range(min = 10))]
+    #[default = 20]
+    pub fee_bump_percent: u32,
+
+    /// Maximum number of fee bumps of a single transaction.
+    ///
+    /// Defaults to 5.
+    #[default = 5]
+    pub max_fee_bumps: u32,
+
+    /// Interval in which the in-flight transactions are checked.
+    ///
+    /// Defaults to 15 seconds.
+    #[serde_as(as = "DurationMilliSeconds<u64>")]
+    #[default(Duration::from_secs(15))]
+    pub check_interval: Duration,
+
+    /// Path to the file where the in-flight transactions are persisted.
+    ///
+    /// Defaults to `None`, which means the in-flight transactions are not persisted.
+    #[serde(default)]
+    pub store_path: Option<String>,
+}
+
+/// Transaction tracked by the [`TransactionManager`] until it is mined.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TrackedTransaction {
+    /// Request of the latest broadcast, with the nonce, gas limit and fees filled in.
+    pub request: TransactionRequest,
+    /// Hash of the latest broadcast.
+    pub tx_hash: Hash,
+    /// Hashes of the previous broadcasts, which were replaced.
+    pub replaced: Vec<Hash>,
+    /// Number of fee bumps so far.
+    pub fee_bumps: u32,
+    /// Time of the latest broadcast since the Unix epoch.
+    pub broadcast_at: Duration,
+}
+
+impl TrackedTransaction {
+    /// Nonce of the transaction.
+    pub fn nonce(&self) -> u64 {
+        self.request.nonce.unwrap_or_default()
+    }
+}
+
+/// Replacement of a transaction by another transaction with the same nonce.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransactionReplacement {
+    /// Hash of the replaced transaction.
+    pub old_tx_hash: Hash,
+    /// Hash of the transaction that replaced it.
+    pub new_tx_hash: Hash,
+}
+
+#[derive(Debug, Default)]
+struct TransactionManagerState {
+    next_nonce: Option<u64>,
+    in_flight: BTreeMap<u64, TrackedTransaction>,
+    /// Notified with the hash of the broadcast that got mined, keyed by the nonce.
+    mined_notifiers: HashMap<u64, oneshot::Sender<Hash>>,
+}
+
+/// Assigns nonces to the sent transactions and tracks them until they are mined.
+///
+/// See the [module documentation](self) for details.
+#[derive(Debug, Clone)]
+pub struct TransactionManager<R: HttpRequestor + 'static + Clone> {
+    provider: Arc<HoprProvider<R>>,
+    sender: Address,
+    cfg: TransactionManagerConfig,
+    state: Arc<Mutex<TransactionManagerState>>,
+}
+
+impl<R: HttpRequestor + 'static + Clone> TransactionManager<R> {
+    pub(crate) fn new(provider: Arc<HoprProvider<R>>, sender: Address, cfg: TransactionManagerConfig) -> Self {
+        let in_flight = cfg.store_path.as_deref().map(load_transactions).unwrap_or_default();
+
+        if !in_flight.is_empty() {
+            info!(count = in_flight.len(), "loaded persisted in-flight transactions");
+        }
+
+        Self {
+            provider,
+            sender,
+            cfg,
+            state: Arc::new(Mutex::new(TransactionManagerState {
+                next_nonce: None,
+                in_flight,
+                mined_notifiers: HashMap::new(),
+            })),
+        }
+    }
+
+    /// Returns all transactions which are not mined yet, ordered by their nonce.
+    pub async fn in_flight_transactions(&self) -> Vec<TrackedTransaction> {
+        self.state.lock().await.in_flight.values().cloned().collect()
+    }
+
+    /// Assigns the next nonce to the transaction, broadcasts it and starts tracking it.
+    ///
+    /// Returns the hash of the transaction and a receiver of the hash of its broadcast that eventually got mined.
+    pub(crate) async fn send(&self, mut tx: TransactionRequest) -> Result<(Hash, oneshot::Receiver<Hash>)> {
+        let mut state = self.state.lock().await;
+
+        let nonce = self.next_nonce(&mut state).await?;
+        tx.nonce = Some(nonce);
+
+        match self.broadcast(tx).await {
+            Ok((request, pending)) => {
+                let tx_hash = Hash::from(pending.tx_hash().0);
+                debug!(nonce, %tx_hash, "tracking sent transaction");
+
+                state.next_nonce = Some(nonce + 1);
+                state.in_flight.insert(
+                    nonce,
+                    TrackedTransaction {
+                        request,
+                        tx_hash,
+                        replaced: Vec::new(),
+                        fee_bumps: 0,
+                        broadcast_at: current_time().as_unix_timestamp(),
+                    },
+                );
+                self.persist(&state);
+
+                let (mined_tx, mined_rx) = oneshot::channel();
+                state.mined_notifiers.insert(nonce, mined_tx);
+
+                Ok((tx_hash, mined_rx))
+            }
+            Err(error) => {
+                // The nonce might be out of sync, so re-synchronize it before the next transaction
+                state.next_nonce = None;
+                Err(error)
+            }
+        }
+    }
+
+    /// Runs the periodic checks of the in-flight transactions.
+    ///
+    /// Replacements of the transactions are sent to the given `replacements` sender.
+    pub async fn run(self, replacements: UnboundedSender<TransactionReplacement>) {
+        loop {
+            sleep(self.cfg.check_interval).await;
+
+            if let Err(error) = self.check_in_flight(&replacements).await {
+                error!(%error, "failed to check in-flight transactions");
+            }
+        }
+    }
+
+    /// Checks all in-flight transactions once:
+    /// stops tracking the mined transactions, re-broadcasts the dropped ones and bumps fees of the stuck ones.
+    ///
+    /// The state is not locked while the RPC provider is queried, so the transactions can be sent meanwhile.
+    pub(crate) async fn check_in_flight(&self, replacements: &UnboundedSender<TransactionReplacement>) -> Result<()> {
+        // All transactions with a nonce lower than the nonce of the latest block are mined
+        let mined_nonce = self.provider.get_transaction_count(self.sender.into()).latest().await?;
+        let pending_nonce = self
+            .provider
+            .get_transaction_count(self.sender.into())
+            .pending()
+            .await?;
+
+        let (mined, in_flight) = {
+            let mut state = self.state.lock().await;
+
+            let still_in_flight = state.in_flight.split_off(&mined_nonce);
+            let mined = std::mem::replace(&mut state.in_flight, still_in_flight)
+                .into_values()
+                .map(|tx| {
+                    let notifier = state.mined_notifiers.remove(&tx.nonce());
+                    (tx, notifier)
+                })
+                .collect::<Vec<_>>();
+
+            if state.in_flight.is_empty() {
+                // Re-synchronize the nonce with the chain before the next transaction
+                state.next_nonce = None;
+            } else if state.next_nonce.is_some_and(|next| next < pending_nonce) {
+                warn!(
+                    next_nonce = ?state.next_nonce,
+                    pending_nonce, "local nonce is behind the chain, re-synchronizing"
+                );
+                state.next_nonce = Some(pending_nonce);
+            }
+
+            (mined, state.in_flight.values().cloned().collect::<Vec<_>>())
+        };
+
+        for (tx, notifier) in mined {
+            self.resolve_mined(tx, notifier, replacements).await;
+        }
+
+        let now = current_time().as_unix_timestamp();
+        let mut updated = Vec::new();
+        for mut tx in in_flight {
+            let is_known = match self
+                .provider
+                .get_transaction_by_hash(B256::from_slice(tx.tx_hash.as_ref()))
+                .await
+            {
+                Ok(found) => found.is_some(),
+                Err(error) => {
+                    warn!(%error, nonce = tx.nonce(), tx_hash = %tx.tx_hash, "failed to check in-flight transaction");
+                    continue;
+                }
+            };
+
+            if !is_known {
+                warn!(nonce = tx.nonce(), tx_hash = %tx.tx_hash, "transaction was dropped, re-broadcasting");
+                self.rebroadcast(&mut tx, false, replacements).await;
+                updated.push(tx);
+            } else if now.saturating_sub(tx.broadcast_at) >= self.cfg.fee_bump_after {
+                if tx.fee_bumps < self.cfg.max_fee_bumps {
+                    debug!(nonce = tx.nonce(), tx_hash = %tx.tx_hash, "transaction is stuck, bumping fees");
+                    self.rebroadcast(&mut tx, true, replacements).await;
+                    updated.push(tx);
+                } else {
+                    warn!(nonce = tx.nonce(), tx_hash = %tx.tx_hash, "transaction is stuck after the maximum number of fee bumps");
+                }
+            }
+        }
+
+        let mut state = self.state.lock().await;
+        for tx in updated {
+            if let Some(tracked) = state.in_flight.get_mut(&tx.nonce()) {
+                *tracked = tx;
+            }
+        }
+        self.persist(&state);
+
+        Ok(())
+    }
+
+    /// Returns the nonce of the next transaction, re-synchronizing it with the chain if unknown.
+    async fn next_nonce(&self, state: &mut TransactionManagerState) -> Result<u64> {
+        if let Some(nonce) = state.next_nonce {
+            return Ok(nonce);
+        }
+
+        let pending_nonce = self
+            .provider
+            .get_transaction_count(self.sender.into())
+            .pending()
+            .await?;
+        let nonce = state
+            .in_flight
+            .keys()
+            .next_back()
+            .map_or(pending_nonce, |last| pending_nonce.max(last + 1));
+
+        debug!(nonce, "synchronized nonce with the chain");
+        state.next_nonce = Some(nonce);
+        Ok(nonce)
+    }
+
+    /// Signs and broadcasts the transaction.
+    ///
+    /// Returns the request with the nonce, gas limit and fees filled in, so it can be re-broadcast later.
+    async fn broadcast(
+        &self,
+        tx: TransactionRequest,
+    ) -> Result<(TransactionRequest, PendingTransactionBuilder<Ethereum>)> {
+        let SendableTx::Envelope(envelope) = self.provider.fill(tx.clone()).await? else {
+            return Err(RpcError::Other("transaction was not signed by the provider".into()));
+        };
+
+        let mut request = tx;
+        request.nonce = Some(envelope.nonce());
+        request.gas = Some(envelope.gas_limit());
+        if let Some(gas_price) = envelope.gas_price() {
+            request.gas_price = Some(gas_price);
+        } else {
+            request.max_fee_per_gas = Some(envelope.max_fee_per_gas());
+            request.max_priority_fee_per_gas = envelope.max_priority_fee_per_gas();
+        }
+
+        let pending = self.provider.send_tx_envelope(envelope).await?;
+        Ok((request, pending))
+    }
+
+    /// Re-broadcasts the in-flight transaction, optionally with bumped fees.
+    async fn rebroadcast(
+        &self,
+        tx: &mut TrackedTransaction,
+        bump_fees: bool,
+        replacements: &UnboundedSender<TransactionReplacement>,
+    ) {
+        let mut request = tx.request.clone();
+        if bump_fees {
+            request.gas_price = request.gas_price.map(|fee| self.bump_fee(fee));
+            request.max_fee_per_gas = request.max_fee_per_gas.map(|fee| self.bump_fee(fee));
+            request.max_priority_fee_per_gas = request.max_priority_fee_per_gas.map(|fee| self.bump_fee(fee));
+        }
+
+        match self.broadcast(request).await {
+            Ok((request, pending)) => {
+                let new_tx_hash = Hash::from(pending.tx_hash().0);
+                if new_tx_hash != tx.tx_hash {
+                    info!(nonce = tx.nonce(), old_tx_hash = %tx.tx_hash, %new_tx_hash, "transaction replaced");
+                    let _ = replacements.unbounded_send(TransactionReplacement {
+                        old_tx_hash: tx.tx_hash,
+                        new_tx_hash,
+                    });
+                    tx.replaced.push(tx.tx_hash);
+                    tx.tx_hash = new_tx_hash;
+                }
+
+                tx.request = request;
+                tx.broadcast_at = current_time().as_unix_timestamp();
+                if bump_fees {
+                    tx.fee_bumps += 1;
+                }
+
+                #[cfg(all(feature = "prometheus", not(test)))]
+                METRIC_TX_REBROADCASTS.increment(&[if bump_fees { "fee_bump" } else { "dropped" }]);
+            }
+            Err(error) => {
+                warn!(%error, nonce = tx.nonce(), tx_hash = %tx.tx_hash, "failed to re-broadcast transaction");
+            }
+        }
+    }
+
+    /// Determines which broadcast of the mined transaction made it to the chain
+    /// and notifies the `notifier` with its hash.
+    async fn resolve_mined(
+        &self,
+        tx: TrackedTransaction,
+        notifier: Option<oneshot::Sender<Hash>>,
+        replacements: &UnboundedSender<TransactionReplacement>,
+    ) {
+        for candidate in std::iter::once(tx.tx_hash).chain(tx.replaced.iter().rev().copied()) {
+            match self
+                .provider
+                .get_transaction_receipt(B256::from_slice(candidate.as_ref()))
+                .await
+            {
+                Ok(Some(_)) => {
+                    debug!(nonce = tx.nonce(), tx_hash = %candidate, "transaction mined");
+                    if candidate != tx.tx_hash {
+                        // One of the previous broadcasts got mined instead of the latest one
+                        let _ = replacements.unbounded_send(TransactionReplacement {
+                            old_tx_hash: tx.tx_hash,
+                            new_tx_hash: candidate,
+                        });
+                    }
+                    if let Some(notifier) = notifier {
+                        let _ = notifier.send(candidate);
+                    }
+                    return;
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    warn!(%error, nonce = tx.nonce(), tx_hash = %candidate, "failed to get transaction receipt");
+                    return;
+                }
+            }
+        }
+
+        warn!(
+            nonce = tx.nonce(),
+            tx_hash = %tx.tx_hash,
+            "transaction was replaced by an unknown transaction with the same nonce"
+        );
+    }
+
+    fn bump_fee(&self, fee: u128) -> u128 {
+        fee.saturating_mul(100 + self.cfg.fee_bump_percent as u128)
+            .div_ceil(100)
+            .max(fee.saturating_add(1))
+    }
+
+    fn persist(&self, state: &TransactionManagerState) {
+        #[cfg(all(feature = "prometheus", not(test)))]
+        METRIC_IN_FLIGHT_TXS.set(state.in_flight.len() as f64);
+
+        if let Some(path) = &self.cfg.store_path {
+            if let Err(error) = serde_json::to_vec(&state.in_flight.values().collect::<Vec<_>>())
+                .map_err(|e| e.to_string())
+                .and_then(|data| write(path, data).map_err(|e| e.to_string()))
+            {
+                error!(%error, path, "failed to persist in-flight transactions");
+            }
+        }
+    }
+}
+
+fn load_transactions(path: &str) -> BTreeMap<u64, TrackedTransaction> {
+    read_file(path)
+        .ok()
+        .and_then(|data| {
+            serde_json::from_slice::<Vec<TrackedTransaction>>(&data)
+                .inspect_err(|error| warn!(%error, path, "cannot load persisted in-flight transactions"))
+                .ok()
+        })
+        .unwrap_or_default()
+        .into_iter()
+        .map(|tx| (tx.nonce(), tx))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use alloy::{
+        network::Ethereum,
+        node_bindings::{Anvil, AnvilInstance},
+        primitives::{B256, U256},
+        providers::Provider,
+        rpc::client::ClientBuilder,
+        transports::{http::ReqwestTransport, layers::RetryBackoffLayer},
+    };
+    use hex_literal::hex;
+    use hopr_chain_types::utils::create_native_transfer;
+    use hopr_crypto_types::keypairs::{ChainKeypair, Keypair};
+
+    use super::{TransactionManagerConfig, TransactionReplacement};
+    use crate::{
+        HoprRpcOperations,
+        rpc::{RpcOperations, RpcOperationsConfig},
+        transport::ReqwestClient,
+    };
+
+    const RANDY: [u8; 20] = hex!("762614a5ed652457a2f1cdb8006380530c26ae6a");
+
+    fn create_rpc(
+        anvil: &AnvilInstance,
+        cfg: TransactionManagerConfig,
+    ) -> anyhow::Result<RpcOperations<ReqwestClient>> {
+        let chain_key = ChainKeypair::from_secret(anvil.keys()[0].to_bytes().as_ref())?;
+
+        let transport_client = ReqwestTransport::new(anvil.endpoint_url());
+        let rpc_client = ClientBuilder::default()
+            .layer(RetryBackoffLayer::new(2, 100, 100))
+            .transport(transport_client.clone(), transport_client.guess_local());
+
+        let rpc_cfg = RpcOperationsConfig {
+            chain_id: anvil.chain_id(),
+            gas_oracle_url: None,
+            finality: 2,
+            tx_polling_interval: Duration::from_millis(100),
+            ..RpcOperationsConfig::default()
+        };
+
+        Ok(
+            RpcOperations::new(rpc_client, transport_client.client().clone(), &chain_key, rpc_cfg)?
+                .with_transaction_manager(TransactionManagerConfig { enabled: true, ..cfg }),
+        )
+    }
+
+    async fn mine_block(rpc: &RpcOperations<ReqwestClient>) -> anyhow::Result<()> {
+        rpc.provider.raw_request::<_, String>("evm_mine".into(), ()).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_transaction_manager_should_bump_fees_of_stuck_transaction() -> anyhow::Result<()> {
+        let anvil = Anvil::new().arg("--no-mining").spawn();
+        let rpc = create_rpc(
+            &anvil,
+            TransactionManagerConfig {
+                fee_bump_after: Duration::ZERO,
+                ..Default::default()
+            },
+        )?;
+        let tx_manager = rpc
+            .transaction_manager()
+            .expect("transaction manager must be enabled")
+            .clone();
+
+        let tx = create_native_transfer::<Ethereum>(RANDY.into(), U256::from(1_u32));
+        let pending = rpc.send_transaction(tx).await?;
+        let original_tx_hash = pending.tx_hash();
+
+        let in_flight = tx_manager.in_flight_transactions().await;
+        assert_eq!(1, in_flight.len());
+        assert_eq!(original_tx_hash, in_flight[0].tx_hash);
+        let original_fee = in_flight[0]
+            .request
+            .max_fee_per_gas
+            .expect("must be an EIP-1559 transaction");
+
+        let (replacements_tx, mut replacements_rx) = futures::channel::mpsc::unbounded();
+        tx_manager.check_in_flight(&replacements_tx).await?;
+
+        let replacement = replacements_rx.try_next()?.expect("must report the replacement");
+        assert_eq!(original_tx_hash, replacement.old_tx_hash);
+
+        let in_flight = tx_manager.in_flight_transactions().await;
+        assert_eq!(1, in_flight[0].fee_bumps);
+        assert_eq!(vec![original_tx_hash], in_flight[0].replaced);
+        assert!(in_flight[0].request.max_fee_per_gas.unwrap() >= original_fee * 120 / 100);
+
+        mine_block(&rpc).await?;
+        tx_manager.check_in_flight(&replacements_tx).await?;
+
+        assert!(
+            tx_manager.in_flight_transactions().await.is_empty(),
+            "mined tx must not be tracked"
+        );
+        assert!(
+            rpc.provider
+                .get_transaction_receipt(B256::from_slice(replacement.new_tx_hash.as_ref()))
+                .await?
+                .is_some_and(|r| r.status()),
+            "the replacement must be mined"
+        );
+        assert!(
+            replacements_rx.try_next().is_err(),
+            "no other replacement must be reported"
+        );
+
+        // The pending transaction follows the replacement
+        mine_block(&rpc).await?;
+        let confirmed_tx_hash = tokio::time::timeout(Duration::from_secs(5), pending).await??;
+        assert_eq!(replacement.new_tx_hash, confirmed_tx_hash);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_transaction_manager_should_rebroadcast_dropped_and_persisted_transactions() -> anyhow::Result<()> {
+        let store_dir = tempfile::tempdir()?;
+        let cfg = TransactionManagerConfig {
+            store_path: Some(store_dir.path().join("txs.json").to_string_lossy().into()),
+            ..Default::default()
+        };
+
+        let anvil = Anvil::new().arg("--no-mining").spawn();
+        let rpc = create_rpc(&anvil, cfg.clone())?;
+
+        for amount in 1..=2_u32 {
+            let tx = create_native_transfer::<Ethereum>(RANDY.into(), U256::from(amount));
+            rpc.send_transaction(tx).await?;
+        }
+
+        let in_flight = rpc.transaction_manager().unwrap().in_flight_transactions().await;
+        assert_eq!(vec![0, 1], in_flight.iter().map(|tx| tx.nonce()).collect::<Vec<_>>());
+
+        // A restarted node loads the in-flight transactions
+        let restarted_rpc = create_rpc(&anvil, cfg)?;
+        let tx_manager = restarted_rpc.transaction_manager().unwrap().clone();
+        assert_eq!(in_flight, tx_manager.in_flight_transactions().await);
+
+        let dropped_tx_hash = B256::from_slice(in_flight[1].tx_hash.as_ref());
+        restarted_rpc
+            .provider
+            .raw_request::<_, serde_json::Value>("anvil_dropTransaction".into(), (dropped_tx_hash,))
+            .await?;
+        assert!(
+            restarted_rpc
+                .provider
+                .get_transaction_by_hash(dropped_tx_hash)
+                .await?
+                .is_none()
+        );
+
+        let (replacements_tx, mut replacements_rx) = futures::channel::mpsc::unbounded::<TransactionReplacement>();
+        tx_manager.check_in_flight(&replacements_tx).await?;
+
+        assert!(
+            restarted_rpc
+                .provider
+                .get_transaction_by_hash(dropped_tx_hash)
+                .await?
+                .is_some(),
+            "dropped transaction must be re-broadcast"
+        );
+        assert!(
+            replacements_rx.try_next().is_err(),
+            "re-broadcast must not replace the tx"
+        );
+
+        // The next transaction continues after the in-flight ones
+        let tx = create_native_transfer::<Ethereum>(RANDY.into(), U256::from(3_u32));
+        restarted_rpc.send_transaction(tx).await?;
+        assert_eq!(
+            vec![0, 1, 2],
+            tx_manager
+                .in_flight_transactions()
+                .await
+                .iter()
+                .map(|tx| tx.nonce())
+                .collect::<Vec<_>>()
+        );
+
+        mine_block(&restarted_rpc).await?;
+        tx_manager.check_in_flight(&replacements_tx).await?;
+        assert!(tx_manager.in_flight_transactions().await.is_empty());
+
+        Ok(())
+    }
+}
@@ -27,6 +27,7 @@ pub mod actions;
 pub mod chain_events;
 pub mod constants;
 pub mod errors;
+pub mod multisend;
 // Various (mostly testing related) utility functions
 pub mod utils;
 
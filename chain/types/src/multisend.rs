@@ -0,0 +1,125 @@
+//! Encoding of transactions batched through the Safe `MultiSend` library.
+//!
+//! [SafeTxOperation] corresponds to the `Operation` Enum used in Safe smart contract.
+//!
+//! [MultisendTransaction] struct is used for building transactions interacting with Multisend contract
+use alloy::{
+    primitives::{Address, Bytes, U256},
+    sol,
+    sol_types::SolValue,
+};
+use hex_literal::hex;
+use tracing::debug;
+
+sol!(
+    #![sol(abi)]
+    #![sol(rpc)]
+    function multiSend(bytes memory transactions) public payable;
+);
+
+/// Enums of Safe transaction operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SafeTxOperation {
+    Call,
+    DelegateCall,
+}
+impl SafeTxOperation {
+    /// convert the SafeTxOperation to exact one byte
+    pub fn to_byte(&self) -> [u8; 1] {
+        match self {
+            SafeTxOperation::Call => hex!("00"),
+            SafeTxOperation::DelegateCall => hex!("01"),
+        }
+    }
+}
+
+impl From<SafeTxOperation> for u8 {
+    fn from(s: SafeTxOperation) -> u8 {
+        s as u8
+    }
+}
+
+/// Struct to make a multisend transaction, mainly used by safe instances
+#[derive(Debug, Clone)]
+pub struct MultisendTransaction {
+    // data paylaod encoded with selector
+    pub encoded_data: Bytes,
+    // transaction type
+    pub tx_operation: SafeTxOperation,
+    // target address
+    pub to: Address,
+    // payable eth sending along the tx
+    pub value: U256,
+}
+
+/// Methods for Multisend transaction
+impl MultisendTransaction {
+    /// encode a multisend transaction
+    pub fn encode_packed(&self) -> Vec<u8> {
+        let tx_operation_bytes: Bytes = self.tx_operation.to_byte().into();
+
+        let value = (
+            tx_operation_bytes,                  // 1 bytes
+            self.to,                             // 20 bytes
+            U256::from(self.value),              // 32 bytes
+            U256::from(self.encoded_data.len()), // 32 bytes
+            self.encoded_data.clone(),           // bytes
+        );
+        value.abi_encode_packed()
+    }
+
+    /// build a multisend transaction data payload
+    pub fn build_multisend_tx(transactions: Vec<MultisendTransaction>) -> Vec<u8> {
+        let mut payload: Vec<u8> = Vec::new();
+        for transaction in transactions {
+            payload = [payload, transaction.encode_packed()].concat();
+        }
+        debug!("payload {:?}", alloy::hex::encode(&payload));
+        payload
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{primitives::address, sol_types::SolCall};
+
+    use super::*;
+
+    #[test]
+    fn test_multisend_transactions_are_packed_in_order() -> anyhow::Result<()> {
+        let txs = vec![
+            MultisendTransaction {
+                encoded_data: Bytes::from(vec![0xaa, 0xbb]),
+                tx_operation: SafeTxOperation::Call,
+                to: address!("1111111111111111111111111111111111111111"),
+                value: U256::ZERO,
+            },
+            MultisendTransaction {
+                encoded_data: Bytes::from(vec![0xcc]),
+                tx_operation: SafeTxOperation::DelegateCall,
+                to: address!("2222222222222222222222222222222222222222"),
+                value: U256::from(5),
+            },
+        ];
+
+        let payload = MultisendTransaction::build_multisend_tx(txs.clone());
+        assert_eq!(payload.len(), (85 + 2) + (85 + 1));
+        assert_eq!(payload, [txs[0].encode_packed(), txs[1].encode_packed()].concat());
+
+        // operation | to | value | data length | data
+        assert_eq!(payload[0], 0x00);
+        assert_eq!(&payload[1..21], txs[0].to.as_slice());
+        assert_eq!(U256::from_be_slice(&payload[53..85]), U256::from(2));
+        assert_eq!(&payload[85..87], &[0xaa, 0xbb]);
+        assert_eq!(payload[87], 0x01);
+        assert_eq!(U256::from_be_slice(&payload[108..140]), U256::from(5));
+
+        let call = multiSendCall {
+            transactions: payload.clone().into(),
+        }
+        .abi_encode();
+        assert_eq!(multiSendCall::abi_decode(&call)?.transactions.to_vec(), payload);
+
+        Ok(())
+    }
+}
@@ -14,12 +14,15 @@
 //! - Protocol messages are defined in the [`protocol`] submodule.
 //! - Protocol state machine is defined in the [`state`] submodule.
 //! - Frames, segmentation and reassembly are defined in the `frame` submodule.
+//! - Counters of the protocol state machine are defined in the `stats` submodule.
 
 /// Contains errors thrown from this module.
 pub mod errors;
 mod frame;
 pub mod protocol;
 pub mod state;
+mod stats;
 mod utils;
 
 pub use frame::{Frame, FrameId, FrameInfo, FrameReassembler, Segment, SegmentId};
+pub use stats::SessionSocketStats;
@@ -117,6 +117,7 @@ use crate::{
         errors::SessionError,
         frame::{FrameId, FrameReassembler, Segment, SegmentId, segment},
         protocol::{FrameAcknowledgements, SegmentRequest, SessionMessage},
+        stats::SessionSocketStats,
         utils::{RetryResult, RetryToken},
     },
     utils::AsyncReadStreamer,
@@ -260,6 +261,7 @@ pub struct SessionState<const C: usize> {
     outgoing_frame_resends: Arc<DashMap<FrameId, RetryToken>>,
     outgoing_frame_id: Arc<AtomicU32>,
     frame_reassembler: Arc<FrameReassembler>,
+    stats: Arc<SessionSocketStats>,
     cfg: SessionConfig,
     segment_egress_send: UnboundedSender<SessionMessage<C>>,
 }
@@ -346,6 +348,7 @@ impl<const C: usize> SessionState<C> {
             .map_err(|e| SessionError::ProcessingError(e.to_string()))?;
 
         trace!(session_id = self.session_id, count, "retransmitted requested segments");
+        self.stats.segments_retransmitted_inc(count);
 
         Ok(())
     }
@@ -367,6 +370,7 @@ impl<const C: usize> SessionState<C> {
                     duration_in_ms = to_ack.as_millis(),
                     "frame acknowledgement duratin"
                 );
+                self.stats.rtt_sample(to_ack);
 
                 #[cfg(all(feature = "prometheus", not(test)))]
                 METRIC_TIME_TO_ACK.observe(&[self.session_id()], to_ack.as_secs_f64())
@@ -473,6 +477,7 @@ impl<const C: usize> SessionState<C> {
             count = sent,
             "RETRANSMISSION BATCH COMPLETE: sent {sent} re-send requests",
         );
+        self.stats.retransmission_requests_sent(sent);
         Ok(sent)
     }
 
@@ -602,6 +607,7 @@ impl<const C: usize> SessionState<C> {
             session_id = self.session_id,
             count, "AUTO-RETRANSMIT BATCH COMPLETE: re-sent segments",
         );
+        self.stats.segments_retransmitted_inc(count);
 
         Ok(count)
     }
@@ -649,6 +655,7 @@ impl<const C: usize> SessionState<C> {
             session_id = self.session_id,
             frame_id, count, "FRAME SEND COMPLETE: sent segments",
         );
+        self.stats.frame_sent();
 
         Ok(())
     }
@@ -735,6 +742,11 @@ impl<const C: usize> SessionState<C> {
     pub fn session_id(&self) -> &str {
         &self.session_id
     }
+
+    /// Returns the [counters](SessionSocketStats) of this session.
+    pub fn stats(&self) -> &Arc<SessionSocketStats> {
+        &self.stats
+    }
 }
 
 // Sink for data coming from downstream
@@ -893,6 +905,8 @@ impl<const C: usize> SessionSocket<C> {
         let incoming_frame_retries_clone = incoming_frame_retries.clone();
         let id_clone = id.to_string().clone();
         let to_acknowledge_clone = to_acknowledge.clone();
+        let stats = Arc::new(SessionSocketStats::new(id.to_string()));
+        let stats_clone = stats.clone();
         let ack_enabled = cfg.enabled_features.contains(&SessionFeature::AcknowledgeFrames);
 
         let frame_egress = Box::new(
@@ -903,6 +917,7 @@ impl<const C: usize> SessionSocket<C> {
                             trace!(session_id = id_clone, frame_id = frame.frame_id, "frame completed");
                             // The frame has been completed, so remove its retry record
                             incoming_frame_retries_clone.remove(&frame.frame_id);
+                            stats_clone.frame_received();
                             if ack_enabled {
                                 // Acts as a ring buffer, so if the buffer is full, any unsent acknowledgements
                                 // will be discarded.
@@ -913,6 +928,7 @@ impl<const C: usize> SessionSocket<C> {
                         Err(SessionError::FrameDiscarded(fid)) | Err(SessionError::IncompleteFrame(fid)) => {
                             // Remove the retry token because the frame has been discarded
                             incoming_frame_retries_clone.remove(&fid);
+                            stats_clone.frame_skipped();
                             warn!(session_id = id_clone, frame_id = fid, "frame skipped");
                             futures::future::ready(None) // Skip discarded frames
                         }
@@ -943,6 +959,7 @@ impl<const C: usize> SessionSocket<C> {
             lookbehind: Arc::new(SkipMap::new()),
             outgoing_frame_id: Arc::new(AtomicU32::new(1)),
             frame_reassembler: Arc::new(reassembler),
+            stats,
             outgoing_frame_resends: Arc::new(DashMap::new()),
             session_id: id.to_string(),
             to_acknowledge,
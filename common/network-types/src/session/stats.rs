@@ -0,0 +1,165 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+#[cfg(all(feature = "prometheus", not(test)))]
+lazy_static::lazy_static! {
+    static ref METRIC_FRAMES: hopr_metrics::MultiCounter =
+        hopr_metrics::MultiCounter::new(
+            "hopr_session_frames",
+            "Number of frames sent, received or skipped by the Session protocol",
+            &["direction"]
+    ).unwrap();
+    static ref METRIC_RETRANSMISSIONS: hopr_metrics::MultiCounter =
+        hopr_metrics::MultiCounter::new(
+            "hopr_session_retransmissions",
+            "Number of segments retransmitted and retransmission requests sent by the Session protocol",
+            &["kind"]
+    ).unwrap();
+    static ref METRIC_RTT: hopr_metrics::SimpleHistogram =
+        hopr_metrics::SimpleHistogram::new(
+            "hopr_session_rtt_sec",
+            "Round-trip time samples of all Sessions in seconds",
+            vec![0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 15.0, 30.0],
+    ).unwrap();
+}
+
+/// Weight of a new round-trip time sample in the smoothed estimate (as in RFC 6298).
+const RTT_SAMPLE_WEIGHT: f64 = 0.125;
+
+/// Counters of the Session protocol, shared by all the clones of a [`SessionState`](super::state::SessionState).
+///
+/// The counters are only ever incremented by the state machine and can be read at any time.
+#[derive(Debug, Default)]
+pub struct SessionSocketStats {
+    session_id: String,
+    frames_sent: AtomicU64,
+    frames_received: AtomicU64,
+    frames_skipped: AtomicU64,
+    segments_retransmitted: AtomicU64,
+    retransmission_requests: AtomicU64,
+    smoothed_rtt_us: AtomicU64,
+}
+
+impl SessionSocketStats {
+    pub(crate) fn new(session_id: String) -> Self {
+        Self {
+            session_id,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn frame_sent(&self) {
+        self.frames_sent.fetch_add(1, Ordering::Relaxed);
+
+        #[cfg(all(feature = "prometheus", not(test)))]
+        METRIC_FRAMES.increment(&["sent"]);
+    }
+
+    pub(crate) fn frame_received(&self) {
+        self.frames_received.fetch_add(1, Ordering::Relaxed);
+
+        #[cfg(all(feature = "prometheus", not(test)))]
+        METRIC_FRAMES.increment(&["received"]);
+    }
+
+    pub(crate) fn frame_skipped(&self) {
+        self.frames_skipped.fetch_add(1, Ordering::Relaxed);
+
+        #[cfg(all(feature = "prometheus", not(test)))]
+        METRIC_FRAMES.increment(&["skipped"]);
+    }
+
+    pub(crate) fn segments_retransmitted_inc(&self, count: usize) {
+        self.segments_retransmitted.fetch_add(count as u64, Ordering::Relaxed);
+
+        #[cfg(all(feature = "prometheus", not(test)))]
+        METRIC_RETRANSMISSIONS.increment_by(&["segment"], count as u64);
+    }
+
+    pub(crate) fn retransmission_requests_sent(&self, count: usize) {
+        self.retransmission_requests.fetch_add(count as u64, Ordering::Relaxed);
+
+        #[cfg(all(feature = "prometheus", not(test)))]
+        METRIC_RETRANSMISSIONS.increment_by(&["request"], count as u64);
+    }
+
+    /// Updates the smoothed round-trip time estimate with a new sample.
+    ///
+    /// The first sample is taken as is, the subsequent ones are averaged exponentially.
+    pub(crate) fn rtt_sample(&self, sample: Duration) {
+        #[cfg(all(feature = "prometheus", not(test)))]
+        METRIC_RTT.observe(sample.as_secs_f64());
+
+        let sample = sample.as_micros() as f64;
+        let _ = self
+            .smoothed_rtt_us
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+                Some(if current == 0 {
+                    sample as u64
+                } else {
+                    ((1.0 - RTT_SAMPLE_WEIGHT) * current as f64 + RTT_SAMPLE_WEIGHT * sample) as u64
+                })
+            });
+    }
+
+    /// ID of the Session these counters belong to.
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    /// Number of frames written to the Session.
+    pub fn frames_sent(&self) -> u64 {
+        self.frames_sent.load(Ordering::Relaxed)
+    }
+
+    /// Number of frames completely reassembled and read from the Session.
+    pub fn frames_received(&self) -> u64 {
+        self.frames_received.load(Ordering::Relaxed)
+    }
+
+    /// Number of incoming frames that were discarded or evicted from the
+    /// [`FrameReassembler`](super::FrameReassembler) before they were complete.
+    pub fn frames_skipped(&self) -> u64 {
+        self.frames_skipped.load(Ordering::Relaxed)
+    }
+
+    /// Number of segments sent again, either on request or due to a missing acknowledgement.
+    pub fn segments_retransmitted(&self) -> u64 {
+        self.segments_retransmitted.load(Ordering::Relaxed)
+    }
+
+    /// Number of requests for missing segments sent to the counterparty.
+    pub fn retransmission_requests(&self) -> u64 {
+        self.retransmission_requests.load(Ordering::Relaxed)
+    }
+
+    /// Smoothed round-trip time estimate, based on the time it takes the counterparty
+    /// to acknowledge a sent frame.
+    ///
+    /// Returns `None` if no frame has been acknowledged yet.
+    pub fn smoothed_rtt(&self) -> Option<Duration> {
+        match self.smoothed_rtt_us.load(Ordering::Relaxed) {
+            0 => None,
+            rtt => Some(Duration::from_micros(rtt)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_socket_stats_should_smooth_rtt_samples() {
+        let stats = SessionSocketStats::new("test".into());
+        assert_eq!(None, stats.smoothed_rtt());
+
+        stats.rtt_sample(Duration::from_millis(800));
+        assert_eq!(Some(Duration::from_millis(800)), stats.smoothed_rtt());
+
+        stats.rtt_sample(Duration::from_millis(1600));
+        assert_eq!(Some(Duration::from_millis(900)), stats.smoothed_rtt());
+    }
+}
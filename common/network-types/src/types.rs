@@ -8,6 +8,7 @@ use hickory_resolver::{AsyncResolver, name_server::ConnectionProvider};
 use hopr_crypto_packet::{HoprSurb, prelude::HoprSenderId};
 use hopr_crypto_random::Randomizable;
 use hopr_internal_types::prelude::HoprPseudonym;
+pub use hopr_path::PathConstraints;
 use hopr_path::ValidatedPath;
 use hopr_primitive_types::{
     bounded::{BoundedSize, BoundedVec},
@@ -357,6 +358,8 @@ pub enum DestinationRouting {
         forward_options: RoutingOptions,
         /// Optional return path.
         return_options: Option<RoutingOptions>,
+        /// Additional constraints on the relays of both forward and return paths.
+        path_constraints: PathConstraints,
     },
     /// Return routing using a SURB with the given pseudonym.
     ///
@@ -372,6 +375,7 @@ impl DestinationRouting {
             pseudonym: None,
             forward_options,
             return_options: None,
+            path_constraints: PathConstraints::default(),
         }
     }
 }
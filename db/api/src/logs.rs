@@ -4,6 +4,19 @@ use hopr_primitive_types::prelude::{Address, SerializableLog};
 
 use crate::errors::Result;
 
+/// Hashes of a block processed by the Indexer.
+///
+/// These are kept for the most recent blocks, so that chain reorganizations can be detected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockHashes {
+    /// Number of the block
+    pub block_number: u64,
+    /// Hash of the block
+    pub hash: Hash,
+    /// Hash of the parent block
+    pub parent_hash: Hash,
+}
+
 #[async_trait]
 pub trait HoprDbLogOperations {
     /// Ensures that logs in this database have been created by scanning the given contract address
@@ -70,6 +83,19 @@ pub trait HoprDbLogOperations {
         block_offset: Option<u64>,
     ) -> Result<Vec<SerializableLog>>;
 
+    /// Retrieves the log entries emitted by the given contract from the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The address of the contract which emitted the logs.
+    /// * `processed` - An optional processed filter.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a `Vec` of `SerializableLog` entries ordered by their position on the chain
+    /// if the operation succeeds or an error if it fails.
+    async fn get_contract_logs(&self, address: Address, processed: Option<bool>) -> Result<Vec<SerializableLog>>;
+
     /// Retrieves the count of log entries from the database.
     ///
     /// # Arguments
@@ -148,4 +174,50 @@ pub trait HoprDbLogOperations {
     ///
     /// A `Result` which is `Ok(Hash)` if the operation succeeds or an error if it fails.
     async fn update_logs_checksums(&self) -> Result<Hash>;
+
+    /// Stores the hashes of a processed block, replacing any hashes previously stored for the same block number.
+    ///
+    /// # Arguments
+    ///
+    /// * `hashes` - The hashes of the processed block.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is `Ok(())` if the operation succeeds or an error if it fails.
+    async fn store_block_hashes(&self, hashes: BlockHashes) -> Result<()>;
+
+    /// Retrieves the hashes of the most recent processed blocks, starting with the latest block.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - The maximum number of blocks to retrieve.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a `Vec` of `BlockHashes` ordered by descending block number.
+    async fn get_latest_block_hashes(&self, limit: u64) -> Result<Vec<BlockHashes>>;
+
+    /// Removes the hashes of all blocks before the given block number.
+    ///
+    /// # Arguments
+    ///
+    /// * `block_number` - The first block number whose hashes are kept.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` which is `Ok(())` if the operation succeeds or an error if it fails.
+    async fn prune_block_hashes(&self, block_number: u64) -> Result<()>;
+
+    /// Removes all logs and block hashes of the blocks after the given block number.
+    ///
+    /// This is used to roll back the blocks that are no longer part of the chain after a reorganization.
+    ///
+    /// # Arguments
+    ///
+    /// * `block_number` - The last block number which is kept.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of removed log entries if the operation succeeds or an error if it fails.
+    async fn rollback_logs(&self, block_number: u64) -> Result<u64>;
 }
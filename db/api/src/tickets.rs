@@ -194,6 +194,30 @@ impl TicketSelector {
     }
 }
 
+/// Position of a ticket in the ordering used by [HoprDbTicketOperations::get_tickets_page].
+///
+/// The tickets are ordered by their channel ID, channel epoch and ticket index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TicketCursor {
+    /// ID of the ticket's channel.
+    pub channel_id: Hash,
+    /// Epoch of the ticket's channel.
+    pub epoch: U256,
+    /// Index of the ticket.
+    pub index: u64,
+}
+
+impl From<&AcknowledgedTicket> for TicketCursor {
+    fn from(value: &AcknowledgedTicket) -> Self {
+        let ticket = value.verified_ticket();
+        Self {
+            channel_id: ticket.channel_id,
+            epoch: ticket.channel_epoch.into(),
+            index: ticket.index,
+        }
+    }
+}
+
 impl From<&AcknowledgedTicket> for TicketSelector {
     fn from(value: &AcknowledgedTicket) -> Self {
         Self {
@@ -280,6 +304,18 @@ pub trait HoprDbTicketOperations {
     /// The optional transaction `tx` must be in the database.
     async fn get_tickets(&self, selector: TicketSelector) -> Result<Vec<AcknowledgedTicket>>;
 
+    /// Retrieve at most `limit` acknowledged winning tickets, according to the given `selector`.
+    ///
+    /// The tickets are ordered by their channel ID, channel epoch and ticket index.
+    /// If `after` is given, only the tickets following it in this order are retrieved,
+    /// which allows iterating over large amounts of tickets page by page.
+    async fn get_tickets_page(
+        &self,
+        selector: TicketSelector,
+        after: Option<TicketCursor>,
+        limit: usize,
+    ) -> Result<Vec<AcknowledgedTicket>>;
+
     /// Marks tickets as the given [`TicketMarker`], removing them from the DB and updating the
     /// ticket statistics for each ticket's channel.
     ///
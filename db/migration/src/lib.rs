@@ -22,6 +22,8 @@ mod m20250107_000019_logs_meta_table;
 mod m20250219_000020_logs_add_index;
 mod m20250219_000021_channels_add_index;
 mod m20250419_000022_account_add_published_block;
+mod m20250610_000023_index_create_node_safe_registry;
+mod m20250620_000024_logs_create_block_hash;
 
 #[derive(PartialEq)]
 pub enum BackendType {
@@ -67,6 +69,8 @@ impl MigratorTrait for Migrator {
             Box::new(m20250219_000020_logs_add_index::Migration),
             Box::new(m20250219_000021_channels_add_index::Migration),
             Box::new(m20250419_000022_account_add_published_block::Migration),
+            Box::new(m20250610_000023_index_create_node_safe_registry::Migration),
+            Box::new(m20250620_000024_logs_create_block_hash::Migration),
         ]
     }
 }
@@ -94,6 +98,7 @@ impl MigratorTrait for MigratorIndex {
             Box::new(m20240917_000015_add_minimum_incoming_ticket_win_prob_column::Migration),
             Box::new(m20250219_000021_channels_add_index::Migration),
             Box::new(m20250419_000022_account_add_published_block::Migration),
+            Box::new(m20250610_000023_index_create_node_safe_registry::Migration),
         ]
     }
 }
@@ -143,6 +148,7 @@ impl MigratorTrait for MigratorChainLogs {
             Box::new(m20241112_000018_logs_add_index::Migration),
             Box::new(m20250107_000019_logs_meta_table::Migration),
             Box::new(m20250219_000020_logs_add_index::Migration),
+            Box::new(m20250620_000024_logs_create_block_hash::Migration),
         ]
     }
 }
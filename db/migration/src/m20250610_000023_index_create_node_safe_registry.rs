@@ -0,0 +1,46 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(NodeSafeRegistry::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(NodeSafeRegistry::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(NodeSafeRegistry::NodeAddress)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(NodeSafeRegistry::SafeAddress).string().not_null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(NodeSafeRegistry::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum NodeSafeRegistry {
+    Table,
+    Id,
+    NodeAddress,
+    SafeAddress,
+}
@@ -0,0 +1,44 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Hashes of the processed blocks are used by the Indexer to detect chain reorganizations.
+        manager
+            .create_table(
+                Table::create()
+                    .table(LogBlockHash::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(LogBlockHash::BlockNumber)
+                            .binary_len(8)
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(LogBlockHash::BlockHash).binary_len(32).not_null())
+                    .col(ColumnDef::new(LogBlockHash::ParentHash).binary_len(32).not_null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(LogBlockHash::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum LogBlockHash {
+    Table,
+    /// Number of the processed block
+    BlockNumber,
+    /// Hash of the processed block
+    BlockHash,
+    /// Hash of the parent of the processed block
+    ParentHash,
+}
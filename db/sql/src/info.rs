@@ -4,7 +4,9 @@ use hopr_crypto_types::prelude::Hash;
 use hopr_db_api::info::*;
 use hopr_db_entity::{
     chain_info, global_settings, node_info,
-    prelude::{Account, Announcement, ChainInfo, Channel, NetworkEligibility, NetworkRegistry, NodeInfo},
+    prelude::{
+        Account, Announcement, ChainInfo, Channel, NetworkEligibility, NetworkRegistry, NodeInfo, NodeSafeRegistry,
+    },
 };
 use hopr_internal_types::prelude::WinningProbability;
 use hopr_primitive_types::prelude::*;
@@ -57,6 +59,9 @@ pub trait HoprDbInfoOperations {
 
     /// Removes all data from all tables in the index database.
     ///
+    /// The Safe and module addresses of this node are kept, because they are not indexed
+    /// (see [`HoprDbInfoOperations::set_safe_info`]).
+    ///
     /// # Returns
     ///
     /// A `Result` indicating the success or failure of the operation.
@@ -152,6 +157,10 @@ impl HoprDbInfoOperations for HoprDb {
             return Ok(false);
         }
 
+        if NodeSafeRegistry::find().one(c).await?.is_some() {
+            return Ok(false);
+        }
+
         Ok(true)
     }
 
@@ -160,11 +169,17 @@ impl HoprDbInfoOperations for HoprDb {
             .await?
             .perform(|tx| {
                 Box::pin(async move {
+                    let safe_addresses = NodeInfo::find_by_id(SINGULAR_TABLE_FIXED_ID)
+                        .one(tx.as_ref())
+                        .await?
+                        .map(|info| (info.safe_address, info.module_address));
+
                     Account::delete_many().exec(tx.as_ref()).await?;
                     Announcement::delete_many().exec(tx.as_ref()).await?;
                     Channel::delete_many().exec(tx.as_ref()).await?;
                     NetworkEligibility::delete_many().exec(tx.as_ref()).await?;
                     NetworkRegistry::delete_many().exec(tx.as_ref()).await?;
+                    NodeSafeRegistry::delete_many().exec(tx.as_ref()).await?;
                     ChainInfo::delete_many().exec(tx.as_ref()).await?;
                     NodeInfo::delete_many().exec(tx.as_ref()).await?;
 
@@ -177,6 +192,10 @@ impl HoprDbInfoOperations for HoprDb {
 
                     let mut initial_row = node_info::ActiveModel::new();
                     initial_row.id = Set(1);
+                    if let Some((safe_address, module_address)) = safe_addresses {
+                        initial_row.safe_address = Set(safe_address);
+                        initial_row.module_address = Set(module_address);
+                    }
                     NodeInfo::insert(initial_row).exec(tx.as_ref()).await?;
 
                     Ok::<(), DbSqlError>(())
@@ -697,6 +716,26 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_clear_index_db_should_keep_the_safe_info() -> anyhow::Result<()> {
+        let db = HoprDb::new_in_memory(ChainKeypair::random()).await?;
+
+        let safe_info = SafeInfo {
+            safe_address: *ADDR_1,
+            module_address: *ADDR_2,
+        };
+
+        db.set_safe_info(None, safe_info).await?;
+        db.set_safe_hopr_balance(None, HoprBalance::from(10_u32)).await?;
+
+        db.clear_index_db(None).await?;
+        db.caches.single_values.invalidate_all();
+
+        assert_eq!(Some(safe_info), db.get_safe_info(None).await?);
+        assert_eq!(HoprBalance::zero(), db.get_safe_hopr_balance(None).await?);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_set_get_global_setting() -> anyhow::Result<()> {
         let db = HoprDb::new_in_memory(ChainKeypair::random()).await?;
@@ -12,6 +12,7 @@ pub mod peers;
 pub mod protocol;
 pub mod registry;
 pub mod resolver;
+pub mod snapshot;
 mod ticket_manager;
 pub mod tickets;
 
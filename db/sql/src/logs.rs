@@ -3,12 +3,12 @@ use futures::{StreamExt, stream};
 use hopr_crypto_types::prelude::Hash;
 use hopr_db_api::{
     errors::{DbError, Result},
-    logs::HoprDbLogOperations,
+    logs::{BlockHashes, HoprDbLogOperations},
 };
 use hopr_db_entity::{
     errors::DbEntityError,
-    log, log_status, log_topic_info,
-    prelude::{Log, LogStatus, LogTopicInfo},
+    log, log_block_hash, log_status, log_topic_info,
+    prelude::{Log, LogBlockHash, LogStatus, LogTopicInfo},
 };
 use hopr_primitive_types::prelude::*;
 use sea_orm::{
@@ -153,6 +153,34 @@ impl HoprDbLogOperations for HoprDb {
         }
     }
 
+    async fn get_contract_logs(&self, address: Address, processed: Option<bool>) -> Result<Vec<SerializableLog>> {
+        let query = Log::find()
+            .find_also_related(LogStatus)
+            .filter(log::Column::Address.eq(address.as_ref().to_vec()))
+            .apply_if(processed, |q, v| q.filter(log_status::Column::Processed.eq(v)))
+            .order_by_asc(log::Column::BlockNumber)
+            .order_by_asc(log::Column::TransactionIndex)
+            .order_by_asc(log::Column::LogIndex);
+
+        match query.all(self.conn(TargetDb::Logs)).await {
+            Ok(logs) => Ok(logs
+                .into_iter()
+                .map(|(log, status)| {
+                    if let Some(status) = status {
+                        create_log(log, status).unwrap()
+                    } else {
+                        error!("Missing log status for log in db: {:?}", log);
+                        SerializableLog::try_from(log).unwrap()
+                    }
+                })
+                .collect()),
+            Err(e) => {
+                error!("Failed to get contract logs from db: {:?}", e);
+                Err(DbError::from(DbSqlError::from(e)))
+            }
+        }
+    }
+
     async fn get_logs_count(&self, block_number: Option<u64>, block_offset: Option<u64>) -> Result<u64> {
         let min_block_number = block_number.unwrap_or(0);
         let max_block_number = block_offset.map(|v| min_block_number + v + 1);
@@ -318,16 +346,8 @@ impl HoprDbLogOperations for HoprDb {
                         Ok(entries) => {
                             let mut entries = entries.into_iter();
                             while let Some((status, Some(log_entry))) = entries.next() {
-                                let slog = create_log(log_entry.clone(), status.clone())?;
-                                // we compute the hash of a single log as a combination of the block
-                                // hash, TX hash, and the log index
-                                let log_hash = Hash::create(&[
-                                    log_entry.block_hash.as_slice(),
-                                    log_entry.transaction_hash.as_slice(),
-                                    log_entry.log_index.as_slice(),
-                                ]);
-
-                                let next_checksum = Hash::create(&[last_checksum.as_ref(), log_hash.as_ref()]);
+                                let next_checksum = log_checksum(&last_checksum, &log_entry);
+                                let slog = create_log(log_entry, status.clone())?;
 
                                 let mut updated_status = status.into_active_model();
                                 updated_status.checksum = Set(Some(next_checksum.as_ref().to_vec()));
@@ -408,6 +428,89 @@ impl HoprDbLogOperations for HoprDb {
             })
             .await
     }
+
+    async fn store_block_hashes(&self, hashes: BlockHashes) -> Result<()> {
+        let model = log_block_hash::ActiveModel {
+            block_number: Set(hashes.block_number.to_be_bytes().to_vec()),
+            block_hash: Set(hashes.hash.as_ref().to_vec()),
+            parent_hash: Set(hashes.parent_hash.as_ref().to_vec()),
+        };
+
+        LogBlockHash::insert(model)
+            .on_conflict(
+                OnConflict::column(log_block_hash::Column::BlockNumber)
+                    .update_columns([log_block_hash::Column::BlockHash, log_block_hash::Column::ParentHash])
+                    .to_owned(),
+            )
+            .exec(self.conn(TargetDb::Logs))
+            .await
+            .map(|_| ())
+            .map_err(|e| DbError::from(DbSqlError::from(e)))
+    }
+
+    async fn get_latest_block_hashes(&self, limit: u64) -> Result<Vec<BlockHashes>> {
+        LogBlockHash::find()
+            .order_by_desc(log_block_hash::Column::BlockNumber)
+            .limit(limit)
+            .all(self.conn(TargetDb::Logs))
+            .await
+            .map_err(|e| DbError::from(DbSqlError::from(e)))?
+            .into_iter()
+            .map(|model| create_block_hashes(model).map_err(DbError::from))
+            .collect()
+    }
+
+    async fn prune_block_hashes(&self, block_number: u64) -> Result<()> {
+        LogBlockHash::delete_many()
+            .filter(log_block_hash::Column::BlockNumber.lt(block_number.to_be_bytes().to_vec()))
+            .exec(self.conn(TargetDb::Logs))
+            .await
+            .map(|_| ())
+            .map_err(|e| DbError::from(DbSqlError::from(e)))
+    }
+
+    async fn rollback_logs(&self, block_number: u64) -> Result<u64> {
+        self.nest_transaction_in_db(None, TargetDb::Logs)
+            .await?
+            .perform(|tx| {
+                Box::pin(async move {
+                    let removed = Log::delete_many()
+                        .filter(log::Column::BlockNumber.gt(block_number.to_be_bytes().to_vec()))
+                        .exec(tx.as_ref())
+                        .await
+                        .map_err(|e| DbError::from(DbSqlError::from(e)))?
+                        .rows_affected;
+
+                    LogStatus::delete_many()
+                        .filter(log_status::Column::BlockNumber.gt(block_number.to_be_bytes().to_vec()))
+                        .exec(tx.as_ref())
+                        .await
+                        .map_err(|e| DbError::from(DbSqlError::from(e)))?;
+
+                    LogBlockHash::delete_many()
+                        .filter(log_block_hash::Column::BlockNumber.gt(block_number.to_be_bytes().to_vec()))
+                        .exec(tx.as_ref())
+                        .await
+                        .map_err(|e| DbError::from(DbSqlError::from(e)))?;
+
+                    Ok(removed)
+                })
+            })
+            .await
+    }
+}
+
+/// Computes the checksum of the given log, chained to the checksum of the previous log.
+pub(crate) fn log_checksum(previous_checksum: &Hash, log: &log::Model) -> Hash {
+    // we compute the hash of a single log as a combination of the block
+    // hash, TX hash, and the log index
+    let log_hash = Hash::create(&[
+        log.block_hash.as_slice(),
+        log.transaction_hash.as_slice(),
+        log.log_index.as_slice(),
+    ]);
+
+    Hash::create(&[previous_checksum.as_ref(), log_hash.as_ref()])
 }
 
 fn create_log(raw_log: log::Model, status: log_status::Model) -> crate::errors::Result<SerializableLog> {
@@ -447,6 +550,19 @@ fn create_log(raw_log: log::Model, status: log_status::Model) -> crate::errors::
     Ok(log)
 }
 
+fn create_block_hashes(model: log_block_hash::Model) -> crate::errors::Result<BlockHashes> {
+    let hash = |bytes: Vec<u8>| {
+        Hash::try_from(bytes.as_slice())
+            .map_err(|_| DbSqlError::from(DbEntityError::ConversionError("Invalid block hash".into())))
+    };
+
+    Ok(BlockHashes {
+        block_number: U256::from_be_bytes(model.block_number).as_u64(),
+        hash: hash(model.block_hash)?,
+        parent_hash: hash(model.parent_hash)?,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use hopr_crypto_types::prelude::{ChainKeypair, Hash, Keypair};
@@ -528,6 +644,50 @@ mod tests {
         assert_eq!(log_2, log_2_retrieved);
     }
 
+    #[tokio::test]
+    async fn test_get_contract_logs() -> anyhow::Result<()> {
+        let db = HoprDb::new_in_memory(ChainKeypair::random()).await?;
+
+        let contract = Address::new(b"my address 123456789");
+        let logs = [
+            (contract, 1u64, false),
+            (Address::new(b"my address 223456789"), 2, true),
+            (contract, 3, true),
+        ]
+        .into_iter()
+        .map(|(address, block_number, processed)| SerializableLog {
+            address,
+            topics: [Hash::create(&[b"my topic"]).into()].into(),
+            data: [1, 2, 3, 4].into(),
+            tx_index: 1u64,
+            block_number,
+            block_hash: Hash::create(&[b"my block hash", &block_number.to_be_bytes()]).into(),
+            tx_hash: Hash::create(&[b"my tx hash", &block_number.to_be_bytes()]).into(),
+            log_index: 1u64,
+            removed: false,
+            processed: Some(processed),
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+
+        for log in logs.iter().cloned() {
+            db.store_log(log).await?;
+        }
+
+        assert_eq!(
+            vec![logs[0].clone(), logs[2].clone()],
+            db.get_contract_logs(contract, None).await?
+        );
+        assert_eq!(vec![logs[2].clone()], db.get_contract_logs(contract, Some(true)).await?);
+        assert!(
+            db.get_contract_logs(Address::new(b"my address 323456789"), None)
+                .await?
+                .is_empty()
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_store_duplicate_log() {
         let db = HoprDb::new_in_memory(ChainKeypair::random()).await.unwrap();
@@ -893,4 +1053,88 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_store_and_prune_block_hashes() -> anyhow::Result<()> {
+        let db = HoprDb::new_in_memory(ChainKeypair::random()).await?;
+
+        let block_hashes = (1..=5_u64)
+            .map(|block_number| BlockHashes {
+                block_number,
+                hash: Hash::create(&[format!("block hash {block_number}").as_bytes()]),
+                parent_hash: Hash::create(&[format!("block hash {}", block_number - 1).as_bytes()]),
+            })
+            .collect::<Vec<_>>();
+
+        for hashes in block_hashes.iter() {
+            db.store_block_hashes(*hashes).await?;
+        }
+
+        let latest = db.get_latest_block_hashes(2).await?;
+        assert_eq!(vec![block_hashes[4], block_hashes[3]], latest);
+
+        // Storing the same block again replaces its hashes
+        let replaced = BlockHashes {
+            hash: Hash::create(&[b"replaced block hash 5"]),
+            ..block_hashes[4]
+        };
+        db.store_block_hashes(replaced).await?;
+        assert_eq!(vec![replaced], db.get_latest_block_hashes(1).await?);
+
+        db.prune_block_hashes(4).await?;
+        assert_eq!(vec![replaced, block_hashes[3]], db.get_latest_block_hashes(10).await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rollback_logs() -> anyhow::Result<()> {
+        let db = HoprDb::new_in_memory(ChainKeypair::random()).await?;
+
+        for block_number in 1..=4_u64 {
+            let log = SerializableLog {
+                address: Address::new(b"my address 123456789"),
+                topics: [Hash::create(&[b"my topic"]).into()].into(),
+                data: [1, 2, 3, 4].into(),
+                tx_index: 1u64,
+                block_number,
+                block_hash: Hash::create(&[format!("block hash {block_number}").as_bytes()]).into(),
+                tx_hash: Hash::create(&[format!("tx hash {block_number}").as_bytes()]).into(),
+                log_index: 1u64,
+                removed: false,
+                ..Default::default()
+            };
+            db.store_log(log.clone()).await?;
+            db.store_block_hashes(BlockHashes {
+                block_number,
+                hash: log.block_hash.into(),
+                parent_hash: Hash::create(&[format!("block hash {}", block_number - 1).as_bytes()]),
+            })
+            .await?;
+        }
+        db.set_logs_processed(None, None).await?;
+        db.update_logs_checksums().await?;
+
+        assert_eq!(2, db.rollback_logs(2).await?);
+
+        assert_eq!(vec![1, 2], db.get_logs_block_numbers(None, None, None).await?);
+        assert_eq!(2, db.get_logs_count(None, None).await?);
+        assert_eq!(
+            vec![2, 1],
+            db.get_latest_block_hashes(10)
+                .await?
+                .into_iter()
+                .map(|h| h.block_number)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(2),
+            db.get_last_checksummed_log().await?.map(|log| log.block_number)
+        );
+
+        // Nothing else to remove
+        assert_eq!(0, db.rollback_logs(2).await?);
+
+        Ok(())
+    }
 }
@@ -20,7 +20,7 @@ use tracing::{instrument, trace, warn};
 
 use crate::{
     channels::HoprDbChannelOperations, db::HoprDb, errors::DbSqlError, info::HoprDbInfoOperations,
-    prelude::HoprDbTicketOperations,
+    prelude::HoprDbTicketOperations, registry::HoprDbRegistryOperations,
 };
 
 #[cfg(all(feature = "prometheus", not(test)))]
@@ -657,4 +657,10 @@ impl PathAddressResolver for HoprDb {
             .await
             .map_err(|_| PathError::UnknownPeer(key.to_string()))
     }
+
+    async fn resolve_safe_address(&self, address: &Address) -> std::result::Result<Option<Address>, PathError> {
+        self.get_node_safe(None, *address)
+            .await
+            .map_err(|e| PathError::OtherError(GeneralError::NonSpecificError(e.to_string())))
+    }
 }
@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use hopr_db_entity::{network_eligibility, network_registry};
+use hopr_db_entity::{network_eligibility, network_registry, node_safe_registry};
 use hopr_primitive_types::prelude::{Address, ToHex};
 use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter, Set};
 use sea_query::OnConflict;
@@ -28,6 +28,15 @@ pub trait HoprDbRegistryOperations {
 
     /// Returns `true` if the given Safe is NR eligible.
     async fn is_safe_eligible<'a>(&'a self, tx: OptTx<'a>, address: Address) -> Result<bool>;
+
+    /// Sets the Safe the given node is registered with, or removes the registration if `None`.
+    async fn set_node_safe<'a>(&'a self, tx: OptTx<'a>, node: Address, safe: Option<Address>) -> Result<()>;
+
+    /// Returns the Safe the given node is registered with, if any.
+    async fn get_node_safe<'a>(&'a self, tx: OptTx<'a>, node: Address) -> Result<Option<Address>>;
+
+    /// Returns `true` if no node is registered with a Safe.
+    async fn is_node_safe_registry_empty<'a>(&'a self, tx: OptTx<'a>) -> Result<bool>;
 }
 
 #[async_trait]
@@ -146,6 +155,66 @@ impl HoprDbRegistryOperations for HoprDb {
             })
             .await
     }
+
+    async fn set_node_safe<'a>(&'a self, tx: OptTx<'a>, node: Address, safe: Option<Address>) -> Result<()> {
+        self.nest_transaction(tx)
+            .await?
+            .perform(|tx| {
+                Box::pin(async move {
+                    if let Some(safe) = safe {
+                        let entry = node_safe_registry::ActiveModel {
+                            node_address: Set(node.to_hex()),
+                            safe_address: Set(safe.to_hex()),
+                            ..Default::default()
+                        };
+
+                        node_safe_registry::Entity::insert(entry)
+                            .on_conflict(
+                                OnConflict::column(node_safe_registry::Column::NodeAddress)
+                                    .update_column(node_safe_registry::Column::SafeAddress)
+                                    .to_owned(),
+                            )
+                            .exec(tx.as_ref())
+                            .await?;
+                    } else {
+                        node_safe_registry::Entity::delete_many()
+                            .filter(node_safe_registry::Column::NodeAddress.eq(node.to_hex()))
+                            .exec(tx.as_ref())
+                            .await?;
+                    }
+                    Ok::<_, DbSqlError>(())
+                })
+            })
+            .await
+    }
+
+    async fn get_node_safe<'a>(&'a self, tx: OptTx<'a>, node: Address) -> Result<Option<Address>> {
+        self.nest_transaction(tx)
+            .await?
+            .perform(|tx| {
+                Box::pin(async move {
+                    node_safe_registry::Entity::find()
+                        .filter(node_safe_registry::Column::NodeAddress.eq(node.to_hex()))
+                        .one(tx.as_ref())
+                        .await?
+                        .map(|entry| Address::from_hex(&entry.safe_address))
+                        .transpose()
+                        .map_err(|_| DbSqlError::DecodingError)
+                })
+            })
+            .await
+    }
+
+    async fn is_node_safe_registry_empty<'a>(&'a self, tx: OptTx<'a>) -> Result<bool> {
+        self.nest_transaction(tx)
+            .await?
+            .perform(|tx| {
+                Box::pin(async move {
+                    Ok::<_, DbSqlError>(node_safe_registry::Entity::find().one(tx.as_ref()).await?.is_none())
+                })
+            })
+            .await
+    }
 }
 
 #[cfg(test)]
@@ -154,7 +223,7 @@ mod tests {
     use hopr_primitive_types::prelude::Address;
     use lazy_static::lazy_static;
 
-    use crate::{db::HoprDb, registry::HoprDbRegistryOperations};
+    use crate::{db::HoprDb, info::HoprDbInfoOperations, registry::HoprDbRegistryOperations};
 
     lazy_static! {
         static ref ADDR_1: Address = "4331eaa9542b6b034c43090d9ec1c2198758dbc3"
@@ -222,4 +291,39 @@ mod tests {
         assert!(!db.is_safe_eligible(None, *ADDR_2).await?);
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_node_safe_registry_db() -> anyhow::Result<()> {
+        let db = HoprDb::new_in_memory(ChainKeypair::random()).await?;
+
+        assert_eq!(None, db.get_node_safe(None, *ADDR_1).await?);
+
+        db.set_node_safe(None, *ADDR_1, Some(*ADDR_2)).await?;
+        assert_eq!(Some(*ADDR_2), db.get_node_safe(None, *ADDR_1).await?);
+        assert_eq!(None, db.get_node_safe(None, *ADDR_2).await?);
+
+        db.set_node_safe(None, *ADDR_1, Some(*ADDR_1)).await?;
+        assert_eq!(Some(*ADDR_1), db.get_node_safe(None, *ADDR_1).await?);
+
+        db.set_node_safe(None, *ADDR_1, None).await?;
+        assert_eq!(None, db.get_node_safe(None, *ADDR_1).await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_node_safe_registry_should_be_cleared_with_the_index() -> anyhow::Result<()> {
+        let db = HoprDb::new_in_memory(ChainKeypair::random()).await?;
+        assert!(db.index_is_empty().await?);
+
+        assert!(db.is_node_safe_registry_empty(None).await?);
+
+        db.set_node_safe(None, *ADDR_1, Some(*ADDR_2)).await?;
+        assert!(!db.index_is_empty().await?);
+        assert!(!db.is_node_safe_registry_empty(None).await?);
+
+        db.clear_index_db(None).await?;
+        assert!(db.index_is_empty().await?);
+        assert_eq!(None, db.get_node_safe(None, *ADDR_1).await?);
+        Ok(())
+    }
 }
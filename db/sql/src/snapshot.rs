@@ -0,0 +1,389 @@
+//! Export and import of snapshots of the logs database.
+//!
+//! A snapshot is a copy of the logs database, whose log checksum chain has been verified.
+//! A new node can import a snapshot instead of fetching all the logs over RPC and resume indexing from the last
+//! log contained in the snapshot.
+//!
+//! The checksum chain covers only the block hash, transaction hash and log index of each log,
+//! therefore the contents of the logs (contract address, topics and data) are not verified here.
+use std::path::{Path, PathBuf};
+
+use hopr_crypto_types::prelude::Hash;
+use hopr_db_entity::{
+    log, log_status,
+    prelude::{Log, LogStatus},
+};
+use hopr_primitive_types::prelude::*;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, SqlxSqliteConnector,
+    sea_query::{Expr, Value},
+};
+use sqlx::{
+    pool::PoolOptions,
+    sqlite::{SqliteConnectOptions, SqliteJournalMode},
+};
+use tracing::{debug, info};
+
+use crate::{
+    db::SQL_DB_LOGS_FILE_NAME,
+    errors::{DbSqlError, Result},
+    logs::log_checksum,
+};
+
+/// Number of logs fetched at once while verifying a snapshot.
+const SNAPSHOT_VERIFICATION_PAGE_SIZE: u64 = 10_000;
+
+/// Summary of a verified snapshot of the logs database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogsSnapshot {
+    /// Block number of the last log in the snapshot.
+    pub block_number: u64,
+    /// Number of logs in the snapshot.
+    pub log_count: u64,
+    /// Checksum of the last log in the snapshot.
+    pub checksum: Hash,
+}
+
+/// Exports the logs database from the given database `directory` into a snapshot file at `target`.
+///
+/// Logs which have not been checksummed yet are not part of the snapshot.
+/// The checksum chain of the exported logs is verified before returning.
+pub async fn export_logs_snapshot(directory: &Path, target: &Path) -> Result<LogsSnapshot> {
+    let source = directory.join(SQL_DB_LOGS_FILE_NAME);
+    if !source.is_file() {
+        return Err(DbSqlError::Construction(format!(
+            "logs database {source:?} does not exist"
+        )));
+    }
+
+    if target.exists() {
+        return Err(DbSqlError::Construction(format!(
+            "snapshot file {target:?} already exists"
+        )));
+    }
+
+    let logs_db = open_logs_db(&source, SqliteJournalMode::Wal).await?;
+    sqlx::query("VACUUM INTO ?")
+        .bind(target.to_string_lossy())
+        .execute(logs_db.get_sqlite_connection_pool())
+        .await
+        .map_err(|e| DbSqlError::Construction(format!("failed to copy the logs database: {e}")))?;
+    logs_db.close().await?;
+
+    let result = async {
+        let snapshot_db = open_logs_db(target, SqliteJournalMode::Delete).await?;
+        remove_unchecksummed_logs(&snapshot_db).await?;
+        let snapshot = verify_logs(&snapshot_db).await;
+        snapshot_db.close().await?;
+        snapshot
+    }
+    .await;
+
+    if result.is_err() {
+        let _ = std::fs::remove_file(target);
+    }
+
+    let snapshot = result?;
+    info!(?snapshot, ?target, "exported logs snapshot");
+    Ok(snapshot)
+}
+
+/// Imports the logs snapshot file at `source` into the logs database in the given database `directory`.
+///
+/// The checksum chain of the snapshot is verified and must end with the `expected_checksum`.
+/// The contents of the logs are not covered by the checksums and are not verified.
+/// The logs database must not contain any logs yet. All imported logs are marked as unprocessed,
+/// so the Indexer processes them again using the fast sync.
+pub async fn import_logs_snapshot(directory: &Path, source: &Path, expected_checksum: Hash) -> Result<LogsSnapshot> {
+    let target = directory.join(SQL_DB_LOGS_FILE_NAME);
+    if target.is_file() {
+        let logs_db = open_logs_db(&target, SqliteJournalMode::Wal).await?;
+        let log_count = Log::find().count(&logs_db).await;
+        logs_db.close().await?;
+
+        if log_count? > 0 {
+            return Err(DbSqlError::LogicalError(format!(
+                "cannot import a logs snapshot into the non-empty logs database {target:?}"
+            )));
+        }
+    }
+
+    std::fs::create_dir_all(directory)
+        .map_err(|e| DbSqlError::Construction(format!("cannot create main database directory {directory:?}: {e}")))?;
+
+    let imported = directory.join(format!("{SQL_DB_LOGS_FILE_NAME}.snapshot"));
+    std::fs::copy(source, &imported)
+        .map_err(|e| DbSqlError::Construction(format!("failed to copy the snapshot file {source:?}: {e}")))?;
+
+    let result = async {
+        let snapshot_db = open_logs_db(&imported, SqliteJournalMode::Delete).await?;
+        let snapshot = verify_logs(&snapshot_db).await.and_then(|snapshot| {
+            if snapshot.checksum == expected_checksum {
+                Ok(snapshot)
+            } else {
+                Err(DbSqlError::LogicalError(format!(
+                    "snapshot checksum {} does not match the expected checksum {expected_checksum}",
+                    snapshot.checksum
+                )))
+            }
+        });
+
+        if snapshot.is_ok() {
+            LogStatus::update_many()
+                .col_expr(log_status::Column::Processed, Expr::value(Value::Bool(Some(false))))
+                .col_expr(
+                    log_status::Column::ProcessedAt,
+                    Expr::value(Value::ChronoDateTimeUtc(None)),
+                )
+                .exec(&snapshot_db)
+                .await?;
+        }
+
+        snapshot_db.close().await?;
+        snapshot
+    }
+    .await;
+
+    let snapshot = match result {
+        Ok(snapshot) => snapshot,
+        Err(error) => {
+            let _ = std::fs::remove_file(&imported);
+            return Err(error);
+        }
+    };
+
+    // Remove the empty logs database, including its WAL files, and replace it with the snapshot
+    for suffix in ["", "-wal", "-shm"] {
+        let path = PathBuf::from(format!("{}{suffix}", target.to_string_lossy()));
+        if path.exists() {
+            std::fs::remove_file(&path)
+                .map_err(|e| DbSqlError::Construction(format!("failed to remove {path:?}: {e}")))?;
+        }
+    }
+    std::fs::rename(&imported, &target)
+        .map_err(|e| DbSqlError::Construction(format!("failed to move the snapshot to {target:?}: {e}")))?;
+
+    info!(?snapshot, ?source, "imported logs snapshot");
+    Ok(snapshot)
+}
+
+async fn open_logs_db(path: &Path, journal_mode: SqliteJournalMode) -> Result<DatabaseConnection> {
+    let pool = PoolOptions::new()
+        .min_connections(0)
+        .max_connections(1)
+        .connect_with(
+            SqliteConnectOptions::default()
+                .filename(path)
+                .create_if_missing(false)
+                .journal_mode(journal_mode),
+        )
+        .await
+        .map_err(|e| DbSqlError::Construction(format!("failed to open logs database {path:?}: {e}")))?;
+
+    Ok(SqlxSqliteConnector::from_sqlx_sqlite_pool(pool))
+}
+
+async fn remove_unchecksummed_logs(db: &DatabaseConnection) -> Result<()> {
+    let unchecksummed = LogStatus::find()
+        .filter(log_status::Column::Checksum.is_null())
+        .all(db)
+        .await?;
+
+    debug!(
+        count = unchecksummed.len(),
+        "removing logs without checksum from the snapshot"
+    );
+
+    for status in unchecksummed {
+        Log::delete_many()
+            .filter(log::Column::BlockNumber.eq(status.block_number))
+            .filter(log::Column::TransactionIndex.eq(status.transaction_index))
+            .filter(log::Column::LogIndex.eq(status.log_index))
+            .exec(db)
+            .await?;
+    }
+
+    LogStatus::delete_many()
+        .filter(log_status::Column::Checksum.is_null())
+        .exec(db)
+        .await?;
+
+    Ok(())
+}
+
+/// Recomputes the checksum chain of all logs and compares it with the stored checksums.
+///
+/// Only the block hashes, transaction hashes and log indices are covered by the checksums.
+async fn verify_logs(db: &DatabaseConnection) -> Result<LogsSnapshot> {
+    let mut snapshot = LogsSnapshot {
+        block_number: 0,
+        log_count: 0,
+        checksum: Hash::default(),
+    };
+
+    let mut pages = LogStatus::find()
+        .order_by_asc(log_status::Column::BlockNumber)
+        .order_by_asc(log_status::Column::TransactionIndex)
+        .order_by_asc(log_status::Column::LogIndex)
+        .find_also_related(Log)
+        .paginate(db, SNAPSHOT_VERIFICATION_PAGE_SIZE);
+
+    while let Some(entries) = pages.fetch_and_next().await? {
+        for (status, log) in entries {
+            let log = log.ok_or(DbSqlError::MissingLog)?;
+            let block_number = U256::from_be_bytes(&status.block_number).as_u64();
+
+            let checksum = log_checksum(&snapshot.checksum, &log);
+            if status.checksum.as_deref() != Some(checksum.as_ref()) {
+                return Err(DbSqlError::LogicalError(format!(
+                    "invalid checksum of log {} in block {block_number}, tx {}",
+                    U256::from_be_bytes(&status.log_index).as_u64(),
+                    U256::from_be_bytes(&status.transaction_index).as_u64(),
+                )));
+            }
+
+            snapshot = LogsSnapshot {
+                block_number,
+                log_count: snapshot.log_count + 1,
+                checksum,
+            };
+        }
+    }
+
+    if snapshot.log_count == 0 {
+        return Err(DbSqlError::LogicalError("snapshot does not contain any logs".into()));
+    }
+
+    Ok(snapshot)
+}
+
+#[cfg(test)]
+mod tests {
+    use hopr_crypto_types::prelude::{ChainKeypair, Hash, Keypair};
+    use hopr_db_api::logs::HoprDbLogOperations;
+    use hopr_primitive_types::prelude::*;
+    use sea_orm::{EntityTrait, sea_query::Expr};
+    use sqlx::sqlite::SqliteJournalMode;
+
+    use super::*;
+    use crate::db::{HoprDb, HoprDbConfig};
+
+    async fn create_db_with_logs(directory: &Path, block_count: u64) -> anyhow::Result<Hash> {
+        let db = HoprDb::new(directory, ChainKeypair::random(), HoprDbConfig::default()).await?;
+
+        for block_number in 1..=block_count {
+            db.store_log(SerializableLog {
+                address: Address::new(b"my address 123456789"),
+                topics: [Hash::create(&[b"my topic"]).into()].into(),
+                data: [1, 2, 3, 4].into(),
+                tx_index: 1u64,
+                block_number,
+                block_hash: Hash::create(&[format!("block hash {block_number}").as_bytes()]).into(),
+                tx_hash: Hash::create(&[format!("tx hash {block_number}").as_bytes()]).into(),
+                log_index: 1u64,
+                removed: false,
+                ..Default::default()
+            })
+            .await?;
+        }
+        db.set_logs_processed(None, None).await?;
+
+        Ok(db.update_logs_checksums().await?)
+    }
+
+    #[tokio::test]
+    async fn test_logs_snapshot_export_and_import() -> anyhow::Result<()> {
+        let tmp_dir = tempfile::tempdir()?;
+        let source_dir = tmp_dir.path().join("source");
+        let target_dir = tmp_dir.path().join("target");
+        let snapshot_file = tmp_dir.path().join("snapshot.db");
+
+        let checksum = create_db_with_logs(&source_dir, 3).await?;
+
+        let exported = export_logs_snapshot(&source_dir, &snapshot_file).await?;
+        assert_eq!(
+            LogsSnapshot {
+                block_number: 3,
+                log_count: 3,
+                checksum
+            },
+            exported
+        );
+
+        assert!(
+            export_logs_snapshot(&source_dir, &snapshot_file).await.is_err(),
+            "must not overwrite existing snapshot"
+        );
+
+        // Node with an empty logs database
+        drop(HoprDb::new(&target_dir, ChainKeypair::random(), HoprDbConfig::default()).await?);
+
+        let imported = import_logs_snapshot(&target_dir, &snapshot_file, checksum).await?;
+        assert_eq!(exported, imported);
+
+        let db = HoprDb::new(&target_dir, ChainKeypair::random(), HoprDbConfig::default()).await?;
+        let last_log = db
+            .get_last_checksummed_log()
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("must have a checksummed log"))?;
+        assert_eq!(3, last_log.block_number);
+        assert_eq!(Some(checksum.to_hex()), last_log.checksum);
+        assert_eq!(
+            vec![1, 2, 3],
+            db.get_logs_block_numbers(None, None, Some(false)).await?,
+            "imported logs must be unprocessed"
+        );
+
+        assert!(
+            import_logs_snapshot(&target_dir, &snapshot_file, checksum)
+                .await
+                .is_err(),
+            "must not import into a non-empty logs database"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_logs_snapshot_import_should_reject_invalid_checksums() -> anyhow::Result<()> {
+        let tmp_dir = tempfile::tempdir()?;
+        let source_dir = tmp_dir.path().join("source");
+        let target_dir = tmp_dir.path().join("target");
+        let snapshot_file = tmp_dir.path().join("snapshot.db");
+
+        let checksum = create_db_with_logs(&source_dir, 3).await?;
+        export_logs_snapshot(&source_dir, &snapshot_file).await?;
+
+        assert!(
+            import_logs_snapshot(&target_dir, &snapshot_file, Hash::create(&[b"other checksum"]))
+                .await
+                .is_err(),
+            "must reject snapshot with an unexpected checksum"
+        );
+
+        // Tamper with a log in the middle of the checksum chain
+        let snapshot_db = open_logs_db(&snapshot_file, SqliteJournalMode::Delete).await?;
+        Log::update_many()
+            .col_expr(
+                log::Column::BlockHash,
+                Expr::value(Hash::create(&[b"forged block hash"]).as_ref().to_vec()),
+            )
+            .filter(log::Column::BlockNumber.eq(2_u64.to_be_bytes().to_vec()))
+            .exec(&snapshot_db)
+            .await?;
+        snapshot_db.close().await?;
+
+        assert!(
+            import_logs_snapshot(&target_dir, &snapshot_file, checksum)
+                .await
+                .is_err(),
+            "must reject snapshot with a broken checksum chain"
+        );
+        assert!(
+            !target_dir.join(SQL_DB_LOGS_FILE_NAME).exists(),
+            "rejected snapshot must not be imported"
+        );
+
+        Ok(())
+    }
+}
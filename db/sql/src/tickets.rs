@@ -16,7 +16,9 @@ use hopr_db_api::{
     info::DomainSeparator,
     prelude::{TicketIndexSelector, TicketMarker},
     resolver::HoprDbResolverOperations,
-    tickets::{AggregationPrerequisites, ChannelTicketStatistics, HoprDbTicketOperations, TicketSelector},
+    tickets::{
+        AggregationPrerequisites, ChannelTicketStatistics, HoprDbTicketOperations, TicketCursor, TicketSelector,
+    },
 };
 use hopr_db_entity::{outgoing_ticket_index, ticket, ticket_statistics};
 use hopr_internal_types::prelude::*;
@@ -325,6 +327,58 @@ impl HoprDbTicketOperations for HoprDb {
             .await?)
     }
 
+    async fn get_tickets_page(
+        &self,
+        selector: TicketSelector,
+        after: Option<TicketCursor>,
+        limit: usize,
+    ) -> Result<Vec<AcknowledgedTicket>> {
+        debug!(?after, limit, "fetching page of tickets via {selector}");
+        let selector: WrappedTicketSelector = selector.into();
+
+        Ok(self
+            .nest_transaction_in_db(None, TargetDb::Tickets)
+            .await?
+            .perform(|tx| {
+                Box::pin(async move {
+                    let mut query = ticket::Entity::find().filter(selector);
+
+                    if let Some(cursor) = after {
+                        let channel_id = cursor.channel_id.to_hex();
+                        let epoch = cursor.epoch.to_be_bytes().to_vec();
+                        query = query.filter(
+                            Condition::any()
+                                .add(ticket::Column::ChannelId.gt(channel_id.clone()))
+                                .add(
+                                    ticket::Column::ChannelId
+                                        .eq(channel_id.clone())
+                                        .and(ticket::Column::ChannelEpoch.gt(epoch.clone())),
+                                )
+                                .add(
+                                    ticket::Column::ChannelId
+                                        .eq(channel_id)
+                                        .and(ticket::Column::ChannelEpoch.eq(epoch))
+                                        .and(ticket::Column::Index.gt(cursor.index.to_be_bytes().to_vec())),
+                                ),
+                        );
+                    }
+
+                    query
+                        .order_by_asc(ticket::Column::ChannelId)
+                        .order_by_asc(ticket::Column::ChannelEpoch)
+                        .order_by_asc(ticket::Column::Index)
+                        .limit(limit as u64)
+                        .all(tx.as_ref())
+                        .await?
+                        .into_iter()
+                        .map(AcknowledgedTicket::try_from)
+                        .collect::<hopr_db_entity::errors::Result<Vec<_>>>()
+                        .map_err(DbSqlError::from)
+                })
+            })
+            .await?)
+    }
+
     async fn mark_tickets_as(&self, selector: TicketSelector, mark_as: TicketMarker) -> Result<usize> {
         let myself = self.clone();
         Ok(self
@@ -1314,7 +1368,7 @@ mod tests {
     use hopr_db_api::{
         info::DomainSeparator,
         prelude::{DbError, TicketMarker},
-        tickets::ChannelTicketStatistics,
+        tickets::{ChannelTicketStatistics, TicketCursor},
     };
     use hopr_db_entity::ticket;
     use hopr_internal_types::prelude::*;
@@ -1463,6 +1517,39 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_get_tickets_page_should_iterate_over_all_tickets() -> anyhow::Result<()> {
+        let db = HoprDb::new_in_memory(ALICE.clone()).await?;
+        db.set_domain_separator(None, DomainSeparator::Channel, Hash::default())
+            .await?;
+
+        const COUNT_TICKETS: u64 = 25;
+        let (channel, tickets) = init_db_with_tickets(&db, COUNT_TICKETS).await?;
+
+        let mut fetched = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = db.get_tickets_page((&channel).into(), cursor, 10).await?;
+            assert!(page.len() <= 10, "page must not exceed the limit");
+            cursor = page.last().map(TicketCursor::from);
+            fetched.extend(page);
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        assert_eq!(tickets, fetched, "all tickets must be fetched in order");
+
+        let selector = TicketSelector::from(&channel).with_index_range(5..8);
+        let page = db.get_tickets_page(selector, None, 10).await?;
+        assert_eq!(
+            vec![5, 6, 7],
+            page.iter().map(|t| t.verified_ticket().index).collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_mark_redeemed() -> anyhow::Result<()> {
         let db = HoprDb::new_in_memory(ALICE.clone()).await?;
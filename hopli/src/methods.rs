@@ -32,13 +32,15 @@ use hopr_bindings::{
     hoprnodestakefactory::HoprNodeStakeFactory::{HoprNodeStakeFactoryInstance, cloneCall},
     hoprtoken::HoprToken::{HoprTokenInstance, approveCall},
 };
+pub use hopr_chain_types::multisend::{MultisendTransaction, SafeTxOperation};
+use hopr_chain_types::{constants::SAFE_MULTISEND_ADDRESS, multisend::multiSendCall};
 use hopr_crypto_types::keypairs::{ChainKeypair, Keypair};
 use tracing::{debug, info};
 
 use crate::utils::{
     DEFAULT_ANNOUNCEMENT_PERMISSIONS, DEFAULT_CAPABILITY_PERMISSIONS, DEFAULT_NODE_PERMISSIONS,
-    DOMAIN_SEPARATOR_TYPEHASH, HelperErrors, SAFE_COMPATIBILITYFALLBACKHANDLER_ADDRESS, SAFE_MULTISEND_ADDRESS,
-    SAFE_SAFE_ADDRESS, SAFE_SAFEPROXYFACTORY_ADDRESS, SAFE_TX_TYPEHASH, SENTINEL_OWNERS, get_create2_address,
+    DOMAIN_SEPARATOR_TYPEHASH, HelperErrors, SAFE_COMPATIBILITYFALLBACKHANDLER_ADDRESS, SAFE_SAFE_ADDRESS,
+    SAFE_SAFEPROXYFACTORY_ADDRESS, SAFE_TX_TYPEHASH, SENTINEL_OWNERS, get_create2_address,
 };
 
 sol!(
@@ -70,12 +72,6 @@ sol!(
     }
 );
 
-sol!(
-    #![sol(abi)]
-    #![sol(rpc)]
-    function multiSend(bytes memory transactions) public payable;
-);
-
 sol!(
     #![sol(abi)]
     #![sol(rpc)]
@@ -84,68 +80,6 @@ sol!(
     }
 );
 
-/// Enums of Safe transaction operation
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum SafeTxOperation {
-    Call,
-    DelegateCall,
-}
-impl SafeTxOperation {
-    /// convert the SafeTxOperation to exact one byte
-    pub fn to_byte(&self) -> [u8; 1] {
-        match self {
-            SafeTxOperation::Call => hex!("00"),
-            SafeTxOperation::DelegateCall => hex!("01"),
-        }
-    }
-}
-
-impl From<SafeTxOperation> for u8 {
-    fn from(s: SafeTxOperation) -> u8 {
-        s as u8
-    }
-}
-
-/// Struct to make a multisend transaction, mainly used by safe instances
-#[derive(Debug, Clone)]
-pub struct MultisendTransaction {
-    // data paylaod encoded with selector
-    pub encoded_data: Bytes,
-    // transaction type
-    pub tx_operation: SafeTxOperation,
-    // target address
-    pub to: Address,
-    // payable eth sending along the tx
-    pub value: U256,
-}
-
-/// Methods for Multisend transaction
-impl MultisendTransaction {
-    /// encode a multisend transaction
-    fn encode_packed(&self) -> Vec<u8> {
-        let tx_operation_bytes: Bytes = self.tx_operation.to_byte().into();
-
-        let value = (
-            tx_operation_bytes,                  // 1 bytes
-            self.to,                             // 20 bytes
-            U256::from(self.value),              // 32 bytes
-            U256::from(self.encoded_data.len()), // 32 bytes
-            self.encoded_data.clone(),           // bytes
-        );
-        value.abi_encode_packed()
-    }
-
-    /// build a multisend transaction data payload
-    fn build_multisend_tx(transactions: Vec<MultisendTransaction>) -> Vec<u8> {
-        let mut payload: Vec<u8> = Vec::new();
-        for transaction in transactions {
-            payload = [payload, transaction.encode_packed()].concat();
-        }
-        debug!("payload {:?}", hex::encode(&payload));
-        payload
-    }
-}
-
 /// get the domain separator of a safe instance
 /// contract_address should be safe address
 fn get_domain_separator(chain_id: U256, contract_address: Address) -> [u8; 32] {
@@ -1088,7 +1022,7 @@ pub async fn deregister_nodes_from_node_safe_registry_and_remove_from_module<P:
             send_multisend_safe_transaction_with_threshold_one(
                 safe,
                 owner_chain_key.clone(),
-                Address::from_str(SAFE_MULTISEND_ADDRESS).unwrap(),
+                SAFE_MULTISEND_ADDRESS,
                 multisend_txns,
                 chain_id,
                 safe_nonce,
@@ -1130,7 +1064,7 @@ pub async fn include_nodes_to_module<P: WalletProvider + Provider>(
     send_multisend_safe_transaction_with_threshold_one(
         safe,
         owner_chain_key.clone(),
-        Address::from_str(SAFE_MULTISEND_ADDRESS).unwrap(),
+        SAFE_MULTISEND_ADDRESS,
         multisend_txns,
         chain_id,
         safe_nonce,
@@ -1208,7 +1142,7 @@ pub async fn migrate_nodes<P: WalletProvider + Provider>(
     send_multisend_safe_transaction_with_threshold_one(
         safe,
         owner_chain_key.clone(),
-        Address::from_str(SAFE_MULTISEND_ADDRESS).unwrap(),
+        SAFE_MULTISEND_ADDRESS,
         multisend_txns,
         chain_id,
         safe_nonce,
@@ -1993,7 +1927,7 @@ mod tests {
         send_multisend_safe_transaction_with_threshold_one(
             safe.clone(),
             contract_deployer,
-            Address::from_str(SAFE_MULTISEND_ADDRESS)?,
+            SAFE_MULTISEND_ADDRESS,
             multisend_txns,
             U256::from(chain_id),
             safe_nonce,
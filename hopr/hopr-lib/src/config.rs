@@ -1,6 +1,8 @@
+use std::path::PathBuf;
+
 use hopr_primitive_types::prelude::*;
 pub use hopr_strategy::StrategyConfig;
-use hopr_transport::config::SessionGlobalConfig;
+use hopr_transport::config::{CoverTrafficConfig, PathSelectionConfig, SessionGlobalConfig};
 pub use hopr_transport::config::{
     HeartbeatConfig, HostConfig, HostType, NetworkConfig, ProtocolConfig, TransportConfig, validate_external_host,
 };
@@ -45,6 +47,14 @@ pub struct Chain {
     #[serde(default)]
     pub provider: Option<String>,
     #[serde(default)]
+    pub fallback_providers: Vec<String>,
+    #[serde(default)]
+    pub provider_pool: hopr_chain_rpc::pool::ProviderPoolConfig,
+    #[serde(default)]
+    pub tx_batching: hopr_chain_api::executors::TransactionBatchingConfig,
+    #[serde(default)]
+    pub tx_manager: hopr_chain_rpc::tx_manager::TransactionManagerConfig,
+    #[serde(default)]
     pub max_rpc_requests_per_sec: Option<u32>,
     #[serde(default)]
     pub protocols: hopr_chain_api::config::ProtocolsConfig,
@@ -106,6 +116,13 @@ pub struct Db {
     pub force_initialize: bool,
 }
 
+impl Db {
+    /// Directory containing the database files.
+    pub fn directory(&self) -> PathBuf {
+        [&self.data, "db"].iter().collect()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, smart_default::SmartDefault, Serialize, Deserialize, Validate)]
 pub struct HoprLibConfig {
     /// Configuration related to host specifics
@@ -146,6 +163,14 @@ pub struct HoprLibConfig {
     #[validate(nested)]
     #[serde(default)]
     pub session: SessionGlobalConfig,
+    /// Configuration of the cover traffic generation.
+    #[validate(nested)]
+    #[serde(default)]
+    pub cover_traffic: CoverTrafficConfig,
+    /// Configuration of the automatic path selection.
+    #[validate(nested)]
+    #[serde(default)]
+    pub path_selection: PathSelectionConfig,
     /// Blockchain-specific configuration
     #[validate(nested)]
     #[serde(default)]
@@ -36,6 +36,9 @@ pub enum HoprLibError {
     #[error(transparent)]
     ChainApi(#[from] hopr_chain_api::errors::HoprChainError),
 
+    #[error(transparent)]
+    StrategyError(#[from] hopr_strategy::errors::StrategyError),
+
     #[error(transparent)]
     TypeError(#[from] hopr_primitive_types::errors::GeneralError),
 }
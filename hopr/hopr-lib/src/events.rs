@@ -0,0 +1,103 @@
+use hopr_chain_api::IndexerSyncProgress;
+use hopr_chain_types::chain_events::ChainEventType;
+use hopr_internal_types::prelude::*;
+use hopr_primitive_types::prelude::*;
+use hopr_transport::{PeerConnectionEvent, PeerId, SessionId, SessionLifecycleEvent};
+
+/// Maximum number of node events queued until they are consumed, before new events are dropped.
+pub const NODE_EVENT_QUEUE_SIZE: usize = 4096;
+
+/// Significant change of the node state, as it happens.
+#[derive(Debug, Clone, PartialEq, strum::EnumDiscriminants)]
+#[strum_discriminants(name(HoprNodeEventType))]
+#[strum_discriminants(derive(strum::Display, strum::EnumString, Hash))]
+#[strum_discriminants(strum(serialize_all = "snake_case"))]
+pub enum HoprNodeEvent {
+    /// One of the node's own channels has been opened.
+    ChannelOpened(ChannelEntry),
+    /// One of the node's own channels has been funded with the given amount.
+    ChannelFunded(ChannelEntry, HoprBalance),
+    /// Closure of one of the node's own channels has been initiated.
+    ChannelClosureInitiated(ChannelEntry),
+    /// One of the node's own channels has been closed.
+    ChannelClosed(ChannelEntry),
+    /// A ticket has been redeemed in one of the node's own channels.
+    TicketRedeemed(ChannelEntry),
+    /// The first connection to a peer has been established.
+    PeerConnected(PeerId),
+    /// The last connection to a peer has been closed.
+    PeerDisconnected(PeerId),
+    /// A Session has been established.
+    SessionOpened {
+        /// ID of the Session.
+        session_id: SessionId,
+        /// Indicates whether the Session was initiated by the other party.
+        incoming: bool,
+    },
+    /// A Session has been closed.
+    SessionClosed {
+        /// ID of the Session.
+        session_id: SessionId,
+    },
+    /// The Indexer made progress in the initial synchronization with the chain.
+    IndexerSyncProgress(IndexerSyncProgress),
+}
+
+impl HoprNodeEvent {
+    /// Creates a node event from an on-chain event concerning one of the node's own channels.
+    ///
+    /// Returns `None` if the on-chain event is not of interest.
+    pub(crate) fn from_own_channel_event(event: &ChainEventType) -> Option<Self> {
+        match event {
+            ChainEventType::ChannelOpened(channel) => Some(Self::ChannelOpened(*channel)),
+            ChainEventType::ChannelBalanceIncreased(channel, amount) => Some(Self::ChannelFunded(*channel, *amount)),
+            ChainEventType::ChannelClosureInitiated(channel) => Some(Self::ChannelClosureInitiated(*channel)),
+            ChainEventType::ChannelClosed(channel) => Some(Self::ChannelClosed(*channel)),
+            ChainEventType::TicketRedeemed(channel, _) => Some(Self::TicketRedeemed(*channel)),
+            _ => None,
+        }
+    }
+}
+
+impl From<PeerConnectionEvent> for HoprNodeEvent {
+    fn from(value: PeerConnectionEvent) -> Self {
+        match value {
+            PeerConnectionEvent::Connected(peer) => Self::PeerConnected(peer),
+            PeerConnectionEvent::Disconnected(peer) => Self::PeerDisconnected(peer),
+        }
+    }
+}
+
+impl From<SessionLifecycleEvent> for HoprNodeEvent {
+    fn from(value: SessionLifecycleEvent) -> Self {
+        match value {
+            SessionLifecycleEvent::Opened { session_id, incoming } => Self::SessionOpened { session_id, incoming },
+            SessionLifecycleEvent::Closed { session_id } => Self::SessionClosed { session_id },
+        }
+    }
+}
+
+impl From<IndexerSyncProgress> for HoprNodeEvent {
+    fn from(value: IndexerSyncProgress) -> Self {
+        Self::IndexerSyncProgress(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn node_event_types_should_be_parsable_from_their_names() -> anyhow::Result<()> {
+        assert_eq!("channel_opened", HoprNodeEventType::ChannelOpened.to_string());
+        assert_eq!(
+            HoprNodeEventType::IndexerSyncProgress,
+            HoprNodeEventType::from_str("indexer_sync_progress")?
+        );
+        assert!(HoprNodeEventType::from_str("unknown").is_err());
+
+        Ok(())
+    }
+}
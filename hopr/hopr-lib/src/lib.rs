@@ -19,12 +19,15 @@ pub mod config;
 pub mod constants;
 /// Lists all errors thrown from this library.
 pub mod errors;
+/// Events notifying about the node state changes.
+pub mod events;
+/// Export and import of the Indexer logs snapshots for a fast bootstrap of new nodes.
+pub mod snapshot;
 
 use std::{
     collections::HashMap,
     fmt::{Display, Formatter},
     ops::Deref,
-    path::PathBuf,
     sync::{Arc, atomic::Ordering},
     time::Duration,
 };
@@ -47,8 +50,8 @@ pub use hopr_chain_api::config::{
     Addresses as NetworkContractAddresses, EnvironmentType, Network as ChainNetwork, ProtocolsConfig,
 };
 use hopr_chain_api::{
-    HoprChain, HoprChainProcess, SignificantChainEvent, can_register_with_safe, config::ChainNetworkConfig,
-    errors::HoprChainError, wait_for_funds,
+    HoprChain, HoprChainProcess, IndexerSyncProgress, SignificantChainEvent, can_register_with_safe,
+    config::ChainNetworkConfig, errors::HoprChainError, wait_for_funds,
 };
 use hopr_chain_rpc::HoprRpcOperations;
 use hopr_chain_types::{ContractAddresses, chain_events::ChainEventType};
@@ -57,7 +60,11 @@ use hopr_db_api::logs::HoprDbLogOperations;
 use hopr_db_sql::{
     HoprDbAllOperations, HoprDbGeneralModelOperations,
     accounts::HoprDbAccountOperations,
-    api::{info::SafeInfo, resolver::HoprDbResolverOperations, tickets::HoprDbTicketOperations},
+    api::{
+        info::SafeInfo,
+        resolver::HoprDbResolverOperations,
+        tickets::{HoprDbTicketOperations, TicketCursor, TicketSelector},
+    },
     channels::HoprDbChannelOperations,
     db::{HoprDb, HoprDbConfig},
     info::{HoprDbInfoOperations, IndexerStateInfo},
@@ -65,24 +72,28 @@ use hopr_db_sql::{
     registry::HoprDbRegistryOperations,
 };
 pub use hopr_internal_types::prelude::*;
-pub use hopr_network_types::prelude::{DestinationRouting, IpProtocol, RoutingOptions};
+pub use hopr_network_types::prelude::{DestinationRouting, IpProtocol, PathConstraints, RoutingOptions};
 pub use hopr_path::channel_graph::GraphExportConfig;
 use hopr_path::channel_graph::{ChannelGraph, ChannelGraphConfig, NodeScoreUpdate};
 use hopr_platform::file::native::{join, remove_dir_all};
 pub use hopr_primitive_types::prelude::*;
-pub use hopr_strategy::Strategy;
-use hopr_strategy::strategy::{MultiStrategy, SingularStrategy};
+use hopr_strategy::strategy::{ReconfigurableStrategy, SingularStrategy};
+pub use hopr_strategy::{
+    Strategy,
+    dry_run::{IntendedAction, StrategyIntent},
+    history::{StrategyEvent, StrategyExecution},
+};
 #[cfg(feature = "runtime-tokio")]
 pub use hopr_transport::transfer_session;
 use hopr_transport::{
     ChainKeypair, Hash, HoprTransport, HoprTransportConfig, HoprTransportProcess, IncomingSession, OffchainKeypair,
-    PeerDiscovery, PeerStatus, execute_on_tick,
+    PeerConnectionEvent, PeerDiscovery, PeerStatus, SessionLifecycleEvent, execute_on_tick,
 };
 pub use hopr_transport::{
-    HalfKeyChallenge, Health, IncomingSession as HoprIncomingSession, Keypair, Multiaddr,
-    OffchainKeypair as HoprOffchainKeypair, PeerId, SESSION_PAYLOAD_SIZE, SendMsg, ServiceId, Session as HoprSession,
-    SessionCapability, SessionClientConfig, SessionId as HoprSessionId, SessionTarget, SurbBalancerConfig,
-    TicketStatistics, USABLE_PAYLOAD_CAPACITY_FOR_SESSION,
+    HalfKeyChallenge, Health, IncomingSession as HoprIncomingSession, Keypair, Multiaddr, MultipathConfig,
+    OffchainKeypair as HoprOffchainKeypair, PeerId, RateController, RateLimitExt, SESSION_PAYLOAD_SIZE, SendMsg,
+    ServiceId, Session as HoprSession, SessionCapability, SessionClientConfig, SessionId as HoprSessionId,
+    SessionStatsSnapshot, SessionTarget, SurbBalancerConfig, TicketStatistics, USABLE_PAYLOAD_CAPACITY_FOR_SESSION,
     config::{HostConfig, HostType, looks_like_domain},
     constants::RESERVED_TAG_UPPER_LIMIT,
     errors::{HoprTransportError, NetworkingError, ProtocolError},
@@ -95,6 +106,7 @@ use {
     std::str::FromStr,
 };
 
+pub use crate::events::{HoprNodeEvent, HoprNodeEventType};
 use crate::{
     config::SafeModule,
     constants::{MIN_NATIVE_BALANCE, ONBOARDING_INFORMATION_INTERVAL, SUGGESTED_NATIVE_BALANCE},
@@ -175,6 +187,8 @@ pub enum HoprLibProcesses {
     IndexReflection,
     #[strum(to_string = "on-chain transaction queue component for outgoing transactions")]
     OutgoingOnchainActionQueue,
+    #[strum(to_string = "tracking of outgoing on-chain transactions until they are mined")]
+    OnchainTransactionManager,
     #[strum(to_string = "flush operation of outgoing ticket indices to the DB")]
     TicketIndexFlush,
     #[strum(to_string = "on received ack ticket trigger")]
@@ -203,14 +217,16 @@ impl From<HoprTransportProcess> for HoprLibProcesses {
 ///   generate any events.
 /// * `preloading_event_stream` - a stream used by the components to preload the data from the objects (db, channel
 ///   graph...)
+/// * `node_events` - notified about the events concerning the node's own channels.
 #[allow(clippy::too_many_arguments)]
 pub async fn chain_events_to_transport_events<StreamIn, Db>(
     event_stream: StreamIn,
     me_onchain: Address,
     db: Db,
-    multi_strategy: Arc<MultiStrategy>,
+    multi_strategy: Arc<ReconfigurableStrategy>,
     channel_graph: Arc<RwLock<hopr_path::channel_graph::ChannelGraph>>,
     indexer_action_tracker: Arc<IndexerActionTracker>,
+    node_events: async_channel::Sender<HoprNodeEvent>,
 ) -> impl Stream<Item = PeerDiscovery> + Send + 'static
 where
     Db: HoprDbAllOperations + Clone + Send + Sync + std::fmt::Debug + 'static,
@@ -221,6 +237,7 @@ where
         let multi_strategy = multi_strategy.clone();
         let channel_graph = channel_graph.clone();
         let indexer_action_tracker = indexer_action_tracker.clone();
+        let node_events = node_events.clone();
 
         async move {
             let resolved = indexer_action_tracker.match_and_resolve(&event).await;
@@ -245,6 +262,10 @@ where
 
                     // Check if this is our own channel
                     if let Some(own_channel_direction) = maybe_direction {
+                        if let Some(node_event) = HoprNodeEvent::from_own_channel_event(&event.event_type) {
+                            notify_node_event(&node_events, node_event);
+                        }
+
                         if let Some(change_set) = change {
                             for channel_change in change_set {
                                 let _ = hopr_strategy::strategy::SingularStrategy::on_own_channel_changed(
@@ -311,6 +332,12 @@ where
     }))
 }
 
+fn notify_node_event(node_events: &async_channel::Sender<HoprNodeEvent>, event: HoprNodeEvent) {
+    if let Err(error) = node_events.try_send(event) {
+        trace!(%error, "node event dropped");
+    }
+}
+
 /// Represents the socket behavior of the hopr-lib spawned [`Hopr`] object.
 ///
 /// Provides a read and write stream for Hopr socket recognized data formats.
@@ -362,8 +389,11 @@ pub struct Hopr {
     db: HoprDb,
     chain_cfg: ChainNetworkConfig,
     channel_graph: Arc<RwLock<hopr_path::channel_graph::ChannelGraph>>,
-    multistrategy: Arc<MultiStrategy>,
+    multistrategy: Arc<ReconfigurableStrategy>,
     rx_indexer_significant_events: async_channel::Receiver<SignificantChainEvent>,
+    rx_indexer_sync_progress: async_channel::Receiver<IndexerSyncProgress>,
+    tx_node_events: async_channel::Sender<HoprNodeEvent>,
+    rx_node_events: async_channel::Receiver<HoprNodeEvent>,
 }
 
 impl Hopr {
@@ -374,7 +404,7 @@ impl Hopr {
     ) -> crate::errors::Result<Self> {
         let multiaddress: Multiaddr = (&cfg.host).try_into()?;
 
-        let db_path: PathBuf = [&cfg.db.data, "db"].iter().collect();
+        let db_path = cfg.db.directory();
         info!(path = ?db_path, "Initiating DB");
 
         if cfg.db.force_initialize {
@@ -410,11 +440,23 @@ impl Hopr {
         } else {
             info!("Creating chain components using the default provider");
         }
+        // In-flight transactions are persisted next to the DB, unless configured otherwise
+        let mut tx_manager_cfg = cfg.chain.tx_manager.clone();
+        if tx_manager_cfg.store_path.is_none() {
+            tx_manager_cfg.store_path = Some(join(&[&cfg.db.data, "pending_transactions.json"]).map_err(|e| {
+                HoprLibError::GeneralError(format!("Failed to construct the pending transactions path: {e}"))
+            })?);
+        }
+
         let resolved_environment = hopr_chain_api::config::ChainNetworkConfig::new(
             &cfg.chain.network,
             crate::constants::APP_VERSION_COERCED,
             cfg.chain.provider.as_deref(),
             cfg.chain.max_rpc_requests_per_sec,
+            &cfg.chain.fallback_providers,
+            cfg.chain.provider_pool.clone(),
+            cfg.chain.tx_batching.clone(),
+            tx_manager_cfg,
             &mut cfg.chain.protocols,
         )
         .map_err(|e| HoprLibError::GeneralError(format!("Failed to resolve blockchain environment: {e}")))?;
@@ -429,6 +471,9 @@ impl Hopr {
         let my_multiaddresses = vec![multiaddress];
 
         let (tx_indexer_events, rx_indexer_events) = async_channel::unbounded::<SignificantChainEvent>();
+        let (tx_indexer_sync_progress, rx_indexer_sync_progress) =
+            async_channel::bounded::<IndexerSyncProgress>(events::NODE_EVENT_QUEUE_SIZE);
+        let (tx_node_events, rx_node_events) = async_channel::bounded::<HoprNodeEvent>(events::NODE_EVENT_QUEUE_SIZE);
 
         let channel_graph = Arc::new(RwLock::new(ChannelGraph::new(
             me_onchain.public().to_address(),
@@ -444,6 +489,8 @@ impl Hopr {
                 protocol: cfg.protocol,
                 heartbeat: cfg.heartbeat,
                 session: cfg.session,
+                cover_traffic: cfg.cover_traffic,
+                path_selection: cfg.path_selection.clone(),
             },
             db.clone(),
             channel_graph.clone(),
@@ -471,11 +518,13 @@ impl Hopr {
             hopr_chain_indexer::IndexerConfig {
                 start_block_number: resolved_environment.channel_contract_deploy_block as u64,
                 fast_sync: cfg.chain.fast_sync,
+                ..Default::default()
             },
             tx_indexer_events,
-        )?;
+        )?
+        .with_indexer_sync_progress(tx_indexer_sync_progress);
 
-        let multi_strategy = Arc::new(MultiStrategy::new(
+        let multi_strategy = Arc::new(ReconfigurableStrategy::new(
             cfg.strategy.clone(),
             db.clone(),
             hopr_hopr_chain_api.actions_ref().clone(),
@@ -517,6 +566,9 @@ impl Hopr {
             channel_graph,
             multistrategy: multi_strategy,
             rx_indexer_significant_events: rx_indexer_events,
+            rx_indexer_sync_progress,
+            tx_node_events,
+            rx_node_events,
         })
     }
 
@@ -688,9 +740,27 @@ impl Hopr {
             self.multistrategy.clone(),
             self.channel_graph.clone(),
             self.hopr_chain_api.action_state(),
+            self.tx_node_events.clone(),
         )
         .await;
 
+        let (peer_connection_tx, peer_connection_rx) = unbounded::<PeerConnectionEvent>();
+        let (session_lifecycle_tx, session_lifecycle_rx) = unbounded::<SessionLifecycleEvent>();
+
+        // terminated once all the event sources are dropped
+        let tx_node_events = self.tx_node_events.clone();
+        spawn(
+            futures::stream::select_all([
+                self.rx_indexer_sync_progress.clone().map(HoprNodeEvent::from).boxed(),
+                peer_connection_rx.map(HoprNodeEvent::from).boxed(),
+                session_lifecycle_rx.map(HoprNodeEvent::from).boxed(),
+            ])
+            .for_each(move |event| {
+                notify_node_event(&tx_node_events, event);
+                futures::future::ready(())
+            }),
+        );
+
         // terminated once all senders are dropped and no items in the receiver remain
         spawn(async move {
             indexer_event_pipeline
@@ -705,6 +775,7 @@ impl Hopr {
             let nid = match id {
                 HoprChainProcess::Indexer => HoprLibProcesses::Indexing,
                 HoprChainProcess::OutgoingOnchainActionQueue => HoprLibProcesses::OutgoingOnchainActionQueue,
+                HoprChainProcess::TransactionManager => HoprLibProcesses::OnchainTransactionManager,
             };
             processes.insert(nid, proc);
         }
@@ -932,6 +1003,8 @@ impl Hopr {
                 transport_output_tx,
                 indexer_peer_update_rx,
                 session_tx,
+                peer_connection_tx,
+                session_lifecycle_tx,
             )
             .await?
             .into_iter()
@@ -967,26 +1040,35 @@ impl Hopr {
 
         // NOTE: strategy ticks must start after the chain is synced, otherwise
         // the strategy would react to historical data and drain through the native
-        // balance on chain operations not relevant for the present network state
+        // balance on chain operations not relevant for the present network state.
+        // The execution interval is re-read on every tick, because the strategies can be reconfigured at runtime.
+        // A tick is never interrupted, so that the strategies do not abandon their actions halfway. Instead, the ticks
+        // which would overlap with a tick running longer than the interval are skipped.
         let multi_strategy = self.multistrategy.clone();
-        let strategy_interval = self.cfg.strategy.execution_interval;
         processes.insert(
             HoprLibProcesses::StrategyTick,
             spawn(async move {
-                execute_on_tick(
-                    Duration::from_secs(strategy_interval),
-                    move || {
-                        let multi_strategy = multi_strategy.clone();
-
-                        async move {
-                            trace!(state = "started", "strategy tick");
-                            let _ = multi_strategy.on_tick().await;
-                            trace!(state = "finished", "strategy tick");
-                        }
-                    },
-                    "run strategies".into(),
-                )
-                .await;
+                loop {
+                    let strategy_interval = Duration::from_secs(multi_strategy.config().await.execution_interval);
+                    let start = std::time::Instant::now();
+
+                    trace!(state = "started", "strategy tick");
+                    let _ = multi_strategy.on_tick().await;
+                    trace!(state = "finished", "strategy tick");
+
+                    let elapsed = start.elapsed().as_millis();
+                    let interval = strategy_interval.as_millis().max(1);
+                    if elapsed >= interval {
+                        warn!(
+                            elapsed_ms = elapsed,
+                            skipped_ticks = elapsed / interval,
+                            "strategy tick took longer than the execution interval"
+                        );
+                    }
+
+                    // Wait until the next tick that is due after the current one has finished
+                    sleep(Duration::from_millis((interval - elapsed % interval) as u64)).await;
+                }
             }),
         );
 
@@ -1012,6 +1094,14 @@ impl Hopr {
         Ok((socket, processes))
     }
 
+    /// Stream of the [events](HoprNodeEvent) notifying about the node state changes as they happen.
+    ///
+    /// The events are distributed among all the returned streams, so there should be a single consumer.
+    /// Events are dropped if they are not consumed fast enough.
+    pub fn node_events(&self) -> impl Stream<Item = HoprNodeEvent> + Send + 'static {
+        self.rx_node_events.clone()
+    }
+
     // p2p transport =========
     /// Own PeerId used in the libp2p transport layer
     pub fn me_peer_id(&self) -> PeerId {
@@ -1097,6 +1187,16 @@ impl Hopr {
         .await?)
     }
 
+    /// Statistics of the given active Session, if it exists.
+    pub fn session_stats(&self, session_id: &HoprSessionId) -> Option<SessionStatsSnapshot> {
+        self.transport_api.session_stats(session_id)
+    }
+
+    /// Statistics of all the active Sessions, both incoming and outgoing.
+    pub fn all_session_stats(&self) -> Vec<SessionStatsSnapshot> {
+        self.transport_api.all_session_stats()
+    }
+
     /// Send a message to another peer in the network
     ///
     /// @param msg message to send
@@ -1211,6 +1311,18 @@ impl Hopr {
         Ok(self.transport_api.tickets_in_channel(channel).await?)
     }
 
+    /// Get at most `limit` tickets matching the `selector`, ordered by channel ID, channel epoch and ticket index.
+    ///
+    /// If `after` is given, only the tickets following it in this order are returned.
+    pub async fn tickets_page(
+        &self,
+        selector: TicketSelector,
+        after: Option<TicketCursor>,
+        limit: usize,
+    ) -> errors::Result<Vec<AcknowledgedTicket>> {
+        Ok(self.db.get_tickets_page(selector, after, limit).await?)
+    }
+
     /// Get all tickets
     pub async fn all_tickets(&self) -> errors::Result<Vec<Ticket>> {
         Ok(self.transport_api.all_tickets().await?)
@@ -1226,6 +1338,29 @@ impl Hopr {
         Ok(self.db.reset_ticket_statistics().await?)
     }
 
+    // Strategy ========
+    /// Get the actions the strategies would have taken, if they run in the dry-run mode.
+    pub async fn strategy_intents(&self) -> Vec<StrategyIntent> {
+        self.multistrategy.intent_log().intents().await
+    }
+
+    /// Get the recorded executions of the individual strategies, oldest first.
+    pub async fn strategy_history(&self) -> Vec<StrategyExecution> {
+        self.multistrategy.history().executions().await
+    }
+
+    /// Get the configuration of the currently running strategies.
+    pub async fn strategy_config(&self) -> config::StrategyConfig {
+        self.multistrategy.config().await
+    }
+
+    /// Validate the given strategy configuration and replace the currently running strategies with it.
+    ///
+    /// Waits until the strategy events currently being executed finish.
+    pub async fn reconfigure_strategy(&self, cfg: config::StrategyConfig) -> errors::Result<()> {
+        Ok(self.multistrategy.reconfigure(cfg).await?)
+    }
+
     // DB ============
     pub fn peer_resolver(&self) -> &impl HoprDbResolverOperations {
         &self.db
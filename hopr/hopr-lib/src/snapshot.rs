@@ -0,0 +1,93 @@
+use std::path::Path;
+
+use hopr_crypto_types::types::Hash;
+use hopr_db_sql::snapshot::LogsSnapshot;
+use hopr_platform::file::native::write;
+use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, serde_as};
+use tracing::info;
+
+use crate::{
+    config::HoprLibConfig,
+    constants::APP_VERSION,
+    errors::{HoprLibError, Result},
+};
+
+/// Manifest describing a snapshot of the Indexer logs database.
+///
+/// The manifest is stored next to the snapshot file (see [`logs_snapshot_manifest_path`]).
+/// It is informative only, the checksum a snapshot is verified against when imported must come
+/// from a trusted source instead of the manifest distributed with the snapshot.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogsSnapshotManifest {
+    /// Version of the node which exported the snapshot.
+    pub version: String,
+    /// Block number of the last log in the snapshot.
+    pub block_number: u64,
+    /// Number of logs in the snapshot.
+    pub log_count: u64,
+    /// Checksum of the last log in the snapshot.
+    #[serde_as(as = "DisplayFromStr")]
+    pub checksum: Hash,
+}
+
+impl From<LogsSnapshot> for LogsSnapshotManifest {
+    fn from(value: LogsSnapshot) -> Self {
+        Self {
+            version: APP_VERSION.into(),
+            block_number: value.block_number,
+            log_count: value.log_count,
+            checksum: value.checksum,
+        }
+    }
+}
+
+/// Path of the manifest belonging to the given logs snapshot file.
+pub fn logs_snapshot_manifest_path(snapshot_file: &str) -> String {
+    format!("{snapshot_file}.json")
+}
+
+/// Exports the Indexer logs database of the node with the given configuration into the `snapshot_file`
+/// and writes its manifest next to it.
+pub async fn export_logs_snapshot(cfg: &HoprLibConfig, snapshot_file: &str) -> Result<LogsSnapshotManifest> {
+    let manifest_file = logs_snapshot_manifest_path(snapshot_file);
+    let manifest = LogsSnapshotManifest::from(
+        hopr_db_sql::snapshot::export_logs_snapshot(&cfg.db.directory(), Path::new(snapshot_file)).await?,
+    );
+
+    let manifest_json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| HoprLibError::GeneralError(format!("failed to serialize the snapshot manifest: {e}")))?;
+    write(&manifest_file, manifest_json)
+        .map_err(|e| HoprLibError::GeneralError(format!("failed to write the snapshot manifest: {e}")))?;
+
+    info!(snapshot_file, manifest_file, ?manifest, "Exported logs snapshot");
+    Ok(manifest)
+}
+
+/// Imports the Indexer logs database of the node with the given configuration from the `snapshot_file`.
+///
+/// The snapshot is accepted only if its checksum chain is valid and ends with the `expected_checksum`.
+/// The checksums cover only the block hashes, transaction hashes and log indices, not the contents of the logs.
+/// The Indexer compares the logs of a sample of the imported blocks with the chain before processing them.
+/// The node then processes the imported logs using the fast sync and resumes indexing after the last imported block,
+/// therefore the import fails if the fast sync is disabled.
+pub async fn import_logs_snapshot(
+    cfg: &HoprLibConfig,
+    snapshot_file: &str,
+    expected_checksum: Hash,
+) -> Result<LogsSnapshotManifest> {
+    if !cfg.chain.fast_sync {
+        return Err(HoprLibError::GeneralError(
+            "fast sync must be enabled to process the imported logs".into(),
+        ));
+    }
+
+    let manifest = LogsSnapshotManifest::from(
+        hopr_db_sql::snapshot::import_logs_snapshot(&cfg.db.directory(), Path::new(snapshot_file), expected_checksum)
+            .await?,
+    );
+
+    info!(snapshot_file, ?manifest, "Imported logs snapshot");
+    Ok(manifest)
+}
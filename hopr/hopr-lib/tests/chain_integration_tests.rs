@@ -247,6 +247,7 @@ async fn integration_test_indexer() -> anyhow::Result<()> {
     let indexer_cfg = IndexerConfig {
         start_block_number: 1,
         fast_sync: false,
+        ..Default::default()
     };
 
     // Setup ALICE
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use clap::{ArgAction, Parser, builder::ValueParser};
+use clap::{ArgAction, Parser, Subcommand, builder::ValueParser};
 use hopr_lib::{HostConfig, looks_like_domain};
 use serde::{Deserialize, Serialize};
 
@@ -41,6 +41,32 @@ fn parse_api_token(mut s: &str) -> Result<String, String> {
     }
 }
 
+/// Auxiliary commands, which are run instead of the node.
+#[derive(Serialize, Deserialize, Clone, Subcommand)]
+pub enum HoprdCommand {
+    /// Exports a verified snapshot of the Indexer logs database together with its manifest
+    ExportLogsSnapshot {
+        #[arg(
+            long,
+            help = "Path of the snapshot file to create, the manifest is written next to it with the .json extension"
+        )]
+        output: String,
+    },
+    /// Imports a snapshot of the Indexer logs database, so that a new node resumes indexing from its last block
+    ImportLogsSnapshot {
+        #[arg(long, help = "Path of the snapshot file to import")]
+        input: String,
+        #[arg(
+            long,
+            help = "Checksum of the last log in the snapshot, which must be obtained from a trusted source. The \
+                    checksum covers only the block hashes, transaction hashes and log indices, not the log contents \
+                    (contract address, topics and data), which are only checked against the RPC provider for a sample \
+                    of blocks on start"
+        )]
+        checksum: String,
+    },
+}
+
 /// Takes all CLI arguments whose structure is known at compile-time.
 /// Arguments whose structure, e.g. their default values depend on
 /// file contents need be specified using `clap`s builder API
@@ -312,4 +338,7 @@ pub struct CliArgs {
         env = "HOPRD_PROTOCOL_CONFIG_PATH"
     )]
     pub protocol_config_path: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<HoprdCommand>,
 }
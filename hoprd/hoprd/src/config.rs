@@ -1,20 +1,23 @@
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     net::{IpAddr, SocketAddr},
     str::FromStr,
     time::Duration,
 };
 
-use hopr_lib::{Address, HostConfig, HostType, ProtocolsConfig, config::HoprLibConfig};
+use hopr_lib::{Address, HostConfig, HostType, ProtocolsConfig, ServiceId, config::HoprLibConfig};
 use hopr_platform::file::native::read_to_string;
-use hoprd_api::config::{Api, Auth};
+use hoprd_api::{
+    SessionListenerConfig,
+    config::{Api, Auth},
+};
 use proc_macro_regex::regex;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use tracing::debug;
 use validator::{Validate, ValidationError};
 
-use crate::errors::HoprdError;
+use crate::{errors::HoprdError, services::ExitService, target_policy::TargetRule};
 
 pub const DEFAULT_HOST: &str = "0.0.0.0";
 pub const DEFAULT_PORT: u16 = 9091;
@@ -351,7 +354,8 @@ fn just_true() -> bool {
 )]
 pub struct SessionIpForwardingConfig {
     /// Controls whether allowlisting should be done via `target_allow_list`.
-    /// If set to `false`, the node will act as an Exit node for any target.
+    /// If set to `false`, the node will act as an Exit node for any target
+    /// that is not denied by `target_rules` or `deny_private_targets`.
     ///
     /// Defaults to `true`.
     #[serde(default = "just_true")]
@@ -368,6 +372,25 @@ pub struct SessionIpForwardingConfig {
     #[serde_as(as = "HashSet<serde_with::DisplayFromStr>")]
     pub target_allow_list: HashSet<SocketAddr>,
 
+    /// Rules allowing or denying Session targets by their network, port, protocol or DNS name.
+    ///
+    /// The rules are evaluated in order for each resolved target address, and the first matching
+    /// rule takes precedence over `target_allow_list` and `deny_private_targets`, except that private
+    /// addresses are allowed only by rules listing their network
+    /// (see [`TargetPolicy`](crate::target_policy::TargetPolicy)).
+    ///
+    /// Defaults to empty.
+    #[serde(default)]
+    pub target_rules: Vec<TargetRule>,
+
+    /// Denies Session targets in private, loopback, link-local and multicast address ranges,
+    /// unless they are allowed by `target_rules` listing their network, or listed in `target_allow_list`.
+    ///
+    /// Defaults to `true`.
+    #[serde(default = "just_true")]
+    #[default(true)]
+    pub deny_private_targets: bool,
+
     /// Delay between retries in seconds to reach a TCP target.
     ///
     /// Defaults to 2 seconds.
@@ -390,6 +413,61 @@ pub struct SessionIpForwardingConfig {
     #[default(default_entry_listen_host())]
     #[serde_as(as = "serde_with::DisplayFromStr")]
     pub default_entry_listen_host: SocketAddr,
+
+    /// Session listeners bound at an Entry node whenever the node starts.
+    ///
+    /// Each listener is specified the same way as the body of the `POST /session/{protocol}`
+    /// REST API request, with an additional `protocol` field.
+    ///
+    /// Defaults to empty.
+    #[serde(default)]
+    pub entry_listeners: Vec<SessionListenerConfig>,
+
+    /// Maximum bandwidth in bytes per second of a single Session at the Exit node.
+    ///
+    /// The limit applies to both directions of the Session combined.
+    ///
+    /// Defaults to unlimited.
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub max_session_bandwidth: Option<u64>,
+
+    /// Maximum bandwidth in bytes per second shared by all the Sessions of the same
+    /// pseudonym at the Exit node.
+    ///
+    /// The limit applies to both directions of the Sessions combined.
+    ///
+    /// Defaults to unlimited.
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub max_pseudonym_bandwidth: Option<u64>,
+
+    /// Maximum number of concurrent Sessions of the same pseudonym at the Exit node.
+    ///
+    /// Sessions above this limit are closed right away.
+    ///
+    /// Defaults to unlimited.
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub max_sessions_per_pseudonym: Option<usize>,
+
+    /// Maximum bandwidth in bytes per second shared by all the Sessions at the Exit node.
+    ///
+    /// The limit applies to both directions of the Sessions combined.
+    ///
+    /// Defaults to unlimited.
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub max_exit_bandwidth: Option<u64>,
+
+    /// Services provided by the Exit node to the Sessions targeting the given service IDs.
+    ///
+    /// The loopback service ID `0` is provided as the `echo` service, unless configured otherwise
+    /// (see [`ExitServiceRegistry`](crate::services::ExitServiceRegistry)).
+    ///
+    /// Defaults to empty.
+    #[serde(default)]
+    pub services: HashMap<ServiceId, ExitService>,
 }
 
 #[cfg(test)]
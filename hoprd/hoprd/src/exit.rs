@@ -1,10 +1,19 @@
 use std::net::SocketAddr;
 
-use hopr_lib::{HoprOffchainKeypair, ServiceId, errors::HoprLibError, transfer_session};
-use hopr_network_types::{prelude::ForeignDataMode, udp::UdpStreamParallelism};
+use futures::AsyncWriteExt;
+use hopr_lib::{HoprOffchainKeypair, HoprSession, IpProtocol, ServiceId, errors::HoprLibError};
+use hopr_network_types::{
+    prelude::{ForeignDataMode, IpOrHost},
+    udp::UdpStreamParallelism,
+};
 use hoprd_api::{HOPR_TCP_BUFFER_SIZE, HOPR_UDP_BUFFER_SIZE, HOPR_UDP_QUEUE_SIZE};
 
-use crate::config::SessionIpForwardingConfig;
+use crate::{
+    config::SessionIpForwardingConfig,
+    services::{ExitService, ExitServiceRegistry},
+    shaping::{ExitTrafficShaper, ShapingPermit},
+    target_policy::TargetPolicy,
+};
 
 #[cfg(all(feature = "prometheus", not(test)))]
 lazy_static::lazy_static! {
@@ -17,28 +26,210 @@ lazy_static::lazy_static! {
 
 /// Implementation of [`hopr_lib::HoprSessionReactor`] that facilitates
 /// bridging of TCP or UDP sockets from the Session Exit node to a destination.
+///
+/// The targets are checked against the [`TargetPolicy`], and the bridged Sessions are subject
+/// to the bandwidth and concurrency limits enforced by the [`ExitTrafficShaper`].
+/// Sessions targeting a service ID are served by the [`ExitServiceRegistry`].
 #[derive(Debug, Clone)]
 pub struct HoprServerIpForwardingReactor {
     keypair: HoprOffchainKeypair,
+    policy: TargetPolicy,
+    shaper: ExitTrafficShaper,
+    services: ExitServiceRegistry,
     cfg: SessionIpForwardingConfig,
 }
 
 impl HoprServerIpForwardingReactor {
     pub fn new(keypair: HoprOffchainKeypair, cfg: SessionIpForwardingConfig) -> Self {
-        Self { keypair, cfg }
+        Self {
+            keypair,
+            policy: TargetPolicy::new(&cfg),
+            shaper: ExitTrafficShaper::new(&cfg),
+            services: ExitServiceRegistry::new(&cfg),
+            cfg,
+        }
     }
 
-    fn all_ips_allowed(&self, addrs: &[SocketAddr]) -> bool {
-        if self.cfg.use_target_allow_list {
-            for addr in addrs {
-                if !self.cfg.target_allow_list.contains(addr) {
-                    tracing::error!(%addr, "address not allowed by the target allow list, denying the target");
-                    return false;
-                }
-                tracing::debug!(%addr, "address allowed by the target allow list, accepting the target");
+    fn all_ips_allowed(&self, protocol: IpProtocol, target: &IpOrHost, addrs: &[SocketAddr]) -> bool {
+        let host = match target {
+            IpOrHost::Dns(host, _) => Some(host.as_str()),
+            IpOrHost::Ip(_) => None,
+        };
+        self.policy.is_allowed(protocol, host, addrs)
+    }
+
+    /// Bridges the Session to the given UDP target.
+    ///
+    /// The target is checked against the [`TargetPolicy`] only if `check_policy` is set.
+    async fn bridge_udp(
+        &self,
+        mut session: HoprSession,
+        permit: ShapingPermit,
+        udp_target: IpOrHost,
+        check_policy: bool,
+    ) -> hopr_lib::errors::Result<()> {
+        let session_id = *session.id();
+        tracing::debug!(
+            session_id = ?session_id,
+            %udp_target,
+            "binding socket to the UDP server"
+        );
+
+        // In UDP, it is impossible to determine if the target is viable,
+        // so we just take the first resolved address.
+        let resolved_udp_target = udp_target
+            .clone()
+            .resolve_tokio()
+            .await
+            .map_err(|e| HoprLibError::GeneralError(format!("failed to resolve DNS name {udp_target}: {e}")))?
+            .first()
+            .ok_or(HoprLibError::GeneralError(format!(
+                "failed to resolve DNS name {udp_target}"
+            )))?
+            .to_owned();
+        tracing::debug!(
+            ?session_id,
+            %udp_target,
+            resolution = ?resolved_udp_target,
+            "UDP target resolved"
+        );
+
+        if check_policy && !self.all_ips_allowed(IpProtocol::UDP, &udp_target, &[resolved_udp_target]) {
+            return Err(HoprLibError::GeneralError(format!(
+                "denied target address {resolved_udp_target}"
+            )));
+        }
+
+        let mut udp_bridge = hopr_network_types::udp::ConnectedUdpStream::builder()
+            .with_buffer_size(HOPR_UDP_BUFFER_SIZE)
+            .with_counterparty(resolved_udp_target)
+            .with_foreign_data_mode(ForeignDataMode::Error)
+            .with_queue_size(HOPR_UDP_QUEUE_SIZE)
+            .with_receiver_parallelism(UdpStreamParallelism::Auto)
+            .build(("0.0.0.0", 0))
+            .map_err(|e| {
+                HoprLibError::GeneralError(format!("could not bridge the incoming session to {udp_target}: {e}"))
+            })?;
+
+        tracing::debug!(
+            ?session_id,
+            %udp_target,
+            "bridging the session to the UDP server"
+        );
+
+        tokio::task::spawn(async move {
+            #[cfg(all(feature = "prometheus", not(test)))]
+            METRIC_ACTIVE_TARGETS.increment(&["udp"], 1.0);
+
+            match permit
+                .transfer(&mut session, &mut udp_bridge, HOPR_UDP_BUFFER_SIZE)
+                .await
+            {
+                Ok((session_to_stream_bytes, stream_to_session_bytes)) => tracing::info!(
+                    ?session_id,
+                    session_to_stream_bytes,
+                    stream_to_session_bytes,
+                    %udp_target,
+                    "server bridged session to UDP ended"
+                ),
+                Err(e) => tracing::error!(
+                    ?session_id,
+                    %udp_target,
+                    error = %e,
+                    "UDP server stream is closed"
+                ),
             }
+
+            #[cfg(all(feature = "prometheus", not(test)))]
+            METRIC_ACTIVE_TARGETS.decrement(&["udp"], 1.0);
+        });
+
+        Ok(())
+    }
+
+    /// Bridges the Session to the given TCP target.
+    ///
+    /// The target is checked against the [`TargetPolicy`] only if `check_policy` is set.
+    async fn bridge_tcp(
+        &self,
+        mut session: HoprSession,
+        permit: ShapingPermit,
+        tcp_target: IpOrHost,
+        check_policy: bool,
+    ) -> hopr_lib::errors::Result<()> {
+        let session_id = *session.id();
+        tracing::debug!(?session_id, %tcp_target, "creating a connection to the TCP server");
+
+        // TCP is able to determine which of the resolved multiple addresses is viable,
+        // and therefore we can pass all of them.
+        let resolved_tcp_targets = tcp_target
+            .clone()
+            .resolve_tokio()
+            .await
+            .map_err(|e| HoprLibError::GeneralError(format!("failed to resolve DNS name {tcp_target}: {e}")))?;
+        tracing::debug!(
+            ?session_id,
+            %tcp_target,
+            resolution = ?resolved_tcp_targets,
+            "TCP target resolved"
+        );
+
+        if check_policy && !self.all_ips_allowed(IpProtocol::TCP, &tcp_target, &resolved_tcp_targets) {
+            return Err(HoprLibError::GeneralError(format!(
+                "denied target address {resolved_tcp_targets:?}"
+            )));
         }
-        true
+
+        let strategy = tokio_retry::strategy::FixedInterval::new(self.cfg.tcp_target_retry_delay)
+            .take(self.cfg.max_tcp_target_retries as usize);
+
+        let mut tcp_bridge = tokio_retry::Retry::spawn(strategy, || {
+            tokio::net::TcpStream::connect(resolved_tcp_targets.as_slice())
+        })
+        .await
+        .map_err(|e| {
+            HoprLibError::GeneralError(format!("could not bridge the incoming session to {tcp_target}: {e}"))
+        })?;
+
+        tcp_bridge.set_nodelay(true).map_err(|e| {
+            HoprLibError::GeneralError(format!(
+                "could not set the TCP_NODELAY option for the bridged session to {tcp_target}: {e}",
+            ))
+        })?;
+
+        tracing::debug!(
+            ?session_id,
+            %tcp_target,
+            "bridging the session to the TCP server"
+        );
+        tokio::task::spawn(async move {
+            #[cfg(all(feature = "prometheus", not(test)))]
+            METRIC_ACTIVE_TARGETS.increment(&["tcp"], 1.0);
+
+            match permit
+                .transfer(&mut session, &mut tcp_bridge, HOPR_TCP_BUFFER_SIZE)
+                .await
+            {
+                Ok((session_to_stream_bytes, stream_to_session_bytes)) => tracing::info!(
+                    ?session_id,
+                    session_to_stream_bytes,
+                    stream_to_session_bytes,
+                    %tcp_target,
+                    "server bridged session to TCP ended"
+                ),
+                Err(error) => tracing::error!(
+                    ?session_id,
+                    %tcp_target,
+                    %error,
+                    "TCP server stream is closed"
+                ),
+            }
+
+            #[cfg(all(feature = "prometheus", not(test)))]
+            METRIC_ACTIVE_TARGETS.decrement(&["tcp"], 1.0);
+        });
+
+        Ok(())
     }
 }
 
@@ -49,187 +240,80 @@ impl hopr_lib::HoprSessionReactor for HoprServerIpForwardingReactor {
     #[tracing::instrument(level = "debug", skip(self, session))]
     async fn process(&self, mut session: hopr_lib::HoprIncomingSession) -> hopr_lib::errors::Result<()> {
         let session_id = *session.session.id();
+        let permit = match self.shaper.admit(session_id) {
+            Ok(permit) => permit,
+            Err(error) => {
+                tracing::warn!(?session_id, %error, "session rejected by the traffic shaper");
+                let _ = session.session.close().await;
+                return Err(error);
+            }
+        };
+
         match session.target {
             hopr_lib::SessionTarget::UdpStream(udp_target) => {
                 let udp_target = udp_target
                     .unseal(&self.keypair)
                     .map_err(|e| HoprLibError::GeneralError(format!("cannot unseal target: {e}")))?;
 
-                tracing::debug!(
-                    session_id = ?session_id,
-                    %udp_target,
-                    "binding socket to the UDP server"
-                );
-
-                // In UDP, it is impossible to determine if the target is viable,
-                // so we just take the first resolved address.
-                let resolved_udp_target = udp_target
-                    .clone()
-                    .resolve_tokio()
-                    .await
-                    .map_err(|e| HoprLibError::GeneralError(format!("failed to resolve DNS name {udp_target}: {e}")))?
-                    .first()
-                    .ok_or(HoprLibError::GeneralError(format!(
-                        "failed to resolve DNS name {udp_target}"
-                    )))?
-                    .to_owned();
-                tracing::debug!(
-                    ?session_id,
-                    %udp_target,
-                    resolution = ?resolved_udp_target,
-                    "UDP target resolved"
-                );
-
-                if !self.all_ips_allowed(&[resolved_udp_target]) {
-                    return Err(HoprLibError::GeneralError(format!(
-                        "denied target address {resolved_udp_target}"
-                    )));
-                }
-
-                let mut udp_bridge = hopr_network_types::udp::ConnectedUdpStream::builder()
-                    .with_buffer_size(HOPR_UDP_BUFFER_SIZE)
-                    .with_counterparty(resolved_udp_target)
-                    .with_foreign_data_mode(ForeignDataMode::Error)
-                    .with_queue_size(HOPR_UDP_QUEUE_SIZE)
-                    .with_receiver_parallelism(UdpStreamParallelism::Auto)
-                    .build(("0.0.0.0", 0))
-                    .map_err(|e| {
-                        HoprLibError::GeneralError(format!(
-                            "could not bridge the incoming session to {udp_target}: {e}"
-                        ))
-                    })?;
-
-                tracing::debug!(
-                    ?session_id,
-                    %udp_target,
-                    "bridging the session to the UDP server"
-                );
-
-                tokio::task::spawn(async move {
-                    #[cfg(all(feature = "prometheus", not(test)))]
-                    METRIC_ACTIVE_TARGETS.increment(&["udp"], 1.0);
-
-                    match transfer_session(&mut session.session, &mut udp_bridge, HOPR_UDP_BUFFER_SIZE).await {
-                        Ok((session_to_stream_bytes, stream_to_session_bytes)) => tracing::info!(
-                            ?session_id,
-                            session_to_stream_bytes,
-                            stream_to_session_bytes,
-                            %udp_target,
-                            "server bridged session to UDP ended"
-                        ),
-                        Err(e) => tracing::error!(
-                            ?session_id,
-                            %udp_target,
-                            error = %e,
-                            "UDP server stream is closed"
-                        ),
-                    }
-
-                    #[cfg(all(feature = "prometheus", not(test)))]
-                    METRIC_ACTIVE_TARGETS.decrement(&["udp"], 1.0);
-                });
-
-                Ok(())
+                self.bridge_udp(session.session, permit, udp_target, true).await
             }
             hopr_lib::SessionTarget::TcpStream(tcp_target) => {
                 let tcp_target = tcp_target
                     .unseal(&self.keypair)
                     .map_err(|e| HoprLibError::GeneralError(format!("cannot unseal target: {e}")))?;
 
-                tracing::debug!(?session_id, %tcp_target, "creating a connection to the TCP server");
-
-                // TCP is able to determine which of the resolved multiple addresses is viable,
-                // and therefore we can pass all of them.
-                let resolved_tcp_targets =
-                    tcp_target.clone().resolve_tokio().await.map_err(|e| {
-                        HoprLibError::GeneralError(format!("failed to resolve DNS name {tcp_target}: {e}"))
-                    })?;
-                tracing::debug!(
-                    ?session_id,
-                    %tcp_target,
-                    resolution = ?resolved_tcp_targets,
-                    "TCP target resolved"
-                );
-
-                if !self.all_ips_allowed(&resolved_tcp_targets) {
-                    return Err(HoprLibError::GeneralError(format!(
-                        "denied target address {resolved_tcp_targets:?}"
-                    )));
+                self.bridge_tcp(session.session, permit, tcp_target, true).await
+            }
+            hopr_lib::SessionTarget::ExitNode(service_id) => match self.services.get(service_id) {
+                Some(ExitService::Forward { name, protocol, target }) => {
+                    tracing::debug!(
+                        ?session_id,
+                        service_id,
+                        %name,
+                        "forwarding the session to the service backend"
+                    );
+                    match protocol {
+                        IpProtocol::TCP => self.bridge_tcp(session.session, permit, target.clone(), false).await,
+                        IpProtocol::UDP => self.bridge_udp(session.session, permit, target.clone(), false).await,
+                    }
                 }
+                Some(service) => {
+                    let kind = service.kind();
+                    tracing::debug!(?session_id, service_id, kind, "bridging the session to the service");
 
-                let strategy = tokio_retry::strategy::FixedInterval::new(self.cfg.tcp_target_retry_delay)
-                    .take(self.cfg.max_tcp_target_retries as usize);
-
-                let mut tcp_bridge = tokio_retry::Retry::spawn(strategy, || {
-                    tokio::net::TcpStream::connect(resolved_tcp_targets.as_slice())
-                })
-                .await
-                .map_err(|e| {
-                    HoprLibError::GeneralError(format!("could not bridge the incoming session to {tcp_target}: {e}"))
-                })?;
-
-                tcp_bridge.set_nodelay(true).map_err(|e| {
-                    HoprLibError::GeneralError(format!(
-                        "could not set the TCP_NODELAY option for the bridged session to {tcp_target}: {e}",
-                    ))
-                })?;
-
-                tracing::debug!(
-                    ?session_id,
-                    %tcp_target,
-                    "bridging the session to the TCP server"
-                );
-                tokio::task::spawn(async move {
                     #[cfg(all(feature = "prometheus", not(test)))]
-                    METRIC_ACTIVE_TARGETS.increment(&["tcp"], 1.0);
+                    METRIC_ACTIVE_TARGETS.increment(&[kind], 1.0);
 
-                    match transfer_session(&mut session.session, &mut tcp_bridge, HOPR_TCP_BUFFER_SIZE).await {
-                        Ok((session_to_stream_bytes, stream_to_session_bytes)) => tracing::info!(
+                    match service.serve(session.session, &permit).await {
+                        Ok((received, sent)) => tracing::info!(
                             ?session_id,
-                            session_to_stream_bytes,
-                            stream_to_session_bytes,
-                            %tcp_target,
-                            "server bridged session to TCP ended"
+                            service_id,
+                            kind,
+                            received,
+                            sent,
+                            "server session service ended"
                         ),
                         Err(error) => tracing::error!(
                             ?session_id,
-                            %tcp_target,
+                            service_id,
+                            kind,
                             %error,
-                            "TCP server stream is closed"
+                            "server session service ended with an error"
                         ),
                     }
 
                     #[cfg(all(feature = "prometheus", not(test)))]
-                    METRIC_ACTIVE_TARGETS.decrement(&["tcp"], 1.0);
-                });
-
-                Ok(())
-            }
-            hopr_lib::SessionTarget::ExitNode(SERVICE_ID_LOOPBACK) => {
-                tracing::debug!(?session_id, "bridging the session to the loopback service");
-                let (mut reader, mut writer) = tokio::io::split(session.session);
+                    METRIC_ACTIVE_TARGETS.decrement(&[kind], 1.0);
 
-                #[cfg(all(feature = "prometheus", not(test)))]
-                METRIC_ACTIVE_TARGETS.increment(&["loopback"], 1.0);
-
-                // Uses 4 kB buffer for copying
-                match tokio::io::copy(&mut reader, &mut writer).await {
-                    Ok(copied) => tracing::info!(?session_id, copied, "server loopback session service ended"),
-                    Err(error) => tracing::error!(
-                        ?session_id,
-                        %error,
-                        "server loopback session service ended with an error"
-                    ),
+                    Ok(())
                 }
-
-                #[cfg(all(feature = "prometheus", not(test)))]
-                METRIC_ACTIVE_TARGETS.decrement(&["loopback"], 1.0);
-
-                Ok(())
-            }
-            hopr_lib::SessionTarget::ExitNode(_) => Err(HoprLibError::GeneralError(
-                "server does not support internal session processing".into(),
-            )),
+                None => {
+                    let _ = session.session.close().await;
+                    Err(HoprLibError::GeneralError(format!(
+                        "server does not provide service {service_id}"
+                    )))
+                }
+            },
         }
     }
 }
@@ -76,3 +76,6 @@ pub mod cli;
 pub mod config;
 pub mod errors;
 pub mod exit;
+pub mod services;
+pub mod shaping;
+pub mod target_policy;
@@ -1,12 +1,24 @@
-use std::{collections::HashMap, fmt::Formatter, str::FromStr, sync::Arc};
+use std::{
+    collections::HashMap,
+    fmt::Formatter,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
 
 use async_lock::RwLock;
 use async_signal::{Signal, Signals};
 use futures::StreamExt;
 use hopr_async_runtime::prelude::{JoinHandle, cancel_join_handle, spawn};
 use hopr_lib::{HoprLibProcesses, ToHex};
-use hoprd::{cli::CliArgs, errors::HoprdError, exit::HoprServerIpForwardingReactor};
-use hoprd_api::{ListenerJoinHandles, RestApiParameters, serve_api};
+use hoprd::{
+    cli::{CliArgs, HoprdCommand},
+    errors::HoprdError,
+    exit::HoprServerIpForwardingReactor,
+};
+use hoprd_api::{
+    ApiTokenStore, ListenerJoinHandles, MessageInbox, NodeEventLog, RestApiParameters, SessionListenerStore, serve_api,
+};
 use hoprd_keypair::key_pair::{HoprKeys, IdentityRetrievalModes};
 use signal_hook::low_level;
 use tracing::{error, info, warn};
@@ -109,6 +121,8 @@ enum HoprdProcesses {
     HoprLib(HoprLibProcesses, JoinHandle<()>),
     ListenerSockets(ListenerJoinHandles),
     RestApi(JoinHandle<()>),
+    MessageInbox(JoinHandle<()>),
+    NodeEvents(JoinHandle<()>),
 }
 
 // Manual implementation needed, since Strum does not support skipping arguments
@@ -118,6 +132,8 @@ impl std::fmt::Display for HoprdProcesses {
             HoprdProcesses::HoprLib(p, _) => write!(f, "HoprLib process: {p}"),
             HoprdProcesses::ListenerSockets(_) => write!(f, "SessionListenerSockets"),
             HoprdProcesses::RestApi(_) => write!(f, "RestApi"),
+            HoprdProcesses::MessageInbox(_) => write!(f, "MessageInbox"),
+            HoprdProcesses::NodeEvents(_) => write!(f, "NodeEvents"),
         }
     }
 }
@@ -129,6 +145,33 @@ impl std::fmt::Debug for HoprdProcesses {
     }
 }
 
+/// Runs an auxiliary command instead of the node.
+async fn run_command(
+    command: HoprdCommand,
+    cfg: &hopr_lib::config::HoprLibConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match command {
+        HoprdCommand::ExportLogsSnapshot { output } => {
+            let manifest = hopr_lib::snapshot::export_logs_snapshot(cfg, &output).await?;
+            info!(
+                block_number = manifest.block_number,
+                checksum = %manifest.checksum,
+                "Logs snapshot exported"
+            );
+        }
+        HoprdCommand::ImportLogsSnapshot { input, checksum } => {
+            let manifest = hopr_lib::snapshot::import_logs_snapshot(cfg, &input, checksum.parse()?).await?;
+            info!(
+                block_number = manifest.block_number,
+                checksum = %manifest.checksum,
+                "Logs snapshot imported, the node will resume indexing from its last block"
+            );
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "runtime-tokio", tokio::main)]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     init_logger()?;
@@ -142,8 +185,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     let args = <CliArgs as clap::Parser>::parse();
+    let cfg_path = args.configuration_file_path.clone();
+    let command = args.command.clone();
     let cfg = hoprd::config::HoprdConfig::from_cli_args(args, false)?;
 
+    if let Some(command) = command {
+        return run_command(command, &cfg.hopr).await;
+    }
+
     let git_hash = option_env!("VERGEN_GIT_SHA").unwrap_or("unknown");
     info!(
         version = hopr_lib::constants::APP_VERSION,
@@ -197,7 +246,22 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let mut processes: Vec<HoprdProcesses> = Vec::new();
 
-    if cfg.api.enable {
+    let message_inbox = cfg
+        .api
+        .enable
+        .then(|| MessageInbox::new(cfg.api.message_inbox_capacity));
+
+    if let Some(message_inbox) = message_inbox.clone() {
+        // The node events are consumed from the start, to capture also the indexer sync progress
+        let node_events = NodeEventLog::new(cfg.api.event_log_capacity);
+        let node_events_clone = node_events.clone();
+        let mut events = Box::pin(node.node_events());
+        processes.push(HoprdProcesses::NodeEvents(spawn(async move {
+            while let Some(event) = events.next().await {
+                node_events_clone.push(event).await;
+            }
+        })));
+
         let node_cfg_str = cfg.as_redacted_string()?;
         let api_cfg = cfg.api.clone();
 
@@ -215,15 +279,31 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
         let session_listener_sockets = Arc::new(RwLock::new(HashMap::new()));
 
+        let api_tokens_file = cfg
+            .api
+            .tokens_file
+            .clone()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| Path::new(&cfg.hopr.db.data).join(hoprd_api::config::DEFAULT_API_TOKENS_FILE_NAME));
+        let api_tokens = ApiTokenStore::load(Some(api_tokens_file))?;
+        let session_listeners = SessionListenerStore::load(cfg.api.session_listeners_file.clone().map(PathBuf::from))?;
+        let configured_session_listeners = cfg.session_ip_forwarding.entry_listeners.clone();
+
         processes.push(HoprdProcesses::ListenerSockets(session_listener_sockets.clone()));
         processes.push(HoprdProcesses::RestApi(spawn(async move {
             if let Err(e) = serve_api(RestApiParameters {
                 listener: api_listener,
                 hoprd_cfg: node_cfg_str,
+                hoprd_cfg_path: cfg_path,
                 cfg: api_cfg,
                 hopr: node_clone,
                 session_listener_sockets,
                 default_session_listen_host: cfg.session_ip_forwarding.default_entry_listen_host,
+                message_inbox,
+                api_tokens,
+                node_events,
+                session_listeners,
+                configured_session_listeners,
             })
             .await
             {
@@ -232,7 +312,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         })));
     }
 
-    let (_hopr_socket, hopr_processes) = node
+    let (hopr_socket, hopr_processes) = node
         .run(HoprServerIpForwardingReactor::new(
             hopr_keys.packet_key.clone(),
             cfg.session_ip_forwarding,
@@ -241,6 +321,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     processes.extend(hopr_processes.into_iter().map(|(k, v)| HoprdProcesses::HoprLib(k, v)));
 
+    // Without the REST API, the received messages are not consumed, but the socket is kept open until the end
+    if let Some(message_inbox) = message_inbox {
+        let mut incoming = hopr_socket.reader();
+        processes.push(HoprdProcesses::MessageInbox(spawn(async move {
+            while let Some(data) = incoming.next().await {
+                message_inbox.push(data).await;
+            }
+        })));
+    }
+
     let mut signals = Signals::new([Signal::Hup, Signal::Int]).map_err(|e| HoprdError::OsError(e.to_string()))?;
     while let Some(Ok(signal)) = signals.next().await {
         match signal {
@@ -254,7 +344,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         let mut join_handles: Vec<JoinHandle<()>> = Vec::new();
                         info!("Stopping process '{process}'");
                         match process {
-                            HoprdProcesses::HoprLib(_, jh) | HoprdProcesses::RestApi(jh) => join_handles.push(jh),
+                            HoprdProcesses::HoprLib(_, jh)
+                            | HoprdProcesses::RestApi(jh)
+                            | HoprdProcesses::MessageInbox(jh)
+                            | HoprdProcesses::NodeEvents(jh) => join_handles.push(jh),
                             HoprdProcesses::ListenerSockets(jhs) => {
                                 join_handles.extend(jhs.write().await.drain().map(|(_, entry)| entry.jh));
                             }
@@ -0,0 +1,366 @@
+//! Services provided by the Exit node to the Sessions targeting
+//! [`SessionTarget::ExitNode`](hopr_lib::SessionTarget::ExitNode).
+//!
+//! The services are identified by their [`ServiceId`] and configured in
+//! [`services`](SessionIpForwardingConfig::services). Apart from the built-in services,
+//! a service ID can be an alias of a backend address, which then stays hidden from the clients.
+
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use hopr_lib::{HoprSession, IpProtocol, ServiceId};
+use hopr_network_types::prelude::IpOrHost;
+use hoprd_api::HOPR_TCP_BUFFER_SIZE;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use crate::{config::SessionIpForwardingConfig, exit::SERVICE_ID_LOOPBACK, shaping::ShapingPermit};
+
+/// Maximum time to wait for the response of the upstream DNS resolver.
+const DNS_RESPONSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Size of the chunks used by the speed test service.
+const SPEED_TEST_BUFFER_SIZE: usize = 16 * 1024;
+
+/// Service provided by the Exit node under a [`ServiceId`].
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum ExitService {
+    /// Sends back all the data received over the Session.
+    Echo,
+    /// Discards all the data received over the Session.
+    Discard,
+    /// Resolves DNS queries using the given upstream resolver.
+    ///
+    /// The queries and responses are sent over the Session prefixed by their length
+    /// as a 2-byte big-endian integer, the same way as in DNS over TCP (RFC 1035, section 4.2.2).
+    Dns {
+        #[serde_as(as = "serde_with::DisplayFromStr")]
+        upstream: SocketAddr,
+    },
+    /// Discards all the data received over the Session, while sending zeroes back as fast as allowed.
+    ///
+    /// If `max_bytes` is set, the Session is closed once that many bytes were sent.
+    SpeedTest {
+        #[serde(default)]
+        max_bytes: Option<u64>,
+    },
+    /// Forwards the Session to the given backend target, like a TCP or UDP Session target would.
+    ///
+    /// The backend is not subject to the target policy of the Exit node, since it is configured
+    /// by the node operator.
+    Forward {
+        name: String,
+        #[serde_as(as = "serde_with::DisplayFromStr")]
+        protocol: IpProtocol,
+        #[serde_as(as = "serde_with::DisplayFromStr")]
+        target: IpOrHost,
+    },
+}
+
+impl ExitService {
+    /// Short name of the kind of the service.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ExitService::Echo => "echo",
+            ExitService::Discard => "discard",
+            ExitService::Dns { .. } => "dns",
+            ExitService::SpeedTest { .. } => "speed_test",
+            ExitService::Forward { .. } => "forward",
+        }
+    }
+
+    /// Runs a built-in service over the given Session until the Session is closed.
+    ///
+    /// All the data of the Session is transferred via the given `permit`, so that the traffic
+    /// of the built-in services is subject to the same bandwidth limits as any other Session.
+    ///
+    /// Returns the number of bytes received from and sent to the Session.
+    ///
+    /// [`ExitService::Forward`] is not a built-in service and is handled by the
+    /// [`HoprServerIpForwardingReactor`](crate::exit::HoprServerIpForwardingReactor) instead.
+    pub async fn serve(&self, mut session: HoprSession, permit: &ShapingPermit) -> std::io::Result<(u64, u64)> {
+        match self {
+            ExitService::Echo => {
+                serve_via_permit(session, permit, |stream| async move {
+                    let (mut reader, mut writer) = tokio::io::split(stream);
+                    tokio::io::copy(&mut reader, &mut writer).await?;
+                    writer.shutdown().await
+                })
+                .await
+            }
+            ExitService::Discard => {
+                serve_via_permit(session, permit, |mut stream| async move {
+                    tokio::io::copy(&mut stream, &mut tokio::io::sink()).await?;
+                    stream.shutdown().await
+                })
+                .await
+            }
+            ExitService::Dns { upstream } => {
+                serve_via_permit(session, permit, |stream| resolve_dns_queries(stream, *upstream)).await
+            }
+            ExitService::SpeedTest { max_bytes } => {
+                let mut stream = SpeedTestStream {
+                    remaining: max_bytes.unwrap_or(u64::MAX),
+                };
+                let (received, sent) = permit
+                    .transfer(&mut session, &mut stream, SPEED_TEST_BUFFER_SIZE)
+                    .await?;
+                Ok((received as u64, sent as u64))
+            }
+            ExitService::Forward { name, .. } => Err(std::io::Error::other(format!(
+                "forwarding service {name} is not a built-in service"
+            ))),
+        }
+    }
+}
+
+/// Runs the `service` on one end of an in-memory stream, while the other end is connected to the Session
+/// via the `permit`.
+///
+/// Returns the number of bytes received from and sent to the Session.
+async fn serve_via_permit<F, Fut>(
+    mut session: HoprSession,
+    permit: &ShapingPermit,
+    service: F,
+) -> std::io::Result<(u64, u64)>
+where
+    F: FnOnce(tokio::io::DuplexStream) -> Fut,
+    Fut: std::future::Future<Output = std::io::Result<()>>,
+{
+    let (mut session_end, service_end) = tokio::io::duplex(HOPR_TCP_BUFFER_SIZE);
+    let ((received, sent), _) = futures::future::try_join(
+        permit.transfer(&mut session, &mut session_end, HOPR_TCP_BUFFER_SIZE),
+        service(service_end),
+    )
+    .await?;
+    Ok((received as u64, sent as u64))
+}
+
+/// Stream of zeroes used by the [`ExitService::SpeedTest`] service,
+/// which discards all the data written into it.
+struct SpeedTestStream {
+    remaining: u64,
+}
+
+impl tokio::io::AsyncRead for SpeedTestStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let len = (buf.remaining() as u64).min(self.remaining) as usize;
+        buf.initialize_unfilled_to(len).fill(0);
+        buf.advance(len);
+        self.remaining -= len as u64;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl tokio::io::AsyncWrite for SpeedTestStream {
+    fn poll_write(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Relays the length-prefixed DNS queries from the `stream` to the `upstream` resolver over UDP,
+/// and sends the responses back the same way.
+async fn resolve_dns_queries<S>(stream: S, upstream: SocketAddr) -> std::io::Result<()>
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite,
+{
+    let bind_addr: SocketAddr = if upstream.is_ipv4() {
+        ([0, 0, 0, 0], 0).into()
+    } else {
+        ([0u16; 8], 0).into()
+    };
+    let socket = tokio::net::UdpSocket::bind(bind_addr).await?;
+    socket.connect(upstream).await?;
+
+    let (mut reader, mut writer) = tokio::io::split(stream);
+    let mut response = vec![0u8; u16::MAX as usize];
+    loop {
+        let mut len = [0u8; 2];
+        match reader.read_exact(&mut len).await {
+            Ok(_) => {}
+            Err(error) if error.kind() == std::io::ErrorKind::UnexpectedEof => break,
+            Err(error) => return Err(error),
+        }
+
+        let mut query = vec![0u8; u16::from_be_bytes(len) as usize];
+        reader.read_exact(&mut query).await?;
+        if query.len() < 2 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "DNS query too short",
+            ));
+        }
+
+        socket.send(&query).await?;
+
+        // Skip any late responses to the previous queries that might have timed out
+        let response_len = tokio::time::timeout(DNS_RESPONSE_TIMEOUT, async {
+            loop {
+                let response_len = socket.recv(&mut response).await?;
+                if response_len >= 2 && response[..2] == query[..2] {
+                    return Ok::<_, std::io::Error>(response_len);
+                }
+            }
+        })
+        .await
+        .map_err(|_| std::io::Error::new(std::io::ErrorKind::TimedOut, "upstream DNS resolver timed out"))??;
+
+        writer.write_all(&(response_len as u16).to_be_bytes()).await?;
+        writer.write_all(&response[..response_len]).await?;
+        writer.flush().await?;
+    }
+
+    writer.shutdown().await
+}
+
+/// Registry of the services provided by the Exit node.
+///
+/// Unless configured otherwise, [`SERVICE_ID_LOOPBACK`] is always provided as the [`ExitService::Echo`] service.
+#[derive(Debug, Clone)]
+pub struct ExitServiceRegistry {
+    services: Arc<HashMap<ServiceId, ExitService>>,
+}
+
+impl ExitServiceRegistry {
+    pub fn new(cfg: &SessionIpForwardingConfig) -> Self {
+        let mut services = cfg.services.clone();
+        services.entry(SERVICE_ID_LOOPBACK).or_insert(ExitService::Echo);
+
+        Self {
+            services: Arc::new(services),
+        }
+    }
+
+    /// Gets the service with the given ID, if any.
+    pub fn get(&self, id: ServiceId) -> Option<&ExitService> {
+        self.services.get(&id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exit_services_should_deserialize_from_config() -> anyhow::Result<()> {
+        let services: HashMap<ServiceId, ExitService> = serde_yaml::from_str(
+            r#"
+            1:
+              type: discard
+            2:
+              type: dns
+              upstream: 127.0.0.1:53
+            3:
+              type: speed_test
+              max_bytes: 1048576
+            4:
+              type: forward
+              name: web
+              protocol: tcp
+              target: backend.internal:8080
+            "#,
+        )?;
+
+        assert_eq!(Some(&ExitService::Discard), services.get(&1));
+        assert_eq!(
+            Some(&ExitService::Dns {
+                upstream: "127.0.0.1:53".parse()?
+            }),
+            services.get(&2)
+        );
+        assert_eq!(
+            Some(&ExitService::SpeedTest {
+                max_bytes: Some(1048576)
+            }),
+            services.get(&3)
+        );
+        assert_eq!(
+            Some(&ExitService::Forward {
+                name: "web".into(),
+                protocol: IpProtocol::TCP,
+                target: IpOrHost::Dns("backend.internal".into(), 8080),
+            }),
+            services.get(&4)
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn speed_test_stream_should_yield_at_most_max_bytes() -> anyhow::Result<()> {
+        let mut stream = SpeedTestStream { remaining: 100_000 };
+        stream.write_all(b"discarded").await?;
+
+        let mut data = Vec::new();
+        stream.read_to_end(&mut data).await?;
+        assert_eq!(100_000, data.len());
+        assert!(data.iter().all(|b| *b == 0));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn dns_service_should_relay_length_prefixed_queries() -> anyhow::Result<()> {
+        // Upstream resolver which responds with the query itself
+        let upstream = tokio::net::UdpSocket::bind("127.0.0.1:0").await?;
+        let upstream_addr = upstream.local_addr()?;
+        tokio::spawn(async move {
+            let mut buf = [0u8; 512];
+            while let Ok((len, from)) = upstream.recv_from(&mut buf).await {
+                let _ = upstream.send_to(&buf[..len], from).await;
+            }
+        });
+
+        let (mut client, service) = tokio::io::duplex(1024);
+        let service = tokio::spawn(resolve_dns_queries(service, upstream_addr));
+
+        let query = b"\x12\x34query";
+        client.write_all(&(query.len() as u16).to_be_bytes()).await?;
+        client.write_all(query).await?;
+
+        let mut len = [0u8; 2];
+        client.read_exact(&mut len).await?;
+        let mut response = vec![0u8; u16::from_be_bytes(len) as usize];
+        client.read_exact(&mut response).await?;
+        assert_eq!(query.as_slice(), response.as_slice());
+
+        client.shutdown().await?;
+        service.await??;
+
+        Ok(())
+    }
+
+    #[test]
+    fn exit_service_registry_should_provide_loopback_by_default() {
+        let registry = ExitServiceRegistry::new(&SessionIpForwardingConfig::default());
+        assert_eq!(Some(&ExitService::Echo), registry.get(SERVICE_ID_LOOPBACK));
+        assert_eq!(None, registry.get(1));
+
+        let registry = ExitServiceRegistry::new(&SessionIpForwardingConfig {
+            services: HashMap::from([(SERVICE_ID_LOOPBACK, ExitService::Discard)]),
+            ..Default::default()
+        });
+        assert_eq!(Some(&ExitService::Discard), registry.get(SERVICE_ID_LOOPBACK));
+    }
+}
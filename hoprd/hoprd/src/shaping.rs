@@ -0,0 +1,342 @@
+//! Bandwidth shaping of the Sessions bridged by the Exit node
+//! (see [`HoprServerIpForwardingReactor`](crate::exit::HoprServerIpForwardingReactor)).
+//!
+//! Both directions of a shaped Session are charged to a single [`BandwidthLimiter`] of the Session,
+//! which releases the bytes in chunks at the rate set by its [`RateController`]. Whenever a Session is opened or
+//! closed, the bandwidths of all the active Sessions are recalculated, so that the configured per-pseudonym and global
+//! budgets are shared evenly.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, Mutex, PoisonError,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use futures::{AsyncReadExt as _, AsyncWriteExt as _, StreamExt, stream::BoxStream};
+use hopr_lib::{
+    HoprPseudonym, HoprSession, HoprSessionId, RateController, RateLimitExt, errors::HoprLibError, transfer_session,
+};
+use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+
+use crate::config::SessionIpForwardingConfig;
+
+/// Number of chunks of bytes per second released to a Session by its [`RateController`].
+///
+/// The size of the chunks is derived from the bandwidth of the Session.
+const BANDWIDTH_CHUNKS_PER_SEC: u64 = 100;
+
+struct LimiterState {
+    chunks: BoxStream<'static, ()>,
+    credit: i64,
+}
+
+/// Limits the number of bytes per second transferred by a Session.
+///
+/// The bytes are released in chunks by a rate-limited stream, whose rate is set
+/// using its [`RateController`].
+///
+/// The limiter is cheaply cloneable, and all the clones share the same rate.
+#[derive(Clone)]
+struct BandwidthLimiter {
+    state: Arc<tokio::sync::Mutex<LimiterState>>,
+    chunk_size: Arc<AtomicU64>,
+    controller: Arc<RateController>,
+}
+
+impl std::fmt::Debug for BandwidthLimiter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BandwidthLimiter")
+            .field("chunk_size", &self.chunk_size.load(Ordering::Relaxed))
+            .field("chunks_per_sec", &self.controller.get_rate_per_sec())
+            .finish()
+    }
+}
+
+impl Default for BandwidthLimiter {
+    fn default() -> Self {
+        let (chunks, controller) = futures::stream::repeat(()).rate_limit_per_unit(0, Duration::from_secs(1));
+        Self {
+            state: Arc::new(tokio::sync::Mutex::new(LimiterState {
+                chunks: chunks.boxed(),
+                credit: 0,
+            })),
+            chunk_size: Arc::new(AtomicU64::new(1)),
+            controller: Arc::new(controller),
+        }
+    }
+}
+
+impl BandwidthLimiter {
+    /// Sets the bandwidth in bytes per second.
+    fn set_bandwidth(&self, bandwidth: u64) {
+        let chunk_size = (bandwidth / BANDWIDTH_CHUNKS_PER_SEC).max(1);
+        self.chunk_size.store(chunk_size, Ordering::Relaxed);
+        self.controller
+            .set_rate_per_unit(bandwidth as usize, Duration::from_secs(chunk_size));
+    }
+
+    /// Waits until the given number of bytes can be transferred.
+    ///
+    /// Chunks released beyond the given number of bytes are credited to the next transfer.
+    async fn consume(&self, bytes: usize) {
+        let mut state = self.state.lock().await;
+        state.credit -= bytes as i64;
+        while state.credit < 0 && state.chunks.next().await.is_some() {
+            state.credit += self.chunk_size.load(Ordering::Relaxed) as i64;
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ShapedSession {
+    pseudonym: HoprPseudonym,
+    limiter: BandwidthLimiter,
+}
+
+/// Enforces the bandwidth and concurrency limits of the Sessions at the Exit node.
+///
+/// The bandwidth limits apply to both directions of the Sessions combined.
+///
+/// The shaper is cheaply cloneable, and all the clones share the same set of active Sessions.
+#[derive(Debug, Clone, Default)]
+pub struct ExitTrafficShaper {
+    max_session_bandwidth: Option<u64>,
+    max_pseudonym_bandwidth: Option<u64>,
+    max_sessions_per_pseudonym: Option<usize>,
+    max_exit_bandwidth: Option<u64>,
+    sessions: Arc<Mutex<HashMap<HoprSessionId, ShapedSession>>>,
+}
+
+impl ExitTrafficShaper {
+    pub fn new(cfg: &SessionIpForwardingConfig) -> Self {
+        Self {
+            max_session_bandwidth: cfg.max_session_bandwidth,
+            max_pseudonym_bandwidth: cfg.max_pseudonym_bandwidth,
+            max_sessions_per_pseudonym: cfg.max_sessions_per_pseudonym,
+            max_exit_bandwidth: cfg.max_exit_bandwidth,
+            sessions: Default::default(),
+        }
+    }
+
+    fn is_shaping(&self) -> bool {
+        self.max_session_bandwidth.is_some()
+            || self.max_pseudonym_bandwidth.is_some()
+            || self.max_exit_bandwidth.is_some()
+    }
+
+    /// Registers a new Session, unless its pseudonym already has the maximum number of concurrent Sessions.
+    ///
+    /// The Session is unregistered once the returned [`ShapingPermit`] is dropped.
+    pub fn admit(&self, session_id: HoprSessionId) -> Result<ShapingPermit, HoprLibError> {
+        let mut sessions = self.sessions.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let pseudonym = *session_id.pseudonym();
+        if let Some(max_sessions) = self.max_sessions_per_pseudonym {
+            if sessions.values().filter(|s| s.pseudonym == pseudonym).count() >= max_sessions {
+                return Err(HoprLibError::GeneralError(format!(
+                    "pseudonym {pseudonym} reached the maximum of {max_sessions} concurrent sessions"
+                )));
+            }
+        }
+
+        let limiter = BandwidthLimiter::default();
+        sessions.insert(
+            session_id,
+            ShapedSession {
+                pseudonym,
+                limiter: limiter.clone(),
+            },
+        );
+        self.rebalance(&sessions);
+
+        Ok(ShapingPermit {
+            shaper: self.clone(),
+            session_id,
+            limiter,
+        })
+    }
+
+    /// Bandwidth in bytes per second of a Session, given the number of active Sessions
+    /// of its pseudonym and the total number of active Sessions.
+    ///
+    /// Returns `None` if no bandwidth limit is configured.
+    fn session_bandwidth(&self, pseudonym_sessions: usize, total_sessions: usize) -> Option<u64> {
+        [
+            self.max_session_bandwidth,
+            self.max_pseudonym_bandwidth
+                .map(|bandwidth| bandwidth / pseudonym_sessions.max(1) as u64),
+            self.max_exit_bandwidth
+                .map(|bandwidth| bandwidth / total_sessions.max(1) as u64),
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+        .map(|bandwidth| bandwidth.max(1))
+    }
+
+    fn rebalance(&self, sessions: &HashMap<HoprSessionId, ShapedSession>) {
+        let mut pseudonym_sessions = HashMap::<HoprPseudonym, usize>::new();
+        for session in sessions.values() {
+            *pseudonym_sessions.entry(session.pseudonym).or_default() += 1;
+        }
+
+        for session in sessions.values() {
+            if let Some(bandwidth) = self.session_bandwidth(pseudonym_sessions[&session.pseudonym], sessions.len()) {
+                session.limiter.set_bandwidth(bandwidth);
+            }
+        }
+    }
+}
+
+/// Registration of an active Session in the [`ExitTrafficShaper`].
+#[derive(Debug)]
+pub struct ShapingPermit {
+    shaper: ExitTrafficShaper,
+    session_id: HoprSessionId,
+    limiter: BandwidthLimiter,
+}
+
+impl ShapingPermit {
+    /// Transfers data in both directions between the Session and the `target` stream, until both are closed.
+    ///
+    /// If any bandwidth limit is configured, the data is transferred in chunks of at most `max_buffer` bytes,
+    /// each charged to the bandwidth of the Session. Otherwise, this is equivalent to [`transfer_session`].
+    pub async fn transfer<T>(
+        &self,
+        session: &mut HoprSession,
+        target: &mut T,
+        max_buffer: usize,
+    ) -> std::io::Result<(usize, usize)>
+    where
+        T: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+    {
+        if !self.shaper.is_shaping() {
+            return transfer_session(session, target, max_buffer).await;
+        }
+
+        let (mut session_rx, mut session_tx) = session.split();
+        let (mut target_rx, mut target_tx) = tokio::io::split(target);
+
+        let session_to_target = async {
+            let mut buffer = vec![0u8; max_buffer];
+            let mut transferred = 0;
+            loop {
+                let len = session_rx.read(&mut buffer).await?;
+                if len == 0 {
+                    break;
+                }
+                self.limiter.consume(len).await;
+                target_tx.write_all(&buffer[..len]).await?;
+                transferred += len;
+            }
+            target_tx.shutdown().await?;
+            Ok::<_, std::io::Error>(transferred)
+        };
+
+        let target_to_session = async {
+            let mut buffer = vec![0u8; max_buffer];
+            let mut transferred = 0;
+            loop {
+                let len = target_rx.read(&mut buffer).await?;
+                if len == 0 {
+                    break;
+                }
+                self.limiter.consume(len).await;
+                session_tx.write_all(&buffer[..len]).await?;
+                transferred += len;
+            }
+            session_tx.close().await?;
+            Ok::<_, std::io::Error>(transferred)
+        };
+
+        futures::future::try_join(session_to_target, target_to_session).await
+    }
+}
+
+impl Drop for ShapingPermit {
+    fn drop(&mut self) {
+        let mut sessions = self.shaper.sessions.lock().unwrap_or_else(PoisonError::into_inner);
+        sessions.remove(&self.session_id);
+        self.shaper.rebalance(&sessions);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hopr_crypto_random::Randomizable;
+
+    use super::*;
+
+    fn shaper(cfg: SessionIpForwardingConfig) -> ExitTrafficShaper {
+        ExitTrafficShaper::new(&cfg)
+    }
+
+    #[test]
+    fn exit_traffic_shaper_should_share_the_bandwidth_budgets() {
+        let shaper = shaper(SessionIpForwardingConfig {
+            max_session_bandwidth: Some(1000),
+            max_pseudonym_bandwidth: Some(1500),
+            max_exit_bandwidth: Some(2000),
+            ..Default::default()
+        });
+
+        assert_eq!(Some(1000), shaper.session_bandwidth(1, 1));
+        assert_eq!(Some(750), shaper.session_bandwidth(2, 2));
+        assert_eq!(Some(500), shaper.session_bandwidth(1, 4));
+        assert_eq!(None, ExitTrafficShaper::default().session_bandwidth(1, 1));
+    }
+
+    #[test]
+    fn exit_traffic_shaper_should_limit_concurrent_sessions_per_pseudonym() -> anyhow::Result<()> {
+        let shaper = shaper(SessionIpForwardingConfig {
+            max_sessions_per_pseudonym: Some(2),
+            ..Default::default()
+        });
+
+        let pseudonym = HoprPseudonym::random();
+        let first = shaper.admit(HoprSessionId::new(1, pseudonym))?;
+        let _second = shaper.admit(HoprSessionId::new(2, pseudonym))?;
+        assert!(shaper.admit(HoprSessionId::new(3, pseudonym)).is_err());
+        let _other = shaper.admit(HoprSessionId::new(3, HoprPseudonym::random()))?;
+
+        drop(first);
+        let _third = shaper.admit(HoprSessionId::new(3, pseudonym))?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn bandwidth_limiter_should_charge_bytes_of_all_directions() {
+        let limiter = BandwidthLimiter::default();
+        let other_direction = limiter.clone();
+        limiter.set_bandwidth(1000);
+
+        let start = std::time::Instant::now();
+        futures::join!(limiter.consume(100), other_direction.consume(100));
+        let elapsed = start.elapsed();
+
+        assert!(
+            elapsed >= Duration::from_millis(180) && elapsed <= Duration::from_millis(400),
+            "200 bytes at 1000 bytes per second took {elapsed:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn bandwidth_limiter_should_credit_the_remainder_of_a_chunk() {
+        let limiter = BandwidthLimiter::default();
+        limiter.set_bandwidth(100);
+
+        // A single byte releases a whole chunk of 1 byte
+        limiter.consume(1).await;
+
+        limiter.set_bandwidth(10_000);
+        // A single byte releases a whole chunk of 100 bytes, whose remainder covers the next transfer
+        limiter.consume(1).await;
+        let start = std::time::Instant::now();
+        limiter.consume(99).await;
+        assert!(start.elapsed() < Duration::from_millis(5));
+    }
+}
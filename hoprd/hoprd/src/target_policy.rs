@@ -0,0 +1,399 @@
+//! Policy deciding to which targets the Exit node
+//! (see [`HoprServerIpForwardingReactor`](crate::exit::HoprServerIpForwardingReactor)) bridges Sessions.
+//!
+//! Each resolved address of an unsealed Session target is evaluated as follows:
+//! 1. the first matching rule in [`target_rules`](SessionIpForwardingConfig::target_rules) decides,
+//! 2. otherwise, addresses listed in [`target_allow_list`](SessionIpForwardingConfig::target_allow_list) are allowed,
+//! 3. otherwise, private addresses are denied if
+//!    [`deny_private_targets`](SessionIpForwardingConfig::deny_private_targets) is set,
+//! 4. otherwise, the address is denied if [`use_target_allow_list`](SessionIpForwardingConfig::use_target_allow_list)
+//!    is set, and allowed if not.
+//!
+//! If [`deny_private_targets`](SessionIpForwardingConfig::deny_private_targets) is set, a private address can be
+//! allowed only by a rule listing a network which contains it, or by the allow list. Otherwise, a rule matching
+//! only the DNS name of the target would allow any host name resolving to a private address.
+
+use std::{
+    collections::HashSet,
+    fmt::{Display, Formatter},
+    net::{IpAddr, SocketAddr},
+    str::FromStr,
+};
+
+use hopr_lib::IpProtocol;
+use ipnet::IpNet;
+use serde_with::{DisplayFromStr, serde_as};
+
+use crate::config::SessionIpForwardingConfig;
+
+#[cfg(all(feature = "prometheus", not(test)))]
+lazy_static::lazy_static! {
+    static ref METRIC_TARGET_DECISIONS: hopr_metrics::MultiCounter = hopr_metrics::MultiCounter::new(
+        "hopr_session_hoprd_target_decisions",
+        "Number of Session target addresses allowed or denied by the Exit node target policy",
+        &["protocol", "action", "reason"]
+    ).unwrap();
+}
+
+/// Action taken on the targets matched by a [`TargetRule`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TargetAction {
+    Allow,
+    Deny,
+}
+
+impl Display for TargetAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TargetAction::Allow => write!(f, "allow"),
+            TargetAction::Deny => write!(f, "deny"),
+        }
+    }
+}
+
+/// Inclusive range of ports, written either as a single port (`443`) or as a range (`8000-9000`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PortRange {
+    pub start: u16,
+    pub end: u16,
+}
+
+impl PortRange {
+    pub fn contains(&self, port: u16) -> bool {
+        (self.start..=self.end).contains(&port)
+    }
+}
+
+impl Display for PortRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.start == self.end {
+            write!(f, "{}", self.start)
+        } else {
+            write!(f, "{}-{}", self.start, self.end)
+        }
+    }
+}
+
+impl FromStr for PortRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s.split_once('-').unwrap_or((s, s));
+        let start = start
+            .trim()
+            .parse()
+            .map_err(|e| format!("invalid port '{start}': {e}"))?;
+        let end = end.trim().parse().map_err(|e| format!("invalid port '{end}': {e}"))?;
+
+        if start > end {
+            return Err(format!("invalid port range '{s}'"));
+        }
+
+        Ok(Self { start, end })
+    }
+}
+
+/// Rule of the Exit node target policy.
+///
+/// A rule matches a target address if all of its given criteria match. Criteria left empty match anything.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TargetRule {
+    /// Whether the matching targets are allowed or denied.
+    pub action: TargetAction,
+    /// IP protocol of the Session target.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub protocol: Option<IpProtocol>,
+    /// Networks in CIDR notation (e.g. `10.0.0.0/8`), one of which must contain the resolved target address.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub networks: Vec<IpNet>,
+    /// Port ranges (e.g. `443` or `8000-9000`), one of which must contain the target port.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub ports: Vec<PortRange>,
+    /// DNS name patterns, one of which the target host name must match.
+    ///
+    /// A pattern is either an exact name (`example.com`) or matches all the subdomains of a name
+    /// (`*.example.com`). Targets given as an IP address never match a rule with DNS name patterns.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hosts: Vec<String>,
+}
+
+impl TargetRule {
+    fn matches(&self, protocol: IpProtocol, host: Option<&str>, addr: &SocketAddr) -> bool {
+        self.protocol.is_none_or(|p| p == protocol)
+            && (self.networks.is_empty() || self.networks.iter().any(|net| net.contains(&addr.ip())))
+            && (self.ports.is_empty() || self.ports.iter().any(|ports| ports.contains(addr.port())))
+            && (self.hosts.is_empty()
+                || host.is_some_and(|host| self.hosts.iter().any(|pattern| host_matches(pattern, host))))
+    }
+}
+
+fn host_matches(pattern: &str, host: &str) -> bool {
+    let host = host.trim_end_matches('.');
+    match pattern.strip_prefix("*.") {
+        Some(domain) => host
+            .to_ascii_lowercase()
+            .strip_suffix(&domain.to_ascii_lowercase())
+            .is_some_and(|subdomain| subdomain.len() > 1 && subdomain.ends_with('.')),
+        None => host.eq_ignore_ascii_case(pattern),
+    }
+}
+
+/// Checks whether the address belongs to a private, loopback, link-local or otherwise non-public range.
+fn is_private(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                // "This network" (RFC 1122), including the unspecified address
+                || ip.octets()[0] == 0
+                // Shared address space (RFC 6598)
+                || (ip.octets()[0] == 100 && (ip.octets()[1] & 0xc0) == 64)
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_private(&IpAddr::V4(ip)),
+            None => {
+                ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    // Unique local (fc00::/7) and link-local (fe80::/10) addresses
+                    || (ip.segments()[0] & 0xfe00) == 0xfc00
+                    || (ip.segments()[0] & 0xffc0) == 0xfe80
+            }
+        },
+    }
+}
+
+/// Reason of a target policy decision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DecisionReason {
+    Rule(usize),
+    AllowList,
+    Private,
+    Default,
+}
+
+impl DecisionReason {
+    fn as_str(&self) -> &'static str {
+        match self {
+            DecisionReason::Rule(_) => "rule",
+            DecisionReason::AllowList => "allow_list",
+            DecisionReason::Private => "private",
+            DecisionReason::Default => "default",
+        }
+    }
+}
+
+/// Decides whether a Session target is allowed, see the [module documentation](self).
+#[derive(Debug, Clone)]
+pub struct TargetPolicy {
+    rules: Vec<TargetRule>,
+    use_allow_list: bool,
+    allow_list: HashSet<SocketAddr>,
+    deny_private: bool,
+}
+
+impl TargetPolicy {
+    pub fn new(cfg: &SessionIpForwardingConfig) -> Self {
+        Self {
+            rules: cfg.target_rules.clone(),
+            use_allow_list: cfg.use_target_allow_list,
+            allow_list: cfg.target_allow_list.clone(),
+            deny_private: cfg.deny_private_targets,
+        }
+    }
+
+    fn evaluate(&self, protocol: IpProtocol, host: Option<&str>, addr: &SocketAddr) -> (TargetAction, DecisionReason) {
+        let denied_private = self.deny_private && is_private(&addr.ip());
+        if let Some((index, rule)) = self
+            .rules
+            .iter()
+            .enumerate()
+            .find(|(_, rule)| rule.matches(protocol, host, addr))
+        {
+            if rule.action == TargetAction::Allow && denied_private && rule.networks.is_empty() {
+                // Only rules explicitly listing private networks can allow private addresses
+                (TargetAction::Deny, DecisionReason::Private)
+            } else {
+                (rule.action, DecisionReason::Rule(index))
+            }
+        } else if self.use_allow_list && self.allow_list.contains(addr) {
+            (TargetAction::Allow, DecisionReason::AllowList)
+        } else if denied_private {
+            (TargetAction::Deny, DecisionReason::Private)
+        } else if self.use_allow_list {
+            (TargetAction::Deny, DecisionReason::Default)
+        } else {
+            (TargetAction::Allow, DecisionReason::Default)
+        }
+    }
+
+    /// Checks that all the resolved addresses of the target are allowed.
+    ///
+    /// The `host` is the DNS name of the target, if it was not given as an IP address.
+    pub fn is_allowed(&self, protocol: IpProtocol, host: Option<&str>, addrs: &[SocketAddr]) -> bool {
+        addrs.iter().all(|addr| {
+            let (action, reason) = self.evaluate(protocol, host, addr);
+
+            #[cfg(all(feature = "prometheus", not(test)))]
+            METRIC_TARGET_DECISIONS.increment(&[&protocol.to_string(), &action.to_string(), reason.as_str()]);
+
+            let rule = match reason {
+                DecisionReason::Rule(index) => Some(index),
+                _ => None,
+            };
+            match action {
+                TargetAction::Allow => tracing::info!(
+                    %protocol, ?host, %addr, reason = reason.as_str(), ?rule, "session target allowed"
+                ),
+                TargetAction::Deny => tracing::warn!(
+                    %protocol, ?host, %addr, reason = reason.as_str(), ?rule, "session target denied"
+                ),
+            }
+
+            action == TargetAction::Allow
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(yaml: &str) -> anyhow::Result<TargetRule> {
+        Ok(serde_yaml::from_str(yaml)?)
+    }
+
+    #[test]
+    fn port_range_should_parse_single_ports_and_ranges() -> anyhow::Result<()> {
+        assert_eq!(
+            PortRange { start: 443, end: 443 },
+            "443".parse().map_err(anyhow::Error::msg)?
+        );
+        assert_eq!(
+            PortRange { start: 8000, end: 9000 },
+            "8000-9000".parse().map_err(anyhow::Error::msg)?
+        );
+        assert!("9000-8000".parse::<PortRange>().is_err());
+        assert!("http".parse::<PortRange>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn host_patterns_should_match_exact_names_and_subdomains() {
+        assert!(host_matches("example.com", "Example.com."));
+        assert!(!host_matches("example.com", "www.example.com"));
+        assert!(host_matches("*.example.com", "www.example.com"));
+        assert!(host_matches("*.example.com", "a.b.example.com"));
+        assert!(!host_matches("*.example.com", "example.com"));
+        assert!(!host_matches("*.example.com", "badexample.com"));
+    }
+
+    #[test]
+    fn private_ranges_should_be_detected() -> anyhow::Result<()> {
+        for private in [
+            "10.1.2.3",
+            "127.0.0.1",
+            "192.168.1.1",
+            "100.64.0.1",
+            "::1",
+            "fd00::1",
+            "::ffff:10.0.0.1",
+            "0.0.0.0",
+            "0.1.2.3",
+            "224.0.0.1",
+            "239.255.255.250",
+            "ff02::1",
+        ] {
+            assert!(is_private(&private.parse()?), "{private} should be private");
+        }
+        for public in ["1.1.1.1", "100.128.0.1", "2606:4700::1111"] {
+            assert!(!is_private(&public.parse()?), "{public} should not be private");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn target_policy_should_evaluate_rules_before_the_defaults() -> anyhow::Result<()> {
+        let policy = TargetPolicy::new(&SessionIpForwardingConfig {
+            use_target_allow_list: false,
+            target_rules: vec![
+                rule("{ action: deny, protocol: udp, ports: ['53'] }")?,
+                rule("{ action: allow, networks: ['10.0.0.0/8'], ports: ['8000-9000'] }")?,
+                rule("{ action: deny, hosts: ['*.blocked.org'] }")?,
+            ],
+            ..Default::default()
+        });
+
+        assert!(!policy.is_allowed(IpProtocol::UDP, None, &["1.1.1.1:53".parse()?]));
+        assert!(policy.is_allowed(IpProtocol::TCP, None, &["1.1.1.1:53".parse()?]));
+        assert!(policy.is_allowed(IpProtocol::TCP, None, &["10.0.0.1:8080".parse()?]));
+        assert!(!policy.is_allowed(IpProtocol::TCP, None, &["10.0.0.1:22".parse()?]));
+        assert!(!policy.is_allowed(IpProtocol::TCP, Some("www.blocked.org"), &["1.1.1.1:443".parse()?]));
+        assert!(!policy.is_allowed(
+            IpProtocol::TCP,
+            None,
+            &["1.1.1.1:443".parse()?, "192.168.0.1:443".parse()?]
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn target_policy_should_deny_private_addresses_allowed_only_by_host_name() -> anyhow::Result<()> {
+        let policy = TargetPolicy::new(&SessionIpForwardingConfig {
+            use_target_allow_list: false,
+            target_rules: vec![
+                rule("{ action: allow, hosts: ['*.example.com'] }")?,
+                rule("{ action: allow, networks: ['10.0.0.0/8'], hosts: ['internal.example.org'] }")?,
+            ],
+            ..Default::default()
+        });
+
+        assert!(policy.is_allowed(IpProtocol::TCP, Some("www.example.com"), &["1.1.1.1:443".parse()?]));
+        assert!(!policy.is_allowed(IpProtocol::TCP, Some("www.example.com"), &["127.0.0.1:443".parse()?]));
+        assert!(policy.is_allowed(
+            IpProtocol::TCP,
+            Some("internal.example.org"),
+            &["10.0.0.1:443".parse()?]
+        ));
+
+        let policy = TargetPolicy::new(&SessionIpForwardingConfig {
+            use_target_allow_list: false,
+            deny_private_targets: false,
+            target_rules: vec![rule("{ action: allow, hosts: ['*.example.com'] }")?],
+            ..Default::default()
+        });
+        assert!(policy.is_allowed(IpProtocol::TCP, Some("www.example.com"), &["127.0.0.1:443".parse()?]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn target_policy_should_keep_the_allow_list_semantics() -> anyhow::Result<()> {
+        let policy = TargetPolicy::new(&SessionIpForwardingConfig {
+            target_allow_list: HashSet::from(["127.0.0.1:8080".parse()?]),
+            ..Default::default()
+        });
+
+        assert!(policy.is_allowed(IpProtocol::TCP, None, &["127.0.0.1:8080".parse()?]));
+        assert!(!policy.is_allowed(IpProtocol::TCP, None, &["127.0.0.1:8081".parse()?]));
+        assert!(!policy.is_allowed(IpProtocol::TCP, None, &["1.1.1.1:443".parse()?]));
+
+        let policy = TargetPolicy::new(&SessionIpForwardingConfig {
+            use_target_allow_list: false,
+            deny_private_targets: false,
+            ..Default::default()
+        });
+        assert!(policy.is_allowed(IpProtocol::UDP, None, &["127.0.0.1:8081".parse()?]));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,523 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+
+use async_lock::{Mutex, RwLock};
+use axum::{
+    extract::{Json, Path, State},
+    http::{Method, status::StatusCode},
+    response::IntoResponse,
+};
+use hopr_crypto_types::types::Hash;
+use hopr_lib::{AsUnixTimestamp, ToHex};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::{ApiError, ApiErrorStatus, BASE_PATH, InternalState};
+
+/// Length of the window in which the number of requests made with a token is limited.
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+/// Permission granted to a scoped API token.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, strum::Display, strum::EnumString, utoipa::ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum ApiTokenScope {
+    /// Read-only access to all the endpoints that do not change the state of the node.
+    ReadOnly,
+    /// Creating and using sessions and sending and receiving messages.
+    Sessions,
+    /// Closing channels and managing tickets.
+    Channels,
+    /// Moving funds out of the node, i.e. withdrawing, opening and funding channels.
+    Funds,
+}
+
+/// Returns the scope a token must have to access the given endpoint.
+///
+/// Returns `None` if the endpoint is reserved for the admin token configured in [`Auth`](crate::config::Auth).
+pub(crate) fn required_scope(method: &Method, path: &str) -> Option<ApiTokenScope> {
+    let path = path.strip_prefix(BASE_PATH).unwrap_or(path);
+
+    if path.starts_with("/tokens") || (path.starts_with("/strategy") && method != Method::GET) {
+        None
+    } else if path.starts_with("/session") || path.starts_with("/messages") {
+        Some(ApiTokenScope::Sessions)
+    } else if method == Method::POST
+        && (path == "/account/withdraw"
+            || path.trim_end_matches('/') == "/channels"
+            || (path.starts_with("/channels/") && path.ends_with("/fund")))
+    {
+        Some(ApiTokenScope::Funds)
+    } else if method == Method::GET
+        || method == Method::HEAD
+        || (method == Method::POST && path.starts_with("/peers/") && path.ends_with("/ping"))
+    {
+        Some(ApiTokenScope::ReadOnly)
+    } else if path.starts_with("/channels") || path.starts_with("/tickets") {
+        Some(ApiTokenScope::Channels)
+    } else {
+        None
+    }
+}
+
+/// Scoped API token as persisted in the [`ApiTokenStore`].
+///
+/// Only the hash of the token secret is stored.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct StoredApiToken {
+    id: String,
+    name: String,
+    secret_hash: String,
+    scopes: Vec<ApiTokenScope>,
+    /// Creation time in seconds since the UNIX epoch.
+    created_at: u64,
+    /// Expiration time in seconds since the UNIX epoch.
+    expires_at: Option<u64>,
+    /// Maximum number of requests per minute.
+    rate_limit: Option<u32>,
+}
+
+impl StoredApiToken {
+    fn is_expired(&self, now: SystemTime) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| now.as_unix_timestamp().as_secs() >= expires_at)
+    }
+}
+
+fn hash_secret(secret: &str) -> String {
+    Hash::create(&[secret.as_bytes()]).to_hex()
+}
+
+/// Reason a request made with a scoped API token was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ApiTokenRejection {
+    /// The token is unknown or expired.
+    Unauthorized,
+    /// The token does not have the required scope.
+    Forbidden,
+    /// The token exceeded its request rate limit.
+    RateLimited,
+}
+
+/// Store of the scoped API tokens.
+///
+/// The tokens are persisted in a JSON file, if a path is given.
+#[derive(Debug, Clone, Default)]
+pub struct ApiTokenStore {
+    path: Option<PathBuf>,
+    tokens: Arc<RwLock<Vec<StoredApiToken>>>,
+    usage: Arc<Mutex<HashMap<String, (Instant, u32)>>>,
+}
+
+impl ApiTokenStore {
+    /// Loads the tokens from the given file, if it exists.
+    ///
+    /// The file is created once the first token is created.
+    pub fn load(path: Option<PathBuf>) -> std::io::Result<Self> {
+        let tokens = match &path {
+            Some(path) if path.exists() => serde_json::from_slice(&std::fs::read(path)?)?,
+            _ => Vec::new(),
+        };
+
+        Ok(Self {
+            path,
+            tokens: Arc::new(RwLock::new(tokens)),
+            usage: Default::default(),
+        })
+    }
+
+    async fn persist(&self, tokens: &[StoredApiToken]) -> std::io::Result<()> {
+        if let Some(path) = &self.path {
+            let mut tmp_path = path.clone().into_os_string();
+            tmp_path.push(".tmp");
+            tokio::fs::write(&tmp_path, serde_json::to_vec_pretty(tokens)?).await?;
+            tokio::fs::rename(&tmp_path, path).await?;
+        }
+        Ok(())
+    }
+
+    async fn list(&self) -> Vec<StoredApiToken> {
+        self.tokens.read().await.clone()
+    }
+
+    /// Creates a new token and returns it together with its secret.
+    async fn create(
+        &self,
+        name: String,
+        scopes: Vec<ApiTokenScope>,
+        expires_in: Option<Duration>,
+        rate_limit: Option<u32>,
+    ) -> std::io::Result<(StoredApiToken, String)> {
+        let secret = hex::encode(hopr_crypto_random::random_bytes::<32>());
+        let now = SystemTime::now();
+        let token = StoredApiToken {
+            id: hex::encode(hopr_crypto_random::random_bytes::<8>()),
+            name,
+            secret_hash: hash_secret(&secret),
+            scopes,
+            created_at: now.as_unix_timestamp().as_secs(),
+            expires_at: expires_in.map(|expires_in| (now + expires_in).as_unix_timestamp().as_secs()),
+            rate_limit,
+        };
+
+        let mut tokens = self.tokens.write().await;
+        tokens.push(token.clone());
+        if let Err(e) = self.persist(&tokens).await {
+            tokens.pop();
+            return Err(e);
+        }
+
+        Ok((token, secret))
+    }
+
+    /// Deletes the token with the given ID and indicates whether it existed.
+    async fn delete(&self, id: &str) -> std::io::Result<bool> {
+        let mut tokens = self.tokens.write().await;
+        let Some(position) = tokens.iter().position(|t| t.id == id) else {
+            return Ok(false);
+        };
+
+        let removed = tokens.remove(position);
+        if let Err(e) = self.persist(&tokens).await {
+            tokens.insert(position, removed);
+            return Err(e);
+        }
+        self.usage.lock().await.remove(id);
+
+        Ok(true)
+    }
+
+    /// Checks that the given secret belongs to a valid token with the required scope and
+    /// counts the request towards the token's rate limit.
+    ///
+    /// Tokens can never access endpoints without a required scope.
+    pub(crate) async fn authorize(
+        &self,
+        secret: &str,
+        required: Option<ApiTokenScope>,
+    ) -> Result<(), ApiTokenRejection> {
+        let secret_hash = hash_secret(secret);
+        let tokens = self.tokens.read().await;
+        let token = tokens
+            .iter()
+            .find(|t| t.secret_hash == secret_hash && !t.is_expired(SystemTime::now()))
+            .ok_or(ApiTokenRejection::Unauthorized)?;
+
+        if !required.is_some_and(|scope| token.scopes.contains(&scope)) {
+            return Err(ApiTokenRejection::Forbidden);
+        }
+
+        if let Some(rate_limit) = token.rate_limit {
+            let mut usage = self.usage.lock().await;
+            let now = Instant::now();
+            let (window_start, count) = usage.entry(token.id.clone()).or_insert((now, 0));
+            if now.duration_since(*window_start) >= RATE_LIMIT_WINDOW {
+                *window_start = now;
+                *count = 0;
+            }
+            if *count >= rate_limit {
+                return Err(ApiTokenRejection::RateLimited);
+            }
+            *count += 1;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[schema(example = json!({
+        "id": "6f2a1c0d9e8b7a65",
+        "name": "monitoring",
+        "scopes": ["readOnly"],
+        "createdAt": 1690000000,
+        "expiresAt": 1700000000,
+        "rateLimit": 60
+    }))]
+#[serde(rename_all = "camelCase")]
+/// Scoped API token, without its secret.
+pub(crate) struct ApiTokenResponse {
+    /// Identifier of the token.
+    id: String,
+    /// Name of the token.
+    name: String,
+    /// Permissions granted to the token.
+    scopes: Vec<ApiTokenScope>,
+    /// Creation time in seconds since the UNIX epoch.
+    created_at: u64,
+    /// Expiration time in seconds since the UNIX epoch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires_at: Option<u64>,
+    /// Maximum number of requests per minute.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rate_limit: Option<u32>,
+}
+
+impl From<StoredApiToken> for ApiTokenResponse {
+    fn from(value: StoredApiToken) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            scopes: value.scopes,
+            created_at: value.created_at,
+            expires_at: value.expires_at,
+            rate_limit: value.rate_limit,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+/// List of the scoped API tokens.
+pub(crate) struct ApiTokensResponse {
+    tokens: Vec<ApiTokenResponse>,
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[schema(example = json!({
+        "name": "monitoring",
+        "scopes": ["readOnly"],
+        "expiresIn": 2592000,
+        "rateLimit": 60
+    }))]
+#[serde(rename_all = "camelCase")]
+/// Request body for creating a scoped API token.
+pub(crate) struct CreateApiTokenBodyRequest {
+    /// Name of the token.
+    name: String,
+    /// Permissions granted to the token.
+    scopes: Vec<ApiTokenScope>,
+    /// Number of seconds after which the token expires. The token never expires if not given.
+    #[schema(required = false)]
+    expires_in: Option<u64>,
+    /// Maximum number of requests per minute. The requests are not limited if not given.
+    #[schema(required = false)]
+    rate_limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[schema(example = json!({
+        "id": "6f2a1c0d9e8b7a65",
+        "name": "monitoring",
+        "scopes": ["readOnly"],
+        "createdAt": 1690000000,
+        "expiresAt": 1692592000,
+        "rateLimit": 60,
+        "token": "9c1e6a8d2b3f4e5d6c7b8a9f0e1d2c3b4a5f6e7d8c9b0a1f2e3d4c5b6a7f8e9d"
+    }))]
+#[serde(rename_all = "camelCase")]
+/// Newly created scoped API token.
+pub(crate) struct CreateApiTokenResponse {
+    #[serde(flatten)]
+    info: ApiTokenResponse,
+    /// Secret of the token, used to authenticate the requests.
+    ///
+    /// The secret is not stored by the node and cannot be retrieved again.
+    token: String,
+}
+
+/// Lists the scoped API tokens.
+#[utoipa::path(
+        get,
+        path = const_format::formatcp!("{BASE_PATH}/tokens"),
+        description = "Lists the scoped API tokens. Only accessible with the admin API token.",
+        responses(
+            (status = 200, description = "Scoped API tokens.", body = ApiTokensResponse),
+            (status = 401, description = "Invalid authorization token.", body = ApiError),
+            (status = 403, description = "The endpoint is only accessible with the admin API token.", body = ApiError),
+        ),
+        security(
+            ("api_token" = []),
+            ("bearer_token" = [])
+        ),
+        tag = "Tokens",
+    )]
+pub(super) async fn list_tokens(State(state): State<Arc<InternalState>>) -> impl IntoResponse {
+    let tokens = state.api_tokens.list().await;
+
+    (
+        StatusCode::OK,
+        Json(ApiTokensResponse {
+            tokens: tokens.into_iter().map(ApiTokenResponse::from).collect(),
+        }),
+    )
+}
+
+/// Creates a new scoped API token.
+#[utoipa::path(
+        post,
+        path = const_format::formatcp!("{BASE_PATH}/tokens"),
+        description = "Creates a new scoped API token. The response contains the token secret, which cannot be retrieved again. Only accessible with the admin API token.",
+        request_body(
+            content = CreateApiTokenBodyRequest,
+            description = "Name, scopes and limits of the new token.",
+            content_type = "application/json"),
+        responses(
+            (status = 201, description = "Scoped API token created.", body = CreateApiTokenResponse),
+            (status = 400, description = "Invalid name or scopes.", body = ApiError),
+            (status = 401, description = "Invalid authorization token.", body = ApiError),
+            (status = 403, description = "The endpoint is only accessible with the admin API token.", body = ApiError),
+            (status = 422, description = "Unknown failure", body = ApiError),
+        ),
+        security(
+            ("api_token" = []),
+            ("bearer_token" = [])
+        ),
+        tag = "Tokens",
+    )]
+pub(super) async fn create_token(
+    State(state): State<Arc<InternalState>>,
+    Json(args): Json<CreateApiTokenBodyRequest>,
+) -> impl IntoResponse {
+    if args.name.trim().is_empty() || args.scopes.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            ApiError {
+                status: ApiErrorStatus::InvalidInput.to_string(),
+                error: Some("token must have a name and at least one scope".into()),
+            },
+        )
+            .into_response();
+    }
+
+    match state
+        .api_tokens
+        .create(
+            args.name,
+            args.scopes,
+            args.expires_in.map(Duration::from_secs),
+            args.rate_limit,
+        )
+        .await
+    {
+        Ok((token, secret)) => {
+            info!(id = token.id, name = token.name, "created a scoped API token");
+            (
+                StatusCode::CREATED,
+                Json(CreateApiTokenResponse {
+                    info: token.into(),
+                    token: secret,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => (StatusCode::UNPROCESSABLE_ENTITY, ApiErrorStatus::from(e)).into_response(),
+    }
+}
+
+/// Deletes a scoped API token.
+#[utoipa::path(
+        delete,
+        path = const_format::formatcp!("{BASE_PATH}/tokens/{{id}}"),
+        description = "Deletes a scoped API token, so it can no longer be used. Only accessible with the admin API token.",
+        params(
+            ("id" = String, Path, description = "ID of the token.")
+        ),
+        responses(
+            (status = 204, description = "Scoped API token deleted."),
+            (status = 401, description = "Invalid authorization token.", body = ApiError),
+            (status = 403, description = "The endpoint is only accessible with the admin API token.", body = ApiError),
+            (status = 404, description = "Token not found.", body = ApiError),
+            (status = 422, description = "Unknown failure", body = ApiError),
+        ),
+        security(
+            ("api_token" = []),
+            ("bearer_token" = [])
+        ),
+        tag = "Tokens",
+    )]
+pub(super) async fn delete_token(Path(id): Path<String>, State(state): State<Arc<InternalState>>) -> impl IntoResponse {
+    match state.api_tokens.delete(&id).await {
+        Ok(true) => {
+            info!(id, "deleted a scoped API token");
+            (StatusCode::NO_CONTENT, "").into_response()
+        }
+        Ok(false) => (StatusCode::NOT_FOUND, ApiErrorStatus::TokenNotFound).into_response(),
+        Err(e) => {
+            warn!(id, error = %e, "failed to delete a scoped API token");
+            (StatusCode::UNPROCESSABLE_ENTITY, ApiErrorStatus::from(e)).into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn required_scope_should_guard_funds_and_admin_endpoints() {
+        let scope = |method: Method, path: &str| required_scope(&method, &format!("{BASE_PATH}{path}"));
+
+        assert_eq!(Some(ApiTokenScope::ReadOnly), scope(Method::GET, "/channels"));
+        assert_eq!(Some(ApiTokenScope::ReadOnly), scope(Method::POST, "/peers/0x1234/ping"));
+        assert_eq!(Some(ApiTokenScope::Sessions), scope(Method::POST, "/session/tcp"));
+        assert_eq!(Some(ApiTokenScope::Sessions), scope(Method::GET, "/messages/websocket"));
+        assert_eq!(Some(ApiTokenScope::Funds), scope(Method::POST, "/channels"));
+        assert_eq!(Some(ApiTokenScope::Funds), scope(Method::POST, "/channels/"));
+        assert_eq!(Some(ApiTokenScope::Channels), scope(Method::DELETE, "/channels/0x1234"));
+        assert_eq!(Some(ApiTokenScope::Channels), scope(Method::POST, "/tickets/redeem"));
+        assert_eq!(Some(ApiTokenScope::Funds), scope(Method::POST, "/account/withdraw"));
+        assert_eq!(Some(ApiTokenScope::Funds), scope(Method::POST, "/channels/0x1234/fund"));
+        assert_eq!(None, scope(Method::GET, "/tokens"));
+        assert_eq!(None, scope(Method::PUT, "/strategy/config"));
+        assert_eq!(Some(ApiTokenScope::ReadOnly), required_scope(&Method::GET, "/metrics"));
+    }
+
+    #[tokio::test]
+    async fn api_token_store_should_authorize_scoped_tokens_and_persist_them() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("api_tokens.json");
+        let store = ApiTokenStore::load(Some(path.clone()))?;
+
+        let (monitoring, monitoring_secret) = store
+            .create("monitoring".into(), vec![ApiTokenScope::ReadOnly], None, Some(2))
+            .await?;
+        let (_, expired_secret) = store
+            .create("expired".into(), vec![ApiTokenScope::Funds], Some(Duration::ZERO), None)
+            .await?;
+
+        let read_only = Some(ApiTokenScope::ReadOnly);
+        assert_eq!(Ok(()), store.authorize(&monitoring_secret, read_only).await);
+        assert_eq!(
+            Err(ApiTokenRejection::Forbidden),
+            store.authorize(&monitoring_secret, Some(ApiTokenScope::Funds)).await
+        );
+        assert_eq!(
+            Err(ApiTokenRejection::Forbidden),
+            store.authorize(&monitoring_secret, None).await
+        );
+        assert_eq!(Ok(()), store.authorize(&monitoring_secret, read_only).await);
+        assert_eq!(
+            Err(ApiTokenRejection::RateLimited),
+            store.authorize(&monitoring_secret, read_only).await
+        );
+        assert_eq!(
+            Err(ApiTokenRejection::Unauthorized),
+            store.authorize(&expired_secret, Some(ApiTokenScope::Funds)).await
+        );
+        assert_eq!(
+            Err(ApiTokenRejection::Unauthorized),
+            store.authorize("unknown", read_only).await
+        );
+
+        let reloaded = ApiTokenStore::load(Some(path.clone()))?;
+        assert_eq!(2, reloaded.list().await.len());
+        assert_eq!(Ok(()), reloaded.authorize(&monitoring_secret, read_only).await);
+
+        assert!(store.delete(&monitoring.id).await?);
+        assert!(!store.delete(&monitoring.id).await?);
+        let reloaded = ApiTokenStore::load(Some(path))?;
+        assert_eq!(
+            Err(ApiTokenRejection::Unauthorized),
+            reloaded.authorize(&monitoring_secret, read_only).await
+        );
+
+        Ok(())
+    }
+}
@@ -7,6 +7,7 @@ use validator::{Validate, ValidationError};
 pub const DEFAULT_API_HOST: &str = "127.0.0.1";
 pub const DEFAULT_API_PORT: u16 = 3001;
 pub const MINIMAL_API_TOKEN_LENGTH: usize = 8;
+pub const DEFAULT_API_TOKENS_FILE_NAME: &str = "api_tokens.json";
 
 fn validate_api_auth(token: &Auth) -> Result<(), ValidationError> {
     match &token {
@@ -43,6 +44,20 @@ pub struct Api {
     #[serde(default = "default_api_host")]
     #[default(default_api_host())]
     pub host: HostConfig,
+    /// Maximum number of received messages kept in the inbox until they are popped
+    #[serde(default = "default_message_inbox_capacity")]
+    #[default(default_message_inbox_capacity())]
+    pub message_inbox_capacity: usize,
+    /// Maximum number of node events kept for resuming the event streams
+    #[serde(default = "default_event_log_capacity")]
+    #[default(default_event_log_capacity())]
+    pub event_log_capacity: usize,
+    /// File persisting the scoped API tokens, defaults to `api_tokens.json` in the node's data directory
+    #[serde(default)]
+    pub tokens_file: Option<String>,
+    /// File persisting the Session listeners created via the REST API, these are not persisted if not given
+    #[serde(default)]
+    pub session_listeners_file: Option<String>,
 }
 
 #[inline]
@@ -50,3 +65,13 @@ fn default_api_host() -> HostConfig {
     HostConfig::from_str(format!("{DEFAULT_API_HOST}:{DEFAULT_API_PORT}").as_str())
         .expect("default credentials should always work")
 }
+
+#[inline]
+fn default_message_inbox_capacity() -> usize {
+    crate::messages::DEFAULT_MESSAGE_INBOX_CAPACITY
+}
+
+#[inline]
+fn default_event_log_capacity() -> usize {
+    crate::events::DEFAULT_EVENT_LOG_CAPACITY
+}
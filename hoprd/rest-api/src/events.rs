@@ -0,0 +1,461 @@
+use std::{collections::VecDeque, convert::Infallible, str::FromStr, sync::Arc, time::SystemTime};
+
+use async_lock::Mutex;
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, status::StatusCode},
+    response::{
+        IntoResponse,
+        sse::{Event, KeepAlive, Sse},
+    },
+};
+use futures::{
+    StreamExt,
+    channel::mpsc::{Receiver, Sender, channel},
+};
+use hopr_crypto_types::types::Hash;
+use hopr_lib::{Address, AsUnixTimestamp, ChannelStatus, HoprBalance, HoprNodeEvent, HoprNodeEventType};
+use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, serde_as};
+use tracing::{error, warn};
+
+use crate::{ApiError, ApiErrorStatus, BASE_PATH, InternalState, option_checksum_address_serializer};
+
+/// Default maximum number of events kept in the [`NodeEventLog`] for resuming event streams.
+pub const DEFAULT_EVENT_LOG_CAPACITY: usize = 1024;
+
+/// Number of events that can be queued for a single event stream client,
+/// before new events are dropped for that client.
+const SSE_EVENT_QUEUE_SIZE: usize = 256;
+
+/// Node event numbered by its position in the [`NodeEventLog`].
+#[derive(Debug, Clone, PartialEq)]
+struct SequencedEvent {
+    seq: u64,
+    event: HoprNodeEvent,
+    emitted_at: SystemTime,
+}
+
+impl SequencedEvent {
+    fn matches(&self, types: &[HoprNodeEventType]) -> bool {
+        types.is_empty() || types.contains(&HoprNodeEventType::from(&self.event))
+    }
+}
+
+#[derive(Debug, Default)]
+struct NodeEventLogState {
+    next_seq: u64,
+    events: VecDeque<SequencedEvent>,
+    subscribers: Vec<Sender<SequencedEvent>>,
+}
+
+/// Bounded log of the events emitted by the node.
+///
+/// Each event gets a sequence number, which the clients can use to resume the event stream
+/// after reconnecting. Only the last `capacity` events can be replayed this way.
+#[derive(Debug, Clone)]
+pub struct NodeEventLog {
+    capacity: usize,
+    state: Arc<Mutex<NodeEventLogState>>,
+}
+
+impl Default for NodeEventLog {
+    fn default() -> Self {
+        Self::new(DEFAULT_EVENT_LOG_CAPACITY)
+    }
+}
+
+impl NodeEventLog {
+    /// Creates a log keeping at most `capacity` events.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Arc::new(Mutex::new(NodeEventLogState {
+                next_seq: 1,
+                events: VecDeque::with_capacity(capacity),
+                subscribers: Vec::new(),
+            })),
+        }
+    }
+
+    /// Numbers the event, stores it and delivers it to the connected event stream clients.
+    pub async fn push(&self, event: HoprNodeEvent) {
+        let mut state = self.state.lock().await;
+
+        let event = SequencedEvent {
+            seq: state.next_seq,
+            event,
+            emitted_at: SystemTime::now(),
+        };
+        state.next_seq += 1;
+
+        state
+            .subscribers
+            .retain_mut(|subscriber| match subscriber.try_send(event.clone()) {
+                Ok(_) => true,
+                Err(e) if e.is_full() => {
+                    warn!(seq = event.seq, "event stream client is too slow, dropping node event");
+                    true
+                }
+                Err(_) => false,
+            });
+
+        if self.capacity > 0 {
+            if state.events.len() >= self.capacity {
+                state.events.pop_front();
+            }
+            state.events.push_back(event);
+        }
+    }
+
+    /// Subscribes to the new events, returning also the retained events following the `since` sequence number.
+    async fn subscribe(&self, since: Option<u64>) -> (Vec<SequencedEvent>, Receiver<SequencedEvent>) {
+        let mut state = self.state.lock().await;
+
+        let replayed = since
+            .map(|since| state.events.iter().filter(|e| e.seq > since).cloned().collect())
+            .unwrap_or_default();
+
+        let (tx, rx) = channel(SSE_EVENT_QUEUE_SIZE);
+        state.subscribers.push(tx);
+
+        (replayed, rx)
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize, utoipa::IntoParams, utoipa::ToSchema)]
+#[into_params(parameter_in = Query)]
+#[serde(default, rename_all = "camelCase")]
+#[schema(example = json!({
+        "types": "channel_opened,peer_connected",
+        "since": 10
+    }))]
+/// Parameters for streaming the node events.
+pub(crate) struct NodeEventsQueryRequest {
+    /// Comma-separated list of the event types to stream, all event types are streamed if not given.
+    ///
+    /// Possible values: `channel_opened`, `channel_funded`, `channel_closure_initiated`, `channel_closed`,
+    /// `ticket_redeemed`, `peer_connected`, `peer_disconnected`, `session_opened`, `session_closed`,
+    /// `indexer_sync_progress`.
+    #[schema(required = false, example = "channel_opened,peer_connected")]
+    #[serde(default)]
+    types: Option<String>,
+    /// Replay the retained events following the event with this ID, before streaming the new events.
+    ///
+    /// The `Last-Event-ID` header takes precedence over this parameter.
+    #[schema(required = false, example = 10)]
+    #[serde(default)]
+    since: Option<u64>,
+}
+
+impl NodeEventsQueryRequest {
+    fn event_types(&self) -> Result<Vec<HoprNodeEventType>, String> {
+        self.types
+            .iter()
+            .flat_map(|types| types.split(','))
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(|t| HoprNodeEventType::from_str(t).map_err(|_| format!("unknown event type '{t}'")))
+            .collect()
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[schema(example = json!({
+        "id": 11,
+        "type": "channel_funded",
+        "timestamp": 1690000000000_u64,
+        "channelId": "0x04efc1481d3f106b88527b3844ba40042b823218a9cd29d1aa11c2c2ef8f538f",
+        "source": "0x07eaf07d6624f741e04f4092a755a9027aaab7f6",
+        "destination": "0x188c4462b75e46f0c7262d7f48d182447b93a93c",
+        "status": "Open",
+        "balance": "20 wxHOPR",
+        "amount": "10 wxHOPR"
+    }))]
+#[serde(rename_all = "camelCase")]
+/// Event emitted by the node, fields not relevant for the event type are omitted.
+pub(crate) struct NodeEventResponse {
+    /// Sequence number of the event.
+    #[schema(example = 11)]
+    id: u64,
+    /// Type of the event.
+    #[serde(rename = "type")]
+    #[serde_as(as = "DisplayFromStr")]
+    #[schema(value_type = String, example = "channel_funded")]
+    event_type: HoprNodeEventType,
+    /// Time the event was emitted in milliseconds since the UNIX epoch.
+    #[schema(example = 1690000000000_u64)]
+    timestamp: u128,
+    /// ID of the channel, for channel and ticket events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[schema(value_type = Option<String>, example = "0x04efc1481d3f106b88527b3844ba40042b823218a9cd29d1aa11c2c2ef8f538f")]
+    channel_id: Option<Hash>,
+    /// Source of the channel, for channel and ticket events.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "option_checksum_address_serializer"
+    )]
+    #[schema(value_type = Option<String>, example = "0x07eaf07d6624f741e04f4092a755a9027aaab7f6")]
+    source: Option<Address>,
+    /// Destination of the channel, for channel and ticket events.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "option_checksum_address_serializer"
+    )]
+    #[schema(value_type = Option<String>, example = "0x188c4462b75e46f0c7262d7f48d182447b93a93c")]
+    destination: Option<Address>,
+    /// Status of the channel after the event, for channel and ticket events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[schema(value_type = Option<String>, example = "Open")]
+    status: Option<ChannelStatus>,
+    /// Balance of the channel after the event, for channel and ticket events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[schema(value_type = Option<String>, example = "20 wxHOPR")]
+    balance: Option<HoprBalance>,
+    /// Amount the channel has been funded with.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[schema(value_type = Option<String>, example = "10 wxHOPR")]
+    amount: Option<HoprBalance>,
+    /// Peer the node connected to or disconnected from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "12D3KooWRWeTozREYHzWTbuCYskdYhED1MXpDwTrmccwzFrd2mEA")]
+    peer_id: Option<String>,
+    /// ID of the opened or closed Session.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "0x5112D584a1C72Fc25017:487")]
+    session_id: Option<String>,
+    /// Indicates whether the opened Session was initiated by the other party.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = false)]
+    incoming: Option<bool>,
+    /// Last block processed by the Indexer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 38000000)]
+    block_number: Option<u64>,
+    /// Last known chain head block.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 38000100)]
+    head: Option<u64>,
+    /// Indexer synchronization progress in the range `[0.0, 1.0]`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 0.95)]
+    progress: Option<f64>,
+}
+
+impl From<SequencedEvent> for NodeEventResponse {
+    fn from(value: SequencedEvent) -> Self {
+        let mut response = Self {
+            id: value.seq,
+            event_type: HoprNodeEventType::from(&value.event),
+            timestamp: value.emitted_at.as_unix_timestamp().as_millis(),
+            channel_id: None,
+            source: None,
+            destination: None,
+            status: None,
+            balance: None,
+            amount: None,
+            peer_id: None,
+            session_id: None,
+            incoming: None,
+            block_number: None,
+            head: None,
+            progress: None,
+        };
+
+        let channel = match value.event {
+            HoprNodeEvent::ChannelOpened(channel)
+            | HoprNodeEvent::ChannelClosureInitiated(channel)
+            | HoprNodeEvent::ChannelClosed(channel)
+            | HoprNodeEvent::TicketRedeemed(channel) => Some(channel),
+            HoprNodeEvent::ChannelFunded(channel, amount) => {
+                response.amount = Some(amount);
+                Some(channel)
+            }
+            HoprNodeEvent::PeerConnected(peer) | HoprNodeEvent::PeerDisconnected(peer) => {
+                response.peer_id = Some(peer.to_string());
+                None
+            }
+            HoprNodeEvent::SessionOpened { session_id, incoming } => {
+                response.session_id = Some(session_id.to_string());
+                response.incoming = Some(incoming);
+                None
+            }
+            HoprNodeEvent::SessionClosed { session_id } => {
+                response.session_id = Some(session_id.to_string());
+                None
+            }
+            HoprNodeEvent::IndexerSyncProgress(sync) => {
+                response.block_number = Some(sync.block_number);
+                response.head = Some(sync.head);
+                response.progress = Some(sync.progress);
+                None
+            }
+        };
+
+        if let Some(channel) = channel {
+            response.channel_id = Some(channel.get_id());
+            response.source = Some(channel.source);
+            response.destination = Some(channel.destination);
+            response.status = Some(channel.status);
+            response.balance = Some(channel.balance);
+        }
+
+        response
+    }
+}
+
+fn to_sse_event(event: SequencedEvent) -> Option<Event> {
+    let response = NodeEventResponse::from(event);
+    match Event::default()
+        .id(response.id.to_string())
+        .event(response.event_type.to_string())
+        .json_data(&response)
+    {
+        Ok(event) => Some(event),
+        Err(e) => {
+            error!(error = %e, "Failed to serialize a node event");
+            None
+        }
+    }
+}
+
+/// Streams the events emitted by the node as Server-Sent Events.
+///
+/// Each event carries its type as the SSE event name, its sequence number as the SSE event ID
+/// and the JSON-encoded [`NodeEventResponse`] as data.
+///
+/// After reconnecting, the stream can be resumed from the last received event either
+/// by the `Last-Event-ID` header or by the `since` parameter.
+///
+/// Authentication (if enabled) can also be done by the `apiToken` query parameter.
+#[utoipa::path(
+        get,
+        path = const_format::formatcp!("{BASE_PATH}/events"),
+        description = "Streams the events emitted by the node as Server-Sent Events, optionally only those of the given types.",
+        params(NodeEventsQueryRequest),
+        responses(
+            (status = 200, description = "Stream of the node events.", body = NodeEventResponse, content_type = "text/event-stream"),
+            (status = 400, description = "Unknown event type.", body = ApiError),
+            (status = 401, description = "Invalid authorization token.", body = ApiError),
+        ),
+        security(
+            ("api_token" = []),
+            ("bearer_token" = [])
+        ),
+        tag = "Events",
+    )]
+pub(super) async fn events(
+    Query(query): Query<NodeEventsQueryRequest>,
+    headers: HeaderMap,
+    State(state): State<Arc<InternalState>>,
+) -> impl IntoResponse {
+    let types = match query.event_types() {
+        Ok(types) => types,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                ApiError {
+                    status: ApiErrorStatus::InvalidInput.to_string(),
+                    error: Some(e),
+                },
+            )
+                .into_response();
+        }
+    };
+
+    let since = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok())
+        .or(query.since);
+
+    let (replayed, events) = state.node_events.subscribe(since).await;
+
+    let stream = futures::stream::iter(replayed)
+        .chain(events)
+        .filter(move |event| futures::future::ready(event.matches(&types)))
+        .filter_map(|event| futures::future::ready(to_sse_event(event).map(Ok::<_, Infallible>)));
+
+    Sse::new(stream).keep_alive(KeepAlive::default()).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use hopr_lib::{ChannelEntry, PeerId, U256};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn node_event_log_should_replay_retained_events_since_the_given_sequence_number() {
+        let log = NodeEventLog::new(3);
+        let peer = PeerId::random();
+
+        for _ in 0..5 {
+            log.push(HoprNodeEvent::PeerConnected(peer)).await;
+        }
+
+        let (replayed, mut events) = log.subscribe(Some(3)).await;
+        assert_eq!(vec![4, 5], replayed.iter().map(|e| e.seq).collect::<Vec<_>>());
+
+        let (replayed, _) = log.subscribe(Some(0)).await;
+        assert_eq!(
+            vec![3, 4, 5],
+            replayed.iter().map(|e| e.seq).collect::<Vec<_>>(),
+            "only the retained events must be replayed"
+        );
+
+        log.push(HoprNodeEvent::PeerDisconnected(peer)).await;
+        let event = events.next().await.expect("must receive an event");
+        assert_eq!(6, event.seq);
+        assert!(event.matches(&[HoprNodeEventType::PeerDisconnected]));
+        assert!(!event.matches(&[HoprNodeEventType::PeerConnected]));
+    }
+
+    #[test]
+    fn node_events_query_should_parse_event_types() {
+        let query = NodeEventsQueryRequest {
+            types: Some("channel_opened, peer_connected".into()),
+            since: None,
+        };
+        assert_eq!(
+            Ok(vec![HoprNodeEventType::ChannelOpened, HoprNodeEventType::PeerConnected]),
+            query.event_types()
+        );
+
+        let query = NodeEventsQueryRequest {
+            types: Some("channel_exploded".into()),
+            since: None,
+        };
+        assert!(query.event_types().is_err());
+    }
+
+    #[test]
+    fn node_event_response_should_contain_channel_fields() -> anyhow::Result<()> {
+        let channel = ChannelEntry::new(
+            Address::new(&[1; 20]),
+            Address::new(&[2; 20]),
+            HoprBalance::from(20_u32),
+            U256::zero(),
+            ChannelStatus::Open,
+            U256::one(),
+        );
+
+        let response = NodeEventResponse::from(SequencedEvent {
+            seq: 1,
+            event: HoprNodeEvent::ChannelFunded(channel, HoprBalance::from(10_u32)),
+            emitted_at: SystemTime::now(),
+        });
+
+        let json = serde_json::to_value(&response)?;
+        assert_eq!("channel_funded", json["type"]);
+        assert_eq!(channel.get_id().to_string(), json["channelId"]);
+        assert!(json.get("peerId").is_none());
+
+        Ok(())
+    }
+}
@@ -2,14 +2,20 @@
 pub mod config;
 
 mod account;
+mod api_tokens;
 mod channels;
 mod checks;
+mod events;
+mod messages;
 mod network;
 mod node;
 mod peers;
 mod preconditions;
 mod prometheus;
 mod session;
+mod session_listeners;
+mod session_proxy;
+mod strategy;
 mod tickets;
 
 pub(crate) mod env {
@@ -32,7 +38,7 @@ use axum::{
     http::{Method, header::AUTHORIZATION, status::StatusCode},
     middleware,
     response::{IntoResponse, Response},
-    routing::{delete, get, post},
+    routing::{delete, get, post, put},
 };
 use hopr_lib::{Address, Hopr, errors::HoprLibError};
 use hopr_network_types::prelude::IpProtocol;
@@ -54,6 +60,12 @@ use utoipa::{
 use utoipa_scalar::{Scalar, Servable as ScalarServable};
 use utoipa_swagger_ui::SwaggerUi;
 
+pub use crate::{
+    api_tokens::ApiTokenStore,
+    events::NodeEventLog,
+    messages::MessageInbox,
+    session_listeners::{SessionListenerConfig, SessionListenerStore},
+};
 use crate::{config::Auth, session::StoredSessionEntry};
 
 pub(crate) const BASE_PATH: &str = "/api/v3";
@@ -73,11 +85,16 @@ pub type ListenerJoinHandles = Arc<RwLock<HashMap<ListenerId, StoredSessionEntry
 #[derive(Clone)]
 pub(crate) struct InternalState {
     pub hoprd_cfg: String,
+    pub hoprd_cfg_path: Option<String>,
     pub auth: Arc<Auth>,
     pub hopr: Arc<Hopr>,
     pub websocket_active_count: Arc<AtomicU16>,
     pub open_listeners: ListenerJoinHandles,
     pub default_listen_host: std::net::SocketAddr,
+    pub message_inbox: MessageInbox,
+    pub api_tokens: ApiTokenStore,
+    pub node_events: NodeEventLog,
+    pub session_listeners: SessionListenerStore,
 }
 
 #[derive(OpenApi)]
@@ -86,6 +103,9 @@ pub(crate) struct InternalState {
         account::addresses,
         account::balances,
         account::withdraw,
+        api_tokens::list_tokens,
+        api_tokens::create_token,
+        api_tokens::delete_token,
         channels::close_channel,
         channels::fund_channel,
         channels::list_channels,
@@ -95,6 +115,12 @@ pub(crate) struct InternalState {
         checks::healthyz,
         checks::readyz,
         checks::startedz,
+        events::events,
+        messages::send_message,
+        messages::pop_message,
+        messages::pop_all_messages,
+        messages::message_inbox_size,
+        messages::websocket,
         network::price,
         network::probability,
         node::configuration,
@@ -107,7 +133,12 @@ pub(crate) struct InternalState {
         peers::show_peer_info,
         session::create_client,
         session::list_clients,
+        session::list_session_stats,
         session::close_client,
+        strategy::show_strategy_intents,
+        strategy::show_strategy_history,
+        strategy::show_strategy_config,
+        strategy::reconfigure_strategy,
         tickets::aggregate_tickets_in_channel,
         tickets::redeem_all_tickets,
         tickets::redeem_tickets_in_channel,
@@ -120,15 +151,22 @@ pub(crate) struct InternalState {
         schemas(
             ApiError,
             account::AccountAddressesResponse, account::AccountBalancesResponse, account::WithdrawBodyRequest, account::WithdrawResponse,
+            api_tokens::ApiTokenScope, api_tokens::ApiTokenResponse, api_tokens::ApiTokensResponse, api_tokens::CreateApiTokenBodyRequest, api_tokens::CreateApiTokenResponse,
             channels::ChannelsQueryRequest,channels::CloseChannelResponse, channels::OpenChannelBodyRequest, channels::OpenChannelResponse, channels::FundChannelResponse,
             channels::NodeChannel, channels::NodeChannelsResponse, channels::ChannelInfoResponse, channels::FundBodyRequest,
+            events::NodeEventsQueryRequest, events::NodeEventResponse,
+            messages::SendMessageBodyRequest, messages::SendMessageResponse, messages::MessageTagQueryRequest,
+            messages::ReceivedMessageResponse, messages::ReceivedMessagesResponse, messages::MessageInboxSizeResponse,
             network::TicketPriceResponse,
             network::TicketProbabilityResponse,
             node::EntryNode, node::NodeInfoResponse, node::NodePeersQueryRequest,
             node::HeartbeatInfo, node::PeerInfo, node::AnnouncedPeer, node::NodePeersResponse, node::NodeVersionResponse, node::GraphExportQuery, node::NodeGraphResponse,
             peers::NodePeerInfoResponse, peers::PingResponse,
-            session::SessionClientRequest, session::SessionCapability, session::RoutingOptions, session::SessionTargetSpec, session::SessionClientResponse, session::IpProtocol,
-            tickets::NodeTicketStatisticsResponse, tickets::ChannelTicket,
+            session::SessionClientRequest, session::SessionCapability, session::RoutingOptions, session::SessionPathConstraints, session::SessionTargetSpec, session::SessionClientResponse, session::SessionStatsResponse, session::IpProtocol, session_proxy::ProxyProtocol,
+            strategy::StrategyIntentResponse, strategy::StrategyActionResponse, strategy::IntendedActionType, strategy::StrategyConfigQueryRequest,
+            strategy::StrategyExecutionResponse, strategy::StrategyEventType, strategy::StrategyHistoryQueryRequest,
+            tickets::NodeTicketStatisticsResponse, tickets::ChannelTicket, tickets::NodeTicket, tickets::NodeTicketsResponse,
+            tickets::TicketStatus, tickets::TicketsQueryRequest,
         )
     ),
     modifiers(&SecurityAddon),
@@ -137,11 +175,15 @@ pub(crate) struct InternalState {
         (name = "Channels", description = "HOPR node chain channels manipulation endpoints"),
         (name = "Configuration", description = "HOPR node configuration endpoints"),
         (name = "Checks", description = "HOPR node functionality checks"),
+        (name = "Events", description = "HOPR node event stream endpoints"),
+        (name = "Messages", description = "HOPR node message sending and receiving endpoints"),
         (name = "Network", description = "HOPR node network endpoints"),
         (name = "Node", description = "HOPR node information endpoints"),
         (name = "Peers", description = "HOPR node peer manipulation endpoints"),
         (name = "Session", description = "HOPR node session management endpoints"),
+        (name = "Strategy", description = "HOPR node strategy endpoints"),
         (name = "Tickets", description = "HOPR node ticket management endpoints"),
+        (name = "Tokens", description = "HOPR node scoped API token management endpoints"),
     )
 )]
 pub struct ApiDoc;
@@ -179,10 +221,24 @@ impl Modify for SecurityAddon {
 pub struct RestApiParameters {
     pub listener: TcpListener,
     pub hoprd_cfg: String,
+    /// Path to the node configuration file, if the node was started with one.
+    ///
+    /// Reconfigured strategies can be persisted into this file.
+    pub hoprd_cfg_path: Option<String>,
     pub cfg: crate::config::Api,
     pub hopr: Arc<hopr_lib::Hopr>,
     pub session_listener_sockets: ListenerJoinHandles,
     pub default_session_listen_host: std::net::SocketAddr,
+    /// Inbox of the messages received by the node.
+    pub message_inbox: MessageInbox,
+    /// Scoped API tokens, in addition to the admin token configured in [`Auth`].
+    pub api_tokens: ApiTokenStore,
+    /// Log of the events emitted by the node.
+    pub node_events: NodeEventLog,
+    /// Session listeners created via the REST API, persisted to be restored after a restart.
+    pub session_listeners: SessionListenerStore,
+    /// Session listeners declared in the node configuration.
+    pub configured_session_listeners: Vec<SessionListenerConfig>,
 }
 
 /// Starts the Rest API listener and router.
@@ -190,18 +246,30 @@ pub async fn serve_api(params: RestApiParameters) -> Result<(), std::io::Error>
     let RestApiParameters {
         listener,
         hoprd_cfg,
+        hoprd_cfg_path,
         cfg,
         hopr,
         session_listener_sockets,
         default_session_listen_host,
+        message_inbox,
+        api_tokens,
+        node_events,
+        session_listeners,
+        configured_session_listeners,
     } = params;
 
     let router = build_api(
         hoprd_cfg,
+        hoprd_cfg_path,
         cfg,
         hopr,
         session_listener_sockets,
         default_session_listen_host,
+        message_inbox,
+        api_tokens,
+        node_events,
+        session_listeners,
+        configured_session_listeners,
     )
     .await;
     axum::serve(listener, router).await
@@ -210,21 +278,37 @@ pub async fn serve_api(params: RestApiParameters) -> Result<(), std::io::Error>
 #[allow(clippy::too_many_arguments)]
 async fn build_api(
     hoprd_cfg: String,
+    hoprd_cfg_path: Option<String>,
     cfg: crate::config::Api,
     hopr: Arc<hopr_lib::Hopr>,
     open_listeners: ListenerJoinHandles,
     default_listen_host: std::net::SocketAddr,
+    message_inbox: MessageInbox,
+    api_tokens: ApiTokenStore,
+    node_events: NodeEventLog,
+    session_listeners: SessionListenerStore,
+    configured_session_listeners: Vec<SessionListenerConfig>,
 ) -> Router {
     let state = AppState { hopr };
     let inner_state = InternalState {
         auth: Arc::new(cfg.auth.clone()),
         hoprd_cfg,
+        hoprd_cfg_path,
         hopr: state.hopr.clone(),
         open_listeners,
         default_listen_host,
+        message_inbox,
+        api_tokens,
+        node_events,
+        session_listeners,
         websocket_active_count: Arc::new(AtomicU16::new(0)),
     };
 
+    hopr_async_runtime::prelude::spawn(session_listeners::restore_listeners(
+        inner_state.clone(),
+        configured_session_listeners,
+    ));
+
     Router::new()
         .merge(
             Router::new()
@@ -247,7 +331,7 @@ async fn build_api(
                     preconditions::authenticate,
                 ))
                 .layer(middleware::from_fn_with_state(
-                    inner_state.clone(),
+                    inner_state.websocket_active_count.clone(),
                     preconditions::cap_websockets,
                 ))
                 .layer(
@@ -266,6 +350,29 @@ async fn build_api(
                         .layer(SetSensitiveRequestHeadersLayer::new(once(AUTHORIZATION))),
                 ),
         )
+        .merge(
+            Router::new()
+                .route(const_format::formatcp!("{BASE_PATH}/events"), get(events::events))
+                .with_state(inner_state.clone().into())
+                .layer(middleware::from_fn_with_state(
+                    inner_state.clone(),
+                    preconditions::authenticate,
+                ))
+                .layer(
+                    ServiceBuilder::new()
+                        .layer(TraceLayer::new_for_http())
+                        .layer(
+                            CorsLayer::new()
+                                .allow_methods([Method::GET])
+                                .allow_origin(Any)
+                                .allow_headers(Any)
+                                .max_age(std::time::Duration::from_secs(86400)),
+                        )
+                        .layer(middleware::from_fn(prometheus::record))
+                        .layer(ValidateRequestHeaderLayer::accept("text/event-stream"))
+                        .layer(SetSensitiveRequestHeadersLayer::new(once(AUTHORIZATION))),
+                ),
+        )
         .nest(
             BASE_PATH,
             Router::new()
@@ -287,10 +394,22 @@ async fn build_api(
                     "/channels/{channelId}/tickets/aggregate",
                     post(tickets::aggregate_tickets_in_channel),
                 )
+                .route("/strategy/intents", get(strategy::show_strategy_intents))
+                .route("/strategy/history", get(strategy::show_strategy_history))
+                .route("/strategy/config", get(strategy::show_strategy_config))
+                .route("/strategy/config", put(strategy::reconfigure_strategy))
                 .route("/tickets", get(tickets::show_all_tickets))
                 .route("/tickets/redeem", post(tickets::redeem_all_tickets))
                 .route("/tickets/statistics", get(tickets::show_ticket_statistics))
                 .route("/tickets/statistics", delete(tickets::reset_ticket_statistics))
+                .route("/messages", post(messages::send_message))
+                .route("/messages/pop", post(messages::pop_message))
+                .route("/messages/pop-all", post(messages::pop_all_messages))
+                .route("/messages/size", get(messages::message_inbox_size))
+                .route("/messages/websocket", get(messages::websocket))
+                .route("/tokens", get(api_tokens::list_tokens))
+                .route("/tokens", post(api_tokens::create_token))
+                .route("/tokens/{id}", delete(api_tokens::delete_token))
                 .route("/network/price", get(network::price))
                 .route("/network/probability", get(network::probability))
                 .route("/node/version", get(node::version))
@@ -301,6 +420,7 @@ async fn build_api(
                 .route("/node/graph", get(node::channel_graph))
                 .route("/peers/{destination}/ping", post(peers::ping_peer))
                 .route("/session/websocket", get(session::websocket))
+                .route("/session/stats", get(session::list_session_stats))
                 .route("/session/{protocol}", post(session::create_client))
                 .route("/session/{protocol}", get(session::list_clients))
                 .route("/session/{protocol}/{ip}/{port}", delete(session::close_client))
@@ -310,7 +430,7 @@ async fn build_api(
                     preconditions::authenticate,
                 ))
                 .layer(middleware::from_fn_with_state(
-                    inner_state.clone(),
+                    inner_state.websocket_active_count.clone(),
                     preconditions::cap_websockets,
                 ))
                 .layer(
@@ -318,7 +438,13 @@ async fn build_api(
                         .layer(TraceLayer::new_for_http())
                         .layer(
                             CorsLayer::new()
-                                .allow_methods([Method::GET, Method::POST, Method::OPTIONS, Method::DELETE])
+                                .allow_methods([
+                                    Method::GET,
+                                    Method::POST,
+                                    Method::PUT,
+                                    Method::OPTIONS,
+                                    Method::DELETE,
+                                ])
                                 .allow_origin(Any)
                                 .allow_headers(Any)
                                 .max_age(std::time::Duration::from_secs(86400)),
@@ -367,6 +493,7 @@ enum ApiErrorStatus {
     PeerNotFound,
     ChannelNotFound,
     TicketsNotFound,
+    MessageNotFound,
     NotEnoughBalance,
     NotEnoughAllowance,
     ChannelAlreadyOpen,
@@ -375,6 +502,9 @@ enum ApiErrorStatus {
     Timeout,
     PingError(String),
     Unauthorized,
+    Forbidden,
+    TooManyRequests,
+    TokenNotFound,
     TooManyOpenWebsocketConnections,
     InvalidQuality,
     NotReady,
@@ -0,0 +1,522 @@
+use std::{collections::VecDeque, sync::Arc, time::SystemTime};
+
+use async_lock::Mutex;
+use axum::{
+    extract::{
+        Extension, Json, Query, State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    http::status::StatusCode,
+    response::IntoResponse,
+};
+use futures::{
+    SinkExt, StreamExt,
+    channel::mpsc::{Receiver, Sender, channel},
+};
+use futures_concurrency::stream::Merge;
+use hopr_lib::{
+    Address, ApplicationData, AsUnixTimestamp, DestinationRouting, RESERVED_TAG_UPPER_LIMIT, Tag,
+    errors::{HoprLibError, HoprStatusError},
+};
+use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, serde_as};
+use tracing::{debug, error, info, trace, warn};
+
+use crate::{
+    ApiError, ApiErrorStatus, BASE_PATH, InternalState, preconditions::WebSocketGuard, session::RoutingOptions,
+};
+
+/// Default maximum number of messages kept in the [`MessageInbox`].
+pub const DEFAULT_MESSAGE_INBOX_CAPACITY: usize = 1024;
+
+/// Number of received messages that can be queued for a single websocket client,
+/// before new messages are dropped for that client.
+const WS_MESSAGE_QUEUE_SIZE: usize = 256;
+
+/// Message received from the network.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ReceivedMessage {
+    tag: Tag,
+    body: Box<[u8]>,
+    received_at: SystemTime,
+}
+
+impl ReceivedMessage {
+    fn matches(&self, tag: Option<Tag>) -> bool {
+        tag.is_none_or(|tag| tag == self.tag)
+    }
+}
+
+/// Bounded inbox of messages received from the network.
+///
+/// Received messages are kept in the inbox until they are popped by a client,
+/// when the inbox is full, the oldest message is dropped.
+/// Additionally, each message is delivered to all the websocket clients connected at the time
+/// the message is received, regardless of the inbox.
+#[derive(Debug, Clone)]
+pub struct MessageInbox {
+    capacity: usize,
+    messages: Arc<Mutex<VecDeque<ReceivedMessage>>>,
+    subscribers: Arc<Mutex<Vec<Sender<ReceivedMessage>>>>,
+}
+
+impl Default for MessageInbox {
+    fn default() -> Self {
+        Self::new(DEFAULT_MESSAGE_INBOX_CAPACITY)
+    }
+}
+
+impl MessageInbox {
+    /// Creates an inbox keeping at most `capacity` messages.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            messages: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            subscribers: Default::default(),
+        }
+    }
+
+    /// Stores a message received from the network and delivers it to the connected websocket clients.
+    pub async fn push(&self, data: ApplicationData) {
+        let message = ReceivedMessage {
+            tag: data.application_tag,
+            body: data.plain_text,
+            received_at: SystemTime::now(),
+        };
+
+        self.subscribers
+            .lock()
+            .await
+            .retain_mut(|subscriber| match subscriber.try_send(message.clone()) {
+                Ok(_) => true,
+                Err(e) if e.is_full() => {
+                    warn!(
+                        tag = message.tag,
+                        "websocket client is too slow, dropping received message"
+                    );
+                    true
+                }
+                Err(_) => false,
+            });
+
+        if self.capacity > 0 {
+            let mut messages = self.messages.lock().await;
+            if messages.len() >= self.capacity {
+                messages.pop_front();
+                trace!("message inbox is full, dropped the oldest message");
+            }
+            messages.push_back(message);
+        }
+    }
+
+    async fn pop(&self, tag: Option<Tag>) -> Option<ReceivedMessage> {
+        let mut messages = self.messages.lock().await;
+        let position = messages.iter().position(|m| m.matches(tag))?;
+        messages.remove(position)
+    }
+
+    async fn pop_all(&self, tag: Option<Tag>) -> Vec<ReceivedMessage> {
+        let mut messages = self.messages.lock().await;
+        let (popped, kept) = messages.drain(..).partition(|m| m.matches(tag));
+        *messages = kept;
+        popped.into()
+    }
+
+    async fn size(&self, tag: Option<Tag>) -> usize {
+        self.messages.lock().await.iter().filter(|m| m.matches(tag)).count()
+    }
+
+    async fn subscribe(&self) -> Receiver<ReceivedMessage> {
+        let (tx, rx) = channel(WS_MESSAGE_QUEUE_SIZE);
+        self.subscribers.lock().await.push(tx);
+        rx
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[schema(example = json!({
+        "tag": 2000,
+        "body": "Hello HOPR",
+        "destination": "0x1B482420Afa04aeC1Ef0e4a00C18451E84466c75",
+        "forwardPath": { "Hops": 1 }
+    }))]
+#[serde(rename_all = "camelCase")]
+/// Request body for sending a message.
+pub(crate) struct SendMessageBodyRequest {
+    /// Application tag of the message, must not be lower than 1024.
+    #[schema(example = 2000)]
+    tag: Tag,
+    /// Body of the message.
+    #[schema(example = "Hello HOPR")]
+    body: String,
+    /// Address of the recipient.
+    #[serde_as(as = "DisplayFromStr")]
+    #[schema(value_type = String)]
+    destination: Address,
+    /// The path for the message.
+    forward_path: RoutingOptions,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[schema(example = json!({
+        "timestamp": 1690000000000_u64
+    }))]
+#[serde(rename_all = "camelCase")]
+/// Response body for sending a message.
+pub(crate) struct SendMessageResponse {
+    /// Time the message was sent in milliseconds since the UNIX epoch.
+    #[schema(example = 1690000000000_u64)]
+    timestamp: u128,
+}
+
+/// Sends a message to the given destination through the HOPR network.
+///
+/// The message is not acknowledged by the recipient and the recipient cannot reply to it.
+#[utoipa::path(
+        post,
+        path = const_format::formatcp!("{BASE_PATH}/messages"),
+        description = "Sends a message to the given destination through the HOPR network.",
+        request_body(
+            content = SendMessageBodyRequest,
+            description = "Message to send and its routing.",
+            content_type = "application/json"),
+        responses(
+            (status = 202, description = "The message was sent.", body = SendMessageResponse),
+            (status = 400, description = "Invalid tag or message.", body = ApiError),
+            (status = 401, description = "Invalid authorization token.", body = ApiError),
+            (status = 412, description = "The node is not ready.", body = ApiError),
+            (status = 422, description = "Unknown failure", body = ApiError)
+        ),
+        security(
+            ("api_token" = []),
+            ("bearer_token" = [])
+        ),
+        tag = "Messages",
+    )]
+pub(super) async fn send_message(
+    State(state): State<Arc<InternalState>>,
+    Json(args): Json<SendMessageBodyRequest>,
+) -> impl IntoResponse {
+    if args.tag < RESERVED_TAG_UPPER_LIMIT {
+        return (
+            StatusCode::BAD_REQUEST,
+            ApiError {
+                status: ApiErrorStatus::InvalidInput.to_string(),
+                error: Some(format!("tag must not be lower than {RESERVED_TAG_UPPER_LIMIT}")),
+            },
+        )
+            .into_response();
+    }
+
+    let forward_options = match args.forward_path.resolve().await {
+        Ok(options) => options,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    let routing = DestinationRouting::forward_only(args.destination, forward_options);
+
+    let hopr = state.hopr.clone();
+    match hopr
+        .send_message(args.body.into_bytes().into_boxed_slice(), routing, args.tag)
+        .await
+    {
+        Ok(()) => (
+            StatusCode::ACCEPTED,
+            Json(SendMessageResponse {
+                timestamp: SystemTime::now().as_unix_timestamp().as_millis(),
+            }),
+        )
+            .into_response(),
+        Err(HoprLibError::StatusError(HoprStatusError::NotThereYet(..))) => {
+            (StatusCode::PRECONDITION_FAILED, ApiErrorStatus::NotReady).into_response()
+        }
+        Err(e) => (StatusCode::UNPROCESSABLE_ENTITY, ApiErrorStatus::from(e)).into_response(),
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize, utoipa::IntoParams, utoipa::ToSchema)]
+#[into_params(parameter_in = Query)]
+#[serde(default, rename_all = "camelCase")]
+#[schema(example = json!({
+        "tag": 2000
+    }))]
+/// Parameters for selecting received messages.
+pub(crate) struct MessageTagQueryRequest {
+    /// Only select messages with the given tag.
+    #[schema(required = false)]
+    #[serde(default)]
+    tag: Option<Tag>,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[schema(example = json!({
+        "tag": 2000,
+        "body": "Hello HOPR",
+        "receivedAt": 1690000000000_u64
+    }))]
+#[serde(rename_all = "camelCase")]
+/// Message received from the network.
+pub(crate) struct ReceivedMessageResponse {
+    /// Application tag of the message.
+    #[schema(example = 2000)]
+    tag: Tag,
+    /// Body of the message.
+    ///
+    /// Invalid UTF-8 sequences are replaced by the replacement character.
+    #[schema(example = "Hello HOPR")]
+    body: String,
+    /// Time the message was received in milliseconds since the UNIX epoch.
+    #[schema(example = 1690000000000_u64)]
+    received_at: u128,
+}
+
+impl From<ReceivedMessage> for ReceivedMessageResponse {
+    fn from(value: ReceivedMessage) -> Self {
+        Self {
+            tag: value.tag,
+            body: String::from_utf8_lossy(&value.body).into_owned(),
+            received_at: value.received_at.as_unix_timestamp().as_millis(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+/// Messages popped from the inbox.
+pub(crate) struct ReceivedMessagesResponse {
+    messages: Vec<ReceivedMessageResponse>,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[schema(example = json!({
+        "size": 5
+    }))]
+#[serde(rename_all = "camelCase")]
+/// Number of messages in the inbox.
+pub(crate) struct MessageInboxSizeResponse {
+    size: usize,
+}
+
+/// Pops the oldest message from the inbox.
+#[utoipa::path(
+        post,
+        path = const_format::formatcp!("{BASE_PATH}/messages/pop"),
+        description = "Pops the oldest message from the inbox, optionally only the oldest with the given tag.",
+        params(MessageTagQueryRequest),
+        responses(
+            (status = 200, description = "Message popped from the inbox.", body = ReceivedMessageResponse),
+            (status = 401, description = "Invalid authorization token.", body = ApiError),
+            (status = 404, description = "The inbox contains no such message.", body = ApiError),
+        ),
+        security(
+            ("api_token" = []),
+            ("bearer_token" = [])
+        ),
+        tag = "Messages",
+    )]
+pub(super) async fn pop_message(
+    Query(query): Query<MessageTagQueryRequest>,
+    State(state): State<Arc<InternalState>>,
+) -> impl IntoResponse {
+    match state.message_inbox.pop(query.tag).await {
+        Some(message) => (StatusCode::OK, Json(ReceivedMessageResponse::from(message))).into_response(),
+        None => (StatusCode::NOT_FOUND, ApiErrorStatus::MessageNotFound).into_response(),
+    }
+}
+
+/// Pops all the messages from the inbox.
+#[utoipa::path(
+        post,
+        path = const_format::formatcp!("{BASE_PATH}/messages/pop-all"),
+        description = "Pops all the messages from the inbox, optionally only those with the given tag. The messages are ordered from the oldest.",
+        params(MessageTagQueryRequest),
+        responses(
+            (status = 200, description = "Messages popped from the inbox.", body = ReceivedMessagesResponse),
+            (status = 401, description = "Invalid authorization token.", body = ApiError),
+        ),
+        security(
+            ("api_token" = []),
+            ("bearer_token" = [])
+        ),
+        tag = "Messages",
+    )]
+pub(super) async fn pop_all_messages(
+    Query(query): Query<MessageTagQueryRequest>,
+    State(state): State<Arc<InternalState>>,
+) -> impl IntoResponse {
+    let messages = state.message_inbox.pop_all(query.tag).await;
+
+    (
+        StatusCode::OK,
+        Json(ReceivedMessagesResponse {
+            messages: messages.into_iter().map(ReceivedMessageResponse::from).collect(),
+        }),
+    )
+}
+
+/// Returns the number of messages in the inbox.
+#[utoipa::path(
+        get,
+        path = const_format::formatcp!("{BASE_PATH}/messages/size"),
+        description = "Returns the number of messages in the inbox, optionally only those with the given tag.",
+        params(MessageTagQueryRequest),
+        responses(
+            (status = 200, description = "Number of messages in the inbox.", body = MessageInboxSizeResponse),
+            (status = 401, description = "Invalid authorization token.", body = ApiError),
+        ),
+        security(
+            ("api_token" = []),
+            ("bearer_token" = [])
+        ),
+        tag = "Messages",
+    )]
+pub(super) async fn message_inbox_size(
+    Query(query): Query<MessageTagQueryRequest>,
+    State(state): State<Arc<InternalState>>,
+) -> impl IntoResponse {
+    let size = state.message_inbox.size(query.tag).await;
+
+    (StatusCode::OK, Json(MessageInboxSizeResponse { size }))
+}
+
+/// Websocket endpoint streaming the messages received from the network.
+///
+/// Each received message is emitted as a text frame containing the JSON-encoded [`ReceivedMessageResponse`].
+/// The streamed messages are not removed from the inbox.
+///
+/// Authentication (if enabled) is done by cookie `X-Auth-Token`.
+///
+/// Connect to the endpoint by using a WS client. No preview available. Example:
+/// `ws://127.0.0.1:3001/api/v3/messages/websocket?tag=2000`
+#[utoipa::path(
+        get,
+        path = const_format::formatcp!("{BASE_PATH}/messages/websocket"),
+        description = "Websocket endpoint streaming the messages received from the network, optionally only those with the given tag.",
+        params(MessageTagQueryRequest),
+        responses(
+            (status = 101, description = "Switching protocols to a websocket streaming the received messages.", body = ReceivedMessageResponse),
+            (status = 401, description = "Invalid authorization token.", body = ApiError),
+            (status = 429, description = "Too many open websocket connections.", body = ApiError),
+        ),
+        security(
+            ("api_token" = []),
+            ("bearer_token" = [])
+        ),
+        tag = "Messages",
+    )]
+pub(super) async fn websocket(
+    ws: WebSocketUpgrade,
+    Query(query): Query<MessageTagQueryRequest>,
+    State(state): State<Arc<InternalState>>,
+    Extension(ws_guard): Extension<Arc<WebSocketGuard>>,
+) -> impl IntoResponse {
+    let messages = state.message_inbox.subscribe().await;
+
+    ws.on_upgrade(move |socket| async move {
+        websocket_connection(socket, messages, query.tag).await;
+        drop(ws_guard);
+    })
+}
+
+enum WebSocketInput {
+    Network(ReceivedMessage),
+    WsInput(Result<Message, axum::Error>),
+}
+
+#[tracing::instrument(level = "debug", skip(socket, messages))]
+async fn websocket_connection(socket: WebSocket, messages: Receiver<ReceivedMessage>, tag: Option<Tag>) {
+    let (mut sender, receiver) = socket.split();
+
+    let mut queue = (
+        receiver.map(WebSocketInput::WsInput),
+        messages
+            .filter(|m| futures::future::ready(m.matches(tag)))
+            .map(WebSocketInput::Network),
+    )
+        .merge();
+
+    let mut messages_emitted = 0_usize;
+
+    while let Some(v) = queue.next().await {
+        match v {
+            WebSocketInput::Network(message) => {
+                let text = match serde_json::to_string(&ReceivedMessageResponse::from(message)) {
+                    Ok(text) => text,
+                    Err(e) => {
+                        error!(error = %e, "Failed to serialize a received message");
+                        continue;
+                    }
+                };
+                if let Err(e) = sender.send(Message::Text(text.into())).await {
+                    error!(error = %e, "Failed to emit a received message onto the websocket, closing connection");
+                    break;
+                }
+                messages_emitted += 1;
+            }
+            WebSocketInput::WsInput(ws_in) => match ws_in {
+                Ok(Message::Close(_)) => {
+                    debug!("Received close frame, closing connection");
+                    break;
+                }
+                Ok(m) => trace!(message = ?m, "skipping an unsupported websocket message"),
+                Err(e) => {
+                    error!(error = %e, "Failed to get a valid websocket message, closing connection");
+                    break;
+                }
+            },
+        }
+    }
+
+    info!(messages_emitted, "WS message stream ended");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn message_inbox_should_drop_the_oldest_messages_when_full() {
+        let inbox = MessageInbox::new(3);
+
+        for i in 0..5_u8 {
+            inbox.push(ApplicationData::new(1024 + (i % 2) as Tag, &[i])).await;
+        }
+
+        assert_eq!(3, inbox.size(None).await);
+        assert_eq!(2, inbox.size(Some(1024)).await);
+
+        let popped = inbox.pop(Some(1025)).await.expect("must pop a message");
+        assert_eq!([3_u8], popped.body.as_ref());
+
+        let all = inbox.pop_all(None).await;
+        assert_eq!(
+            vec![vec![2_u8], vec![4_u8]],
+            all.iter().map(|m| m.body.to_vec()).collect::<Vec<_>>()
+        );
+        assert_eq!(0, inbox.size(None).await);
+    }
+
+    #[tokio::test]
+    async fn message_inbox_should_deliver_messages_to_subscribers() {
+        let inbox = MessageInbox::new(0);
+        let mut subscriber = inbox.subscribe().await;
+
+        inbox.push(ApplicationData::new(2000, b"hello")).await;
+
+        let message = subscriber.next().await.expect("must receive a message");
+        assert_eq!(2000, message.tag);
+        assert_eq!(b"hello", message.body.as_ref());
+        assert_eq!(
+            0,
+            inbox.size(None).await,
+            "inbox with zero capacity must not keep messages"
+        );
+
+        drop(subscriber);
+        inbox.push(ApplicationData::new(2000, b"hello")).await;
+        assert!(
+            inbox.subscribers.lock().await.is_empty(),
+            "closed subscribers must be removed"
+        );
+    }
+}
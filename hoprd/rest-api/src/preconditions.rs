@@ -1,4 +1,10 @@
-use std::{str::FromStr, sync::atomic::Ordering::Relaxed};
+use std::{
+    str::FromStr,
+    sync::{
+        Arc,
+        atomic::{AtomicU16, Ordering::Relaxed},
+    },
+};
 
 use axum::{
     extract::{OriginalUri, Request, State},
@@ -12,19 +18,41 @@ use axum::{
 };
 use urlencoding::decode;
 
-use crate::{ApiErrorStatus, Auth, BASE_PATH, InternalState};
+use crate::{
+    ApiErrorStatus, Auth, BASE_PATH, InternalState,
+    api_tokens::{ApiTokenRejection, required_scope},
+};
 
 fn is_a_websocket_uri(uri: &OriginalUri) -> bool {
     const SESSION_PATH: &str = const_format::formatcp!("{BASE_PATH}/session/websocket");
+    const MESSAGES_PATH: &str = const_format::formatcp!("{BASE_PATH}/messages/websocket");
+
+    uri.path().starts_with(SESSION_PATH) || uri.path().starts_with(MESSAGES_PATH)
+}
+
+fn is_an_event_stream_uri(uri: &OriginalUri) -> bool {
+    const EVENTS_PATH: &str = const_format::formatcp!("{BASE_PATH}/events");
+
+    uri.path().starts_with(EVENTS_PATH)
+}
+
+/// Occupies a slot of the concurrent websocket connection cap until dropped.
+///
+/// The guard is passed to the websocket handlers as a request extension and must be moved into
+/// the `on_upgrade` closure, so that the slot is held for the lifetime of the connection.
+pub(crate) struct WebSocketGuard(Arc<AtomicU16>);
 
-    uri.path().starts_with(SESSION_PATH)
+impl Drop for WebSocketGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Relaxed);
+    }
 }
 
 pub(crate) async fn cap_websockets(
-    State(state): State<InternalState>,
+    State(ws_count): State<Arc<AtomicU16>>,
     uri: OriginalUri,
     _headers: HeaderMap,
-    request: Request,
+    mut request: Request,
     next: Next,
 ) -> impl IntoResponse {
     let max_websocket_count = std::env::var("HOPR_INTERNAL_REST_API_MAX_CONCURRENT_WEBSOCKET_COUNT")
@@ -32,9 +60,7 @@ pub(crate) async fn cap_websockets(
         .unwrap_or(10);
 
     if is_a_websocket_uri(&uri) {
-        let ws_count = state.websocket_active_count;
-
-        if ws_count.fetch_add(1, Relaxed) > max_websocket_count {
+        if ws_count.fetch_add(1, Relaxed) >= max_websocket_count {
             ws_count.fetch_sub(1, Relaxed);
 
             return (
@@ -43,12 +69,52 @@ pub(crate) async fn cap_websockets(
             )
                 .into_response();
         }
+
+        request.extensions_mut().insert(Arc::new(WebSocketGuard(ws_count)));
     }
 
     // Go forward to the next middleware or request handler
     next.run(request).await
 }
 
+/// Collects all the API tokens passed with the request.
+///
+/// Tokens are accepted from the "Authorization: Bearer <token>" and "X-Auth-Token: <token>" headers, and for the
+/// websocket and event stream endpoints also from the `apiToken` query parameter.
+fn provided_tokens(uri: &OriginalUri, headers: &HeaderMap) -> Vec<String> {
+    let x_auth_header = HeaderName::from_str("x-auth-token").expect("Invalid header name: x-auth-token");
+
+    let mut tokens = headers
+        .iter()
+        .filter_map(|(n, v)| {
+            let v = v.to_str().ok()?;
+            if AUTHORIZATION.eq(n) {
+                v.strip_prefix("Bearer ").map(String::from)
+            } else if x_auth_header.eq(n) {
+                Some(v.to_owned())
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // The following would never be needed, if the JavaScript browser was able to properly
+    // pass the x-auth-token or Bearer headers.
+    if is_a_websocket_uri(uri) || is_an_event_stream_uri(uri) {
+        if let Some(query) = uri.query().filter(|q| q.len() <= 2048) {
+            tokens.extend(
+                query
+                    .split('&')
+                    .filter_map(|pair| pair.split_once('='))
+                    .filter(|(key, _)| *key == "apiToken")
+                    .filter_map(|(_, value)| decode(value).ok().map(|v| v.into_owned())),
+            );
+        }
+    }
+
+    tokens
+}
+
 pub(crate) async fn authenticate(
     State(state): State<InternalState>,
     uri: OriginalUri,
@@ -56,54 +122,102 @@ pub(crate) async fn authenticate(
     request: Request,
     next: Next,
 ) -> impl IntoResponse {
-    let auth = state.auth.clone();
+    let Auth::Token(expected_token) = state.auth.as_ref() else {
+        return next.run(request).await;
+    };
 
-    let x_auth_header = HeaderName::from_str("x-auth-token").expect("Invalid header name: x-auth-token");
-    let websocket_proto_header =
-        HeaderName::from_str("Sec-Websocket-Protocol").expect("Invalid header name: Sec-Websocket-Protocol");
-
-    let is_authorized = match auth.as_ref() {
-        Auth::Token(expected_token) => {
-            let auth_headers = headers
-                .iter()
-                .filter_map(|(n, v)| {
-                    (AUTHORIZATION.eq(n) || x_auth_header.eq(n) || websocket_proto_header.eq(n))
-                        .then_some((n, v.to_str().expect("Invalid header value")))
-                })
-                .collect::<Vec<_>>();
-
-            let is_ws_auth = if is_a_websocket_uri(&uri) {
-                uri.query()
-                    .map(|q| {
-                        // Reasonable limit for query string
-                        if q.len() > 2048 {
-                            return false;
-                        }
-                        match decode(q) {
-                            Ok(decoded) => decoded.into_owned().contains(&format!("apiToken={}", expected_token)),
-                            Err(_) => false,
-                        }
-                    })
-                    .unwrap_or(false)
-            } else {
-                false
-            };
-            // Use "Authorization Bearer <token>" and "X-Auth-Token <token>" headers and "Sec-Websocket-Protocol"
-            (!auth_headers.is_empty()
-                    && (auth_headers.contains(&(&AUTHORIZATION, &format!("Bearer {}", expected_token)))
-                        || auth_headers.contains(&(&x_auth_header, expected_token)))
-                )
-                // The following line would never be needed, if the JavaScript browser was able to properly
-                // pass the x-auth-token or Bearer headers.
-                || is_ws_auth
+    let tokens = provided_tokens(&uri, &headers);
+
+    // The admin token has access to all the endpoints
+    if !tokens.iter().any(|token| token == expected_token) {
+        let required = required_scope(request.method(), uri.path());
+
+        let mut rejection = ApiTokenRejection::Unauthorized;
+        for token in &tokens {
+            match state.api_tokens.authorize(token, required).await {
+                Ok(()) => return next.run(request).await,
+                Err(ApiTokenRejection::Unauthorized) => {}
+                Err(e) => rejection = e,
+            }
         }
-        Auth::None => true,
-    };
 
-    if !is_authorized {
-        return (StatusCode::UNAUTHORIZED, ApiErrorStatus::Unauthorized).into_response();
+        return match rejection {
+            ApiTokenRejection::Unauthorized => (StatusCode::UNAUTHORIZED, ApiErrorStatus::Unauthorized).into_response(),
+            ApiTokenRejection::Forbidden => (StatusCode::FORBIDDEN, ApiErrorStatus::Forbidden).into_response(),
+            ApiTokenRejection::RateLimited => {
+                (StatusCode::TOO_MANY_REQUESTS, ApiErrorStatus::TooManyRequests).into_response()
+            }
+        };
     }
 
     // Go forward to the next middleware or request handler
     next.run(request).await
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use axum::{Extension, Router, body::Body, middleware, routing::get};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    const DEFAULT_MAX_WEBSOCKET_COUNT: usize = 10;
+
+    type OpenWebSockets = Arc<Mutex<Vec<Arc<WebSocketGuard>>>>;
+
+    /// Router whose websocket handler keeps the guard in `open` to simulate a connection that is still open.
+    fn capped_router(ws_count: Arc<AtomicU16>, open: OpenWebSockets) -> Router {
+        Router::new()
+            .route(
+                const_format::formatcp!("{BASE_PATH}/messages/websocket"),
+                get(move |Extension(guard): Extension<Arc<WebSocketGuard>>| async move {
+                    open.lock().expect("lock must not be poisoned").push(guard);
+                }),
+            )
+            .layer(middleware::from_fn_with_state(ws_count, cap_websockets))
+    }
+
+    async fn connect(router: &Router) -> anyhow::Result<StatusCode> {
+        let request =
+            axum::http::Request::get(const_format::formatcp!("{BASE_PATH}/messages/websocket")).body(Body::empty())?;
+
+        Ok(router.clone().oneshot(request).await?.status())
+    }
+
+    #[tokio::test]
+    async fn cap_websockets_should_release_the_slot_when_the_connection_closes() -> anyhow::Result<()> {
+        let ws_count = Arc::new(AtomicU16::new(0));
+        let open = OpenWebSockets::default();
+        let router = capped_router(ws_count.clone(), open.clone());
+
+        for _ in 0..3 * DEFAULT_MAX_WEBSOCKET_COUNT {
+            assert_eq!(StatusCode::OK, connect(&router).await?);
+            assert_eq!(1, ws_count.load(Relaxed));
+
+            open.lock().expect("lock must not be poisoned").clear();
+            assert_eq!(0, ws_count.load(Relaxed));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn cap_websockets_should_reject_connections_above_the_limit() -> anyhow::Result<()> {
+        let ws_count = Arc::new(AtomicU16::new(0));
+        let open = OpenWebSockets::default();
+        let router = capped_router(ws_count.clone(), open.clone());
+
+        for _ in 0..DEFAULT_MAX_WEBSOCKET_COUNT {
+            assert_eq!(StatusCode::OK, connect(&router).await?);
+        }
+        assert_eq!(StatusCode::TOO_MANY_REQUESTS, connect(&router).await?);
+        assert_eq!(DEFAULT_MAX_WEBSOCKET_COUNT as u16, ws_count.load(Relaxed));
+
+        open.lock().expect("lock must not be poisoned").pop();
+        assert_eq!(StatusCode::OK, connect(&router).await?);
+
+        Ok(())
+    }
+}
@@ -1,9 +1,10 @@
-use std::{fmt::Formatter, future::Future, net::IpAddr, str::FromStr, sync::Arc};
+use std::{collections::HashSet, fmt::Formatter, future::Future, net::IpAddr, str::FromStr, sync::Arc};
 
+use async_lock::RwLock;
 use axum::{
     Error,
     extract::{
-        Json, Path, State,
+        Extension, Json, Path, State,
         ws::{Message, WebSocket, WebSocketUpgrade},
     },
     http::status::StatusCode,
@@ -11,11 +12,12 @@ use axum::{
 };
 use axum_extra::extract::Query;
 use base64::Engine;
+use either::Either;
 use futures::{AsyncReadExt, AsyncWriteExt, SinkExt, StreamExt, TryStreamExt};
 use futures_concurrency::stream::Merge;
 use hopr_lib::{
-    Address, HoprSession, SESSION_PAYLOAD_SIZE, ServiceId, SessionClientConfig, SessionTarget, SurbBalancerConfig,
-    errors::HoprLibError, transfer_session,
+    Address, HoprSession, HoprSessionId, SESSION_PAYLOAD_SIZE, ServiceId, SessionClientConfig, SessionStatsSnapshot,
+    SessionTarget, SurbBalancerConfig, errors::HoprLibError, transfer_session,
 };
 use hopr_network_types::{
     prelude::{ConnectedUdpStream, IpOrHost, SealedHost, UdpStreamParallelism},
@@ -25,9 +27,14 @@ use hopr_network_types::{
 use serde::{Deserialize, Serialize};
 use serde_with::{DisplayFromStr, serde_as};
 use tokio::net::TcpListener;
-use tracing::{debug, error, info, trace};
+use tracing::{debug, error, info, trace, warn};
 
-use crate::{ApiError, ApiErrorStatus, BASE_PATH, InternalState, ListenerId};
+use crate::{
+    ApiError, ApiErrorStatus, BASE_PATH, InternalState, ListenerId,
+    preconditions::WebSocketGuard,
+    session_listeners::SessionListenerConfig,
+    session_proxy::{ProxyListener, ProxyProtocol},
+};
 
 /// Size of the buffer for forwarding data to/from a TCP stream.
 pub const HOPR_TCP_BUFFER_SIZE: usize = 4096;
@@ -38,6 +45,9 @@ pub const HOPR_UDP_BUFFER_SIZE: usize = 16384;
 /// Size of the queue (back-pressure) for data incoming from a UDP stream.
 pub const HOPR_UDP_QUEUE_SIZE: usize = 8192;
 
+/// Delay between attempts to open the Session of a lazily established UDP listener.
+const UDP_SESSION_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(5);
+
 #[cfg(all(feature = "prometheus", not(test)))]
 lazy_static::lazy_static! {
     static ref METRIC_ACTIVE_CLIENTS: hopr_metrics::MultiGauge = hopr_metrics::MultiGauge::new(
@@ -47,18 +57,23 @@ lazy_static::lazy_static! {
     ).unwrap();
 }
 #[serde_as]
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
 #[schema(
     example = json!({"Plain": "example.com:80"}),
     example = json!({"Sealed": "SGVsbG9Xb3JsZA"}), // base64 for "HelloWorld"
-    example = json!({"Service": 0})
+    example = json!({"Service": 0}),
+    example = json!({"Proxy": "Socks5"})
 )]
 /// Session target specification.
+///
+/// The `Proxy` target is valid only for TCP listeners, whose clients then request
+/// their targets using the given proxy protocol.
 pub enum SessionTargetSpec {
     Plain(String),
     Sealed(#[serde_as(as = "serde_with::base64::Base64")] Vec<u8>),
     #[schema(value_type = u32)]
     Service(ServiceId),
+    Proxy(ProxyProtocol),
 }
 
 impl std::fmt::Display for SessionTargetSpec {
@@ -67,6 +82,7 @@ impl std::fmt::Display for SessionTargetSpec {
             SessionTargetSpec::Plain(t) => write!(f, "{t}"),
             SessionTargetSpec::Sealed(t) => write!(f, "$${}", base64::prelude::BASE64_URL_SAFE.encode(t)),
             SessionTargetSpec::Service(t) => write!(f, "#{t}"),
+            SessionTargetSpec::Proxy(t) => write!(f, "proxy:{t}"),
         }
     }
 }
@@ -87,6 +103,8 @@ impl FromStr for SessionTargetSpec {
                     .parse()
                     .map_err(|_| HoprLibError::GeneralError("cannot parse service id".into()))?,
             )
+        } else if let Some(proxy) = s.strip_prefix("proxy:").and_then(|p| ProxyProtocol::from_str(p).ok()) {
+            Self::Proxy(proxy)
         } else {
             Self::Plain(s.to_owned())
         })
@@ -113,6 +131,11 @@ impl SessionTargetSpec {
                 SessionTarget::UdpStream(SealedHost::Sealed(enc.into_boxed_slice()))
             }
             (_, SessionTargetSpec::Service(id)) => SessionTarget::ExitNode(id),
+            (_, SessionTargetSpec::Proxy(proxy)) => {
+                return Err(HoprLibError::GeneralError(format!(
+                    "{proxy} proxy target is valid only for session listeners"
+                )));
+            }
         })
     }
 }
@@ -128,6 +151,8 @@ pub struct StoredSessionEntry {
     pub forward_path: RoutingOptions,
     /// Return path used for the Session.
     pub return_path: RoutingOptions,
+    /// IDs of the Sessions currently open on this listener.
+    pub sessions: Arc<RwLock<HashSet<HoprSessionId>>>,
     /// The join handle for the Session processing.
     pub jh: hopr_async_runtime::prelude::JoinHandle<()>,
 }
@@ -142,6 +167,7 @@ pub struct StoredSessionEntry {
     Serialize,
     Deserialize,
     PartialEq,
+    Eq,
     utoipa::ToSchema,
 )]
 #[schema(example = "Segmentation")]
@@ -155,6 +181,8 @@ pub enum SessionCapability {
     RetransmissionAckOnly,
     /// Disable packet buffering
     NoDelay,
+    /// Stripe the data across multiple forward paths that do not share any relays
+    Multipath,
 }
 
 impl From<SessionCapability> for hopr_lib::SessionCapability {
@@ -164,6 +192,7 @@ impl From<SessionCapability> for hopr_lib::SessionCapability {
             SessionCapability::Retransmission => hopr_lib::SessionCapability::Retransmission,
             SessionCapability::RetransmissionAckOnly => hopr_lib::SessionCapability::RetransmissionAckOnly,
             SessionCapability::NoDelay => hopr_lib::SessionCapability::NoDelay,
+            SessionCapability::Multipath => hopr_lib::SessionCapability::Multipath,
         }
     }
 }
@@ -266,6 +295,7 @@ pub(crate) async fn websocket(
     ws: WebSocketUpgrade,
     Query(query): Query<SessionWebsocketClientQueryRequest>,
     State(state): State<Arc<InternalState>>,
+    Extension(ws_guard): Extension<Arc<WebSocketGuard>>,
 ) -> Result<impl IntoResponse, impl IntoResponse> {
     let (dst, target, data) = query
         .into_protocol_session_config()
@@ -281,7 +311,10 @@ pub(crate) async fn websocket(
         )
     })?;
 
-    Ok::<_, (StatusCode, ApiErrorStatus)>(ws.on_upgrade(move |socket| websocket_connection(socket, session)))
+    Ok::<_, (StatusCode, ApiErrorStatus)>(ws.on_upgrade(move |socket| async move {
+        websocket_connection(socket, session).await;
+        drop(ws_guard);
+    }))
 }
 
 enum WebSocketInput {
@@ -380,7 +413,37 @@ impl RoutingOptions {
 }
 
 #[serde_as]
-#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[schema(example = json!({
+        "excludedRelays": ["0x5112D584a1C72Fc250176B57aEba5fFbbB287D8F"],
+        "disjointPaths": true,
+        "maxRelaysPerSafe": 1
+    }))]
+#[serde(default, rename_all = "camelCase")]
+/// Additional constraints on the relays used by the Session paths.
+pub struct SessionPathConstraints {
+    /// Nodes that must not be used as relays on either the forward or return path.
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    #[schema(value_type = Vec<String>)]
+    pub excluded_relays: Vec<Address>,
+    /// If set, the return path must not share any relays with the forward path.
+    pub disjoint_paths: bool,
+    /// Maximum number of relays on a single path that can be registered with the same Safe.
+    pub max_relays_per_safe: Option<usize>,
+}
+
+impl From<SessionPathConstraints> for hopr_lib::PathConstraints {
+    fn from(value: SessionPathConstraints) -> Self {
+        Self {
+            excluded_relays: value.excluded_relays.into_iter().collect(),
+            disjoint_paths: value.disjoint_paths,
+            max_relays_per_safe: value.max_relays_per_safe,
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
 #[schema(example = json!({
         "destination": "0x1B482420Afa04aeC1Ef0e4a00C18451E84466c75",
         "forwardPath": { "Hops": 1 },
@@ -392,7 +455,7 @@ impl RoutingOptions {
     }))]
 #[serde(rename_all = "camelCase")]
 /// Request body for creating a new client session.
-pub(crate) struct SessionClientRequest {
+pub struct SessionClientRequest {
     /// Address of the Exit node.
     #[serde_as(as = "DisplayFromStr")]
     #[schema(value_type = String)]
@@ -407,11 +470,13 @@ pub(crate) struct SessionClientRequest {
     ///
     /// Supports also partial specification (only `ip` or only `:port`) with the
     /// respective part replaced by the node's configured default.
+    #[serde(default)]
     pub listen_host: Option<String>,
     #[serde_as(as = "Option<Vec<DisplayFromStr>>")]
     /// Capabilities for the Session protocol.
     ///
     /// Defaults to `Segmentation` and `Retransmission` for TCP and nothing for UDP.
+    #[serde(default)]
     pub capabilities: Option<Vec<SessionCapability>>,
     /// The amount of response data the Session counterparty can deliver back to us,
     /// without us sending any SURBs to them.
@@ -423,21 +488,33 @@ pub(crate) struct SessionClientRequest {
     /// All syntaxes like "2 MB", "128 kiB", "3MiB" are supported. The value must be
     /// at least the size of 2 Session packet payloads.
     #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
     #[schema(value_type = String)]
     pub response_buffer: Option<bytesize::ByteSize>,
+    /// Additional constraints on the relays of the forward and return paths.
+    ///
+    /// No constraints are applied if not given.
+    #[serde(default)]
+    pub path_constraints: Option<SessionPathConstraints>,
 }
 
 impl SessionClientRequest {
+    /// Returns the destination, the Session configuration and either the Session target or the proxy protocol,
+    /// if each client of the listener requests its own target.
     pub(crate) async fn into_protocol_session_config(
         self,
         target_protocol: IpProtocol,
-    ) -> Result<(Address, SessionTarget, SessionClientConfig), ApiErrorStatus> {
+    ) -> Result<(Address, Either<SessionTarget, ProxyProtocol>, SessionClientConfig), ApiErrorStatus> {
         Ok((
             self.destination,
-            self.target.into_target(target_protocol)?,
+            match self.target {
+                SessionTargetSpec::Proxy(proxy) => Either::Right(proxy),
+                target => Either::Left(target.into_target(target_protocol)?),
+            },
             SessionClientConfig {
                 forward_path_options: self.forward_path.resolve().await?,
                 return_path_options: self.return_path.resolve().await?,
+                path_constraints: self.path_constraints.map(Into::into).unwrap_or_default(),
                 capabilities: self
                     .capabilities
                     .map(|vs| {
@@ -514,6 +591,76 @@ pub(crate) struct SessionClientResponse {
     pub port: u16,
     /// MTU used by the Session.
     pub mtu: usize,
+    /// Statistics of the Sessions currently open on the listener.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sessions: Vec<SessionStatsResponse>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[schema(example = json!({
+        "sessionId": "0x3a8f1e2c5b7d9e0f1a2b:1234",
+        "age": 60000,
+        "idle": 1200,
+        "bytesSent": 524288,
+        "bytesReceived": 8388608,
+        "framesSent": 540,
+        "framesReceived": 8650,
+        "framesSkipped": 2,
+        "segmentsRetransmitted": 14,
+        "retransmissionRequests": 6,
+        "rtt": 850,
+        "surbBufferEstimate": 4870,
+        "surbsConsumed": 9120
+    }))]
+#[serde(rename_all = "camelCase")]
+/// Statistics of a single Session.
+pub(crate) struct SessionStatsResponse {
+    /// ID of the Session.
+    pub session_id: String,
+    /// Time since the Session has been opened, in milliseconds.
+    pub age: u128,
+    /// Time since any data has been sent or received over the Session, in milliseconds.
+    pub idle: u128,
+    /// Number of bytes sent over the Session.
+    pub bytes_sent: u64,
+    /// Number of bytes received over the Session.
+    pub bytes_received: u64,
+    /// Number of Session protocol frames sent.
+    pub frames_sent: u64,
+    /// Number of Session protocol frames received.
+    pub frames_received: u64,
+    /// Number of incoming frames that were incomplete and had to be discarded or evicted.
+    pub frames_skipped: u64,
+    /// Number of segments retransmitted to the counterparty.
+    pub segments_retransmitted: u64,
+    /// Number of requests for missing segments sent to the counterparty.
+    pub retransmission_requests: u64,
+    /// Smoothed round-trip time estimate in milliseconds, based on frame acknowledgements.
+    pub rtt: Option<u128>,
+    /// Estimated number of SURBs available to the counterparty, if SURB balancing is enabled.
+    pub surb_buffer_estimate: Option<u64>,
+    /// Estimated number of SURBs consumed by the counterparty, if SURB balancing is enabled.
+    pub surbs_consumed: Option<u64>,
+}
+
+impl From<SessionStatsSnapshot> for SessionStatsResponse {
+    fn from(value: SessionStatsSnapshot) -> Self {
+        Self {
+            session_id: value.session_id.to_string(),
+            age: value.age.as_millis(),
+            idle: value.idle.as_millis(),
+            bytes_sent: value.bytes_sent,
+            bytes_received: value.bytes_received,
+            frames_sent: value.frames_sent,
+            frames_received: value.frames_received,
+            frames_skipped: value.frames_skipped,
+            segments_retransmitted: value.segments_retransmitted,
+            retransmission_requests: value.retransmission_requests,
+            rtt: value.rtt.map(|rtt| rtt.as_millis()),
+            surb_buffer_estimate: value.surb_buffer_estimate,
+            surbs_consumed: value.surbs_consumed,
+        }
+    }
 }
 
 /// This function first tries to parse `requested` as the `ip:port` host pair.
@@ -560,6 +707,9 @@ fn build_binding_host(requested: Option<&str>, default: std::net::SocketAddr) ->
 /// - On the contrary, services running over TCP *almost always* expect data segmentation and
 /// retransmission capabilities, so these should be configured while creating a session that passes
 /// TCP data.
+///
+/// With the `Proxy` target, the TCP listener acts as a SOCKS5 or HTTP `CONNECT` proxy, and each
+/// client connection opens a new Session to the target requested by the client.
 #[utoipa::path(
         post,
         path = const_format::formatcp!("{BASE_PATH}/session/{{protocol}}"),
@@ -589,6 +739,37 @@ pub(crate) async fn create_client(
     Path(protocol): Path<IpProtocol>,
     Json(args): Json<SessionClientRequest>,
 ) -> Result<impl IntoResponse, impl IntoResponse> {
+    let response = create_listener(&state, protocol, args.clone(), false).await?;
+
+    // Persist the listener with its bound host, so that it is re-bound to the same port after a restart
+    let listener = SessionListenerConfig {
+        protocol,
+        session: SessionClientRequest {
+            listen_host: response
+                .ip
+                .parse()
+                .map(|ip| std::net::SocketAddr::new(ip, response.port).to_string())
+                .ok(),
+            ..args
+        },
+    };
+    if let Err(error) = state.session_listeners.insert(listener).await {
+        warn!(%error, "failed to persist the session listener");
+    }
+
+    Ok::<_, (StatusCode, ApiErrorStatus)>((StatusCode::OK, Json(response)).into_response())
+}
+
+/// Binds a new Session listener according to the request and registers it among the open listeners.
+///
+/// If `lazy_udp_session` is set, the Session of a UDP listener is not opened right away, but once
+/// the first datagram arrives at the socket.
+pub(crate) async fn create_listener(
+    state: &InternalState,
+    protocol: IpProtocol,
+    args: SessionClientRequest,
+    lazy_udp_session: bool,
+) -> Result<SessionClientResponse, (StatusCode, ApiErrorStatus)> {
     let bind_host: std::net::SocketAddr = build_binding_host(args.listen_host.as_deref(), state.default_listen_host);
 
     if bind_host.port() > 0
@@ -629,6 +810,19 @@ pub(crate) async fn create_client(
             // For each new TCP connection coming to the listener,
             // open a Session with the same parameters
             let hopr = state.hopr.clone();
+            let sessions = Arc::new(RwLock::new(HashSet::new()));
+            let sessions_clone = sessions.clone();
+
+            // With a proxy, the Session target is requested by each client
+            let target = match target {
+                Either::Left(target) => Either::Left(target),
+                Either::Right(proxy) => Either::Right(
+                    ProxyListener::new(hopr.clone(), proxy, dst, data.clone(), sessions.clone())
+                        .await
+                        .map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e))?,
+                ),
+            };
+
             let jh = hopr_async_runtime::prelude::spawn(
                 tokio_stream::wrappers::TcpListenerStream::new(tcp_listener)
                     .and_then(|sock| async { Ok((sock.peer_addr()?, sock)) })
@@ -636,10 +830,27 @@ pub(crate) async fn create_client(
                         let data = data.clone();
                         let target = target.clone();
                         let hopr = hopr.clone();
+                        let sessions = sessions_clone.clone();
                         async move {
                             match accepted_client {
                                 Ok((sock_addr, stream)) => {
                                     debug!(socket = ?sock_addr, "incoming TCP connection");
+                                    let target = match target {
+                                        Either::Left(target) => target,
+                                        Either::Right(proxy) => {
+                                            #[cfg(all(feature = "prometheus", not(test)))]
+                                            METRIC_ACTIVE_CLIENTS.increment(&[&proxy.protocol().to_string()], 1.0);
+
+                                            if let Err(error) = proxy.serve(stream).await {
+                                                error!(socket = ?sock_addr, protocol = %proxy.protocol(), %error, "proxy client failed");
+                                            }
+
+                                            #[cfg(all(feature = "prometheus", not(test)))]
+                                            METRIC_ACTIVE_CLIENTS.decrement(&[&proxy.protocol().to_string()], 1.0);
+                                            return;
+                                        }
+                                    };
+
                                     let session = match hopr.connect_to(dst, target, data).await {
                                         Ok(s) => s,
                                         Err(error) => {
@@ -657,8 +868,13 @@ pub(crate) async fn create_client(
                                     #[cfg(all(feature = "prometheus", not(test)))]
                                     METRIC_ACTIVE_CLIENTS.increment(&["tcp"], 1.0);
 
+                                    let session_id = *session.id();
+                                    sessions.write().await.insert(session_id);
+
                                     bind_session_to_stream(session, stream, HOPR_TCP_BUFFER_SIZE).await;
 
+                                    sessions.write().await.remove(&session_id);
+
                                     #[cfg(all(feature = "prometheus", not(test)))]
                                     METRIC_ACTIVE_CLIENTS.decrement(&["tcp"], 1.0);
                                 }
@@ -675,14 +891,22 @@ pub(crate) async fn create_client(
                     target: target_spec.clone(),
                     forward_path: args.forward_path.clone(),
                     return_path: args.return_path.clone(),
+                    sessions,
                     jh,
                 },
             );
             bound_host
         }
         IpProtocol::UDP => {
+            let Either::Left(target) = target else {
+                return Err((
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    ApiErrorStatus::UnknownFailure("proxy targets are supported only by TCP listeners".into()),
+                ));
+            };
+
             // Bind the UDP socket first
-            let (bound_host, udp_socket) = udp_bind_to(bind_host).await.map_err(|e| {
+            let (bound_host, mut udp_socket) = udp_bind_to(bind_host).await.map_err(|e| {
                 if e.kind() == std::io::ErrorKind::AddrInUse {
                     (StatusCode::CONFLICT, ApiErrorStatus::ListenHostAlreadyUsed)
                 } else {
@@ -696,17 +920,25 @@ pub(crate) async fn create_client(
             info!(%bound_host, "UDP session listener bound");
 
             let hopr = state.hopr.clone();
+            let sessions = Arc::new(RwLock::new(HashSet::new()));
 
             // Create a single session for the UDP socket
-            let session = hopr.connect_to(dst, target, data).await.map_err(|e| {
-                (
-                    StatusCode::UNPROCESSABLE_ENTITY,
-                    ApiErrorStatus::UnknownFailure(e.to_string()),
-                )
-            })?;
+            let session = if lazy_udp_session {
+                None
+            } else {
+                let session = hopr.connect_to(dst, target.clone(), data.clone()).await.map_err(|e| {
+                    (
+                        StatusCode::UNPROCESSABLE_ENTITY,
+                        ApiErrorStatus::UnknownFailure(e.to_string()),
+                    )
+                })?;
+                sessions.write().await.insert(*session.id());
+                Some(session)
+            };
 
             let open_listeners_clone = state.open_listeners.clone();
             let listener_id = ListenerId(protocol.into(), bound_host);
+            let sessions_clone = sessions.clone();
 
             state.open_listeners.write().await.insert(
                 listener_id,
@@ -715,7 +947,25 @@ pub(crate) async fn create_client(
                     target: target_spec.clone(),
                     forward_path: args.forward_path.clone(),
                     return_path: args.return_path.clone(),
+                    sessions,
                     jh: hopr_async_runtime::prelude::spawn(async move {
+                        let session = match session {
+                            Some(session) => session,
+                            None => {
+                                match open_session_on_udp_traffic(&hopr, &mut udp_socket, dst, target, data).await {
+                                    Ok(session) => {
+                                        sessions_clone.write().await.insert(*session.id());
+                                        session
+                                    }
+                                    Err(error) => {
+                                        error!(%bound_host, %error, "failed to open session for UDP listener");
+                                        open_listeners_clone.write().await.remove(&listener_id);
+                                        return;
+                                    }
+                                }
+                            }
+                        };
+
                         #[cfg(all(feature = "prometheus", not(test)))]
                         METRIC_ACTIVE_CLIENTS.increment(&["udp"], 1.0);
 
@@ -733,29 +983,24 @@ pub(crate) async fn create_client(
         }
     };
 
-    Ok::<_, (StatusCode, ApiErrorStatus)>(
-        (
-            StatusCode::OK,
-            Json(SessionClientResponse {
-                protocol,
-                ip: bound_host.ip().to_string(),
-                port: bound_host.port(),
-                target: target_spec.to_string(),
-                destination: dst,
-                forward_path: args.forward_path.clone(),
-                return_path: args.return_path.clone(),
-                mtu: SESSION_PAYLOAD_SIZE,
-            }),
-        )
-            .into_response(),
-    )
+    Ok(SessionClientResponse {
+        protocol,
+        ip: bound_host.ip().to_string(),
+        port: bound_host.port(),
+        target: target_spec.to_string(),
+        destination: dst,
+        forward_path: args.forward_path.clone(),
+        return_path: args.return_path.clone(),
+        mtu: SESSION_PAYLOAD_SIZE,
+        sessions: vec![],
+    })
 }
 
 /// Lists existing Session listeners for the given IP protocol.
 #[utoipa::path(
     get,
     path = const_format::formatcp!("{BASE_PATH}/session/{{protocol}}"),
-    description = "Lists existing Session listeners for the given IP protocol.",
+    description = "Lists existing Session listeners for the given IP protocol, including the statistics of their open Sessions.",
     params(
         ("protocol" = String, Path, description = "IP transport protocol", example = "tcp"),
     ),
@@ -769,7 +1014,24 @@ pub(crate) async fn create_client(
                 "protocol": "tcp",
                 "ip": "127.0.0.1",
                 "port": 5542,
-                "mtu": 987
+                "mtu": 987,
+                "sessions": [
+                    {
+                        "sessionId": "0x3a8f1e2c5b7d9e0f1a2b:1234",
+                        "age": 60000,
+                        "idle": 1200,
+                        "bytesSent": 524288,
+                        "bytesReceived": 8388608,
+                        "framesSent": 540,
+                        "framesReceived": 8650,
+                        "framesSkipped": 2,
+                        "segmentsRetransmitted": 14,
+                        "retransmissionRequests": 6,
+                        "rtt": 850,
+                        "surbBufferEstimate": 4870,
+                        "surbsConsumed": 9120
+                    }
+                ]
             }
         ])),
         (status = 400, description = "Invalid IP protocol.", body = ApiError),
@@ -786,13 +1048,24 @@ pub(crate) async fn list_clients(
     State(state): State<Arc<InternalState>>,
     Path(protocol): Path<IpProtocol>,
 ) -> Result<impl IntoResponse, impl IntoResponse> {
-    let response = state
+    let mut response = Vec::new();
+    for (id, entry) in state
         .open_listeners
         .read()
         .await
         .iter()
         .filter(|(id, _)| id.0 == protocol.into())
-        .map(|(id, entry)| SessionClientResponse {
+    {
+        let sessions = entry
+            .sessions
+            .read()
+            .await
+            .iter()
+            .filter_map(|session_id| state.hopr.session_stats(session_id))
+            .map(SessionStatsResponse::from)
+            .collect();
+
+        response.push(SessionClientResponse {
             protocol,
             ip: id.1.ip().to_string(),
             port: id.1.port(),
@@ -801,12 +1074,39 @@ pub(crate) async fn list_clients(
             return_path: entry.return_path.clone(),
             destination: entry.destination,
             mtu: SESSION_PAYLOAD_SIZE,
-        })
-        .collect::<Vec<_>>();
+            sessions,
+        });
+    }
 
     Ok::<_, (StatusCode, ApiErrorStatus)>((StatusCode::OK, Json(response)).into_response())
 }
 
+/// Lists statistics of all Sessions open on this node.
+#[utoipa::path(
+    get,
+    path = const_format::formatcp!("{BASE_PATH}/session/stats"),
+    description = "Lists statistics of all Sessions open on this node, both those initiated by this node and those initiated by other nodes.",
+    responses(
+        (status = 200, description = "Statistics of the open Sessions.", body = Vec<SessionStatsResponse>),
+        (status = 401, description = "Invalid authorization token.", body = ApiError),
+    ),
+    security(
+        ("api_token" = []),
+        ("bearer_token" = [])
+    ),
+    tag = "Session",
+)]
+pub(crate) async fn list_session_stats(State(state): State<Arc<InternalState>>) -> impl IntoResponse {
+    let response = state
+        .hopr
+        .all_session_stats()
+        .into_iter()
+        .map(SessionStatsResponse::from)
+        .collect::<Vec<_>>();
+
+    (StatusCode::OK, Json(response)).into_response()
+}
+
 #[derive(
     Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, strum::Display, strum::EnumString, utoipa::ToSchema,
 )]
@@ -902,6 +1202,10 @@ pub(crate) async fn close_client(
                 .ok_or((StatusCode::NOT_FOUND, ApiErrorStatus::InvalidInput))?;
 
             hopr_async_runtime::prelude::cancel_join_handle(entry.jh).await;
+
+            if let Err(error) = state.session_listeners.remove(&bound_addr).await {
+                warn!(%error, "failed to remove the persisted session listener");
+            }
         }
     }
 
@@ -998,7 +1302,35 @@ async fn udp_bind_to<A: std::net::ToSocketAddrs>(
     Ok((*udp_socket.bound_address(), udp_socket))
 }
 
-async fn bind_session_to_stream<T>(mut session: HoprSession, mut stream: T, max_buf: usize)
+/// Opens the Session of a UDP listener once the first datagram arrives at the socket.
+///
+/// Opening the Session is retried until it succeeds, the first datagram is then written to the Session.
+async fn open_session_on_udp_traffic(
+    hopr: &hopr_lib::Hopr,
+    udp_socket: &mut ConnectedUdpStream,
+    destination: Address,
+    target: SessionTarget,
+    cfg: SessionClientConfig,
+) -> std::io::Result<HoprSession> {
+    let mut datagram = vec![0u8; HOPR_UDP_BUFFER_SIZE];
+    let len = tokio::io::AsyncReadExt::read(udp_socket, &mut datagram).await?;
+    debug!(len, "first datagram arrived at UDP listener");
+
+    let mut session = loop {
+        match hopr.connect_to(destination, target.clone(), cfg.clone()).await {
+            Ok(session) => break session,
+            Err(error) => {
+                warn!(%error, "failed to open session for UDP listener, retrying");
+                hopr_async_runtime::prelude::sleep(UDP_SESSION_RETRY_DELAY).await;
+            }
+        }
+    };
+
+    session.write_all(&datagram[..len]).await?;
+    Ok(session)
+}
+
+pub(crate) async fn bind_session_to_stream<T>(mut session: HoprSession, mut stream: T, max_buf: usize)
 where
     T: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
 {
@@ -0,0 +1,215 @@
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use async_lock::RwLock;
+use hopr_lib::HoprState;
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+
+use crate::{
+    InternalState, ListenerId,
+    session::{IpProtocol, SessionClientRequest, create_listener},
+};
+
+/// Interval in which the node state is checked, before the Session listeners are restored.
+const RESTORE_READINESS_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Session listener that is re-created whenever the node starts.
+///
+/// The listener is described the same way as by the `POST /session/{protocol}` request body.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionListenerConfig {
+    /// IP protocol of the listening socket.
+    pub protocol: IpProtocol,
+    /// Configuration of the listener and its Sessions.
+    #[serde(flatten)]
+    pub session: SessionClientRequest,
+}
+
+impl SessionListenerConfig {
+    fn is_bound_to(&self, id: &ListenerId) -> bool {
+        hopr_lib::IpProtocol::from(self.protocol) == id.0
+            && self
+                .session
+                .listen_host
+                .as_deref()
+                .and_then(|host| host.parse::<std::net::SocketAddr>().ok())
+                .is_some_and(|host| host == id.1)
+    }
+}
+
+/// Store of the Session listeners created via the REST API.
+///
+/// The listeners are persisted in a JSON file, if a path is given.
+#[derive(Debug, Clone, Default)]
+pub struct SessionListenerStore {
+    path: Option<PathBuf>,
+    listeners: Arc<RwLock<Vec<SessionListenerConfig>>>,
+}
+
+impl SessionListenerStore {
+    /// Loads the listeners from the given file, if it exists.
+    ///
+    /// The file is created once the first listener is created.
+    pub fn load(path: Option<PathBuf>) -> std::io::Result<Self> {
+        let listeners = match &path {
+            Some(path) if path.exists() => serde_json::from_slice(&std::fs::read(path)?)?,
+            _ => Vec::new(),
+        };
+
+        Ok(Self {
+            path,
+            listeners: Arc::new(RwLock::new(listeners)),
+        })
+    }
+
+    async fn persist(&self, listeners: &[SessionListenerConfig]) -> std::io::Result<()> {
+        if let Some(path) = &self.path {
+            let mut tmp_path = path.clone().into_os_string();
+            tmp_path.push(".tmp");
+            tokio::fs::write(&tmp_path, serde_json::to_vec_pretty(listeners)?).await?;
+            tokio::fs::rename(&tmp_path, path).await?;
+        }
+        Ok(())
+    }
+
+    async fn list(&self) -> Vec<SessionListenerConfig> {
+        self.listeners.read().await.clone()
+    }
+
+    /// Stores the listener, if the store is persistent.
+    pub(crate) async fn insert(&self, listener: SessionListenerConfig) -> std::io::Result<()> {
+        if self.path.is_none() {
+            return Ok(());
+        }
+
+        let mut listeners = self.listeners.write().await;
+        listeners.push(listener);
+        if let Err(e) = self.persist(&listeners).await {
+            listeners.pop();
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// Removes the listener bound to the given address.
+    pub(crate) async fn remove(&self, id: &ListenerId) -> std::io::Result<()> {
+        let mut listeners = self.listeners.write().await;
+        let count = listeners.len();
+        listeners.retain(|listener| !listener.is_bound_to(id));
+
+        if listeners.len() != count {
+            self.persist(&listeners).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Re-creates the configured and the persisted Session listeners, once the node is running.
+///
+/// Sessions of the listeners are established lazily, once a client connects to a TCP listener
+/// or sends the first datagram to a UDP listener, so the restoration does not depend on the
+/// reachability of the Session destinations.
+pub(crate) async fn restore_listeners(state: InternalState, configured: Vec<SessionListenerConfig>) {
+    while state.hopr.status() != HoprState::Running {
+        hopr_async_runtime::prelude::sleep(RESTORE_READINESS_CHECK_INTERVAL).await;
+    }
+
+    let persisted = state.session_listeners.list().await;
+    info!(
+        configured = configured.len(),
+        persisted = persisted.len(),
+        "restoring session listeners"
+    );
+
+    for SessionListenerConfig { protocol, session } in configured.into_iter().chain(persisted) {
+        let listen_host = session.listen_host.clone();
+        match create_listener(&state, protocol, session, true).await {
+            Ok(listener) => info!(%protocol, ip = listener.ip, port = listener.port, "session listener restored"),
+            Err((_, error)) => error!(%protocol, ?listen_host, %error, "failed to restore session listener"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use hopr_lib::Address;
+
+    use super::*;
+    use crate::session::{RoutingOptions, SessionTargetSpec};
+
+    fn listener(listen_host: &str) -> SessionListenerConfig {
+        SessionListenerConfig {
+            protocol: IpProtocol::TCP,
+            session: SessionClientRequest {
+                destination: Address::from_str("0x5112D584a1C72Fc250176B57aEba5fFbbB287D8F").unwrap(),
+                forward_path: RoutingOptions::Hops(1),
+                return_path: RoutingOptions::Hops(1),
+                target: SessionTargetSpec::Plain("localhost:8080".into()),
+                listen_host: Some(listen_host.into()),
+                capabilities: None,
+                response_buffer: None,
+                path_constraints: None,
+            },
+        }
+    }
+
+    #[test]
+    fn session_listener_config_should_deserialize_from_the_request_format() -> anyhow::Result<()> {
+        let config: SessionListenerConfig = serde_json::from_str(
+            r#"{
+                "protocol": "tcp",
+                "destination": "0x5112D584a1C72Fc250176B57aEba5fFbbB287D8F",
+                "forwardPath": { "Hops": 1 },
+                "returnPath": { "Hops": 1 },
+                "target": { "Plain": "localhost:8080" },
+                "listenHost": "127.0.0.1:10000"
+            }"#,
+        )?;
+
+        assert_eq!(listener("127.0.0.1:10000"), config);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn session_listener_store_should_persist_and_remove_listeners() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("session_listeners.json");
+        let store = SessionListenerStore::load(Some(path.clone()))?;
+
+        store.insert(listener("127.0.0.1:10000")).await?;
+        store.insert(listener("127.0.0.1:10001")).await?;
+
+        let loaded = SessionListenerStore::load(Some(path.clone()))?;
+        assert_eq!(
+            vec![listener("127.0.0.1:10000"), listener("127.0.0.1:10001")],
+            loaded.list().await
+        );
+
+        store
+            .remove(&ListenerId(hopr_lib::IpProtocol::TCP, "127.0.0.1:10000".parse()?))
+            .await?;
+        store
+            .remove(&ListenerId(hopr_lib::IpProtocol::UDP, "127.0.0.1:10001".parse()?))
+            .await?;
+
+        let loaded = SessionListenerStore::load(Some(path))?;
+        assert_eq!(vec![listener("127.0.0.1:10001")], loaded.list().await);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn session_listener_store_without_path_should_not_keep_listeners() -> anyhow::Result<()> {
+        let store = SessionListenerStore::load(None)?;
+
+        store.insert(listener("127.0.0.1:10000")).await?;
+        assert!(store.list().await.is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,660 @@
+//! Proxy mode of the TCP Session listeners.
+//!
+//! Instead of opening all Sessions to a single fixed target, a proxy listener lets each client
+//! connection request its own target, using either SOCKS5 ([RFC 1928](https://datatracker.ietf.org/doc/html/rfc1928))
+//! or HTTP `CONNECT`. A new Session is then opened for each client, with the requested target sealed
+//! to the key of the Exit node.
+//!
+//! SOCKS5 clients may also request a UDP relay (`UDP ASSOCIATE`), in which case a new UDP Session
+//! is opened for each target the client sends datagrams to, for as long as the client keeps
+//! the control connection open.
+
+use std::{
+    collections::{HashMap, HashSet},
+    net::{Ipv4Addr, SocketAddr},
+    str::FromStr,
+    sync::Arc,
+};
+
+use async_lock::RwLock;
+use futures::{AsyncReadExt as _, AsyncWriteExt as _, StreamExt, stream::FuturesUnordered};
+use hopr_lib::{Address, Hopr, HoprSession, HoprSessionId, PeerId, SessionClientConfig, SessionTarget};
+use hopr_network_types::prelude::{IpOrHost, SealedHost};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    io::{AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt as _, AsyncWrite, AsyncWriteExt as _, BufReader},
+    net::{TcpStream, UdpSocket},
+};
+use tracing::{debug, warn};
+
+use crate::{
+    ApiErrorStatus,
+    session::{HOPR_TCP_BUFFER_SIZE, HOPR_UDP_BUFFER_SIZE, bind_session_to_stream},
+};
+
+const SOCKS5_VERSION: u8 = 0x05;
+const SOCKS5_METHOD_NO_AUTH: u8 = 0x00;
+const SOCKS5_METHOD_NOT_ACCEPTABLE: u8 = 0xff;
+const SOCKS5_CMD_CONNECT: u8 = 0x01;
+const SOCKS5_CMD_UDP_ASSOCIATE: u8 = 0x03;
+const SOCKS5_ATYP_IPV4: u8 = 0x01;
+const SOCKS5_ATYP_DOMAIN: u8 = 0x03;
+const SOCKS5_ATYP_IPV6: u8 = 0x04;
+const SOCKS5_REP_SUCCEEDED: u8 = 0x00;
+const SOCKS5_REP_GENERAL_FAILURE: u8 = 0x01;
+const SOCKS5_REP_COMMAND_NOT_SUPPORTED: u8 = 0x07;
+const SOCKS5_REP_ADDRESS_NOT_SUPPORTED: u8 = 0x08;
+
+/// Maximum size of the HTTP `CONNECT` request head.
+const MAX_HTTP_REQUEST_HEAD_SIZE: u64 = 8192;
+
+/// Maximum number of targets a single SOCKS5 UDP relay keeps a Session open to.
+const MAX_SOCKS5_UDP_TARGETS: usize = 32;
+
+/// Maximum number of datagrams kept for a SOCKS5 UDP target while its Session is being opened.
+const MAX_SOCKS5_UDP_PENDING_DATAGRAMS: usize = 16;
+
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, strum::Display, strum::EnumString, utoipa::ToSchema,
+)]
+#[strum(serialize_all = "snake_case", ascii_case_insensitive)]
+#[schema(example = "Socks5")]
+/// Proxy protocol spoken by the clients of a proxy Session listener.
+pub enum ProxyProtocol {
+    /// SOCKS5 without authentication, supporting the `CONNECT` and `UDP ASSOCIATE` commands.
+    Socks5,
+    /// HTTP `CONNECT` tunneling.
+    HttpConnect,
+}
+
+fn invalid_data<E: Into<Box<dyn std::error::Error + Send + Sync>>>(error: E) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, error)
+}
+
+/// Decodes the SOCKS5 `ATYP`, `ADDR` and `PORT` fields, returning the address and the number of bytes used.
+fn decode_socks5_address(data: &[u8]) -> Option<(IpOrHost, usize)> {
+    let atyp = *data.first()?;
+    let (offset, host_len) = match atyp {
+        SOCKS5_ATYP_IPV4 => (1, 4),
+        SOCKS5_ATYP_IPV6 => (1, 16),
+        SOCKS5_ATYP_DOMAIN => (2, *data.get(1)? as usize),
+        _ => return None,
+    };
+
+    let host = data.get(offset..offset + host_len)?;
+    let port = u16::from_be_bytes(data.get(offset + host_len..offset + host_len + 2)?.try_into().ok()?);
+    let address = match atyp {
+        SOCKS5_ATYP_IPV4 => IpOrHost::Ip(SocketAddr::new(<[u8; 4]>::try_from(host).ok()?.into(), port)),
+        SOCKS5_ATYP_IPV6 => IpOrHost::Ip(SocketAddr::new(<[u8; 16]>::try_from(host).ok()?.into(), port)),
+        _ => IpOrHost::Dns(String::from_utf8(host.to_vec()).ok()?, port),
+    };
+
+    Some((address, offset + host_len + 2))
+}
+
+/// Encodes the address as the SOCKS5 `ATYP`, `ADDR` and `PORT` fields.
+fn encode_socks5_address(address: &IpOrHost) -> Vec<u8> {
+    let mut data = Vec::new();
+    let port = match address {
+        IpOrHost::Ip(SocketAddr::V4(addr)) => {
+            data.push(SOCKS5_ATYP_IPV4);
+            data.extend(addr.ip().octets());
+            addr.port()
+        }
+        IpOrHost::Ip(SocketAddr::V6(addr)) => {
+            data.push(SOCKS5_ATYP_IPV6);
+            data.extend(addr.ip().octets());
+            addr.port()
+        }
+        IpOrHost::Dns(host, port) => {
+            // Host names longer than 255 bytes cannot be requested by a SOCKS5 client
+            data.push(SOCKS5_ATYP_DOMAIN);
+            data.push(host.len().min(u8::MAX as usize) as u8);
+            data.extend(host.as_bytes().iter().take(u8::MAX as usize));
+            *port
+        }
+    };
+    data.extend(port.to_be_bytes());
+    data
+}
+
+/// Decodes the header of a datagram sent to the SOCKS5 UDP relay, returning the target and the header length.
+///
+/// Fragmented datagrams are not supported.
+fn decode_socks5_udp_header(data: &[u8]) -> Option<(IpOrHost, usize)> {
+    match data {
+        [0, 0, 0, address @ ..] => decode_socks5_address(address).map(|(target, len)| (target, len + 3)),
+        _ => None,
+    }
+}
+
+async fn read_socks5_address<S: AsyncRead + Unpin>(stream: &mut S) -> std::io::Result<Option<IpOrHost>> {
+    let mut data = vec![stream.read_u8().await?];
+    let remaining = match data[0] {
+        SOCKS5_ATYP_IPV4 => 4 + 2,
+        SOCKS5_ATYP_IPV6 => 16 + 2,
+        SOCKS5_ATYP_DOMAIN => {
+            let len = stream.read_u8().await?;
+            data.push(len);
+            len as usize + 2
+        }
+        _ => return Ok(None),
+    };
+
+    data.resize(data.len() + remaining, 0);
+    let offset = data.len() - remaining;
+    stream.read_exact(&mut data[offset..]).await?;
+
+    Ok(decode_socks5_address(&data).map(|(address, _)| address))
+}
+
+async fn socks5_reply<S: AsyncWrite + Unpin>(stream: &mut S, reply: u8, bound: SocketAddr) -> std::io::Result<()> {
+    let mut data = vec![SOCKS5_VERSION, reply, 0];
+    data.extend(encode_socks5_address(&bound.into()));
+    stream.write_all(&data).await?;
+    stream.flush().await
+}
+
+/// Command requested by a SOCKS5 client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Socks5Command {
+    Connect(IpOrHost),
+    UdpAssociate,
+}
+
+/// Performs the SOCKS5 method negotiation and reads the client's request.
+///
+/// Requests that cannot be served are replied to with the corresponding error.
+async fn socks5_handshake<S: AsyncRead + AsyncWrite + Unpin>(stream: &mut S) -> std::io::Result<Socks5Command> {
+    let mut header = [0u8; 2];
+    stream.read_exact(&mut header).await?;
+    if header[0] != SOCKS5_VERSION {
+        return Err(invalid_data(format!("unsupported SOCKS version {}", header[0])));
+    }
+
+    let mut methods = vec![0u8; header[1] as usize];
+    stream.read_exact(&mut methods).await?;
+    if !methods.contains(&SOCKS5_METHOD_NO_AUTH) {
+        stream
+            .write_all(&[SOCKS5_VERSION, SOCKS5_METHOD_NOT_ACCEPTABLE])
+            .await?;
+        return Err(invalid_data("SOCKS5 client does not support unauthenticated access"));
+    }
+    stream.write_all(&[SOCKS5_VERSION, SOCKS5_METHOD_NO_AUTH]).await?;
+    stream.flush().await?;
+
+    let mut request = [0u8; 3];
+    stream.read_exact(&mut request).await?;
+    if request[0] != SOCKS5_VERSION {
+        return Err(invalid_data(format!("unsupported SOCKS version {}", request[0])));
+    }
+
+    let unspecified = SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0);
+    let Some(address) = read_socks5_address(stream).await? else {
+        socks5_reply(stream, SOCKS5_REP_ADDRESS_NOT_SUPPORTED, unspecified).await?;
+        return Err(invalid_data("unsupported SOCKS5 address type"));
+    };
+
+    match request[1] {
+        SOCKS5_CMD_CONNECT => Ok(Socks5Command::Connect(address)),
+        SOCKS5_CMD_UDP_ASSOCIATE => Ok(Socks5Command::UdpAssociate),
+        command => {
+            socks5_reply(stream, SOCKS5_REP_COMMAND_NOT_SUPPORTED, unspecified).await?;
+            Err(invalid_data(format!("unsupported SOCKS5 command {command}")))
+        }
+    }
+}
+
+async fn http_reply<S: AsyncWrite + Unpin>(stream: &mut S, status: &str) -> std::io::Result<()> {
+    stream
+        .write_all(format!("HTTP/1.1 {status}\r\n\r\n").as_bytes())
+        .await?;
+    stream.flush().await
+}
+
+/// Reads the HTTP `CONNECT` request head and returns the requested target.
+///
+/// Requests that cannot be served are replied to with the corresponding error.
+async fn http_connect_handshake<S: AsyncBufRead + AsyncWrite + Unpin>(stream: &mut S) -> std::io::Result<IpOrHost> {
+    let mut head = (&mut *stream).take(MAX_HTTP_REQUEST_HEAD_SIZE);
+
+    let mut request_line = String::new();
+    head.read_line(&mut request_line).await?;
+
+    // Skip the headers, which are not needed to establish the tunnel
+    let mut header = String::new();
+    loop {
+        header.clear();
+        match head.read_line(&mut header).await? {
+            0 => return Err(std::io::ErrorKind::UnexpectedEof.into()),
+            _ if header.trim_end().is_empty() => break,
+            _ => {}
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    match (parts.next(), parts.next().map(IpOrHost::from_str)) {
+        (Some("CONNECT"), Some(Ok(target))) => Ok(target),
+        (Some("CONNECT"), _) => {
+            http_reply(stream, "400 Bad Request").await?;
+            Err(invalid_data(format!(
+                "invalid HTTP CONNECT request: {}",
+                request_line.trim_end()
+            )))
+        }
+        _ => {
+            http_reply(stream, "405 Method Not Allowed").await?;
+            Err(invalid_data(format!(
+                "not a HTTP CONNECT request: {}",
+                request_line.trim_end()
+            )))
+        }
+    }
+}
+
+/// Serves the clients of a proxy Session listener.
+#[derive(Clone)]
+pub(crate) struct ProxyListener {
+    hopr: Arc<Hopr>,
+    protocol: ProxyProtocol,
+    destination: Address,
+    exit: PeerId,
+    cfg: SessionClientConfig,
+    sessions: Arc<RwLock<HashSet<HoprSessionId>>>,
+}
+
+impl ProxyListener {
+    pub(crate) async fn new(
+        hopr: Arc<Hopr>,
+        protocol: ProxyProtocol,
+        destination: Address,
+        cfg: SessionClientConfig,
+        sessions: Arc<RwLock<HashSet<HoprSessionId>>>,
+    ) -> Result<Self, ApiErrorStatus> {
+        // The targets are sealed to the key of the Exit node
+        let exit = hopr
+            .chain_key_to_peerid(&destination)
+            .await?
+            .ok_or(ApiErrorStatus::PeerNotFound)?;
+
+        Ok(Self {
+            hopr,
+            protocol,
+            destination,
+            exit,
+            cfg,
+            sessions,
+        })
+    }
+
+    pub(crate) fn protocol(&self) -> ProxyProtocol {
+        self.protocol
+    }
+
+    async fn open_session(&self, target: SessionTarget, cfg: SessionClientConfig) -> std::io::Result<HoprSession> {
+        let session = self
+            .hopr
+            .connect_to(self.destination, target, cfg)
+            .await
+            .map_err(std::io::Error::other)?;
+
+        self.sessions.write().await.insert(*session.id());
+        Ok(session)
+    }
+
+    async fn open_tcp_session(&self, target: IpOrHost) -> std::io::Result<HoprSession> {
+        let target = SealedHost::seal(target, self.exit).map_err(std::io::Error::other)?;
+        self.open_session(SessionTarget::TcpStream(target), self.cfg.clone())
+            .await
+    }
+
+    async fn bind_tcp_session(&self, session: HoprSession, stream: BufReader<TcpStream>) {
+        let session_id = *session.id();
+        bind_session_to_stream(session, stream, HOPR_TCP_BUFFER_SIZE).await;
+        self.sessions.write().await.remove(&session_id);
+    }
+
+    /// Performs the proxy handshake with the client and bridges it to a new Session with the requested target.
+    pub(crate) async fn serve(&self, stream: TcpStream) -> std::io::Result<()> {
+        let bound = stream.local_addr()?;
+        let mut stream = BufReader::new(stream);
+
+        match self.protocol {
+            ProxyProtocol::Socks5 => match socks5_handshake(&mut stream).await? {
+                Socks5Command::Connect(target) => {
+                    debug!(%target, "SOCKS5 client requested a TCP target");
+                    match self.open_tcp_session(target).await {
+                        Ok(session) => {
+                            socks5_reply(&mut stream, SOCKS5_REP_SUCCEEDED, bound).await?;
+                            self.bind_tcp_session(session, stream).await;
+                            Ok(())
+                        }
+                        Err(error) => {
+                            socks5_reply(&mut stream, SOCKS5_REP_GENERAL_FAILURE, bound).await?;
+                            Err(error)
+                        }
+                    }
+                }
+                Socks5Command::UdpAssociate => {
+                    debug!("SOCKS5 client requested a UDP relay");
+                    self.socks5_udp_associate(stream).await
+                }
+            },
+            ProxyProtocol::HttpConnect => {
+                let target = http_connect_handshake(&mut stream).await?;
+                debug!(%target, "HTTP CONNECT client requested a TCP target");
+                match self.open_tcp_session(target).await {
+                    Ok(session) => {
+                        http_reply(&mut stream, "200 Connection Established").await?;
+                        self.bind_tcp_session(session, stream).await;
+                        Ok(())
+                    }
+                    Err(error) => {
+                        http_reply(&mut stream, "502 Bad Gateway").await?;
+                        Err(error)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Relays the datagrams of a SOCKS5 client over UDP Sessions, one for each target.
+    ///
+    /// At most [`MAX_SOCKS5_UDP_TARGETS`] Sessions are kept open, evicting the least recently used
+    /// target when a new one is needed. A target whose Session fails is forgotten, so that the next
+    /// datagram to it opens a new Session.
+    async fn socks5_udp_associate(&self, mut control: BufReader<TcpStream>) -> std::io::Result<()> {
+        let client_ip = control.get_ref().peer_addr()?.ip();
+        let relay = Arc::new(UdpSocket::bind(SocketAddr::new(control.get_ref().local_addr()?.ip(), 0)).await?);
+        socks5_reply(&mut control, SOCKS5_REP_SUCCEEDED, relay.local_addr()?).await?;
+
+        // Same as for the UDP listeners, the relayed Sessions use no capabilities
+        let cfg = SessionClientConfig {
+            capabilities: Vec::new(),
+            ..self.cfg.clone()
+        };
+
+        let mut targets = HashMap::<IpOrHost, UdpRelayTarget>::new();
+        // Datagrams to the targets whose Session is being opened
+        let mut opening = HashMap::<IpOrHost, (SocketAddr, Vec<Box<[u8]>>)>::new();
+        let mut opened = FuturesUnordered::new();
+        let (closed_tx, mut closed_rx) = futures::channel::mpsc::unbounded::<(IpOrHost, HoprSessionId)>();
+        let mut buffer = vec![0u8; HOPR_UDP_BUFFER_SIZE];
+        let mut control_buffer = [0u8; 1];
+
+        let result = loop {
+            tokio::select! {
+                // The relay lasts only as long as the control connection
+                read = control.read(&mut control_buffer) => {
+                    if !matches!(read, Ok(len) if len > 0) {
+                        break Ok(());
+                    }
+                }
+                Some((target, session)) = opened.next() => {
+                    let Some((client, datagrams)) = opening.remove(&target) else {
+                        continue;
+                    };
+                    let session: HoprSession = match session {
+                        Ok(session) => session,
+                        Err(error) => {
+                            warn!(%target, %error, "failed to open a session for the SOCKS5 UDP target");
+                            continue;
+                        }
+                    };
+                    debug!(%target, session_id = ?session.id(), "new session for the SOCKS5 UDP target");
+
+                    if targets.len() >= MAX_SOCKS5_UDP_TARGETS {
+                        let evicted = targets
+                            .iter()
+                            .min_by_key(|(_, relayed)| relayed.last_used)
+                            .map(|(target, _)| target.clone());
+                        if let Some((evicted, relayed)) = evicted.and_then(|evicted| targets.remove_entry(&evicted)) {
+                            debug!(target = %evicted, "closing the session of the least recently used SOCKS5 UDP target");
+                            self.close_udp_relay_target(relayed).await;
+                        }
+                    }
+
+                    let mut relayed = UdpRelayTarget::new(session, target.clone(), relay.clone(), client, closed_tx.clone());
+                    match relayed.write_all(datagrams).await {
+                        Ok(()) => {
+                            targets.insert(target, relayed);
+                        }
+                        Err(error) => {
+                            warn!(%target, %error, "failed to relay datagram to the session");
+                            self.close_udp_relay_target(relayed).await;
+                        }
+                    }
+                }
+                Some((target, session_id)) = closed_rx.next() => {
+                    if targets.get(&target).is_some_and(|relayed| relayed.session_id == session_id) {
+                        debug!(%target, ?session_id, "session of the SOCKS5 UDP target has ended");
+                        if let Some(relayed) = targets.remove(&target) {
+                            self.close_udp_relay_target(relayed).await;
+                        }
+                    }
+                }
+                received = relay.recv_from(&mut buffer) => {
+                    let (len, client) = match received {
+                        Ok(received) => received,
+                        Err(error) => break Err(error),
+                    };
+                    if client.ip() != client_ip {
+                        debug!(%client, "discarding datagram from a foreign client");
+                        continue;
+                    }
+                    let Some((target, header_len)) = decode_socks5_udp_header(&buffer[..len]) else {
+                        debug!(%client, "discarding invalid or fragmented SOCKS5 datagram");
+                        continue;
+                    };
+                    let datagram = Box::<[u8]>::from(&buffer[header_len..len]);
+
+                    if let Some(relayed) = targets.get_mut(&target) {
+                        if let Err(error) = relayed.write_all([datagram]).await {
+                            warn!(%target, %error, "failed to relay datagram to the session");
+                            if let Some(relayed) = targets.remove(&target) {
+                                self.close_udp_relay_target(relayed).await;
+                            }
+                        }
+                    } else if let Some((_, datagrams)) = opening.get_mut(&target) {
+                        if datagrams.len() < MAX_SOCKS5_UDP_PENDING_DATAGRAMS {
+                            datagrams.push(datagram);
+                        }
+                    } else {
+                        let sealed = match SealedHost::seal(target.clone(), self.exit) {
+                            Ok(sealed) => sealed,
+                            Err(error) => break Err(std::io::Error::other(error)),
+                        };
+                        opening.insert(target.clone(), (client, vec![datagram]));
+
+                        // The Session is opened concurrently, so that other targets are relayed meanwhile
+                        let session = self.open_session(SessionTarget::UdpStream(sealed), cfg.clone());
+                        opened.push(async move { (target, session.await) });
+                    }
+                }
+            }
+        };
+
+        drop(opened);
+        for relayed in targets.into_values() {
+            self.close_udp_relay_target(relayed).await;
+        }
+
+        result
+    }
+
+    async fn close_udp_relay_target(&self, relayed: UdpRelayTarget) {
+        let session_id = relayed.session_id;
+        relayed.close().await;
+        self.sessions.write().await.remove(&session_id);
+    }
+}
+
+/// Session relaying the datagrams of a SOCKS5 client to a single target and back.
+struct UdpRelayTarget {
+    session_id: HoprSessionId,
+    writer: futures::io::WriteHalf<HoprSession>,
+    receiver: hopr_async_runtime::prelude::JoinHandle<()>,
+    last_used: std::time::Instant,
+}
+
+impl UdpRelayTarget {
+    /// Starts relaying the datagrams received over the Session back to the client.
+    ///
+    /// Once the Session ends, the target and the Session ID are sent to `closed`.
+    fn new(
+        session: HoprSession,
+        target: IpOrHost,
+        relay: Arc<UdpSocket>,
+        client: SocketAddr,
+        closed: futures::channel::mpsc::UnboundedSender<(IpOrHost, HoprSessionId)>,
+    ) -> Self {
+        let session_id = *session.id();
+        let (mut reader, writer) = session.split();
+        let header = [&[0, 0, 0][..], &encode_socks5_address(&target)].concat();
+
+        let receiver = hopr_async_runtime::prelude::spawn(async move {
+            let mut buffer = vec![0u8; HOPR_UDP_BUFFER_SIZE];
+            while let Ok(len @ 1..) = reader.read(&mut buffer).await {
+                let datagram = [header.as_slice(), &buffer[..len]].concat();
+                if let Err(error) = relay.send_to(&datagram, client).await {
+                    warn!(%client, %error, "failed to relay datagram to the SOCKS5 client");
+                    break;
+                }
+            }
+            let _ = closed.unbounded_send((target, session_id));
+        });
+
+        Self {
+            session_id,
+            writer,
+            receiver,
+            last_used: std::time::Instant::now(),
+        }
+    }
+
+    /// Relays the given datagrams of the client over the Session.
+    async fn write_all(&mut self, datagrams: impl IntoIterator<Item = Box<[u8]>>) -> std::io::Result<()> {
+        self.last_used = std::time::Instant::now();
+        for datagram in datagrams {
+            self.writer.write_all(&datagram).await?;
+        }
+        Ok(())
+    }
+
+    async fn close(mut self) {
+        let _ = self.writer.close().await;
+        hopr_async_runtime::prelude::cancel_join_handle(self.receiver).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Context;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn socks5_handshake_should_read_the_connect_request() -> anyhow::Result<()> {
+        let (mut client, mut server) = tokio::io::duplex(1024);
+
+        client
+            .write_all(&[SOCKS5_VERSION, 2, 0x02, SOCKS5_METHOD_NO_AUTH])
+            .await?;
+        client.write_all(&[SOCKS5_VERSION, SOCKS5_CMD_CONNECT, 0]).await?;
+        client
+            .write_all(&encode_socks5_address(&IpOrHost::Dns("example.com".into(), 443)))
+            .await?;
+
+        let command = socks5_handshake(&mut server).await?;
+        assert_eq!(
+            Socks5Command::Connect(IpOrHost::Dns("example.com".into(), 443)),
+            command
+        );
+
+        let mut method = [0u8; 2];
+        client.read_exact(&mut method).await?;
+        assert_eq!([SOCKS5_VERSION, SOCKS5_METHOD_NO_AUTH], method);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn socks5_handshake_should_reject_unsupported_commands() -> anyhow::Result<()> {
+        let (mut client, mut server) = tokio::io::duplex(1024);
+
+        client.write_all(&[SOCKS5_VERSION, 1, SOCKS5_METHOD_NO_AUTH]).await?;
+        client.write_all(&[SOCKS5_VERSION, 0x02, 0]).await?; // BIND
+        client
+            .write_all(&encode_socks5_address(&IpOrHost::Ip("10.0.0.1:80".parse()?)))
+            .await?;
+
+        assert!(socks5_handshake(&mut server).await.is_err());
+
+        let mut reply = [0u8; 4];
+        client.read_exact(&mut reply).await?;
+        assert_eq!(
+            [
+                SOCKS5_VERSION,
+                SOCKS5_METHOD_NO_AUTH,
+                SOCKS5_VERSION,
+                SOCKS5_REP_COMMAND_NOT_SUPPORTED
+            ],
+            reply
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn socks5_udp_header_should_be_decoded() -> anyhow::Result<()> {
+        let target = IpOrHost::Ip("[2001:db8::1]:53".parse()?);
+        let datagram = [&[0, 0, 0][..], &encode_socks5_address(&target), b"payload"].concat();
+
+        let (decoded, header_len) = decode_socks5_udp_header(&datagram).context("must decode")?;
+        assert_eq!(target, decoded);
+        assert_eq!(b"payload", &datagram[header_len..]);
+
+        // Fragmented datagrams are not supported
+        let fragment = [&[0, 0, 1][..], &encode_socks5_address(&target), b"payload"].concat();
+        assert!(decode_socks5_udp_header(&fragment).is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn http_connect_handshake_should_read_the_target() -> anyhow::Result<()> {
+        let (mut client, server) = tokio::io::duplex(1024);
+        let mut server = BufReader::new(server);
+
+        client
+            .write_all(b"CONNECT example.com:443 HTTP/1.1\r\nHost: example.com:443\r\n\r\nearly data")
+            .await?;
+
+        let target = http_connect_handshake(&mut server).await?;
+        assert_eq!(IpOrHost::Dns("example.com".into(), 443), target);
+
+        // Data sent after the request head must not be lost
+        let mut early = [0u8; 10];
+        server.read_exact(&mut early).await?;
+        assert_eq!(b"early data", &early);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn http_connect_handshake_should_reject_other_methods() -> anyhow::Result<()> {
+        let (mut client, server) = tokio::io::duplex(1024);
+        let mut server = BufReader::new(server);
+
+        client.write_all(b"GET / HTTP/1.1\r\nHost: example.com\r\n\r\n").await?;
+        assert!(http_connect_handshake(&mut server).await.is_err());
+        drop(server);
+
+        let mut reply = String::new();
+        client.read_to_string(&mut reply).await?;
+        assert!(reply.starts_with("HTTP/1.1 405"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,440 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Json, Query, State},
+    http::status::StatusCode,
+    response::IntoResponse,
+};
+use hopr_crypto_types::prelude::Hash;
+use hopr_lib::{
+    Address, AsUnixTimestamp, HoprBalance, IntendedAction, StrategyEvent, StrategyExecution, StrategyIntent,
+    config::StrategyConfig,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, serde_as};
+use tracing::info;
+use validator::Validate;
+
+use crate::{ApiError, ApiErrorStatus, BASE_PATH, InternalState, option_checksum_address_serializer};
+
+#[derive(Debug, Clone, Copy, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+/// Type of the action a strategy would have taken.
+pub(crate) enum IntendedActionType {
+    OpenChannel,
+    FundChannel,
+    CloseChannel,
+    RedeemTicket,
+    RedeemTickets,
+    AggregateTickets,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[schema(example = json!({
+    "action": "openChannel",
+    "counterparty": "0x188c4462b75e46f0c7262d7f48d182447b93a93c",
+    "amount": "10 wxHOPR"
+}))]
+#[serde(rename_all = "camelCase")]
+/// Action taken by a strategy, or the action it would have taken in the dry-run mode.
+pub(crate) struct StrategyActionResponse {
+    action: IntendedActionType,
+    /// Counterparty of the channel, if the action concerns a channel with a counterparty.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "option_checksum_address_serializer"
+    )]
+    #[schema(value_type = Option<String>, example = "0x188c4462b75e46f0c7262d7f48d182447b93a93c")]
+    counterparty: Option<Address>,
+    /// ID of the channel, if the action concerns a channel identified by its ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[schema(value_type = Option<String>, example = "0x04efc1481d3f106b88527b3844ba40042b823218a9cd29d1aa11c2c2ef8f538f")]
+    channel_id: Option<Hash>,
+    /// Direction of the channel to close.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "outgoing")]
+    direction: Option<String>,
+    /// Amount of tokens staked, funded or redeemed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[schema(value_type = Option<String>, example = "10 wxHOPR")]
+    amount: Option<HoprBalance>,
+    /// Index of the ticket to redeem.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 1)]
+    ticket_index: Option<u64>,
+    /// Description of the tickets to redeem.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    selector: Option<String>,
+}
+
+impl From<IntendedAction> for StrategyActionResponse {
+    fn from(value: IntendedAction) -> Self {
+        let mut response = Self {
+            action: IntendedActionType::OpenChannel,
+            counterparty: None,
+            channel_id: None,
+            direction: None,
+            amount: None,
+            ticket_index: None,
+            selector: None,
+        };
+
+        match value {
+            IntendedAction::OpenChannel { destination, amount } => {
+                response.counterparty = Some(destination);
+                response.amount = Some(amount);
+            }
+            IntendedAction::FundChannel { channel_id, amount } => {
+                response.action = IntendedActionType::FundChannel;
+                response.channel_id = Some(channel_id);
+                response.amount = Some(amount);
+            }
+            IntendedAction::CloseChannel {
+                counterparty,
+                direction,
+            } => {
+                response.action = IntendedActionType::CloseChannel;
+                response.counterparty = Some(counterparty);
+                response.direction = Some(direction.to_string());
+            }
+            IntendedAction::RedeemTicket {
+                channel_id,
+                index,
+                amount,
+            } => {
+                response.action = IntendedActionType::RedeemTicket;
+                response.channel_id = Some(channel_id);
+                response.ticket_index = Some(index);
+                response.amount = Some(amount);
+            }
+            IntendedAction::RedeemTickets { selector } => {
+                response.action = IntendedActionType::RedeemTickets;
+                response.selector = Some(selector);
+            }
+            IntendedAction::AggregateTickets { channel_id } => {
+                response.action = IntendedActionType::AggregateTickets;
+                response.channel_id = Some(channel_id);
+            }
+        }
+
+        response
+    }
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[schema(example = json!({
+    "strategy": "promiscuous",
+    "timestamp": 1690000000000_u64,
+    "action": "openChannel",
+    "counterparty": "0x188c4462b75e46f0c7262d7f48d182447b93a93c",
+    "amount": "10 wxHOPR"
+}))]
+#[serde(rename_all = "camelCase")]
+/// Action a strategy would have taken, if it was not running in the dry-run mode.
+pub(crate) struct StrategyIntentResponse {
+    /// Name of the strategy.
+    #[schema(example = "promiscuous")]
+    strategy: String,
+    /// Time of the intent in milliseconds since the UNIX epoch.
+    #[schema(example = 1690000000000_u64)]
+    timestamp: u128,
+    #[serde(flatten)]
+    action: StrategyActionResponse,
+}
+
+impl From<StrategyIntent> for StrategyIntentResponse {
+    fn from(value: StrategyIntent) -> Self {
+        Self {
+            strategy: value.strategy,
+            timestamp: value.timestamp.as_unix_timestamp().as_millis(),
+            action: value.action.into(),
+        }
+    }
+}
+
+/// Lists the actions the strategies would have taken, if they were not running in the dry-run mode.
+#[utoipa::path(
+        get,
+        path = const_format::formatcp!("{BASE_PATH}/strategy/intents"),
+        description = "Lists the actions the strategies would have taken, if they were not running in the dry-run mode. The list is empty if the dry-run mode is disabled.",
+        responses(
+            (status = 200, description = "Strategy intents fetched successfully, oldest first.", body = [StrategyIntentResponse]),
+            (status = 401, description = "Invalid authorization token.", body = ApiError),
+        ),
+        security(
+            ("api_token" = []),
+            ("bearer_token" = [])
+        ),
+        tag = "Strategy"
+    )]
+pub(super) async fn show_strategy_intents(State(state): State<Arc<InternalState>>) -> impl IntoResponse {
+    let intents = state
+        .hopr
+        .strategy_intents()
+        .await
+        .into_iter()
+        .map(StrategyIntentResponse::from)
+        .collect::<Vec<_>>();
+
+    (StatusCode::OK, Json(intents)).into_response()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+/// Event that invoked a strategy.
+pub(crate) enum StrategyEventType {
+    Tick,
+    AcknowledgedWinningTicket,
+    OwnChannelChanged,
+}
+
+impl From<StrategyEvent> for StrategyEventType {
+    fn from(value: StrategyEvent) -> Self {
+        match value {
+            StrategyEvent::Tick => Self::Tick,
+            StrategyEvent::AcknowledgedWinningTicket => Self::AcknowledgedWinningTicket,
+            StrategyEvent::OwnChannelChanged => Self::OwnChannelChanged,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[schema(example = json!({
+    "strategy": "auto_redeeming",
+    "event": "acknowledgedWinningTicket",
+    "timestamp": 1690000000000_u64,
+    "durationMs": 12,
+    "success": true,
+    "actions": [{
+        "action": "redeemTicket",
+        "channelId": "0x04efc1481d3f106b88527b3844ba40042b823218a9cd29d1aa11c2c2ef8f538f",
+        "ticketIndex": 1,
+        "amount": "0.1 wxHOPR"
+    }]
+}))]
+#[serde(rename_all = "camelCase")]
+/// Record of a single invocation of a strategy.
+pub(crate) struct StrategyExecutionResponse {
+    /// Name of the strategy.
+    #[schema(example = "auto_redeeming")]
+    strategy: String,
+    event: StrategyEventType,
+    /// Start of the invocation in milliseconds since the UNIX epoch.
+    #[schema(example = 1690000000000_u64)]
+    timestamp: u128,
+    /// Duration of the invocation in milliseconds.
+    #[schema(example = 12)]
+    duration_ms: u128,
+    /// Indicates whether the invocation finished successfully.
+    success: bool,
+    /// Error of the failed invocation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    /// Actions triggered by the strategy during the invocation.
+    actions: Vec<StrategyActionResponse>,
+}
+
+impl From<StrategyExecution> for StrategyExecutionResponse {
+    fn from(value: StrategyExecution) -> Self {
+        Self {
+            success: value.is_success(),
+            strategy: value.strategy,
+            event: value.event.into(),
+            timestamp: value.started.as_unix_timestamp().as_millis(),
+            duration_ms: value.duration.as_millis(),
+            error: value.error,
+            actions: value.actions.into_iter().map(StrategyActionResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize, utoipa::IntoParams, utoipa::ToSchema)]
+#[into_params(parameter_in = Query)]
+#[serde(default, rename_all = "camelCase")]
+#[schema(example = json!({
+        "strategy": "auto_redeeming",
+        "event": "tick",
+        "limit": 10
+    }))]
+/// Parameters for filtering the strategy execution history.
+pub(crate) struct StrategyHistoryQueryRequest {
+    /// Only list executions of the given strategy.
+    #[schema(required = false)]
+    #[serde(default)]
+    strategy: Option<String>,
+    /// Only list executions invoked by the given event.
+    #[schema(required = false)]
+    #[serde(default)]
+    event: Option<StrategyEventType>,
+    /// Only list the given number of the most recent executions.
+    #[schema(required = false)]
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+/// Lists the recent executions of the individual strategies.
+#[utoipa::path(
+        get,
+        path = const_format::formatcp!("{BASE_PATH}/strategy/history"),
+        description = "Lists the recent executions of the individual strategies, with their duration, outcome and the actions they triggered. Only a limited number of the most recent executions is kept for each strategy and event.",
+        params(StrategyHistoryQueryRequest),
+        responses(
+            (status = 200, description = "Strategy executions fetched successfully, oldest first.", body = [StrategyExecutionResponse]),
+            (status = 401, description = "Invalid authorization token.", body = ApiError),
+        ),
+        security(
+            ("api_token" = []),
+            ("bearer_token" = [])
+        ),
+        tag = "Strategy"
+    )]
+pub(super) async fn show_strategy_history(
+    Query(query): Query<StrategyHistoryQueryRequest>,
+    State(state): State<Arc<InternalState>>,
+) -> impl IntoResponse {
+    let mut executions = state
+        .hopr
+        .strategy_history()
+        .await
+        .into_iter()
+        .filter(|execution| query.strategy.as_ref().is_none_or(|s| s == &execution.strategy))
+        .map(StrategyExecutionResponse::from)
+        .filter(|execution| query.event.is_none_or(|e| e == execution.event))
+        .collect::<Vec<_>>();
+
+    if let Some(limit) = query.limit {
+        executions.drain(..executions.len().saturating_sub(limit));
+    }
+
+    (StatusCode::OK, Json(executions)).into_response()
+}
+
+/// Returns the configuration of the currently running strategies.
+#[utoipa::path(
+        get,
+        path = const_format::formatcp!("{BASE_PATH}/strategy/config"),
+        description = "Returns the configuration of the currently running strategies, in the same structure as the `strategy` section of the node configuration file.",
+        responses(
+            (status = 200, description = "Strategy configuration fetched successfully.", body = Object),
+            (status = 401, description = "Invalid authorization token.", body = ApiError),
+        ),
+        security(
+            ("api_token" = []),
+            ("bearer_token" = [])
+        ),
+        tag = "Strategy"
+    )]
+pub(super) async fn show_strategy_config(State(state): State<Arc<InternalState>>) -> impl IntoResponse {
+    (StatusCode::OK, Json(state.hopr.strategy_config().await)).into_response()
+}
+
+#[derive(Debug, Default, Copy, Clone, Deserialize, utoipa::IntoParams, utoipa::ToSchema)]
+#[into_params(parameter_in = Query)]
+#[serde(default, rename_all = "camelCase")]
+#[schema(example = json!({
+        "persist": true
+    }))]
+/// Parameters for reconfiguring the strategies.
+pub(crate) struct StrategyConfigQueryRequest {
+    /// Should the new configuration be also written into the configuration file of the node?
+    #[schema(required = false)]
+    #[serde(default)]
+    persist: bool,
+}
+
+/// Replaces the `hopr.strategy` section of the given YAML configuration file.
+///
+/// The file is written into a temporary file first, which then replaces the original.
+/// Note that comments in the original file are not preserved.
+async fn persist_strategy_config(path: &str, cfg: &StrategyConfig) -> Result<(), String> {
+    let contents = tokio::fs::read_to_string(path).await.map_err(|e| e.to_string())?;
+    let mut root: serde_yaml::Value = serde_yaml::from_str(&contents).map_err(|e| e.to_string())?;
+    if root.is_null() {
+        root = serde_yaml::Mapping::new().into();
+    }
+
+    let hopr = root
+        .as_mapping_mut()
+        .ok_or("configuration file is not a YAML mapping")?
+        .entry("hopr".into())
+        .or_insert_with(|| serde_yaml::Mapping::new().into());
+    if hopr.is_null() {
+        *hopr = serde_yaml::Mapping::new().into();
+    }
+    hopr.as_mapping_mut()
+        .ok_or("'hopr' section of the configuration file is not a YAML mapping")?
+        .insert("strategy".into(), serde_yaml::to_value(cfg).map_err(|e| e.to_string())?);
+
+    let tmp_path = format!("{path}.tmp");
+    tokio::fs::write(&tmp_path, serde_yaml::to_string(&root).map_err(|e| e.to_string())?)
+        .await
+        .map_err(|e| e.to_string())?;
+    tokio::fs::rename(&tmp_path, path).await.map_err(|e| e.to_string())
+}
+
+/// Validates the given strategy configuration and replaces the currently running strategies with it.
+///
+/// The strategy events currently being executed are allowed to finish before the replacement.
+#[utoipa::path(
+        put,
+        path = const_format::formatcp!("{BASE_PATH}/strategy/config"),
+        description = "Validates the given strategy configuration and replaces the currently running strategies with it. The strategy events currently being executed are allowed to finish first. Optionally, the new configuration is also written into the configuration file of the node, so it survives a restart.",
+        params(StrategyConfigQueryRequest),
+        request_body(
+            content = Object,
+            description = "New strategy configuration, in the same structure as the `strategy` section of the node configuration file.",
+            content_type = "application/json"),
+        responses(
+            (status = 204, description = "Strategies reconfigured successfully."),
+            (status = 400, description = "Invalid strategy configuration, or persisting requested while the node does not use a configuration file.", body = ApiError),
+            (status = 401, description = "Invalid authorization token.", body = ApiError),
+            (status = 422, description = "Unknown failure. If persisting failed, the strategies were already reconfigured.", body = ApiError)
+        ),
+        security(
+            ("api_token" = []),
+            ("bearer_token" = [])
+        ),
+        tag = "Strategy"
+    )]
+pub(super) async fn reconfigure_strategy(
+    Query(query): Query<StrategyConfigQueryRequest>,
+    State(state): State<Arc<InternalState>>,
+    Json(cfg): Json<StrategyConfig>,
+) -> impl IntoResponse {
+    let invalid_input = |error: String| {
+        (
+            StatusCode::BAD_REQUEST,
+            ApiError {
+                status: ApiErrorStatus::InvalidInput.to_string(),
+                error: Some(error),
+            },
+        )
+            .into_response()
+    };
+
+    if let Err(e) = cfg.validate() {
+        return invalid_input(e.to_string());
+    }
+
+    let cfg_path = match (query.persist, &state.hoprd_cfg_path) {
+        (true, None) => return invalid_input("node was not started with a configuration file".into()),
+        (true, Some(path)) => Some(path.clone()),
+        (false, _) => None,
+    };
+
+    if let Err(e) = state.hopr.reconfigure_strategy(cfg.clone()).await {
+        return (StatusCode::UNPROCESSABLE_ENTITY, ApiErrorStatus::from(e)).into_response();
+    }
+
+    if let Some(path) = cfg_path {
+        if let Err(e) = persist_strategy_config(&path, &cfg).await {
+            return (StatusCode::UNPROCESSABLE_ENTITY, ApiErrorStatus::UnknownFailure(e)).into_response();
+        }
+        info!(path, "strategy configuration persisted");
+    }
+
+    (StatusCode::NO_CONTENT, "").into_response()
+}
@@ -1,19 +1,21 @@
-use std::sync::Arc;
+use std::{ops::Bound, str::FromStr, sync::Arc};
 
 use axum::{
-    extract::{Json, Path, State},
+    extract::{Json, Path, Query, State},
     http::status::StatusCode,
     response::IntoResponse,
 };
 use hopr_crypto_types::types::Hash;
+use hopr_db_api::tickets::{TicketCursor, TicketIndexSelector, TicketSelector};
 use hopr_lib::{
-    HoprBalance, HoprTransportError, ProtocolError, Ticket, TicketStatistics, ToHex,
+    AcknowledgedTicket, AcknowledgedTicketStatus, Address, HoprBalance, HoprTransportError, ProtocolError, Ticket,
+    TicketStatistics, ToHex,
     errors::{HoprLibError, HoprStatusError},
 };
 use serde::Deserialize;
 use serde_with::{DisplayFromStr, serde_as};
 
-use crate::{ApiError, ApiErrorStatus, BASE_PATH, InternalState};
+use crate::{ApiError, ApiErrorStatus, BASE_PATH, InternalState, checksum_address_serializer};
 
 #[serde_as]
 #[derive(Debug, Clone, serde::Serialize, utoipa::ToSchema)]
@@ -119,23 +121,154 @@ pub(super) async fn show_channel_tickets(
     }
 }
 
-/// Endpoint is deprecated and will be removed in the future. Returns an empty array.
+/// Default number of tickets returned in a single page.
+const DEFAULT_TICKETS_PAGE_SIZE: usize = 100;
+
+/// Maximum number of tickets returned in a single page.
+const MAX_TICKETS_PAGE_SIZE: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+/// Status of an acknowledged ticket.
+pub(crate) enum TicketStatus {
+    /// The ticket is available for redeeming or aggregating.
+    Untouched,
+    /// The ticket is currently being redeemed.
+    BeingRedeemed,
+    /// The ticket is currently being aggregated.
+    BeingAggregated,
+}
+
+impl From<AcknowledgedTicketStatus> for TicketStatus {
+    fn from(value: AcknowledgedTicketStatus) -> Self {
+        match value {
+            AcknowledgedTicketStatus::Untouched => Self::Untouched,
+            AcknowledgedTicketStatus::BeingRedeemed => Self::BeingRedeemed,
+            AcknowledgedTicketStatus::BeingAggregated => Self::BeingAggregated,
+        }
+    }
+}
+
+impl From<TicketStatus> for AcknowledgedTicketStatus {
+    fn from(value: TicketStatus) -> Self {
+        match value {
+            TicketStatus::Untouched => Self::Untouched,
+            TicketStatus::BeingRedeemed => Self::BeingRedeemed,
+            TicketStatus::BeingAggregated => Self::BeingAggregated,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, utoipa::ToSchema)]
+#[schema(example = json!({
+        "amount": "1 wxHOPR",
+        "channelEpoch": 1,
+        "channelId": "0x04efc1481d3f106b88527b3844ba40042b823218a9cd29d1aa11c2c2ef8f538f",
+        "index": 0,
+        "indexOffset": 1,
+        "signature": "0xe445fcf4e90d25fe3c9199ccfaff85e23ecce8773304d85e7120f1f38787f2329822470487a37f1b5408c8c0b73e874ee9f7594a632713b6096e616857999891",
+        "winProb": "1",
+        "counterparty": "0x188c4462b75e46f0c7262d7f48d182447b93a93c",
+        "status": "untouched"
+    }))]
+#[serde(rename_all = "camelCase")]
+/// Acknowledged winning ticket received in an incoming channel.
+pub(crate) struct NodeTicket {
+    #[serde(flatten)]
+    ticket: ChannelTicket,
+    /// Address of the ticket issuer.
+    #[serde(serialize_with = "checksum_address_serializer")]
+    #[schema(value_type = String, example = "0x188c4462b75e46f0c7262d7f48d182447b93a93c")]
+    counterparty: Address,
+    status: TicketStatus,
+}
+
+impl From<AcknowledgedTicket> for NodeTicket {
+    fn from(value: AcknowledgedTicket) -> Self {
+        Self {
+            counterparty: *value.ticket.verified_issuer(),
+            status: value.status.into(),
+            ticket: value.ticket.verified_ticket().clone().into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+/// Page of tickets.
+pub(crate) struct NodeTicketsResponse {
+    tickets: Vec<NodeTicket>,
+    /// Cursor to pass to the next request to fetch the following page.
+    /// Missing if there are no more tickets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "0x04efc1481d3f106b88527b3844ba40042b823218a9cd29d1aa11c2c2ef8f538f:1:99")]
+    next_cursor: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, utoipa::IntoParams, utoipa::ToSchema)]
+#[into_params(parameter_in = Query)]
+#[serde(default, rename_all = "camelCase")]
+#[schema(example = json!({
+        "counterparty": "0x188c4462b75e46f0c7262d7f48d182447b93a93c",
+        "status": "untouched",
+        "minAmount": "0.1 wxHOPR",
+        "limit": 50
+    }))]
+/// Parameters for listing tickets.
+pub(crate) struct TicketsQueryRequest {
+    /// Only list tickets in the channel with the given ID.
+    #[schema(required = false)]
+    channel_id: Option<String>,
+    /// Only list tickets issued by the given counterparty.
+    #[schema(required = false)]
+    counterparty: Option<String>,
+    /// Only list tickets with the given status.
+    #[schema(required = false)]
+    status: Option<TicketStatus>,
+    /// Only list tickets with index greater or equal to the given one.
+    #[schema(required = false)]
+    from_index: Option<u64>,
+    /// Only list tickets with index less or equal to the given one.
+    #[schema(required = false)]
+    to_index: Option<u64>,
+    /// Only list tickets worth at least the given amount.
+    #[schema(required = false, example = "0.1 wxHOPR")]
+    min_amount: Option<String>,
+    /// Only list aggregated tickets.
+    #[schema(required = false)]
+    #[serde(default)]
+    only_aggregated: bool,
+    /// Cursor returned with the previous page.
+    #[schema(required = false)]
+    cursor: Option<String>,
+    /// Maximum number of tickets in the page. Default is 100, maximum is 1000.
+    #[schema(required = false)]
+    limit: Option<usize>,
+}
+
+fn format_ticket_cursor(cursor: &TicketCursor) -> String {
+    format!("{}:{}:{}", cursor.channel_id.to_hex(), cursor.epoch, cursor.index)
+}
+
+fn parse_ticket_cursor(cursor: &str) -> Option<TicketCursor> {
+    let mut parts = cursor.split(':');
+    let cursor = TicketCursor {
+        channel_id: Hash::from_hex(parts.next()?).ok()?,
+        epoch: parts.next()?.parse::<u32>().ok()?.into(),
+        index: parts.next()?.parse().ok()?,
+    };
+    parts.next().is_none().then_some(cursor)
+}
+
+/// Lists tickets received in the incoming channels of this node.
 #[utoipa::path(
     get,
     path = const_format::formatcp!("{BASE_PATH}/tickets"),
-    description = "(deprecated) Returns an empty array.",
+    description = "Lists acknowledged winning tickets received in the incoming channels of this node, ordered by channel ID, channel epoch and ticket index. The results are paginated: if there are more tickets than the requested limit, the response contains a cursor to fetch the next page with.",
+    params(TicketsQueryRequest),
     responses(
-        (status = 200, description = "Fetched all tickets in all the channels", body = [ChannelTicket], example = json!([
-        {
-            "amount": "10 wxHOPR",
-            "channelEpoch": 1,
-            "channelId": "0x04efc1481d3f106b88527b3844ba40042b823218a9cd29d1aa11c2c2ef8f538f",
-            "index": 0,
-            "indexOffset": 1,
-            "signature": "0xe445fcf4e90d25fe3c9199ccfaff85e23ecce8773304d85e7120f1f38787f2329822470487a37f1b5408c8c0b73e874ee9f7594a632713b6096e616857999891",
-            "winProb": "1"
-        }
-        ])),
+        (status = 200, description = "Fetched a page of tickets", body = NodeTicketsResponse),
+        (status = 400, description = "Invalid filter or cursor.", body = ApiError),
         (status = 401, description = "Invalid authorization token.", body = ApiError),
         (status = 422, description = "Unknown failure", body = ApiError)
     ),
@@ -145,9 +278,91 @@ pub(super) async fn show_channel_tickets(
     ),
     tag = "Tickets"
     )]
-pub(super) async fn show_all_tickets() -> impl IntoResponse {
-    let tickets: Vec<ChannelTicket> = vec![];
-    (StatusCode::OK, Json(tickets)).into_response()
+pub(super) async fn show_all_tickets(
+    Query(query): Query<TicketsQueryRequest>,
+    State(state): State<Arc<InternalState>>,
+) -> impl IntoResponse {
+    let channel_id = match query.channel_id.as_deref().map(Hash::from_hex).transpose() {
+        Ok(channel_id) => channel_id,
+        Err(_) => return (StatusCode::BAD_REQUEST, ApiErrorStatus::InvalidChannelId).into_response(),
+    };
+    let counterparty = match query.counterparty.as_deref().map(Address::from_hex).transpose() {
+        Ok(counterparty) => counterparty,
+        Err(_) => return (StatusCode::BAD_REQUEST, ApiErrorStatus::InvalidInput).into_response(),
+    };
+    let min_amount = match query.min_amount.as_deref().map(HoprBalance::from_str).transpose() {
+        Ok(min_amount) => min_amount,
+        Err(_) => return (StatusCode::BAD_REQUEST, ApiErrorStatus::InvalidInput).into_response(),
+    };
+    let after = match query.cursor.as_deref().map(parse_ticket_cursor) {
+        Some(None) => return (StatusCode::BAD_REQUEST, ApiErrorStatus::InvalidInput).into_response(),
+        Some(cursor) => cursor,
+        None => None,
+    };
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_TICKETS_PAGE_SIZE)
+        .clamp(1, MAX_TICKETS_PAGE_SIZE);
+
+    let hopr = state.hopr.clone();
+    let channels = match hopr.channels_to(&hopr.me_onchain()).await {
+        Ok(channels) => channels,
+        Err(e) => return (StatusCode::UNPROCESSABLE_ENTITY, ApiErrorStatus::from(e)).into_response(),
+    };
+
+    let mut channels = channels
+        .into_iter()
+        .filter(|c| channel_id.is_none_or(|id| id == c.get_id()))
+        .filter(|c| counterparty.is_none_or(|addr| addr == c.source));
+
+    let Some(first) = channels.next() else {
+        return (
+            StatusCode::OK,
+            Json(NodeTicketsResponse {
+                tickets: vec![],
+                next_cursor: None,
+            }),
+        )
+            .into_response();
+    };
+
+    let mut selector = channels
+        .fold(TicketSelector::from(&first), |selector, c| {
+            selector.also_on_channel(c.get_id(), c.channel_epoch)
+        })
+        .with_aggregated_only(query.only_aggregated);
+
+    if let Some(status) = query.status {
+        selector = selector.with_state(status.into());
+    }
+    if let Some(min_amount) = min_amount {
+        selector = selector.with_amount(min_amount..);
+    }
+    if query.from_index.is_some() || query.to_index.is_some() {
+        // Set directly, because the index range applies to all the selected channels
+        selector.index = TicketIndexSelector::Range((
+            query.from_index.map_or(Bound::Unbounded, Bound::Included),
+            query.to_index.map_or(Bound::Unbounded, Bound::Included),
+        ));
+    }
+
+    match hopr.tickets_page(selector, after, limit).await {
+        Ok(tickets) => {
+            let next_cursor = (tickets.len() == limit)
+                .then(|| tickets.last().map(|t| format_ticket_cursor(&TicketCursor::from(t))))
+                .flatten();
+
+            (
+                StatusCode::OK,
+                Json(NodeTicketsResponse {
+                    tickets: tickets.into_iter().map(NodeTicket::from).collect(),
+                    next_cursor,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => (StatusCode::UNPROCESSABLE_ENTITY, ApiErrorStatus::from(e)).into_response(),
+    }
 }
 
 #[serde_as]
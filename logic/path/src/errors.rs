@@ -25,6 +25,9 @@ pub enum PathError {
     #[error("cannot find {0} hop path {1} -> {2} in the channel graph")]
     PathNotFound(usize, String, String),
 
+    #[error("path violates routing constraints: {0}")]
+    ConstraintViolation(String),
+
     #[error(transparent)]
     OtherError(#[from] GeneralError),
 }
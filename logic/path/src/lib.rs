@@ -7,6 +7,7 @@ pub mod errors;
 pub mod selectors;
 
 use std::{
+    collections::{BTreeSet, HashMap},
     fmt::{Display, Formatter},
     hash::Hash,
     ops::Deref,
@@ -20,7 +21,9 @@ use crate::{
     channel_graph::ChannelGraph,
     errors::{
         PathError,
-        PathError::{ChannelNotOpened, InvalidPeer, LoopsNotAllowed, MissingChannel, PathNotValid},
+        PathError::{
+            ChannelNotOpened, ConstraintViolation, InvalidPeer, LoopsNotAllowed, MissingChannel, PathNotValid,
+        },
     },
 };
 
@@ -242,6 +245,44 @@ pub trait PathAddressResolver {
     async fn resolve_transport_address(&self, address: &Address) -> Result<Option<OffchainPublicKey>, PathError>;
     /// Resolve [`Address`] from the given [`OffchainPublicKey`]
     async fn resolve_chain_address(&self, key: &OffchainPublicKey) -> Result<Option<Address>, PathError>;
+    /// Resolve the address of the Safe the node with the given [`Address`] is registered with.
+    async fn resolve_safe_address(&self, address: &Address) -> Result<Option<Address>, PathError>;
+}
+
+/// Additional constraints that relays on a path must satisfy.
+///
+/// The destination of a path is never considered a relay, therefore it is not subject to
+/// these constraints.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PathConstraints {
+    /// Nodes that must not be used as relays.
+    pub excluded_relays: BTreeSet<Address>,
+    /// If set, the return path must not share any relays with the forward path.
+    pub disjoint_paths: bool,
+    /// Maximum number of relays on a single path that can be registered with the same Safe.
+    ///
+    /// Relays whose Safe is not known are not counted towards this limit.
+    pub max_relays_per_safe: Option<usize>,
+}
+
+impl PathConstraints {
+    /// Returns `true` if these constraints do not restrict the path in any way.
+    pub fn is_unconstrained(&self) -> bool {
+        self.excluded_relays.is_empty() && !self.disjoint_paths && self.max_relays_per_safe.is_none()
+    }
+
+    /// Checks if the given node can be used as a relay.
+    pub fn is_relay_allowed(&self, relay: &Address) -> bool {
+        !self.excluded_relays.contains(relay)
+    }
+
+    /// Returns a copy of these constraints that additionally exclude the given relays.
+    pub fn excluding<I: IntoIterator<Item = Address>>(&self, relays: I) -> Self {
+        let mut ret = self.clone();
+        ret.excluded_relays.extend(relays);
+        ret
+    }
 }
 
 /// Represents [`NonEmptyPath`] that has been resolved and validated.
@@ -262,6 +303,27 @@ impl ValidatedPath {
     /// and do resolve to the corresponding [`OffchainPublicKeys`](OffchainPublicKey) or
     /// [`Addresses`](Address).
     pub async fn new<N, P, O, R>(origin: O, path: P, cg: &ChannelGraph, resolver: &R) -> errors::Result<ValidatedPath>
+    where
+        N: Into<PathAddress> + Copy,
+        P: NonEmptyPath<N>,
+        O: Into<PathAddress>,
+        R: PathAddressResolver,
+    {
+        Self::new_with_constraints(origin, path, cg, resolver, &PathConstraints::default()).await
+    }
+
+    /// Same as [`ValidatedPath::new`], but additionally makes sure the relays on the path
+    /// satisfy the given [`PathConstraints`].
+    ///
+    /// The [disjointness](PathConstraints::disjoint_paths) cannot be checked on a single path,
+    /// and therefore must be ensured by the caller via [excluded relays](PathConstraints::excluded_relays).
+    pub async fn new_with_constraints<N, P, O, R>(
+        origin: O,
+        path: P,
+        cg: &ChannelGraph,
+        resolver: &R,
+        constraints: &PathConstraints,
+    ) -> errors::Result<ValidatedPath>
     where
         N: Into<PathAddress> + Copy,
         P: NonEmptyPath<N>,
@@ -325,6 +387,26 @@ impl ValidatedPath {
 
         debug_assert_eq!(keys.len(), addrs.len());
 
+        let relays = &addrs[..addrs.len() - 1];
+        if let Some(excluded) = relays.iter().find(|relay| !constraints.is_relay_allowed(relay)) {
+            return Err(ConstraintViolation(format!("relay {excluded} is excluded")));
+        }
+
+        if let Some(max_relays_per_safe) = constraints.max_relays_per_safe {
+            let mut relays_per_safe = HashMap::<Address, usize>::new();
+            for relay in relays {
+                if let Some(safe) = resolver.resolve_safe_address(relay).await? {
+                    let count = relays_per_safe.entry(safe).or_default();
+                    *count += 1;
+                    if *count > max_relays_per_safe {
+                        return Err(ConstraintViolation(format!(
+                            "more than {max_relays_per_safe} relays registered with safe {safe}"
+                        )));
+                    }
+                }
+            }
+        }
+
         Ok(ValidatedPath(TransportPath(keys), ChainPath(addrs)))
     }
 
@@ -421,6 +503,10 @@ pub(crate) mod tests {
         async fn resolve_chain_address(&self, key: &OffchainPublicKey) -> Result<Option<Address>, PathError> {
             Ok(self.get_by_left(key).copied())
         }
+
+        async fn resolve_safe_address(&self, _address: &Address) -> Result<Option<Address>, PathError> {
+            Ok(None)
+        }
     }
 
     pub fn dummy_channel(src: Address, dst: Address, status: ChannelStatus) -> ChannelEntry {
@@ -747,4 +833,82 @@ pub(crate) mod tests {
 
         Ok(())
     }
+
+    struct SafeResolver(HashMap<Address, Address>);
+
+    #[async_trait]
+    impl PathAddressResolver for SafeResolver {
+        async fn resolve_transport_address(&self, address: &Address) -> Result<Option<OffchainPublicKey>, PathError> {
+            PATH_ADDRS.resolve_transport_address(address).await
+        }
+
+        async fn resolve_chain_address(&self, key: &OffchainPublicKey) -> Result<Option<Address>, PathError> {
+            PATH_ADDRS.resolve_chain_address(key).await
+        }
+
+        async fn resolve_safe_address(&self, address: &Address) -> Result<Option<Address>, PathError> {
+            Ok(self.0.get(address).copied())
+        }
+    }
+
+    #[tokio::test]
+    async fn validated_path_should_not_allow_excluded_relays() -> anyhow::Result<()> {
+        let (cg, peers) = create_graph_and_resolver_entries(ADDRESSES[0]);
+
+        // path: 0 -> 1 -> 2 -> 3
+        let chain_path = ChainPath::new([peers[1].1, peers[2].1, peers[3].1])?;
+
+        let constraints = PathConstraints::default().excluding([peers[2].1]);
+        let res = ValidatedPath::new_with_constraints(
+            ADDRESSES[0],
+            chain_path.clone(),
+            &cg,
+            PATH_ADDRS.deref(),
+            &constraints,
+        )
+        .await;
+        ensure!(
+            matches!(res, Err(ConstraintViolation(_))),
+            "path must not be valid with an excluded relay"
+        );
+
+        // The destination is not a relay
+        let constraints = PathConstraints::default().excluding([peers[3].1]);
+        ValidatedPath::new_with_constraints(ADDRESSES[0], chain_path, &cg, PATH_ADDRS.deref(), &constraints)
+            .await
+            .context("excluding the destination must not invalidate the path")?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn validated_path_should_limit_relays_per_safe() -> anyhow::Result<()> {
+        let (cg, peers) = create_graph_and_resolver_entries(ADDRESSES[0]);
+        let safe = Address::from_str("0x9000000000000000000000000000000000000001")?;
+
+        // path: 0 -> 1 -> 2 -> 3 -> 4, relays 1 and 2 share the same Safe
+        let chain_path = ChainPath::new([peers[1].1, peers[2].1, peers[3].1, peers[4].1])?;
+        let resolver = SafeResolver(HashMap::from([(peers[1].1, safe), (peers[2].1, safe)]));
+
+        let constraints = PathConstraints {
+            max_relays_per_safe: Some(1),
+            ..Default::default()
+        };
+        let res =
+            ValidatedPath::new_with_constraints(ADDRESSES[0], chain_path.clone(), &cg, &resolver, &constraints).await;
+        ensure!(
+            matches!(res, Err(ConstraintViolation(_))),
+            "path must not be valid with two relays of the same safe"
+        );
+
+        let constraints = PathConstraints {
+            max_relays_per_safe: Some(2),
+            ..Default::default()
+        };
+        ValidatedPath::new_with_constraints(ADDRESSES[0], chain_path, &cg, &resolver, &constraints)
+            .await
+            .context("path must be valid when the limit is not exceeded")?;
+
+        Ok(())
+    }
 }
@@ -7,7 +7,7 @@ use hopr_primitive_types::prelude::*;
 use tracing::trace;
 
 use crate::{
-    ChannelPath,
+    ChannelPath, PathConstraints,
     channel_graph::{ChannelEdge, ChannelGraph, Node},
     errors::{PathError, Result},
     selectors::{EdgeWeighting, PathSelector},
@@ -155,7 +155,8 @@ impl<CW: EdgeWeighting<U256>> DfsPathSelector<CW> {
     /// * `initial_source`: the initial node on the path
     /// * `final_destination`: the desired destination node (will not be part of the path)
     /// * `current_path`: currently selected relayers
-    #[tracing::instrument(level = "trace", skip(self))]
+    /// * `constraints`: additional [`PathConstraints`] the relayers must satisfy
+    #[tracing::instrument(level = "trace", skip(self, constraints))]
     fn is_next_hop_usable(
         &self,
         next_hop: &Node,
@@ -163,6 +164,7 @@ impl<CW: EdgeWeighting<U256>> DfsPathSelector<CW> {
         initial_source: &Address,
         final_destination: &Address,
         current_path: &[Address],
+        constraints: &PathConstraints,
     ) -> bool {
         debug_assert_eq!(next_hop.address, edge.channel.destination);
 
@@ -179,6 +181,12 @@ impl<CW: EdgeWeighting<U256>> DfsPathSelector<CW> {
             return false;
         }
 
+        // Do not use nodes that are explicitly excluded
+        if !constraints.is_relay_allowed(&next_hop.address) {
+            trace!("excluded relay not allowed");
+            return false;
+        }
+
         // Only use nodes that have shown to be somewhat reliable
         if next_hop.node_score < self.cfg.node_score_threshold {
             trace!("node quality threshold not satisfied");
@@ -235,12 +243,13 @@ where
     /// The function implements a randomized best-first search through the path space. The graph
     /// traversal is bounded by `self.max_iterations` to prevent from long-running path
     /// selection runs.
-    async fn select_path(
+    async fn select_constrained_path(
         &self,
         source: Address,
         destination: Address,
         min_hops: usize,
         max_hops: usize,
+        constraints: &PathConstraints,
     ) -> Result<ChannelPath> {
         // The protocol does not support >3 hop paths and will presumably never do,
         // so we can exclude it here.
@@ -253,7 +262,7 @@ where
         // Populate the queue with possible initial path offsprings
         let mut queue = graph
             .open_channels_from(source)
-            .filter(|(node, edge)| self.is_next_hop_usable(node, edge, &source, &destination, &[]))
+            .filter(|(node, edge)| self.is_next_hop_usable(node, edge, &source, &destination, &[], constraints))
             .map(|(_, edge)| WeightedChannelPath::default().extend::<CW>(edge))
             .collect::<BinaryHeap<_>>();
 
@@ -290,7 +299,7 @@ where
             let mut new_channels = graph
                 .open_channels_from(last_peer)
                 .filter(|(next_hop, edge)| {
-                    self.is_next_hop_usable(next_hop, edge, &source, &destination, &current.path)
+                    self.is_next_hop_usable(next_hop, edge, &source, &destination, &current.path, constraints)
                 })
                 .peekable();
 
@@ -647,4 +656,34 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_should_not_find_path_through_excluded_relay() -> anyhow::Result<()> {
+        let graph = Arc::new(RwLock::new(define_graph(
+            "0 [1] -> 1, 0 [1] -> 2",
+            ADDRESSES[0],
+            |_| 1_f64,
+            |_, _| 0.0,
+        )));
+        let selector = DfsPathSelector::<RandomizedEdgeWeighting>::new(graph.clone(), Default::default());
+        let constraints = PathConstraints::default().excluding([ADDRESSES[1]]);
+
+        for _ in 0..10 {
+            let path = selector
+                .select_constrained_path(ADDRESSES[0], ADDRESSES[5], 1, 1, &constraints)
+                .await?;
+            assert_eq!(vec![ADDRESSES[2]], path, "must avoid the excluded relay");
+        }
+
+        let constraints = constraints.excluding([ADDRESSES[2]]);
+        assert!(
+            selector
+                .select_constrained_path(ADDRESSES[0], ADDRESSES[5], 1, 1, &constraints)
+                .await
+                .is_err(),
+            "must not find a path when all relays are excluded"
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,495 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use hopr_crypto_random::random_float;
+use hopr_internal_types::prelude::*;
+use hopr_primitive_types::prelude::*;
+use tracing::trace;
+
+use crate::{
+    ChannelPath, PathConstraints,
+    channel_graph::{ChannelEdge, ChannelGraph, Node},
+    errors::{PathError, Result},
+    selectors::PathSelector,
+};
+
+/// Precision used when computing the ratio of two channel balances.
+const BALANCE_RATIO_PRECISION: u64 = 1_000_000;
+
+/// Configuration of the [`KShortestPathSelector`].
+///
+/// The cost of each hop is a weighted sum of three penalties, each normalized to `[0, 1]`:
+/// - latency penalty: average latency of the hop divided by `reference_latency` (capped at 1, unknown latency is 1)
+/// - quality penalty: `1 - node_score` of the hop
+/// - balance penalty: `1 - balance / balance_saturation` of the channel leading to the hop (capped at 0)
+#[derive(Clone, Debug, PartialEq, smart_default::SmartDefault)]
+pub struct KShortestPathSelectorConfig {
+    /// Number of the cheapest candidate paths the final path is randomly sampled from.
+    /// Default is 5
+    #[default(5)]
+    pub k: usize,
+    /// The maximum number of partial paths expanded before the search stops.
+    /// Default is 1000
+    #[default(1000)]
+    pub max_iterations: usize,
+    /// Weight of the latency penalty of a hop.
+    /// Default is 1.0
+    #[default(1.0)]
+    pub latency_weight: f64,
+    /// Weight of the node quality penalty of a hop.
+    /// Default is 1.0
+    #[default(1.0)]
+    pub quality_weight: f64,
+    /// Weight of the channel balance penalty of a hop.
+    /// Default is 0.5
+    #[default(0.5)]
+    pub balance_weight: f64,
+    /// Latency at which a hop receives the maximum latency penalty.
+    /// Default is 100 ms
+    #[default(Duration::from_millis(100))]
+    pub reference_latency: Duration,
+    /// Channel balance at which a hop receives no balance penalty.
+    /// Default is 10 wxHOPR
+    #[default(HoprBalance::new_base(10))]
+    pub balance_saturation: HoprBalance,
+    /// Peer quality threshold for a node to be taken into account.
+    /// Default is 0.5
+    #[default(0.5)]
+    pub node_score_threshold: f64,
+    /// Channel score threshold for a channel to be taken into account.
+    /// Default is 0
+    #[default(0.0)]
+    pub edge_score_threshold: f64,
+    /// The maximum latency of the first hop
+    /// Default is 100 ms
+    #[default(Some(Duration::from_millis(100)))]
+    pub max_first_hop_latency: Option<Duration>,
+    /// If true, include paths with payment channels, which have no
+    /// funds in it.
+    /// Defaults to false.
+    #[default(false)]
+    pub allow_zero_edge_weight: bool,
+    /// Nodes that must never be used as intermediate hops.
+    pub excluded_nodes: HashSet<Address>,
+    /// Channels that must never be used on the path.
+    pub excluded_channels: HashSet<ChannelId>,
+}
+
+/// Candidate path with its accumulated cost.
+#[derive(Clone, Debug)]
+struct CostedChannelPath {
+    path: Vec<Address>,
+    cost: f64,
+}
+
+impl PartialEq for CostedChannelPath {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for CostedChannelPath {}
+
+impl PartialOrd for CostedChannelPath {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CostedChannelPath {
+    /// Reversed on the cost, so that the [`BinaryHeap`] yields the cheapest path first.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+/// Path selector that samples from the `k` cheapest paths in the channel graph.
+///
+/// The cost of a path is the sum of its hop costs (see [`KShortestPathSelectorConfig`]),
+/// which favors low-latency and high-quality relays connected via well-funded channels.
+/// Because all hop costs are non-negative, a best-first search over partial paths finds
+/// the `k` cheapest paths of the requested length. The final path is then sampled from these
+/// candidates with probability inversely proportional to `1 + cost`, so that the same relays
+/// do not always get selected.
+#[derive(Clone, Debug)]
+pub struct KShortestPathSelector {
+    graph: Arc<async_lock::RwLock<ChannelGraph>>,
+    cfg: KShortestPathSelectorConfig,
+}
+
+impl KShortestPathSelector {
+    /// Creates a new path selector with the given [config](KShortestPathSelectorConfig) and
+    /// [`ChannelGraph`].
+    pub fn new(graph: Arc<async_lock::RwLock<ChannelGraph>>, cfg: KShortestPathSelectorConfig) -> Self {
+        Self { graph, cfg }
+    }
+
+    /// Computes the cost of moving to `next_hop` via the given `edge`.
+    fn hop_cost(&self, next_hop: &Node, edge: &ChannelEdge) -> f64 {
+        let latency_penalty = next_hop
+            .latency
+            .average()
+            .filter(|_| !self.cfg.reference_latency.is_zero())
+            .map(|avg| (avg.as_secs_f64() / self.cfg.reference_latency.as_secs_f64()).min(1.0))
+            .unwrap_or(1.0);
+
+        let quality_penalty = 1.0 - next_hop.node_score.clamp(0.0, 1.0);
+
+        let saturation = self.cfg.balance_saturation.amount();
+        let balance = edge.channel.balance.amount();
+        let balance_penalty = if saturation.is_zero() || balance >= saturation {
+            0.0
+        } else {
+            let ratio = (balance * U256::from(BALANCE_RATIO_PRECISION) / saturation).low_u64();
+            1.0 - ratio as f64 / BALANCE_RATIO_PRECISION as f64
+        };
+
+        self.cfg.latency_weight * latency_penalty
+            + self.cfg.quality_weight * quality_penalty
+            + self.cfg.balance_weight * balance_penalty
+    }
+
+    /// Determines whether a `next_hop` node can extend the `current_path`.
+    ///
+    /// Applies the same rules as the [DFS selector](crate::selectors::dfs::DfsPathSelector),
+    /// and additionally rejects all excluded nodes and channels.
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn is_next_hop_usable(
+        &self,
+        next_hop: &Node,
+        edge: &ChannelEdge,
+        initial_source: &Address,
+        final_destination: &Address,
+        current_path: &[Address],
+        constraints: &PathConstraints,
+    ) -> bool {
+        debug_assert_eq!(next_hop.address, edge.channel.destination);
+
+        if next_hop.address.eq(initial_source) || next_hop.address.eq(final_destination) {
+            trace!("loopback not allowed");
+            return false;
+        }
+
+        if self.cfg.excluded_nodes.contains(&next_hop.address) || !constraints.is_relay_allowed(&next_hop.address) {
+            trace!("node is excluded");
+            return false;
+        }
+
+        if self.cfg.excluded_channels.contains(&edge.channel.get_id()) {
+            trace!("channel is excluded");
+            return false;
+        }
+
+        if next_hop.node_score < self.cfg.node_score_threshold {
+            trace!("node quality threshold not satisfied");
+            return false;
+        }
+
+        if edge
+            .edge_score
+            .is_some_and(|score| score < self.cfg.edge_score_threshold)
+        {
+            trace!("channel score threshold not satisfied");
+            return false;
+        }
+
+        if current_path.is_empty()
+            && self
+                .cfg
+                .max_first_hop_latency
+                .is_some_and(|limit| next_hop.latency.average().is_none_or(|avg_latency| avg_latency > limit))
+        {
+            trace!("first hop latency too high");
+            return false;
+        }
+
+        if current_path.contains(&next_hop.address) {
+            trace!("circles not allowed");
+            return false;
+        }
+
+        if !self.cfg.allow_zero_edge_weight && edge.channel.balance.is_zero() {
+            trace!(%next_hop, "zero stake channels not allowed");
+            return false;
+        }
+
+        true
+    }
+
+    /// Randomly picks one of the candidates, favoring the cheaper ones.
+    fn sample(candidates: Vec<CostedChannelPath>) -> Option<CostedChannelPath> {
+        let weights = candidates.iter().map(|c| 1.0 / (1.0 + c.cost)).collect::<Vec<_>>();
+        let mut threshold = random_float() * weights.iter().sum::<f64>();
+
+        let last = candidates.len().checked_sub(1)?;
+        candidates.into_iter().zip(weights).enumerate().find_map(|(i, (c, w))| {
+            threshold -= w;
+            (threshold < 0.0 || i == last).then_some(c)
+        })
+    }
+}
+
+#[async_trait]
+impl PathSelector for KShortestPathSelector {
+    /// Attempts to find a path with at least `min_hops` hops and at most `max_hops` hops
+    /// that goes from `source` to `destination`. There does not need to be
+    /// a payment channel to `destination`, so the path only includes intermediate hops.
+    ///
+    /// Paths of exactly `max_hops` hops are preferred, shorter paths (of at least `min_hops`)
+    /// are used only if no such path exists.
+    async fn select_constrained_path(
+        &self,
+        source: Address,
+        destination: Address,
+        min_hops: usize,
+        max_hops: usize,
+        constraints: &PathConstraints,
+    ) -> Result<ChannelPath> {
+        if !(1..=INTERMEDIATE_HOPS).contains(&max_hops) || !(1..=max_hops).contains(&min_hops) || self.cfg.k == 0 {
+            return Err(GeneralError::InvalidInput.into());
+        }
+
+        let graph = self.graph.read().await;
+
+        let mut queue = graph
+            .open_channels_from(source)
+            .filter(|(node, edge)| self.is_next_hop_usable(node, edge, &source, &destination, &[], constraints))
+            .map(|(node, edge)| CostedChannelPath {
+                path: vec![node.address],
+                cost: self.hop_cost(node, edge),
+            })
+            .collect::<BinaryHeap<_>>();
+
+        let mut complete = Vec::with_capacity(self.cfg.k);
+        let mut shorter = Vec::with_capacity(self.cfg.k);
+
+        let mut iters = 0;
+        while let Some(current) = queue.pop() {
+            if iters > self.cfg.max_iterations {
+                trace!(iters, "maximum number of iterations reached");
+                break;
+            }
+            iters += 1;
+
+            if current.path.len() == max_hops {
+                complete.push(current);
+                if complete.len() == self.cfg.k {
+                    break;
+                }
+                continue;
+            }
+
+            let last_peer = *current.path.last().expect("path is never empty");
+            queue.extend(
+                graph
+                    .open_channels_from(last_peer)
+                    .filter(|(next_hop, edge)| {
+                        self.is_next_hop_usable(next_hop, edge, &source, &destination, &current.path, constraints)
+                    })
+                    .map(|(next_hop, edge)| {
+                        let mut path = current.path.clone();
+                        path.push(next_hop.address);
+                        CostedChannelPath {
+                            path,
+                            cost: current.cost + self.hop_cost(next_hop, edge),
+                        }
+                    }),
+            );
+
+            if current.path.len() >= min_hops && shorter.len() < self.cfg.k {
+                shorter.push(current);
+            }
+        }
+
+        trace!(
+            iters,
+            complete = complete.len(),
+            shorter = shorter.len(),
+            "finished candidate path search"
+        );
+
+        let candidates = if complete.is_empty() { shorter } else { complete };
+        Self::sample(candidates)
+            .map(|selected| {
+                trace!(path = ?selected.path, cost = selected.cost, "selected path");
+                ChannelPath::from_iter(selected.path)
+            })
+            .ok_or_else(|| PathError::PathNotFound(max_hops, source.to_string(), destination.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Deref;
+
+    use async_lock::RwLock;
+
+    use super::*;
+    use crate::{ChainPath, Path, ValidatedPath, channel_graph::NodeScoreUpdate, tests::ADDRESSES};
+
+    /// Builds a graph from the given channels `(source, destination, stake)` (indices into `ADDRESSES`)
+    /// and initializes each node with the latency (in ms) from `latencies`.
+    fn create_graph(channels: &[(usize, usize, u32)], latencies: &[u64]) -> Arc<RwLock<ChannelGraph>> {
+        let mut graph = ChannelGraph::new(ADDRESSES[0], Default::default());
+        for (src, dst, stake) in channels {
+            graph.update_channel(ChannelEntry::new(
+                ADDRESSES[*src],
+                ADDRESSES[*dst],
+                HoprBalance::new_base(*stake),
+                U256::zero(),
+                ChannelStatus::Open,
+                U256::zero(),
+            ));
+        }
+        for (i, latency) in latencies.iter().enumerate().skip(1) {
+            graph.update_node_score(
+                &ADDRESSES[i],
+                NodeScoreUpdate::Initialize(Duration::from_millis(*latency), 1.0),
+            );
+        }
+        Arc::new(RwLock::new(graph))
+    }
+
+    /// 0 -> 1 -> 3 -> 5 (fast), 0 -> 2 -> 4 -> 5 (slow)
+    fn two_route_graph() -> Arc<RwLock<ChannelGraph>> {
+        create_graph(
+            &[(0, 1, 10), (1, 3, 10), (0, 2, 10), (2, 4, 10), (3, 4, 10), (4, 3, 10)],
+            &[0, 10, 90, 10, 90, 10],
+        )
+    }
+
+    fn single_candidate_cfg() -> KShortestPathSelectorConfig {
+        KShortestPathSelectorConfig {
+            k: 1,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_should_not_find_path_if_isolated() {
+        let selector = KShortestPathSelector::new(create_graph(&[], &[]), Default::default());
+
+        selector
+            .select_path(ADDRESSES[0], ADDRESSES[5], 1, 2)
+            .await
+            .expect_err("should not find a path");
+    }
+
+    #[tokio::test]
+    async fn test_should_reject_invalid_hop_counts() {
+        let selector = KShortestPathSelector::new(two_route_graph(), Default::default());
+
+        assert!(selector.select_path(ADDRESSES[0], ADDRESSES[5], 0, 0).await.is_err());
+        assert!(selector.select_path(ADDRESSES[0], ADDRESSES[5], 3, 2).await.is_err());
+        assert!(
+            selector
+                .select_path(ADDRESSES[0], ADDRESSES[5], 1, INTERMEDIATE_HOPS + 1)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_select_lowest_latency_path() -> anyhow::Result<()> {
+        let graph = two_route_graph();
+        let selector = KShortestPathSelector::new(graph.clone(), single_candidate_cfg());
+
+        let path = selector.select_path(ADDRESSES[0], ADDRESSES[5], 2, 2).await?;
+        assert_eq!(vec![ADDRESSES[1], ADDRESSES[3]], path.hops());
+
+        ValidatedPath::new(
+            ADDRESSES[0],
+            ChainPath::from_channel_path(path, ADDRESSES[5]),
+            graph.read().await.deref(),
+            crate::tests::PATH_ADDRS.deref(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_should_prefer_better_funded_channels() -> anyhow::Result<()> {
+        let graph = create_graph(
+            &[(0, 1, 1), (1, 3, 1), (0, 2, 10), (2, 4, 10)],
+            &[0, 10, 10, 10, 10, 10],
+        );
+        let selector = KShortestPathSelector::new(graph, single_candidate_cfg());
+
+        let path = selector.select_path(ADDRESSES[0], ADDRESSES[5], 2, 2).await?;
+        assert_eq!(vec![ADDRESSES[2], ADDRESSES[4]], path.hops());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_should_sample_from_multiple_candidates() -> anyhow::Result<()> {
+        let graph = create_graph(&[(0, 1, 10), (0, 2, 10)], &[0, 10, 10]);
+        let selector = KShortestPathSelector::new(
+            graph,
+            KShortestPathSelectorConfig {
+                k: 2,
+                ..Default::default()
+            },
+        );
+
+        let mut seen = HashSet::new();
+        for _ in 0..100 {
+            seen.insert(selector.select_path(ADDRESSES[0], ADDRESSES[5], 1, 1).await?.hops()[0]);
+        }
+        assert_eq!(HashSet::from([ADDRESSES[1], ADDRESSES[2]]), seen);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_should_not_use_excluded_nodes() -> anyhow::Result<()> {
+        let selector = KShortestPathSelector::new(
+            two_route_graph(),
+            KShortestPathSelectorConfig {
+                excluded_nodes: HashSet::from([ADDRESSES[3]]),
+                ..single_candidate_cfg()
+            },
+        );
+
+        let path = selector.select_path(ADDRESSES[0], ADDRESSES[5], 2, 2).await?;
+        assert_eq!(vec![ADDRESSES[2], ADDRESSES[4]], path.hops());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_should_not_use_excluded_channels() -> anyhow::Result<()> {
+        let excluded = generate_channel_id(&ADDRESSES[1], &ADDRESSES[3]);
+        let selector = KShortestPathSelector::new(
+            two_route_graph(),
+            KShortestPathSelectorConfig {
+                excluded_channels: HashSet::from([excluded]),
+                ..single_candidate_cfg()
+            },
+        );
+
+        let path = selector.select_path(ADDRESSES[0], ADDRESSES[5], 2, 2).await?;
+        assert_eq!(vec![ADDRESSES[2], ADDRESSES[4]], path.hops());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_should_prefer_longest_path_and_fall_back_to_shorter() -> anyhow::Result<()> {
+        let selector = KShortestPathSelector::new(two_route_graph(), Default::default());
+        let path = selector.select_path(ADDRESSES[0], ADDRESSES[5], 1, 3).await?;
+        assert_eq!(3, path.num_hops());
+
+        let selector = KShortestPathSelector::new(create_graph(&[(0, 1, 10)], &[0, 10]), Default::default());
+        let path = selector.select_path(ADDRESSES[0], ADDRESSES[5], 1, 3).await?;
+        assert_eq!(vec![ADDRESSES[1]], path.hops());
+
+        Ok(())
+    }
+}
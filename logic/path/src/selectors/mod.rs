@@ -1,4 +1,5 @@
 pub mod dfs;
+pub mod k_shortest;
 
 use std::ops::Add;
 
@@ -6,7 +7,7 @@ use async_trait::async_trait;
 use hopr_internal_types::prelude::*;
 use hopr_primitive_types::primitives::Address;
 
-use crate::{ChannelPath, channel_graph::ChannelEdge, errors::Result};
+use crate::{ChannelPath, PathConstraints, channel_graph::ChannelEdge, errors::Result};
 
 /// Computes weights of edges corresponding to [`ChannelEdge`].
 pub trait EdgeWeighting<W>
@@ -20,6 +21,23 @@ where
 /// Trait for implementing a custom path selection algorithm from the channel graph.
 #[async_trait]
 pub trait PathSelector {
+    /// Select a path of maximum `max_hops` from `source` to `destination` in the given channel graph,
+    /// such that all its relays satisfy the given [`PathConstraints`].
+    /// NOTE: the resulting path does not contain `source` but does contain `destination`.
+    /// Fails if no such path can be found.
+    ///
+    /// Constraints that require resolution of additional information (such as
+    /// [`PathConstraints::max_relays_per_safe`]) are not guaranteed to be satisfied by the selector
+    /// and are enforced later during the path validation.
+    async fn select_constrained_path(
+        &self,
+        source: Address,
+        destination: Address,
+        min_hops: usize,
+        max_hops: usize,
+        constraints: &PathConstraints,
+    ) -> Result<ChannelPath>;
+
     /// Select a path of maximum `max_hops` from `source` to `destination` in the given channel graph.
     /// NOTE: the resulting path does not contain `source` but does contain `destination`.
     /// Fails if no such path can be found.
@@ -29,7 +47,10 @@ pub trait PathSelector {
         destination: Address,
         min_hops: usize,
         max_hops: usize,
-    ) -> Result<ChannelPath>;
+    ) -> Result<ChannelPath> {
+        self.select_constrained_path(source, destination, min_hops, max_hops, &PathConstraints::default())
+            .await
+    }
 
     /// Constructs a new valid packet `Path` from source to the given destination.
     /// This method uses `INTERMEDIATE_HOPS` as the maximum number of hops.
@@ -0,0 +1,355 @@
+//! ## Dry-run mode
+//! If `dry_run` is set in the [MultiStrategyConfig](crate::strategy::MultiStrategyConfig), the sub-strategies do not
+//! perform any on-chain actions nor ticket aggregations. Instead, each action that a sub-strategy would have taken is
+//! recorded as a [StrategyIntent] into the [StrategyIntentLog] of the
+//! [MultiStrategy](crate::strategy::MultiStrategy).
+//!
+//! This makes it possible to review the effects of a strategy configuration before letting it act for real.
+//! Since no action is taken, the strategies keep proposing the same actions on each tick, as long as the state of the
+//! node does not change. The log keeps only the last [MAX_RECORDED_INTENTS] intents.
+use std::{collections::VecDeque, sync::Arc, time::SystemTime};
+
+use async_lock::RwLock;
+use async_trait::async_trait;
+use hopr_chain_actions::{
+    action_queue::PendingAction,
+    channels::ChannelActions,
+    errors::{ChainActionsError, Result},
+    redeem::TicketRedeemActions,
+};
+use hopr_crypto_types::prelude::Hash;
+use hopr_db_sql::api::tickets::{AggregationPrerequisites, TicketSelector};
+use hopr_internal_types::prelude::*;
+use hopr_platform::time::native::current_time;
+use hopr_primitive_types::prelude::*;
+use hopr_transport_ticket_aggregation::TicketAggregatorTrait;
+use tracing::info;
+
+use crate::{Strategy, history::TriggeredActions};
+
+/// Maximum number of intents kept in the [StrategyIntentLog].
+pub const MAX_RECORDED_INTENTS: usize = 1000;
+
+/// An action a strategy would have taken if it was not running in the dry-run mode.
+#[derive(Clone, Debug, PartialEq)]
+pub enum IntendedAction {
+    /// Opening of a channel to `destination` with the `amount` staked.
+    OpenChannel { destination: Address, amount: HoprBalance },
+    /// Funding of the given channel with the `amount`.
+    FundChannel { channel_id: Hash, amount: HoprBalance },
+    /// Closure of the channel with the `counterparty` in the given `direction`.
+    CloseChannel {
+        counterparty: Address,
+        direction: ChannelDirection,
+    },
+    /// Redemption of a single ticket.
+    RedeemTicket {
+        channel_id: Hash,
+        index: u64,
+        amount: HoprBalance,
+    },
+    /// Redemption of all tickets matching the description.
+    RedeemTickets { selector: String },
+    /// Aggregation of tickets in the given channel.
+    AggregateTickets { channel_id: Hash },
+}
+
+/// An [IntendedAction] recorded by a strategy.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StrategyIntent {
+    /// Name of the strategy that would have taken the action.
+    pub strategy: String,
+    /// Time when the action would have been taken.
+    pub timestamp: SystemTime,
+    /// The action that would have been taken.
+    pub action: IntendedAction,
+}
+
+/// Shared log of [StrategyIntents](StrategyIntent) recorded in the dry-run mode.
+#[derive(Clone, Debug, Default)]
+pub struct StrategyIntentLog(Arc<RwLock<VecDeque<StrategyIntent>>>);
+
+impl StrategyIntentLog {
+    /// Records an intended action of the given strategy.
+    pub async fn record(&self, strategy: &str, action: IntendedAction) {
+        info!(strategy, ?action, "dry-run strategy intent");
+
+        let mut intents = self.0.write().await;
+        if intents.len() >= MAX_RECORDED_INTENTS {
+            intents.pop_front();
+        }
+        intents.push_back(StrategyIntent {
+            strategy: strategy.into(),
+            timestamp: current_time(),
+            action,
+        });
+    }
+
+    /// Returns all the recorded intents, oldest first.
+    pub async fn intents(&self) -> Vec<StrategyIntent> {
+        self.0.read().await.iter().cloned().collect()
+    }
+}
+
+/// Wraps the actions given to a strategy.
+///
+/// If an intent log is given, the actions are only recorded there, otherwise they are passed to the
+/// inner implementation. In both cases, the actions are also collected as [TriggeredActions] for the
+/// [execution history](crate::history).
+#[derive(Clone, Debug)]
+pub(crate) struct DryRunActions<A> {
+    inner: A,
+    strategy: String,
+    intents: Option<StrategyIntentLog>,
+    triggered: TriggeredActions,
+}
+
+impl<A> DryRunActions<A> {
+    pub fn new(inner: A, strategy: &Strategy, intents: Option<StrategyIntentLog>, triggered: TriggeredActions) -> Self {
+        Self {
+            inner,
+            strategy: strategy.to_string(),
+            intents,
+            triggered,
+        }
+    }
+
+    /// Records the action if in the dry-run mode, returns `false` otherwise.
+    async fn recorded(&self, action: IntendedAction) -> bool {
+        self.triggered.push(action.clone()).await;
+        if let Some(intents) = &self.intents {
+            intents.record(&self.strategy, action).await;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+fn not_executed() -> PendingAction {
+    Box::pin(futures::future::err(ChainActionsError::InvalidState(
+        "action not executed in dry-run mode".into(),
+    )))
+}
+
+#[async_trait]
+impl<A: ChannelActions + Send + Sync> ChannelActions for DryRunActions<A> {
+    async fn open_channel(&self, destination: Address, amount: HoprBalance) -> Result<PendingAction> {
+        if self.recorded(IntendedAction::OpenChannel { destination, amount }).await {
+            Ok(not_executed())
+        } else {
+            self.inner.open_channel(destination, amount).await
+        }
+    }
+
+    async fn fund_channel(&self, channel_id: Hash, amount: HoprBalance) -> Result<PendingAction> {
+        if self.recorded(IntendedAction::FundChannel { channel_id, amount }).await {
+            Ok(not_executed())
+        } else {
+            self.inner.fund_channel(channel_id, amount).await
+        }
+    }
+
+    async fn close_channel(
+        &self,
+        counterparty: Address,
+        direction: ChannelDirection,
+        redeem_before_close: bool,
+    ) -> Result<PendingAction> {
+        if self
+            .recorded(IntendedAction::CloseChannel {
+                counterparty,
+                direction,
+            })
+            .await
+        {
+            Ok(not_executed())
+        } else {
+            self.inner
+                .close_channel(counterparty, direction, redeem_before_close)
+                .await
+        }
+    }
+}
+
+#[async_trait]
+impl<A: TicketRedeemActions + Send + Sync> TicketRedeemActions for DryRunActions<A> {
+    async fn redeem_all_tickets(&self, only_aggregated: bool) -> Result<Vec<PendingAction>> {
+        let selector = format!("all tickets (only aggregated: {only_aggregated})");
+        if self.recorded(IntendedAction::RedeemTickets { selector }).await {
+            Ok(vec![])
+        } else {
+            self.inner.redeem_all_tickets(only_aggregated).await
+        }
+    }
+
+    async fn redeem_tickets_with_counterparty(
+        &self,
+        counterparty: &Address,
+        only_aggregated: bool,
+    ) -> Result<Vec<PendingAction>> {
+        let selector = format!("tickets from {counterparty} (only aggregated: {only_aggregated})");
+        if self.recorded(IntendedAction::RedeemTickets { selector }).await {
+            Ok(vec![])
+        } else {
+            self.inner
+                .redeem_tickets_with_counterparty(counterparty, only_aggregated)
+                .await
+        }
+    }
+
+    async fn redeem_tickets_in_channel(
+        &self,
+        channel: &ChannelEntry,
+        only_aggregated: bool,
+    ) -> Result<Vec<PendingAction>> {
+        let selector = format!("tickets in {channel} (only aggregated: {only_aggregated})");
+        if self.recorded(IntendedAction::RedeemTickets { selector }).await {
+            Ok(vec![])
+        } else {
+            self.inner.redeem_tickets_in_channel(channel, only_aggregated).await
+        }
+    }
+
+    async fn redeem_tickets(&self, selector: TicketSelector) -> Result<Vec<PendingAction>> {
+        if self
+            .recorded(IntendedAction::RedeemTickets {
+                selector: selector.to_string(),
+            })
+            .await
+        {
+            Ok(vec![])
+        } else {
+            self.inner.redeem_tickets(selector).await
+        }
+    }
+
+    async fn redeem_ticket(&self, ack: AcknowledgedTicket) -> Result<PendingAction> {
+        let ticket = ack.verified_ticket();
+        if self
+            .recorded(IntendedAction::RedeemTicket {
+                channel_id: ticket.channel_id,
+                index: ticket.index,
+                amount: ticket.amount,
+            })
+            .await
+        {
+            Ok(not_executed())
+        } else {
+            self.inner.redeem_ticket(ack).await
+        }
+    }
+}
+
+#[async_trait]
+impl TicketAggregatorTrait for DryRunActions<Arc<dyn TicketAggregatorTrait + Send + Sync + 'static>> {
+    async fn aggregate_tickets(
+        &self,
+        channel: &Hash,
+        prerequisites: AggregationPrerequisites,
+    ) -> hopr_transport_ticket_aggregation::Result<()> {
+        if self
+            .recorded(IntendedAction::AggregateTickets { channel_id: *channel })
+            .await
+        {
+            Ok(())
+        } else {
+            self.inner.aggregate_tickets(channel, prerequisites).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+    use mockall::mock;
+
+    use super::*;
+    use crate::auto_funding::AutoFundingStrategyConfig;
+
+    mock! {
+        ChannelAct { }
+        #[async_trait]
+        impl ChannelActions for ChannelAct {
+            async fn open_channel(&self, destination: Address, amount: HoprBalance) -> Result<PendingAction>;
+            async fn fund_channel(&self, channel_id: Hash, amount: HoprBalance) -> Result<PendingAction>;
+            async fn close_channel(
+                &self,
+                counterparty: Address,
+                direction: ChannelDirection,
+                redeem_before_close: bool,
+            ) -> Result<PendingAction>;
+        }
+    }
+
+    #[tokio::test]
+    async fn dry_run_actions_should_record_intents_instead_of_performing_actions() -> anyhow::Result<()> {
+        let destination: Address = hex!("44f23fa14130ca540b37251309700b6c281d972e").into();
+
+        let mut inner = MockChannelAct::new();
+        inner.expect_open_channel().never();
+        inner.expect_close_channel().never();
+
+        let intents = StrategyIntentLog::default();
+        let triggered = TriggeredActions::default();
+        let actions = DryRunActions::new(
+            inner,
+            &Strategy::AutoFunding(AutoFundingStrategyConfig::default()),
+            Some(intents.clone()),
+            triggered.clone(),
+        );
+
+        let pending = actions.open_channel(destination, 10_u32.into()).await?;
+        assert!(pending.await.is_err(), "dry-run action must never be confirmed");
+        let pending = actions
+            .close_channel(destination, ChannelDirection::Outgoing, false)
+            .await?;
+        assert!(pending.await.is_err(), "dry-run action must never be confirmed");
+
+        let recorded = intents.intents().await;
+        assert_eq!(2, recorded.len());
+        assert!(recorded.iter().all(|intent| intent.strategy == "auto_funding"));
+        assert_eq!(
+            IntendedAction::OpenChannel {
+                destination,
+                amount: 10_u32.into()
+            },
+            recorded[0].action
+        );
+        assert_eq!(
+            IntendedAction::CloseChannel {
+                counterparty: destination,
+                direction: ChannelDirection::Outgoing
+            },
+            recorded[1].action
+        );
+        assert_eq!(
+            recorded.into_iter().map(|intent| intent.action).collect::<Vec<_>>(),
+            triggered.take().await
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn intent_log_should_keep_only_the_latest_intents() {
+        let intents = StrategyIntentLog::default();
+        for i in 0..MAX_RECORDED_INTENTS + 1 {
+            intents
+                .record(
+                    "test",
+                    IntendedAction::RedeemTickets {
+                        selector: i.to_string(),
+                    },
+                )
+                .await;
+        }
+
+        let recorded = intents.intents().await;
+        assert_eq!(MAX_RECORDED_INTENTS, recorded.len());
+        assert_eq!(
+            IntendedAction::RedeemTickets { selector: "1".into() },
+            recorded[0].action
+        );
+    }
+}
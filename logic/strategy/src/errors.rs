@@ -8,6 +8,9 @@ pub enum StrategyError {
     #[error("criteria to trigger the strategy were not satisfied")]
     CriteriaNotSatisfied,
 
+    #[error("invalid strategy configuration: {0}")]
+    InvalidConfiguration(String),
+
     #[error("non-specific strategy error: {0}")]
     Other(String),
 
@@ -0,0 +1,141 @@
+//! ## Execution history
+//! The [MultiStrategy](crate::strategy::MultiStrategy) records every invocation of its sub-strategies as a
+//! [StrategyExecution] into the [StrategyHistory]. Each record contains the event that triggered the invocation,
+//! its duration, the error if the invocation failed and all the actions the sub-strategy triggered during it.
+//!
+//! The history is kept in memory only and is bounded: for each sub-strategy and each event type, only the
+//! last [MAX_RECORDED_EXECUTIONS] executions are kept. This makes sure that frequent events (such as winning tickets)
+//! do not push out the rarer ones (such as ticks).
+//!
+//! The actions are attributed to the invocation during which they were triggered. Should the same sub-strategy be
+//! invoked concurrently by different events, the actions might get attributed to either of the invocations.
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use async_lock::{Mutex, RwLock};
+
+use crate::dry_run::IntendedAction;
+
+/// Maximum number of executions kept in the [StrategyHistory] per strategy and event type.
+pub const MAX_RECORDED_EXECUTIONS: usize = 100;
+
+/// Event of the [SingularStrategy](crate::strategy::SingularStrategy) that invoked the strategy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, strum::Display, strum::EnumString, strum::IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum StrategyEvent {
+    /// Periodic tick.
+    Tick,
+    /// A winning ticket has been acknowledged.
+    AcknowledgedWinningTicket,
+    /// One of the node's own channels has changed.
+    OwnChannelChanged,
+}
+
+/// Record of a single invocation of a strategy.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StrategyExecution {
+    /// Name of the strategy.
+    pub strategy: String,
+    /// Event that invoked the strategy.
+    pub event: StrategyEvent,
+    /// Time when the invocation started.
+    pub started: SystemTime,
+    /// Duration of the invocation.
+    pub duration: Duration,
+    /// Description of the [StrategyError](crate::errors::StrategyError), if the invocation failed.
+    pub error: Option<String>,
+    /// Actions triggered by the strategy during the invocation.
+    pub actions: Vec<IntendedAction>,
+}
+
+impl StrategyExecution {
+    /// Indicates whether the invocation finished successfully.
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+type ExecutionsPerStrategyEvent = HashMap<(String, StrategyEvent), VecDeque<StrategyExecution>>;
+
+/// Shared history of [StrategyExecutions](StrategyExecution).
+#[derive(Clone, Debug, Default)]
+pub struct StrategyHistory(Arc<RwLock<ExecutionsPerStrategyEvent>>);
+
+impl StrategyHistory {
+    /// Records a strategy execution.
+    pub async fn record(&self, execution: StrategyExecution) {
+        let mut history = self.0.write().await;
+        let executions = history
+            .entry((execution.strategy.clone(), execution.event))
+            .or_default();
+        if executions.len() >= MAX_RECORDED_EXECUTIONS {
+            executions.pop_front();
+        }
+        executions.push_back(execution);
+    }
+
+    /// Returns all the recorded executions, oldest first.
+    pub async fn executions(&self) -> Vec<StrategyExecution> {
+        let mut executions = self.0.read().await.values().flatten().cloned().collect::<Vec<_>>();
+        executions.sort_by_key(|execution| execution.started);
+        executions
+    }
+}
+
+/// Collects the actions triggered by a single strategy, until they are taken into its [StrategyExecution].
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TriggeredActions(Arc<Mutex<Vec<IntendedAction>>>);
+
+impl TriggeredActions {
+    pub async fn push(&self, action: IntendedAction) {
+        self.0.lock().await.push(action);
+    }
+
+    pub async fn take(&self) -> Vec<IntendedAction> {
+        std::mem::take(&mut *self.0.lock().await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn execution(strategy: &str, event: StrategyEvent, started: u64) -> StrategyExecution {
+        StrategyExecution {
+            strategy: strategy.into(),
+            event,
+            started: SystemTime::UNIX_EPOCH + Duration::from_secs(started),
+            duration: Duration::from_millis(10),
+            error: None,
+            actions: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn strategy_history_should_be_bounded_per_strategy_and_event() {
+        let history = StrategyHistory::default();
+
+        history.record(execution("aggregating", StrategyEvent::Tick, 0)).await;
+        for i in 0..MAX_RECORDED_EXECUTIONS as u64 + 1 {
+            history
+                .record(execution(
+                    "aggregating",
+                    StrategyEvent::AcknowledgedWinningTicket,
+                    i + 1,
+                ))
+                .await;
+        }
+
+        let executions = history.executions().await;
+        assert_eq!(MAX_RECORDED_EXECUTIONS + 1, executions.len());
+        assert_eq!(StrategyEvent::Tick, executions[0].event, "tick must not be pushed out");
+        assert_eq!(SystemTime::UNIX_EPOCH + Duration::from_secs(2), executions[1].started);
+        assert!(
+            executions.windows(2).all(|w| w[0].started <= w[1].started),
+            "executions must be ordered"
+        );
+    }
+}
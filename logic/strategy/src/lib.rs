@@ -7,7 +7,10 @@
 //! - [auto funding strategy](crate::auto_funding)
 //! - [auto redeeming strategy](crate::auto_redeeming)
 //! - [aggregating strategy](crate::aggregating)
+//! - [rebalancing strategy](crate::rebalancing)
 //! - [multiple strategy chains](crate::strategy)
+//! - [dry-run mode](crate::dry_run)
+//! - [execution history](crate::history)
 //!
 //! HOPRd can be configured to use any of the above strategies.
 //!
@@ -44,6 +47,7 @@ use std::str::FromStr;
 use hopr_primitive_types::prelude::*;
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumString, VariantNames};
+use validator::{Validate, ValidationErrors};
 
 use crate::{
     Strategy::{Aggregating, AutoRedeeming},
@@ -52,6 +56,7 @@ use crate::{
     auto_redeeming::AutoRedeemingStrategyConfig,
     channel_finalizer::ClosureFinalizerStrategyConfig,
     promiscuous::PromiscuousStrategyConfig,
+    rebalancing::RebalancingStrategyConfig,
     strategy::MultiStrategyConfig,
 };
 
@@ -59,8 +64,11 @@ pub mod aggregating;
 pub mod auto_funding;
 pub mod auto_redeeming;
 mod channel_finalizer;
+pub mod dry_run;
 pub mod errors;
+pub mod history;
 pub mod promiscuous;
+pub mod rebalancing;
 pub mod strategy;
 
 /// Lists all possible strategies with their respective configurations.
@@ -71,11 +79,27 @@ pub enum Strategy {
     Aggregating(AggregatingStrategyConfig),
     AutoRedeeming(AutoRedeemingStrategyConfig),
     AutoFunding(AutoFundingStrategyConfig),
+    Rebalancing(RebalancingStrategyConfig),
     ClosureFinalizer(ClosureFinalizerStrategyConfig),
     Multi(MultiStrategyConfig),
     Passive,
 }
 
+impl Validate for Strategy {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        match self {
+            Strategy::Promiscuous(cfg) => cfg.validate(),
+            Strategy::Aggregating(cfg) => cfg.validate(),
+            Strategy::AutoRedeeming(cfg) => cfg.validate(),
+            Strategy::AutoFunding(cfg) => cfg.validate(),
+            Strategy::Rebalancing(cfg) => cfg.validate(),
+            Strategy::ClosureFinalizer(cfg) => cfg.validate(),
+            Strategy::Multi(cfg) => cfg.validate(),
+            Strategy::Passive => Ok(()),
+        }
+    }
+}
+
 /// Default HOPR node strategies (in order).
 ///
 /// ## Aggregation strategy
@@ -110,6 +134,7 @@ pub fn hopr_default_strategies() -> MultiStrategyConfig {
                 minimum_redeem_ticket_value: HoprBalance::from_str("0.09 wxHOPR").unwrap(),
             }),
         ],
+        dry_run: false,
     }
 }
 
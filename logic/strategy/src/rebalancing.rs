@@ -0,0 +1,525 @@
+//! ## Rebalancing Strategy
+//! This strategy moves stake from idle outgoing channels to outgoing channels that actually carry traffic.
+//!
+//! On each tick, the strategy samples the number of tickets issued in every open outgoing channel since the previous
+//! tick (using the outgoing ticket index kept in the ticket database). The traffic on the node's own outgoing
+//! channels cannot be deduced from the ticket statistics of the node, because these only cover the tickets the node
+//! has received.
+//!
+//! Based on the samples, the channels are classified as follows:
+//! - **hot** channels are those with at least `hot_channel_threshold` tickets issued since the last tick. If their
+//!   balance is below `hot_channel_target_balance`, the strategy funds them up to this target balance.
+//! - **cold** channels are those without any ticket issued for `cold_channel_ticks` consecutive ticks. If
+//!   `close_cold_channels` is set and their balance is at least `min_cold_channel_balance`, the strategy closes them,
+//!   so their stake returns to the node's Safe. Closing is the only way to take stake out of a channel.
+//!
+//! All the funding done by the strategy is taken from the `budget`, and the stake of each cold channel closed by the
+//! strategy is added back to it, but the budget never grows beyond its configured amount. The strategy never funds
+//! more than the current Safe balance allows.
+//! The number of on-chain transactions issued on a single tick is limited by `max_transactions_per_tick`, closures
+//! of the coldest channels with the largest balance take precedence, followed by funding of the hottest channels.
+//!
+//! For details on default parameters see [RebalancingStrategyConfig].
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display, Formatter},
+    sync::atomic::Ordering,
+};
+
+use async_lock::RwLock;
+use async_trait::async_trait;
+use hopr_chain_actions::channels::ChannelActions;
+use hopr_crypto_types::prelude::Hash;
+use hopr_db_sql::{
+    api::tickets::HoprDbTicketOperations, channels::HoprDbChannelOperations, info::HoprDbInfoOperations,
+};
+use hopr_internal_types::prelude::*;
+#[cfg(all(feature = "prometheus", not(test)))]
+use hopr_metrics::metrics::SimpleCounter;
+use hopr_primitive_types::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, serde_as};
+use tracing::{debug, error, info};
+use validator::Validate;
+
+use crate::{Strategy, errors::Result, strategy::SingularStrategy};
+
+#[cfg(all(feature = "prometheus", not(test)))]
+lazy_static::lazy_static! {
+    static ref METRIC_COUNT_FUNDINGS: SimpleCounter =
+        SimpleCounter::new("hopr_strategy_rebalancing_funding_count", "Count of hot channel funding decisions").unwrap();
+    static ref METRIC_COUNT_CLOSURES: SimpleCounter =
+        SimpleCounter::new("hopr_strategy_rebalancing_closure_count", "Count of cold channel closure decisions").unwrap();
+}
+
+#[inline]
+fn default_hot_channel_threshold() -> u64 {
+    10
+}
+
+#[inline]
+fn default_cold_channel_ticks() -> u32 {
+    60
+}
+
+#[inline]
+fn default_hot_channel_target_balance() -> HoprBalance {
+    HoprBalance::new_base(20)
+}
+
+#[inline]
+fn default_min_cold_channel_balance() -> HoprBalance {
+    HoprBalance::new_base(1)
+}
+
+#[inline]
+fn just_true() -> bool {
+    true
+}
+
+#[inline]
+fn default_budget() -> HoprBalance {
+    HoprBalance::new_base(100)
+}
+
+#[inline]
+fn default_max_transactions_per_tick() -> usize {
+    5
+}
+
+/// Configuration of [RebalancingStrategy].
+#[serde_as]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, smart_default::SmartDefault, Validate, Serialize, Deserialize)]
+pub struct RebalancingStrategyConfig {
+    /// Minimum number of tickets issued in an outgoing channel since the last tick
+    /// for the channel to be considered hot.
+    ///
+    /// Default is 10.
+    #[validate(range(min = 1))]
+    #[serde(default = "default_hot_channel_threshold")]
+    #[default(default_hot_channel_threshold())]
+    pub hot_channel_threshold: u64,
+
+    /// Number of consecutive ticks without any ticket issued in an outgoing channel
+    /// for the channel to be considered cold.
+    ///
+    /// Default is 60.
+    #[validate(range(min = 1))]
+    #[serde(default = "default_cold_channel_ticks")]
+    #[default(default_cold_channel_ticks())]
+    pub cold_channel_ticks: u32,
+
+    /// Balance that hot channels are funded up to.
+    ///
+    /// Default is 20 wxHOPR.
+    #[serde_as(as = "DisplayFromStr")]
+    #[serde(default = "default_hot_channel_target_balance")]
+    #[default(default_hot_channel_target_balance())]
+    pub hot_channel_target_balance: HoprBalance,
+
+    /// Minimum balance of a cold channel for it to be worth closing.
+    ///
+    /// Default is 1 wxHOPR.
+    #[serde_as(as = "DisplayFromStr")]
+    #[serde(default = "default_min_cold_channel_balance")]
+    #[default(default_min_cold_channel_balance())]
+    pub min_cold_channel_balance: HoprBalance,
+
+    /// If set, cold channels are closed to reclaim their stake.
+    /// Otherwise, the strategy only funds hot channels.
+    ///
+    /// Default is true.
+    #[serde(default = "just_true")]
+    #[default(just_true())]
+    pub close_cold_channels: bool,
+
+    /// Total amount of tokens the strategy can use to fund hot channels.
+    /// The stake of cold channels closed by the strategy is added back to the budget,
+    /// up to this configured amount.
+    ///
+    /// Default is 100 wxHOPR.
+    #[serde_as(as = "DisplayFromStr")]
+    #[serde(default = "default_budget")]
+    #[default(default_budget())]
+    pub budget: HoprBalance,
+
+    /// Maximum number of on-chain transactions the strategy issues on a single tick.
+    ///
+    /// Default is 5.
+    #[validate(range(min = 1))]
+    #[serde(default = "default_max_transactions_per_tick")]
+    #[default(default_max_transactions_per_tick())]
+    pub max_transactions_per_tick: usize,
+}
+
+/// A decision made by the Rebalancing strategy on each tick,
+/// represents which channels should be closed and which should be funded (and by how much).
+#[derive(Clone, Debug, PartialEq, Default)]
+struct RebalancingDecision {
+    to_close: Vec<ChannelEntry>,
+    to_fund: Vec<(ChannelEntry, HoprBalance)>,
+}
+
+impl Display for RebalancingDecision {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "rebalancing decision: closing ({}), funding ({})",
+            self.to_close.len(),
+            self.to_fund.len()
+        )
+    }
+}
+
+/// Traffic observed in a single outgoing channel.
+#[derive(Clone, Copy, Debug)]
+struct ChannelActivity {
+    last_ticket_index: u64,
+    idle_ticks: u32,
+}
+
+#[derive(Debug)]
+struct RebalancingState {
+    activity: HashMap<Hash, ChannelActivity>,
+    remaining_budget: HoprBalance,
+}
+
+/// This strategy funds outgoing channels that carry traffic and closes the idle ones,
+/// within a given budget.
+pub struct RebalancingStrategy<Db, A>
+where
+    Db: HoprDbChannelOperations + HoprDbTicketOperations + HoprDbInfoOperations + Clone + Send + Sync,
+    A: ChannelActions,
+{
+    db: Db,
+    hopr_chain_actions: A,
+    cfg: RebalancingStrategyConfig,
+    state: RwLock<RebalancingState>,
+}
+
+impl<Db, A> RebalancingStrategy<Db, A>
+where
+    Db: HoprDbChannelOperations + HoprDbTicketOperations + HoprDbInfoOperations + Clone + Send + Sync,
+    A: ChannelActions,
+{
+    pub fn new(cfg: RebalancingStrategyConfig, db: Db, hopr_chain_actions: A) -> Self {
+        #[cfg(all(feature = "prometheus", not(test)))]
+        {
+            lazy_static::initialize(&METRIC_COUNT_FUNDINGS);
+            lazy_static::initialize(&METRIC_COUNT_CLOSURES);
+        }
+
+        Self {
+            db,
+            hopr_chain_actions,
+            state: RwLock::new(RebalancingState {
+                activity: HashMap::new(),
+                remaining_budget: cfg.budget,
+            }),
+            cfg,
+        }
+    }
+
+    async fn collect_tick_decision(&self, state: &mut RebalancingState) -> Result<RebalancingDecision> {
+        let our_outgoing_open_channels = self
+            .db
+            .get_outgoing_channels(None)
+            .await
+            .map_err(hopr_db_sql::api::errors::DbError::from)?
+            .into_iter()
+            .filter(|channel| channel.status == ChannelStatus::Open)
+            .collect::<Vec<_>>();
+
+        // Stop tracking channels which are no longer open
+        state
+            .activity
+            .retain(|id, _| our_outgoing_open_channels.iter().any(|c| c.get_id().eq(id)));
+
+        let mut hot_channels = Vec::new();
+        let mut cold_channels = Vec::new();
+        for channel in our_outgoing_open_channels {
+            let ticket_index = self
+                .db
+                .get_outgoing_ticket_index(channel.get_id())
+                .await?
+                .load(Ordering::SeqCst);
+
+            let activity = state.activity.entry(channel.get_id()).or_insert(ChannelActivity {
+                last_ticket_index: ticket_index,
+                idle_ticks: 0,
+            });
+
+            // The index can also go down when the channel gets re-opened
+            let issued_tickets = ticket_index.saturating_sub(activity.last_ticket_index);
+            activity.last_ticket_index = ticket_index;
+            if issued_tickets > 0 {
+                activity.idle_ticks = 0;
+            } else {
+                activity.idle_ticks = activity.idle_ticks.saturating_add(1);
+            }
+
+            if issued_tickets >= self.cfg.hot_channel_threshold {
+                if channel.balance < self.cfg.hot_channel_target_balance {
+                    debug!(%channel, issued_tickets, balance = %channel.balance, "channel is hot and needs funding");
+                    hot_channels.push((channel, issued_tickets));
+                }
+            } else if activity.idle_ticks >= self.cfg.cold_channel_ticks
+                && self.cfg.close_cold_channels
+                && channel.balance >= self.cfg.min_cold_channel_balance
+            {
+                debug!(%channel, idle_ticks = activity.idle_ticks, balance = %channel.balance, "channel is cold");
+                cold_channels.push(channel);
+            }
+        }
+
+        let mut decision = RebalancingDecision::default();
+        let mut remaining_budget = state.remaining_budget;
+        let mut remaining_txs = self.cfg.max_transactions_per_tick;
+
+        // Reclaim the largest stakes first
+        cold_channels.sort_unstable_by(|c1, c2| c2.balance.cmp(&c1.balance));
+        for channel in cold_channels.into_iter().take(remaining_txs) {
+            remaining_budget = (remaining_budget + channel.balance).min(self.cfg.budget);
+            decision.to_close.push(channel);
+        }
+        remaining_txs -= decision.to_close.len();
+
+        let safe_balance = self
+            .db
+            .get_safe_hopr_balance(None)
+            .await
+            .map_err(hopr_db_sql::api::errors::DbError::from)?;
+        let mut available = remaining_budget.min(safe_balance);
+
+        // Fund the hottest channels first
+        hot_channels.sort_unstable_by(|(_, t1), (_, t2)| t2.cmp(t1));
+        for (channel, _) in hot_channels.into_iter().take(remaining_txs) {
+            let amount = (self.cfg.hot_channel_target_balance - channel.balance).min(available);
+            if amount.is_zero() {
+                debug!(%channel, "budget exhausted, cannot fund hot channel");
+                break;
+            }
+            available -= amount;
+            decision.to_fund.push((channel, amount));
+        }
+
+        Ok(decision)
+    }
+}
+
+impl<Db, A> Debug for RebalancingStrategy<Db, A>
+where
+    Db: HoprDbChannelOperations + HoprDbTicketOperations + HoprDbInfoOperations + Clone + Send + Sync,
+    A: ChannelActions,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", Strategy::Rebalancing(self.cfg))
+    }
+}
+
+impl<Db, A> Display for RebalancingStrategy<Db, A>
+where
+    Db: HoprDbChannelOperations + HoprDbTicketOperations + HoprDbInfoOperations + Clone + Send + Sync,
+    A: ChannelActions,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Strategy::Rebalancing(self.cfg))
+    }
+}
+
+#[async_trait]
+impl<Db, A> SingularStrategy for RebalancingStrategy<Db, A>
+where
+    Db: HoprDbChannelOperations + HoprDbTicketOperations + HoprDbInfoOperations + Clone + Send + Sync,
+    A: ChannelActions + Send + Sync,
+{
+    async fn on_tick(&self) -> Result<()> {
+        let mut state = self.state.write().await;
+
+        let decision = self.collect_tick_decision(&mut state).await?;
+        debug!(%decision, "collected rebalancing decision");
+
+        for channel in decision.to_close.iter() {
+            match self
+                .hopr_chain_actions
+                .close_channel(channel.destination, ChannelDirection::Outgoing, false)
+                .await
+            {
+                Ok(_) => {
+                    // Intentionally do not await result of the channel transaction
+                    debug!(destination = %channel.destination, "issued closure of cold channel");
+                    state.remaining_budget = (state.remaining_budget + channel.balance).min(self.cfg.budget);
+                    state.activity.remove(&channel.get_id());
+
+                    #[cfg(all(feature = "prometheus", not(test)))]
+                    METRIC_COUNT_CLOSURES.increment();
+                }
+                Err(e) => {
+                    error!(error = %e, %channel, "error while closing cold channel");
+                }
+            }
+        }
+
+        for (channel, amount) in decision.to_fund.iter() {
+            match self.hopr_chain_actions.fund_channel(channel.get_id(), *amount).await {
+                Ok(_) => {
+                    // Intentionally do not await result of the channel transaction
+                    debug!(%channel, %amount, "issued funding of hot channel");
+                    state.remaining_budget -= *amount;
+
+                    #[cfg(all(feature = "prometheus", not(test)))]
+                    METRIC_COUNT_FUNDINGS.increment();
+                }
+                Err(e) => {
+                    error!(error = %e, %channel, "error while funding hot channel");
+                }
+            }
+        }
+
+        info!(%decision, remaining_budget = %state.remaining_budget, "on tick executed");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{FutureExt, future::ok};
+    use hex_literal::hex;
+    use hopr_chain_actions::action_queue::{ActionConfirmation, PendingAction};
+    use hopr_chain_types::{actions::Action, chain_events::ChainEventType};
+    use hopr_crypto_random::random_bytes;
+    use hopr_crypto_types::prelude::*;
+    use hopr_db_sql::db::HoprDb;
+    use lazy_static::lazy_static;
+    use mockall::mock;
+
+    use super::*;
+
+    lazy_static! {
+        static ref ALICE: ChainKeypair = ChainKeypair::from_secret(&hex!(
+            "492057cf93e99b31d2a85bc5e98a9c3aa0021feec52c227cc8170e8f7d047775"
+        ))
+        .expect("lazy static keypair should be valid");
+        static ref BOB: Address = hex!("44f23fa14130ca540b37251309700b6c281d972e").into();
+        static ref CHRIS: Address = hex!("b6021e0860dd9d96c9ff0a73e2e5ba3a466ba234").into();
+        static ref DAVE: Address = hex!("68499f50ff68d523385dc60686069935d17d762a").into();
+        static ref EUGENE: Address = hex!("5f98dc63889681eb4306f0e3b5ee2e04b13af7c8").into();
+    }
+
+    mock! {
+        ChannelAct { }
+        #[async_trait]
+        impl ChannelActions for ChannelAct {
+            async fn open_channel(&self, destination: Address, amount: HoprBalance) -> hopr_chain_actions::errors::Result<PendingAction>;
+            async fn fund_channel(&self, channel_id: Hash, amount: HoprBalance) -> hopr_chain_actions::errors::Result<PendingAction>;
+            async fn close_channel(
+                &self,
+                counterparty: Address,
+                direction: ChannelDirection,
+                redeem_before_close: bool,
+            ) -> hopr_chain_actions::errors::Result<PendingAction>;
+        }
+    }
+
+    async fn mock_channel(db: &HoprDb, dst: Address, balance: HoprBalance) -> anyhow::Result<ChannelEntry> {
+        let channel = ChannelEntry::new(
+            ALICE.public().to_address(),
+            dst,
+            balance,
+            U256::zero(),
+            ChannelStatus::Open,
+            U256::zero(),
+        );
+        db.upsert_channel(None, channel).await?;
+
+        Ok(channel)
+    }
+
+    async fn issue_tickets(db: &HoprDb, channel: &ChannelEntry, count: usize) -> anyhow::Result<()> {
+        for _ in 0..count {
+            db.increment_outgoing_ticket_index(channel.get_id()).await?;
+        }
+        Ok(())
+    }
+
+    fn mock_action_confirmation_closure(channel: ChannelEntry) -> ActionConfirmation {
+        ActionConfirmation {
+            tx_hash: Hash::from(random_bytes::<{ Hash::SIZE }>()),
+            event: Some(ChainEventType::ChannelClosureInitiated(channel)),
+            action: Action::CloseChannel(channel, ChannelDirection::Outgoing),
+        }
+    }
+
+    fn mock_action_confirmation_funding(channel: ChannelEntry, amount: HoprBalance) -> ActionConfirmation {
+        ActionConfirmation {
+            tx_hash: Hash::from(random_bytes::<{ Hash::SIZE }>()),
+            event: Some(ChainEventType::ChannelBalanceIncreased(channel, amount)),
+            action: Action::FundChannel(channel, amount),
+        }
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_rebalancing_strategy_should_move_stake_from_cold_to_hot_channels() -> anyhow::Result<()> {
+        let db = HoprDb::new_in_memory(ALICE.clone()).await?;
+        db.set_safe_hopr_balance(None, 1000.into()).await?;
+
+        let hot_1 = mock_channel(&db, *BOB, 5.into()).await?;
+        let hot_2 = mock_channel(&db, *CHRIS, 10.into()).await?;
+        let cold = mock_channel(&db, *DAVE, 10.into()).await?;
+        let _cold_dust = mock_channel(&db, *EUGENE, 1.into()).await?;
+
+        let cfg = RebalancingStrategyConfig {
+            hot_channel_threshold: 2,
+            cold_channel_ticks: 2,
+            hot_channel_target_balance: 20.into(),
+            min_cold_channel_balance: 2.into(),
+            close_cold_channels: true,
+            budget: 15.into(),
+            max_transactions_per_tick: 2,
+        };
+
+        let mut actions = MockChannelAct::new();
+        actions
+            .expect_close_channel()
+            .times(1)
+            .withf(|dst, dir, _| DAVE.eq(dst) && ChannelDirection::Outgoing.eq(dir))
+            .return_once(move |_, _, _| Ok(ok(mock_action_confirmation_closure(cold)).boxed()));
+        actions
+            .expect_fund_channel()
+            .times(1)
+            .withf(move |id, amount| hot_2.get_id().eq(id) && HoprBalance::from(10_u32).eq(amount))
+            .return_once(move |_, amount| Ok(ok(mock_action_confirmation_funding(hot_2, amount)).boxed()));
+        actions
+            .expect_fund_channel()
+            .times(1)
+            .withf(move |id, amount| hot_1.get_id().eq(id) && HoprBalance::from(5_u32).eq(amount))
+            .return_once(move |_, amount| Ok(ok(mock_action_confirmation_funding(hot_1, amount)).boxed()));
+
+        let strat = RebalancingStrategy::new(cfg, db.clone(), actions);
+
+        // First tick only samples the channels
+        strat.on_tick().await?;
+
+        // Second tick:
+        // - the channel to Dave becomes cold and is closed, the channel to Eugene is not worth closing
+        // - both channels to Bob and Chris are hot, but the transaction limit allows funding only the hottest one
+        issue_tickets(&db, &hot_1, 3).await?;
+        issue_tickets(&db, &hot_2, 5).await?;
+        strat.on_tick().await?;
+        assert_eq!(
+            HoprBalance::from(5_u32),
+            strat.state.read().await.remaining_budget,
+            "closed stake must not grow the budget beyond the configured amount"
+        );
+
+        // Third tick: the channel to Bob is still hot, and gets funded with the rest of the budget
+        issue_tickets(&db, &hot_1, 2).await?;
+        strat.on_tick().await?;
+        assert!(
+            strat.state.read().await.remaining_budget.is_zero(),
+            "budget must be spent"
+        );
+
+        Ok(())
+    }
+}
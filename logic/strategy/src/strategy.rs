@@ -17,33 +17,72 @@
 //! and if this happens, it will issue automatically the final close transaction, which transitions the state to
 //! `Closed`. This can be controlled by the `finalize_channel_closure` parameter.
 //!
+//! If the `dry_run` flag is set, the sub-strategies do not perform any actions and only record them instead.
+//! See the [dry_run](crate::dry_run) module for details.
+//!
+//! Every invocation of a sub-strategy is recorded into the execution history,
+//! see the [history](crate::history) module for details.
+//!
 //! For details on default parameters see [MultiStrategyConfig].
+//!
+//! ## Runtime reconfiguration
+//!
+//! The top-most `MultiStrategy` of a node can be wrapped into a [ReconfigurableStrategy], which allows replacing
+//! it with a newly configured one while the node is running. The replacement waits until all the strategy events
+//! currently being executed finish, so that no strategy is interrupted in the middle of its work.
 use std::{
     fmt::{Debug, Display, Formatter},
+    future::Future,
     sync::Arc,
+    time::Instant,
 };
 
+use async_lock::RwLock;
 use async_trait::async_trait;
 use hopr_chain_actions::ChainActions;
 use hopr_db_sql::HoprDbAllOperations;
 use hopr_internal_types::prelude::*;
+use hopr_platform::time::native::current_time;
 use hopr_transport_ticket_aggregation::TicketAggregatorTrait;
 use serde::{Deserialize, Serialize};
-use tracing::{error, warn};
+use tracing::{error, info, warn};
 use validator::Validate;
 #[cfg(all(feature = "prometheus", not(test)))]
-use {hopr_metrics::metrics::MultiGauge, strum::VariantNames};
+use {
+    hopr_metrics::metrics::{MultiCounter, MultiGauge, MultiHistogram},
+    strum::VariantNames,
+};
 
 use crate::{
-    Strategy, aggregating::AggregatingStrategy, auto_funding::AutoFundingStrategy,
-    auto_redeeming::AutoRedeemingStrategy, channel_finalizer::ClosureFinalizerStrategy, errors::Result,
+    Strategy,
+    aggregating::AggregatingStrategy,
+    auto_funding::AutoFundingStrategy,
+    auto_redeeming::AutoRedeemingStrategy,
+    channel_finalizer::ClosureFinalizerStrategy,
+    dry_run::{DryRunActions, StrategyIntentLog},
+    errors::{Result, StrategyError},
+    history::{StrategyEvent, StrategyExecution, StrategyHistory, TriggeredActions},
     promiscuous::PromiscuousStrategy,
+    rebalancing::RebalancingStrategy,
 };
 
 #[cfg(all(feature = "prometheus", not(test)))]
 lazy_static::lazy_static! {
     static ref METRIC_ENABLED_STRATEGIES: MultiGauge =
         MultiGauge::new("hopr_strategy_enabled_strategies", "List of enabled strategies", &["strategy"]).unwrap();
+    static ref METRIC_STRATEGY_EXECUTION_TIME: MultiHistogram = MultiHistogram::new(
+        "hopr_strategy_execution_time_sec",
+        "Duration of strategy executions in seconds",
+        vec![0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 10.0, 30.0, 60.0],
+        &["strategy", "event"]
+    )
+    .unwrap();
+    static ref METRIC_STRATEGY_EXECUTION_FAILURES: MultiCounter = MultiCounter::new(
+        "hopr_strategy_execution_failures_count",
+        "Number of failed strategy executions",
+        &["strategy", "event"]
+    )
+    .unwrap();
 }
 
 /// Basic single strategy.
@@ -118,10 +157,22 @@ pub struct MultiStrategyConfig {
 
     /// Configuration of individual sub-strategies.
     ///
+    /// Each sub-strategy configuration is validated as well, so a node with an invalid
+    /// sub-strategy configuration (e.g. a ratio outside `[0, 1]`) refuses to start.
+    ///
     /// Default is empty, which makes the `MultiStrategy` behave as passive.
     #[default(_code = "vec![]")]
     #[serde(default = "empty_vector")]
+    #[validate(nested)]
     pub strategies: Vec<Strategy>,
+
+    /// If set, the sub-strategies do not perform any actions,
+    /// but only record the actions they would take as intents.
+    ///
+    /// Default is false.
+    #[default = false]
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 /// Defines an execution chain of `SingularStrategies`.
@@ -129,8 +180,16 @@ pub struct MultiStrategyConfig {
 /// which makes it possible (along with different `on_fail_continue` policies) to construct
 /// various logical strategy chains.
 pub struct MultiStrategy {
-    strategies: Vec<Box<dyn SingularStrategy + Send + Sync>>,
+    strategies: Vec<SubStrategy>,
     cfg: MultiStrategyConfig,
+    intents: StrategyIntentLog,
+    history: StrategyHistory,
+}
+
+/// Sub-strategy of a [MultiStrategy] along with the actions it triggered.
+struct SubStrategy {
+    strategy: Box<dyn SingularStrategy + Send + Sync>,
+    triggered: TriggeredActions,
 }
 
 impl MultiStrategy {
@@ -145,7 +204,32 @@ impl MultiStrategy {
     where
         Db: HoprDbAllOperations + Clone + Send + Sync + std::fmt::Debug + 'static,
     {
-        let mut strategies = Vec::<Box<dyn SingularStrategy + Send + Sync>>::new();
+        Self::new_with_logs(
+            cfg,
+            db,
+            hopr_chain_actions,
+            ticket_aggregator,
+            StrategyIntentLog::default(),
+            StrategyHistory::default(),
+        )
+    }
+
+    fn new_with_logs<Db>(
+        cfg: MultiStrategyConfig,
+        db: Db,
+        hopr_chain_actions: ChainActions<Db>,
+        ticket_aggregator: Arc<dyn TicketAggregatorTrait + Send + Sync + 'static>,
+        intents: StrategyIntentLog,
+        history: StrategyHistory,
+    ) -> Self
+    where
+        Db: HoprDbAllOperations + Clone + Send + Sync + std::fmt::Debug + 'static,
+    {
+        if cfg.dry_run {
+            info!("strategies run in dry-run mode and will only record their intended actions");
+        }
+
+        let mut strategies = Vec::<SubStrategy>::new();
 
         #[cfg(all(feature = "prometheus", not(test)))]
         Strategy::VARIANTS
@@ -153,58 +237,127 @@ impl MultiStrategy {
             .for_each(|s| METRIC_ENABLED_STRATEGIES.set(&[*s], 0_f64));
 
         for strategy in cfg.strategies.iter() {
-            match strategy {
-                Strategy::Promiscuous(sub_cfg) => strategies.push(Box::new(PromiscuousStrategy::new(
-                    sub_cfg.clone(),
-                    db.clone(),
-                    hopr_chain_actions.clone(),
-                ))),
-                Strategy::Aggregating(sub_cfg) => strategies.push(Box::new(AggregatingStrategy::new(
-                    *sub_cfg,
-                    db.clone(),
-                    ticket_aggregator.clone(),
-                ))),
-                Strategy::AutoRedeeming(sub_cfg) => strategies.push(Box::new(AutoRedeemingStrategy::new(
-                    *sub_cfg,
-                    db.clone(),
-                    hopr_chain_actions.clone(),
-                ))),
-                Strategy::AutoFunding(sub_cfg) => {
-                    strategies.push(Box::new(AutoFundingStrategy::new(*sub_cfg, hopr_chain_actions.clone())))
+            let dry_run_intents = cfg.dry_run.then(|| intents.clone());
+            let triggered = TriggeredActions::default();
+            let actions = DryRunActions::new(
+                hopr_chain_actions.clone(),
+                strategy,
+                dry_run_intents.clone(),
+                triggered.clone(),
+            );
+            let tracked_aggregator = Arc::new(DryRunActions::new(
+                ticket_aggregator.clone(),
+                strategy,
+                dry_run_intents,
+                triggered.clone(),
+            ));
+
+            let sub_strategy: Box<dyn SingularStrategy + Send + Sync> = match strategy {
+                Strategy::Promiscuous(sub_cfg) => {
+                    Box::new(PromiscuousStrategy::new(sub_cfg.clone(), db.clone(), actions.clone()))
+                }
+                Strategy::Aggregating(sub_cfg) => {
+                    Box::new(AggregatingStrategy::new(*sub_cfg, db.clone(), tracked_aggregator))
+                }
+                Strategy::AutoRedeeming(sub_cfg) => {
+                    Box::new(AutoRedeemingStrategy::new(*sub_cfg, db.clone(), actions.clone()))
+                }
+                Strategy::AutoFunding(sub_cfg) => Box::new(AutoFundingStrategy::new(*sub_cfg, actions.clone())),
+                Strategy::Rebalancing(sub_cfg) => {
+                    Box::new(RebalancingStrategy::new(*sub_cfg, db.clone(), actions.clone()))
+                }
+                Strategy::ClosureFinalizer(sub_cfg) => {
+                    Box::new(ClosureFinalizerStrategy::new(*sub_cfg, db.clone(), actions.clone()))
                 }
-                Strategy::ClosureFinalizer(sub_cfg) => strategies.push(Box::new(ClosureFinalizerStrategy::new(
-                    *sub_cfg,
-                    db.clone(),
-                    hopr_chain_actions.clone(),
-                ))),
                 Strategy::Multi(sub_cfg) => {
                     if cfg.allow_recursive {
                         let mut cfg_clone = sub_cfg.clone();
                         cfg_clone.allow_recursive = false; // Do not allow more levels of recursion
+                        cfg_clone.dry_run |= cfg.dry_run;
 
-                        strategies.push(Box::new(Self::new(
+                        Box::new(Self::new_with_logs(
                             cfg_clone,
                             db.clone(),
                             hopr_chain_actions.clone(),
                             ticket_aggregator.clone(),
-                        )))
+                            intents.clone(),
+                            history.clone(),
+                        ))
                     } else {
-                        error!("recursive multi-strategy not allowed and skipped")
+                        error!("recursive multi-strategy not allowed and skipped");
+                        continue;
                     }
                 }
 
                 // Passive strategy = empty MultiStrategy
-                Strategy::Passive => strategies.push(Box::new(Self {
+                Strategy::Passive => Box::new(Self {
                     cfg: Default::default(),
                     strategies: Vec::new(),
-                })),
-            }
+                    intents: intents.clone(),
+                    history: history.clone(),
+                }),
+            };
+
+            strategies.push(SubStrategy {
+                strategy: sub_strategy,
+                triggered,
+            });
 
             #[cfg(all(feature = "prometheus", not(test)))]
             METRIC_ENABLED_STRATEGIES.set(&[&strategy.to_string()], 1_f64);
         }
 
-        Self { strategies, cfg }
+        Self {
+            strategies,
+            cfg,
+            intents,
+            history,
+        }
+    }
+
+    /// Invokes the given sub-strategy event and records its execution into the history.
+    async fn execute<F>(&self, sub_strategy: &SubStrategy, event: StrategyEvent, invocation: F) -> Result<()>
+    where
+        F: Future<Output = Result<()>>,
+    {
+        let started = current_time();
+        let timer = Instant::now();
+        let result = invocation.await;
+        let duration = timer.elapsed();
+
+        let strategy = sub_strategy.strategy.to_string();
+
+        #[cfg(all(feature = "prometheus", not(test)))]
+        {
+            let event: &str = event.into();
+            METRIC_STRATEGY_EXECUTION_TIME.observe(&[&strategy, event], duration.as_secs_f64());
+            if result.is_err() {
+                METRIC_STRATEGY_EXECUTION_FAILURES.increment(&[&strategy, event]);
+            }
+        }
+
+        self.history
+            .record(StrategyExecution {
+                strategy,
+                event,
+                started,
+                duration,
+                error: result.as_ref().err().map(|e| e.to_string()),
+                actions: sub_strategy.triggered.take().await,
+            })
+            .await;
+
+        result
+    }
+
+    /// Returns the log of actions intended by the sub-strategies in the dry-run mode.
+    pub fn intent_log(&self) -> &StrategyIntentLog {
+        &self.intents
+    }
+
+    /// Returns the history of the sub-strategy executions.
+    pub fn history(&self) -> &StrategyHistory {
+        &self.history
     }
 }
 
@@ -223,8 +376,12 @@ impl Display for MultiStrategy {
 #[async_trait]
 impl SingularStrategy for MultiStrategy {
     async fn on_tick(&self) -> Result<()> {
-        for strategy in self.strategies.iter() {
-            if let Err(e) = strategy.on_tick().await {
+        for sub_strategy in self.strategies.iter() {
+            let strategy = &sub_strategy.strategy;
+            if let Err(e) = self
+                .execute(sub_strategy, StrategyEvent::Tick, strategy.on_tick())
+                .await
+            {
                 if !self.cfg.on_fail_continue {
                     warn!(%self, %strategy, "on_tick chain stopped at strategy");
                     return Err(e);
@@ -235,8 +392,16 @@ impl SingularStrategy for MultiStrategy {
     }
 
     async fn on_acknowledged_winning_ticket(&self, ack: &AcknowledgedTicket) -> Result<()> {
-        for strategy in self.strategies.iter() {
-            if let Err(e) = strategy.on_acknowledged_winning_ticket(ack).await {
+        for sub_strategy in self.strategies.iter() {
+            let strategy = &sub_strategy.strategy;
+            if let Err(e) = self
+                .execute(
+                    sub_strategy,
+                    StrategyEvent::AcknowledgedWinningTicket,
+                    strategy.on_acknowledged_winning_ticket(ack),
+                )
+                .await
+            {
                 if !self.cfg.on_fail_continue {
                     warn!(%self, %strategy, "on_acknowledged_ticket chain stopped at strategy");
                     return Err(e);
@@ -252,8 +417,15 @@ impl SingularStrategy for MultiStrategy {
         direction: ChannelDirection,
         change: ChannelChange,
     ) -> Result<()> {
-        for strategy in self.strategies.iter() {
-            if let Err(e) = strategy.on_own_channel_changed(channel, direction, change).await {
+        for sub_strategy in self.strategies.iter() {
+            if let Err(e) = self
+                .execute(
+                    sub_strategy,
+                    StrategyEvent::OwnChannelChanged,
+                    sub_strategy.strategy.on_own_channel_changed(channel, direction, change),
+                )
+                .await
+            {
                 if !self.cfg.on_fail_continue {
                     warn!(%self, "on_channel_state_changed chain stopped at strategy");
                     return Err(e);
@@ -264,6 +436,135 @@ impl SingularStrategy for MultiStrategy {
     }
 }
 
+type MultiStrategyFactory =
+    Box<dyn Fn(MultiStrategyConfig, StrategyIntentLog, StrategyHistory) -> MultiStrategy + Send + Sync>;
+
+/// Wraps a [MultiStrategy] that can be replaced at runtime by a newly configured one.
+///
+/// All strategy events are executed while holding a read lock on the current strategy,
+/// therefore the replacement waits until all the currently running events are finished.
+/// The [StrategyIntentLog] and the [StrategyHistory] are kept across reconfigurations.
+pub struct ReconfigurableStrategy {
+    current: RwLock<MultiStrategy>,
+    factory: MultiStrategyFactory,
+    intents: StrategyIntentLog,
+    history: StrategyHistory,
+}
+
+impl ReconfigurableStrategy {
+    /// Constructs new `ReconfigurableStrategy` with the given initial configuration.
+    ///
+    /// The given configuration is not validated.
+    pub fn new<Db>(
+        cfg: MultiStrategyConfig,
+        db: Db,
+        hopr_chain_actions: ChainActions<Db>,
+        ticket_aggregator: Arc<dyn TicketAggregatorTrait + Send + Sync + 'static>,
+    ) -> Self
+    where
+        Db: HoprDbAllOperations + Clone + Send + Sync + std::fmt::Debug + 'static,
+    {
+        Self::new_with_factory(
+            cfg,
+            Box::new(move |cfg, intents, history| {
+                MultiStrategy::new_with_logs(
+                    cfg,
+                    db.clone(),
+                    hopr_chain_actions.clone(),
+                    ticket_aggregator.clone(),
+                    intents,
+                    history,
+                )
+            }),
+        )
+    }
+
+    fn new_with_factory(cfg: MultiStrategyConfig, factory: MultiStrategyFactory) -> Self {
+        let intents = StrategyIntentLog::default();
+        let history = StrategyHistory::default();
+        Self {
+            current: RwLock::new(factory(cfg, intents.clone(), history.clone())),
+            factory,
+            intents,
+            history,
+        }
+    }
+
+    /// Returns the configuration of the currently running strategies.
+    pub async fn config(&self) -> MultiStrategyConfig {
+        self.current.read().await.cfg.clone()
+    }
+
+    /// Validates the given configuration and replaces the currently running strategies with
+    /// the strategies built from it.
+    ///
+    /// Waits until all the strategy events currently being executed finish.
+    pub async fn reconfigure(&self, cfg: MultiStrategyConfig) -> Result<()> {
+        cfg.validate()
+            .map_err(|e| StrategyError::InvalidConfiguration(e.to_string()))?;
+
+        let new_strategy = (self.factory)(cfg, self.intents.clone(), self.history.clone());
+
+        let mut current = self.current.write().await;
+        info!(old = %*current, new = %new_strategy, "reconfiguring strategies");
+        *current = new_strategy;
+
+        Ok(())
+    }
+
+    /// Returns the log of actions intended by the sub-strategies in the dry-run mode.
+    pub fn intent_log(&self) -> &StrategyIntentLog {
+        &self.intents
+    }
+
+    /// Returns the history of the sub-strategy executions.
+    pub fn history(&self) -> &StrategyHistory {
+        &self.history
+    }
+}
+
+impl Debug for ReconfigurableStrategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.current.try_read() {
+            Some(current) => write!(f, "{:?}", *current),
+            None => write!(f, "ReconfigurableStrategy"),
+        }
+    }
+}
+
+impl Display for ReconfigurableStrategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.current.try_read() {
+            Some(current) => write!(f, "{}", *current),
+            None => write!(f, "reconfigurable"),
+        }
+    }
+}
+
+#[async_trait]
+impl SingularStrategy for ReconfigurableStrategy {
+    async fn on_tick(&self) -> Result<()> {
+        self.current.read().await.on_tick().await
+    }
+
+    async fn on_acknowledged_winning_ticket(&self, ack: &AcknowledgedTicket) -> Result<()> {
+        self.current.read().await.on_acknowledged_winning_ticket(ack).await
+    }
+
+    async fn on_own_channel_changed(
+        &self,
+        channel: &ChannelEntry,
+        direction: ChannelDirection,
+        change: ChannelChange,
+    ) -> Result<()> {
+        self.current
+            .read()
+            .await
+            .on_own_channel_changed(channel, direction, change)
+            .await
+    }
+}
+
 #[cfg(test)]
 impl Display for MockSingularStrategy {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -273,13 +574,37 @@ impl Display for MockSingularStrategy {
 
 #[cfg(test)]
 mod tests {
+    use std::{
+        sync::{
+            Arc,
+            atomic::{AtomicBool, Ordering},
+        },
+        time::Duration,
+    };
+
+    use async_trait::async_trait;
     use mockall::Sequence;
 
     use crate::{
-        errors::StrategyError::Other,
-        strategy::{MockSingularStrategy, MultiStrategy, MultiStrategyConfig, SingularStrategy},
+        Strategy,
+        auto_funding::AutoFundingStrategyConfig,
+        dry_run::IntendedAction,
+        errors::{Result, StrategyError::Other},
+        history::{StrategyEvent, TriggeredActions},
+        promiscuous::PromiscuousStrategyConfig,
+        strategy::{
+            MockSingularStrategy, MultiStrategy, MultiStrategyConfig, ReconfigurableStrategy, SingularStrategy,
+            SubStrategy,
+        },
     };
 
+    fn sub_strategy(strategy: impl SingularStrategy + Send + Sync + 'static) -> SubStrategy {
+        SubStrategy {
+            strategy: Box::new(strategy),
+            triggered: TriggeredActions::default(),
+        }
+    }
+
     #[tokio::test]
     async fn test_multi_strategy_logical_or_flow() -> anyhow::Result<()> {
         let mut seq = Sequence::new();
@@ -298,14 +623,36 @@ mod tests {
             allow_recursive: true,
             execution_interval: 1,
             strategies: Vec::new(),
+            dry_run: false,
         };
 
         let ms = MultiStrategy {
-            strategies: vec![Box::new(s1), Box::new(s2)],
+            strategies: vec![sub_strategy(s1), sub_strategy(s2)],
             cfg,
+            intents: Default::default(),
+            history: Default::default(),
         };
+
+        let action = IntendedAction::RedeemTickets { selector: "all".into() };
+        ms.strategies[1].triggered.push(action.clone()).await;
+
         ms.on_tick().await?;
 
+        let executions = ms.history().executions().await;
+        assert_eq!(2, executions.len());
+        assert!(executions.iter().all(|e| e.event == StrategyEvent::Tick));
+        let failed = executions
+            .iter()
+            .find(|e| !e.is_success())
+            .ok_or(anyhow::anyhow!("failed execution must be recorded"))?;
+        assert_eq!(Some(Other("error".into()).to_string()), failed.error);
+        assert!(failed.actions.is_empty());
+        let succeeded = executions
+            .iter()
+            .find(|e| e.is_success())
+            .ok_or(anyhow::anyhow!("successful execution must be recorded"))?;
+        assert_eq!(vec![action], succeeded.actions);
+
         Ok(())
     }
 
@@ -327,12 +674,120 @@ mod tests {
             allow_recursive: true,
             execution_interval: 1,
             strategies: Vec::new(),
+            dry_run: false,
         };
 
         let ms = MultiStrategy {
-            strategies: vec![Box::new(s1), Box::new(s2)],
+            strategies: vec![sub_strategy(s1), sub_strategy(s2)],
             cfg,
+            intents: Default::default(),
+            history: Default::default(),
         };
         ms.on_tick().await.expect_err("on_tick should fail");
+        assert_eq!(
+            1,
+            ms.history().executions().await.len(),
+            "only the executed strategy must be recorded"
+        );
+    }
+
+    struct SlowStrategy(Arc<AtomicBool>);
+
+    impl std::fmt::Display for SlowStrategy {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "slow")
+        }
+    }
+
+    #[async_trait]
+    impl SingularStrategy for SlowStrategy {
+        async fn on_tick(&self) -> Result<()> {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            self.0.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reconfigurable_strategy_should_replace_strategies_after_running_events_finish() -> anyhow::Result<()>
+    {
+        let tick_finished = Arc::new(AtomicBool::new(false));
+        let tick_finished_clone = tick_finished.clone();
+        let strategy = ReconfigurableStrategy::new_with_factory(
+            MultiStrategyConfig::default(),
+            Box::new(move |cfg, intents, history| MultiStrategy {
+                strategies: vec![sub_strategy(SlowStrategy(tick_finished_clone.clone()))],
+                cfg,
+                intents,
+                history,
+            }),
+        );
+
+        strategy
+            .intent_log()
+            .record("test", IntendedAction::RedeemTickets { selector: "all".into() })
+            .await;
+
+        let new_cfg = MultiStrategyConfig {
+            execution_interval: 10,
+            strategies: vec![Strategy::AutoFunding(AutoFundingStrategyConfig::default())],
+            dry_run: true,
+            ..Default::default()
+        };
+
+        let (tick_res, reconfigure_res) = futures::join!(strategy.on_tick(), async {
+            let res = strategy.reconfigure(new_cfg.clone()).await;
+            (res, tick_finished.load(Ordering::SeqCst))
+        });
+        tick_res?;
+        reconfigure_res.0?;
+        assert!(reconfigure_res.1, "running tick must finish before reconfiguration");
+
+        assert_eq!(new_cfg, strategy.config().await);
+        assert_eq!(
+            1,
+            strategy.intent_log().intents().await.len(),
+            "intent log must be kept"
+        );
+        assert_eq!(1, strategy.history().executions().await.len(), "history must be kept");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reconfigurable_strategy_should_reject_invalid_config() {
+        let strategy = ReconfigurableStrategy::new_with_factory(
+            MultiStrategyConfig::default(),
+            Box::new(|cfg, intents, history| MultiStrategy {
+                strategies: Vec::new(),
+                cfg,
+                intents,
+                history,
+            }),
+        );
+
+        let invalid_cfg = MultiStrategyConfig {
+            execution_interval: 0,
+            ..Default::default()
+        };
+
+        strategy
+            .reconfigure(invalid_cfg)
+            .await
+            .expect_err("reconfiguration must fail");
+
+        let invalid_sub_cfg = MultiStrategyConfig {
+            strategies: vec![Strategy::Promiscuous(PromiscuousStrategyConfig {
+                minimum_peer_pings: 0,
+                ..Default::default()
+            })],
+            ..Default::default()
+        };
+
+        strategy
+            .reconfigure(invalid_sub_cfg)
+            .await
+            .expect_err("reconfiguration with invalid sub-strategy must fail");
+        assert_eq!(MultiStrategyConfig::default(), strategy.config().await);
     }
 }
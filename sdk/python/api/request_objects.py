@@ -103,6 +103,7 @@ class SessionCapabilitiesBody(ApiRequestObject):
         "segmentation": "Segmentation",
         "retransmission_ack_only": "RetransmissionAckOnly",
         "no_delay": "NoDelay",
+        "multipath": "Multipath",
     }
 
     def __init__(
@@ -111,6 +112,7 @@ class SessionCapabilitiesBody(ApiRequestObject):
         segmentation: bool = False,
         retransmission_ack_only: bool = False,
         no_delay: bool = False,
+        multipath: bool = False,
     ):
         super().__init__(vars())
 
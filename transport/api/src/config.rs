@@ -1,4 +1,5 @@
 use std::{
+    collections::HashSet,
     fmt::{Display, Formatter},
     net::ToSocketAddrs,
     num::ParseIntError,
@@ -6,13 +7,17 @@ use std::{
     time::Duration,
 };
 
+use hopr_crypto_types::types::Hash;
+use hopr_primitive_types::primitives::Address;
 use hopr_transport_identity::Multiaddr;
+use hopr_transport_mixer::{MixerConfig, MixerOverflowPolicy};
 pub use hopr_transport_network::{config::NetworkConfig, heartbeat::HeartbeatConfig};
 pub use hopr_transport_protocol::config::ProtocolConfig;
 use hopr_transport_session::MIN_BALANCER_SAMPLING_INTERVAL;
 use proc_macro_regex::regex;
 use serde::{Deserialize, Serialize};
-use serde_with::serde_as;
+use serde_with::{DisplayFromStr, serde_as};
+use tracing::warn;
 use validator::{Validate, ValidationError};
 
 use crate::errors::HoprTransportError;
@@ -23,6 +28,8 @@ pub struct HoprTransportConfig {
     pub protocol: hopr_transport_protocol::config::ProtocolConfig,
     pub heartbeat: hopr_transport_network::heartbeat::HeartbeatConfig,
     pub session: SessionGlobalConfig,
+    pub cover_traffic: CoverTrafficConfig,
+    pub path_selection: PathSelectionConfig,
 }
 
 regex!(is_dns_address_regex "^(?:[a-z0-9](?:[a-z0-9-]{0,61}[a-z0-9])?\\.)*[a-z0-9][a-z0-9-]{0,61}[a-z0-9]$");
@@ -275,6 +282,256 @@ pub struct SessionGlobalConfig {
     pub balancer_sampling_interval: std::time::Duration,
 }
 
+const DEFAULT_COVER_TRAFFIC_LOOP_HOPS: u8 = 2;
+
+const DEFAULT_COVER_TRAFFIC_DROP_HOPS: u8 = 1;
+
+const DEFAULT_COVER_TRAFFIC_LOOP_TIMEOUT: Duration = Duration::from_secs(30);
+
+fn default_cover_traffic_loop_hops() -> u8 {
+    DEFAULT_COVER_TRAFFIC_LOOP_HOPS
+}
+
+fn default_cover_traffic_drop_hops() -> u8 {
+    DEFAULT_COVER_TRAFFIC_DROP_HOPS
+}
+
+fn default_cover_traffic_loop_timeout() -> std::time::Duration {
+    DEFAULT_COVER_TRAFFIC_LOOP_TIMEOUT
+}
+
+/// Configuration of the cover traffic generation.
+///
+/// Cover packets are indistinguishable from regular packets on the wire,
+/// but since they travel over multi-hop paths, they do cost tickets.
+#[serde_as]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, Validate, smart_default::SmartDefault)]
+#[serde(deny_unknown_fields)]
+pub struct CoverTrafficConfig {
+    /// Enables the cover traffic generation.
+    ///
+    /// Incoming cover traffic from other nodes is always discarded, regardless of this setting.
+    ///
+    /// Defaults to `false`.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Average number of loop packets per second sent back to ourselves (Poisson rate).
+    ///
+    /// Set 0 to disable loop packets. Defaults to 0.
+    #[validate(range(min = 0.0, max = 100.0))]
+    #[serde(default)]
+    pub loop_rate: f64,
+
+    /// Number of intermediate hops of the loop packets.
+    ///
+    /// Defaults to 2.
+    #[validate(range(min = 1, max = 3))]
+    #[default(DEFAULT_COVER_TRAFFIC_LOOP_HOPS)]
+    #[serde(default = "default_cover_traffic_loop_hops")]
+    pub loop_hops: u8,
+
+    /// Time after which a loop packet that did not return is considered lost.
+    ///
+    /// Defaults to 30 seconds.
+    #[default(DEFAULT_COVER_TRAFFIC_LOOP_TIMEOUT)]
+    #[serde(default = "default_cover_traffic_loop_timeout")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    pub loop_timeout: std::time::Duration,
+
+    /// Average number of drop packets per second sent to random peers (Poisson rate).
+    ///
+    /// Set 0 to disable drop packets. Defaults to 0.
+    #[validate(range(min = 0.0, max = 100.0))]
+    #[serde(default)]
+    pub drop_rate: f64,
+
+    /// Number of intermediate hops of the drop packets.
+    ///
+    /// Defaults to 1.
+    #[validate(range(min = 0, max = 3))]
+    #[default(DEFAULT_COVER_TRAFFIC_DROP_HOPS)]
+    #[serde(default = "default_cover_traffic_drop_hops")]
+    pub drop_hops: u8,
+}
+
+/// Algorithm used to select the paths of packets that are not routed via an explicit path.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PathSelectionAlgorithm {
+    /// Randomized depth-first search favoring channels with higher stake.
+    #[default]
+    Dfs,
+    /// Weighted random sampling over the k cheapest paths, taking the hop latency,
+    /// node quality and channel balance into account.
+    KShortest,
+}
+
+const DEFAULT_PATH_SELECTION_K: usize = 5;
+
+const DEFAULT_PATH_SELECTION_LATENCY_WEIGHT: f64 = 1.0;
+
+const DEFAULT_PATH_SELECTION_QUALITY_WEIGHT: f64 = 1.0;
+
+const DEFAULT_PATH_SELECTION_BALANCE_WEIGHT: f64 = 0.5;
+
+const DEFAULT_PATH_SELECTION_REFERENCE_LATENCY: Duration = Duration::from_millis(100);
+
+fn default_path_selection_k() -> usize {
+    DEFAULT_PATH_SELECTION_K
+}
+
+fn default_path_selection_latency_weight() -> f64 {
+    DEFAULT_PATH_SELECTION_LATENCY_WEIGHT
+}
+
+fn default_path_selection_quality_weight() -> f64 {
+    DEFAULT_PATH_SELECTION_QUALITY_WEIGHT
+}
+
+fn default_path_selection_balance_weight() -> f64 {
+    DEFAULT_PATH_SELECTION_BALANCE_WEIGHT
+}
+
+fn default_path_selection_reference_latency() -> std::time::Duration {
+    DEFAULT_PATH_SELECTION_REFERENCE_LATENCY
+}
+
+/// Configuration of the automatic path selection.
+///
+/// Apart from the `algorithm`, all the values apply only to the [`PathSelectionAlgorithm::KShortest`].
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Validate, smart_default::SmartDefault)]
+#[serde(deny_unknown_fields)]
+pub struct PathSelectionConfig {
+    /// Path selection algorithm.
+    ///
+    /// Defaults to `dfs`.
+    #[serde(default)]
+    pub algorithm: PathSelectionAlgorithm,
+
+    /// Number of the cheapest paths from which the final path is randomly sampled.
+    ///
+    /// Higher values give more path diversity at the cost of choosing slower paths.
+    /// Defaults to 5.
+    #[validate(range(min = 1, max = 100))]
+    #[default(DEFAULT_PATH_SELECTION_K)]
+    #[serde(default = "default_path_selection_k")]
+    pub k: usize,
+
+    /// Weight of the hop latency in the path cost.
+    ///
+    /// Defaults to 1.0.
+    #[validate(range(min = 0.0))]
+    #[default(DEFAULT_PATH_SELECTION_LATENCY_WEIGHT)]
+    #[serde(default = "default_path_selection_latency_weight")]
+    pub latency_weight: f64,
+
+    /// Weight of the node quality in the path cost.
+    ///
+    /// Defaults to 1.0.
+    #[validate(range(min = 0.0))]
+    #[default(DEFAULT_PATH_SELECTION_QUALITY_WEIGHT)]
+    #[serde(default = "default_path_selection_quality_weight")]
+    pub quality_weight: f64,
+
+    /// Weight of the channel balance in the path cost.
+    ///
+    /// Defaults to 0.5.
+    #[validate(range(min = 0.0))]
+    #[default(DEFAULT_PATH_SELECTION_BALANCE_WEIGHT)]
+    #[serde(default = "default_path_selection_balance_weight")]
+    pub balance_weight: f64,
+
+    /// Hop latency in milliseconds at which the hop receives the maximum latency penalty.
+    ///
+    /// Defaults to 100 milliseconds.
+    #[default(DEFAULT_PATH_SELECTION_REFERENCE_LATENCY)]
+    #[serde(default = "default_path_selection_reference_latency")]
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    pub reference_latency: std::time::Duration,
+
+    /// On-chain addresses of nodes that must never be used as relays.
+    #[serde(default)]
+    #[serde_as(as = "HashSet<DisplayFromStr>")]
+    pub excluded_nodes: HashSet<Address>,
+
+    /// IDs of channels that must never be used by the selected paths.
+    #[serde(default)]
+    #[serde_as(as = "HashSet<DisplayFromStr>")]
+    pub excluded_channels: HashSet<Hash>,
+}
+
+/// Creates the mixer configuration from the `HOPR_INTERNAL_MIXER_*` environment variables.
+///
+/// Invalid values are ignored with a warning and replaced by their defaults. The
+/// [`MixerOverflowPolicy::Reject`] policy is not supported, because the outgoing packet
+/// pipeline would stop on the first rejected packet.
+pub(crate) fn mixer_config_from_env() -> MixerConfig {
+    let cfg = MixerConfig {
+        min_delay: std::time::Duration::from_millis(
+            std::env::var("HOPR_INTERNAL_MIXER_MINIMUM_DELAY_IN_MS")
+                .map(|v| {
+                    v.trim()
+                        .parse::<u64>()
+                        .unwrap_or(hopr_transport_mixer::config::HOPR_MIXER_MINIMUM_DEFAULT_DELAY_IN_MS)
+                })
+                .unwrap_or(hopr_transport_mixer::config::HOPR_MIXER_MINIMUM_DEFAULT_DELAY_IN_MS),
+        ),
+        delay_range: std::time::Duration::from_millis(
+            std::env::var("HOPR_INTERNAL_MIXER_DELAY_RANGE_IN_MS")
+                .map(|v| {
+                    v.trim()
+                        .parse::<u64>()
+                        .unwrap_or(hopr_transport_mixer::config::HOPR_MIXER_DEFAULT_DELAY_RANGE_IN_MS)
+                })
+                .unwrap_or(hopr_transport_mixer::config::HOPR_MIXER_DEFAULT_DELAY_RANGE_IN_MS),
+        ),
+        delay_distribution: std::env::var("HOPR_INTERNAL_MIXER_DELAY_DISTRIBUTION")
+            .ok()
+            .and_then(|v| {
+                v.parse::<hopr_transport_mixer::MixerDelayDistribution>()
+                    .inspect_err(|error| warn!(%error, "ignoring invalid mixer delay distribution"))
+                    .ok()
+            })
+            .unwrap_or_default(),
+        capacity: std::env::var("HOPR_INTERNAL_MIXER_CAPACITY")
+            .map(|v| {
+                v.trim()
+                    .parse::<usize>()
+                    .unwrap_or(hopr_transport_mixer::config::HOPR_MIXER_CAPACITY)
+            })
+            .unwrap_or(hopr_transport_mixer::config::HOPR_MIXER_CAPACITY),
+        overflow_policy: std::env::var("HOPR_INTERNAL_MIXER_OVERFLOW_POLICY")
+            .ok()
+            .and_then(|v| {
+                v.parse::<hopr_transport_mixer::MixerOverflowPolicy>()
+                    .inspect_err(|error| warn!(%error, "ignoring invalid mixer overflow policy"))
+                    .ok()
+            })
+            .unwrap_or_default(),
+        ..MixerConfig::default()
+    };
+    if let Err(error) = cfg.validate() {
+        warn!(%error, "ignoring invalid mixer overflow policy");
+        return MixerConfig {
+            overflow_policy: Default::default(),
+            ..cfg
+        };
+    }
+
+    // The outgoing packet pipeline forwards into the mixer, which terminates on the first rejected packet
+    if cfg.overflow_policy == MixerOverflowPolicy::Reject {
+        warn!("ignoring the reject mixer overflow policy, which is not supported by the transport");
+        return MixerConfig {
+            overflow_policy: Default::default(),
+            ..cfg
+        };
+    }
+
+    cfg
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -328,6 +585,42 @@ mod tests {
         assert!(validate_dns_address("-hoprnet-.org").is_err());
     }
 
+    #[test]
+    fn test_path_selection_config_should_parse_exclude_lists() -> anyhow::Result<()> {
+        let cfg: PathSelectionConfig = serde_json::from_str(
+            r#"{
+                "algorithm": "k_shortest",
+                "excluded_nodes": ["0x0000c178cf70d966be0a798e666ce2782c7b2288"]
+            }"#,
+        )?;
+
+        assert_eq!(PathSelectionAlgorithm::KShortest, cfg.algorithm);
+        assert_eq!(DEFAULT_PATH_SELECTION_K, cfg.k);
+        assert!(
+            cfg.excluded_nodes
+                .contains(&"0x0000c178cf70d966be0a798e666ce2782c7b2288".parse()?)
+        );
+        assert!(cfg.validate().is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cover_traffic_config_should_be_disabled_by_default() {
+        let cfg = CoverTrafficConfig::default();
+        assert!(!cfg.enabled);
+        assert!(cfg.validate().is_ok());
+    }
+
+    #[test]
+    fn test_cover_traffic_config_should_not_allow_zero_hop_loops() {
+        let cfg = CoverTrafficConfig {
+            loop_hops: 0,
+            ..Default::default()
+        };
+        assert!(cfg.validate().is_err());
+    }
+
     #[test]
     fn test_multiaddress_on_dappnode_default() {
         temp_env::with_var("DAPPNODE", Some("true"), || {
@@ -385,4 +678,48 @@ mod tests {
             assert_eq!(default_multiaddr_transport(1234), "tcp/1234");
         });
     }
+
+    #[test]
+    fn test_mixer_config_from_env_should_not_use_the_reject_overflow_policy() {
+        temp_env::with_vars(
+            [
+                ("HOPR_INTERNAL_MIXER_OVERFLOW_POLICY", Some("reject")),
+                ("HOPR_INTERNAL_MIXER_CAPACITY", Some("2")),
+            ],
+            || {
+                let cfg = mixer_config_from_env();
+                assert_eq!(cfg.capacity, 2);
+                assert_ne!(cfg.overflow_policy, MixerOverflowPolicy::Reject);
+            },
+        );
+    }
+
+    #[test]
+    fn test_mixer_from_env_should_not_stop_forwarding_when_pushed_past_capacity() -> anyhow::Result<()> {
+        use futures::{StreamExt, executor::block_on};
+
+        const ITEM_COUNT: usize = 10;
+
+        let cfg = temp_env::with_vars(
+            [
+                ("HOPR_INTERNAL_MIXER_OVERFLOW_POLICY", Some("reject")),
+                ("HOPR_INTERNAL_MIXER_CAPACITY", Some("2")),
+                ("HOPR_INTERNAL_MIXER_MINIMUM_DELAY_IN_MS", Some("0")),
+                ("HOPR_INTERNAL_MIXER_DELAY_RANGE_IN_MS", Some("1")),
+            ],
+            mixer_config_from_env,
+        );
+
+        let (tx, rx) = hopr_transport_mixer::channel::<usize>(cfg);
+
+        // The same wiring as the outgoing packet pipeline, which forwards a stream into the mixer
+        block_on(futures::stream::iter(0..ITEM_COUNT).map(Ok).forward(tx.clone()))?;
+
+        let mut received = block_on(rx.take(ITEM_COUNT).collect::<Vec<_>>());
+        received.sort();
+        assert_eq!(received, (0..ITEM_COUNT).collect::<Vec<_>>());
+
+        drop(tx);
+        Ok(())
+    }
 }
@@ -8,6 +8,10 @@ pub(crate) const MAXIMUM_NETWORK_UPDATE_EVENT_QUEUE_SIZE: usize = 4000;
 
 pub(crate) const MAXIMUM_MSG_OUTGOING_BUFFER_SIZE: usize = 20000;
 
+/// The maximum number of path selections attempted when the selected path keeps violating
+/// the routing constraints that cannot be checked by the path selector itself.
+pub(crate) const MAXIMUM_CONSTRAINED_PATH_SELECTION_ATTEMPTS: usize = 5;
+
 pub const RESERVED_TAG_UPPER_LIMIT: u16 = RESERVED_SESSION_TAG_UPPER_LIMIT;
 
 /// The upper limit value for the session reserved tag range.
@@ -22,6 +26,12 @@ pub(crate) const RESERVED_SESSION_TAG_UPPER_LIMIT: u16 = 1024;
 /// specifically dedicated for the internal use by the subprotocols.
 pub(crate) const RESERVED_SUBPROTOCOL_TAG_UPPER_LIMIT: u16 = 16;
 
+/// Subprotocol tag of the cover traffic loop packets.
+pub(crate) const COVER_TRAFFIC_LOOP_TAG: u16 = RESERVED_SUBPROTOCOL_TAG_UPPER_LIMIT - 1;
+
+/// Subprotocol tag of the cover traffic drop packets.
+pub(crate) const COVER_TRAFFIC_DROP_TAG: u16 = RESERVED_SUBPROTOCOL_TAG_UPPER_LIMIT - 2;
+
 /// Time within Start protocol must finish session initiation.
 /// This base value is always multiplied by the (max) number of hops, times 2 (for both-ways).
 pub(crate) const SESSION_INITIATION_TIMEOUT_BASE: Duration = Duration::from_secs(5);
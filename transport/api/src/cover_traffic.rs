@@ -0,0 +1,360 @@
+//! Generation and processing of the cover traffic.
+//!
+//! Two types of cover packets are supported:
+//! - **loop packets** are sent over a multi-hop path back to ourselves. Their return is detected
+//!   and counted, which makes them a probe of the liveness of the used paths.
+//! - **drop packets** are sent to random peers, which silently discard them.
+//!
+//! Both are sent at the configured Poisson rates, so that the traffic volume of the node
+//! does not reveal its activity. Cover packets are never delivered to the application.
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, OnceLock},
+    time::{Duration, Instant},
+};
+
+use futures::channel::mpsc::Sender;
+use hopr_async_runtime::prelude::sleep;
+use hopr_crypto_types::types::OffchainPublicKey;
+use hopr_db_sql::HoprDbAllOperations;
+use hopr_internal_types::prelude::*;
+use hopr_network_types::prelude::{DestinationRouting, ResolvedTransportRouting, RoutingOptions};
+use hopr_path::{ChainPath, PathAddressResolver, selectors::PathSelector};
+use hopr_primitive_types::primitives::Address;
+use hopr_transport_identity::PeerId;
+use hopr_transport_network::network::Network;
+use hopr_transport_protocol::processor::{MsgSender, SendMsgInput};
+use rand::seq::SliceRandom;
+use tracing::{debug, error, trace};
+
+use crate::{
+    config::CoverTrafficConfig,
+    constants::{COVER_TRAFFIC_DROP_TAG, COVER_TRAFFIC_LOOP_TAG, PACKET_QUEUE_TIMEOUT_MILLISECONDS},
+    errors::{HoprTransportError, Result},
+    helpers::PathPlanner,
+};
+
+#[cfg(all(feature = "prometheus", not(test)))]
+lazy_static::lazy_static! {
+    static ref METRIC_COVER_TRAFFIC_COUNT: hopr_metrics::metrics::MultiCounter = hopr_metrics::metrics::MultiCounter::new(
+        "hopr_cover_traffic_count",
+        "Number of cover traffic packets",
+        &["type"]
+    ).unwrap();
+    static ref METRIC_COVER_TRAFFIC_LOOP_RTT: hopr_metrics::metrics::SimpleHistogram = hopr_metrics::metrics::SimpleHistogram::new(
+        "hopr_cover_traffic_loop_rtt_sec",
+        "Round-trip time of the returned cover traffic loop packets",
+        vec![0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0]
+    ).unwrap();
+}
+
+/// Size of the payload of the cover packets.
+const COVER_PACKET_PAYLOAD_SIZE: usize = size_of::<u64>();
+
+/// Loop packet that has been sent and did not return yet.
+#[derive(Debug, Clone)]
+struct PendingLoop {
+    sent_at: Instant,
+    path: ChainPath,
+}
+
+/// Keeps track of the loop packets that have been sent and did not return yet.
+#[derive(Debug)]
+struct LoopTracker {
+    pending: Mutex<HashMap<u64, PendingLoop>>,
+    timeout: Duration,
+}
+
+impl LoopTracker {
+    fn new(timeout: Duration) -> Self {
+        Self {
+            pending: Mutex::new(HashMap::new()),
+            timeout,
+        }
+    }
+
+    fn pending(&self) -> std::sync::MutexGuard<'_, HashMap<u64, PendingLoop>> {
+        self.pending.lock().unwrap_or_else(|e| {
+            self.pending.clear_poison();
+            e.into_inner()
+        })
+    }
+
+    fn register(&self, id: u64, path: ChainPath) {
+        self.pending().insert(
+            id,
+            PendingLoop {
+                sent_at: Instant::now(),
+                path,
+            },
+        );
+    }
+
+    fn unregister(&self, id: u64) {
+        self.pending().remove(&id);
+    }
+
+    /// Marks the loop packet with the given payload as returned and gives its round-trip time.
+    ///
+    /// Returns `None` if the packet is malformed, unknown or has already expired.
+    fn returned(&self, payload: &[u8]) -> Option<Duration> {
+        let id = payload
+            .get(0..COVER_PACKET_PAYLOAD_SIZE)
+            .and_then(|b| b.try_into().ok())
+            .map(u64::from_be_bytes)?;
+
+        let pending = self.pending().remove(&id)?;
+        let rtt = pending.sent_at.elapsed();
+        debug!(id, rtt_ms = rtt.as_millis(), path = %pending.path, "cover traffic loop packet returned");
+
+        Some(rtt)
+    }
+
+    /// Removes all loop packets that did not return within the timeout and returns their count.
+    fn expire(&self) -> usize {
+        let mut pending = self.pending();
+
+        let before = pending.len();
+        pending.retain(|id, pending| {
+            let is_alive = pending.sent_at.elapsed() < self.timeout;
+            if !is_alive {
+                debug!(id, path = %pending.path, "cover traffic loop packet lost");
+            }
+            is_alive
+        });
+
+        before - pending.len()
+    }
+}
+
+/// Generates the cover traffic and filters it from the incoming packets.
+#[derive(Clone)]
+pub(crate) struct CoverTraffic<T, S>
+where
+    T: HoprDbAllOperations + PathAddressResolver + std::fmt::Debug + Clone + Send + Sync + 'static,
+{
+    cfg: CoverTrafficConfig,
+    me: Address,
+    db: T,
+    network: Arc<Network<T>>,
+    planner: PathPlanner<T, S>,
+    process_packet_send: Arc<OnceLock<MsgSender<Sender<SendMsgInput>>>>,
+    loops: Arc<LoopTracker>,
+}
+
+impl<T, S> CoverTraffic<T, S>
+where
+    T: HoprDbAllOperations + PathAddressResolver + std::fmt::Debug + Clone + Send + Sync + 'static,
+    S: PathSelector + Clone + Send + Sync + 'static,
+{
+    pub(crate) fn new(
+        cfg: CoverTrafficConfig,
+        me: Address,
+        db: T,
+        network: Arc<Network<T>>,
+        planner: PathPlanner<T, S>,
+        process_packet_send: Arc<OnceLock<MsgSender<Sender<SendMsgInput>>>>,
+    ) -> Self {
+        Self {
+            cfg,
+            me,
+            db,
+            network,
+            planner,
+            process_packet_send,
+            loops: Arc::new(LoopTracker::new(cfg.loop_timeout)),
+        }
+    }
+
+    /// Consumes the incoming cover traffic.
+    ///
+    /// Returns `None` if the data were cover traffic, otherwise the data is returned unchanged.
+    pub(crate) fn filter_incoming(&self, data: ApplicationData) -> Option<ApplicationData> {
+        match data.application_tag {
+            COVER_TRAFFIC_LOOP_TAG => {
+                self.on_loop_packet_returned(&data.plain_text);
+                None
+            }
+            COVER_TRAFFIC_DROP_TAG => {
+                trace!("discarding cover traffic drop packet");
+
+                #[cfg(all(feature = "prometheus", not(test)))]
+                METRIC_COVER_TRAFFIC_COUNT.increment(&["drop_received"]);
+
+                None
+            }
+            _ => Some(data),
+        }
+    }
+
+    fn on_loop_packet_returned(&self, payload: &[u8]) {
+        if let Some(_rtt) = self.loops.returned(payload) {
+            #[cfg(all(feature = "prometheus", not(test)))]
+            {
+                METRIC_COVER_TRAFFIC_COUNT.increment(&["loop_returned"]);
+                METRIC_COVER_TRAFFIC_LOOP_RTT.observe(_rtt.as_secs_f64());
+            }
+        } else {
+            trace!("discarding malformed, unknown or expired cover traffic loop packet");
+        }
+    }
+
+    async fn send(&self, data: ApplicationData, routing: ResolvedTransportRouting) -> Result<()> {
+        self.process_packet_send
+            .get()
+            .ok_or_else(|| HoprTransportError::Api("cover traffic: message processing is not yet initialized".into()))?
+            .send_packet(data, routing)
+            .await
+            .map_err(|e| HoprTransportError::Api(format!("cover traffic: failed to enqueue packet: {e}")))?
+            .consume_and_wait(PACKET_QUEUE_TIMEOUT_MILLISECONDS)
+            .await
+            .map_err(|e| HoprTransportError::Api(e.to_string()))
+    }
+
+    async fn send_loop_packet(&self) -> Result<()> {
+        let _lost = self.loops.expire();
+
+        #[cfg(all(feature = "prometheus", not(test)))]
+        METRIC_COVER_TRAFFIC_COUNT.increment_by(&["loop_lost"], _lost as u64);
+
+        let id = rand::random::<u64>();
+        let data = ApplicationData::new(COVER_TRAFFIC_LOOP_TAG, &id.to_be_bytes());
+
+        let routing = self
+            .planner
+            .resolve_routing(
+                data.len(),
+                DestinationRouting::forward_only(self.me, RoutingOptions::Hops(self.cfg.loop_hops.try_into()?)),
+            )
+            .await?;
+
+        let ResolvedTransportRouting::Forward { forward_path, .. } = &routing else {
+            return Err(HoprTransportError::Api(
+                "cover traffic: loop packet must use forward routing".into(),
+            ));
+        };
+
+        self.loops.register(id, forward_path.chain_path().clone());
+        self.send(data, routing)
+            .await
+            .inspect_err(|_| self.loops.unregister(id))?;
+
+        #[cfg(all(feature = "prometheus", not(test)))]
+        METRIC_COVER_TRAFFIC_COUNT.increment(&["loop_sent"]);
+
+        Ok(())
+    }
+
+    async fn send_drop_packet(&self) -> Result<()> {
+        let peers = self.network.connected_peers().await?;
+        let peer: PeerId = *peers
+            .choose(&mut rand::thread_rng())
+            .ok_or_else(|| HoprTransportError::Api("cover traffic: no connected peers".into()))?;
+
+        let destination = self
+            .db
+            .resolve_chain_address(&OffchainPublicKey::try_from(peer)?)
+            .await?
+            .ok_or_else(|| HoprTransportError::Api(format!("cover traffic: cannot resolve address of {peer}")))?;
+
+        let data = ApplicationData::new(COVER_TRAFFIC_DROP_TAG, &rand::random::<u64>().to_be_bytes());
+        let routing = self
+            .planner
+            .resolve_routing(
+                data.len(),
+                DestinationRouting::forward_only(destination, RoutingOptions::Hops(self.cfg.drop_hops.try_into()?)),
+            )
+            .await?;
+
+        self.send(data, routing).await?;
+
+        #[cfg(all(feature = "prometheus", not(test)))]
+        METRIC_COVER_TRAFFIC_COUNT.increment(&["drop_sent"]);
+
+        Ok(())
+    }
+
+    /// Runs the cover traffic generation until the end of the process.
+    ///
+    /// Does nothing if the cover traffic is disabled.
+    pub(crate) async fn run(self) {
+        if !self.cfg.enabled {
+            debug!("cover traffic generation is disabled");
+            return;
+        }
+
+        let loops = async {
+            if self.cfg.loop_rate > 0.0 {
+                loop {
+                    sleep(poisson_interval(self.cfg.loop_rate)).await;
+                    if let Err(error) = self.send_loop_packet().await {
+                        error!(%error, "failed to send cover traffic loop packet");
+                    }
+                }
+            }
+        };
+
+        let drops = async {
+            if self.cfg.drop_rate > 0.0 {
+                loop {
+                    sleep(poisson_interval(self.cfg.drop_rate)).await;
+                    if let Err(error) = self.send_drop_packet().await {
+                        error!(%error, "failed to send cover traffic drop packet");
+                    }
+                }
+            }
+        };
+
+        futures::join!(loops, drops);
+    }
+}
+
+/// Samples the time until the next event of a Poisson process with the given `rate` per second.
+fn poisson_interval(rate: f64) -> Duration {
+    let u = rand::random::<f64>();
+    Duration::from_secs_f64(-(1.0 - u).ln() / rate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poisson_interval_should_have_mean_of_inverse_rate() {
+        const SAMPLES: usize = 10_000;
+        const RATE: f64 = 4.0;
+
+        let mean = (0..SAMPLES).map(|_| poisson_interval(RATE).as_secs_f64()).sum::<f64>() / SAMPLES as f64;
+
+        assert!((mean - 1.0 / RATE).abs() < 0.02, "unexpected mean {mean}");
+    }
+
+    #[test]
+    fn loop_tracker_should_detect_returned_loop_packets() {
+        let tracker = LoopTracker::new(Duration::from_secs(10));
+        tracker.register(1, ChainPath::direct(Address::default()));
+
+        assert!(tracker.returned(&2_u64.to_be_bytes()).is_none());
+        assert!(tracker.returned(&[0xff]).is_none());
+        assert!(tracker.returned(&1_u64.to_be_bytes()).is_some());
+        assert!(
+            tracker.returned(&1_u64.to_be_bytes()).is_none(),
+            "packet must not be counted twice"
+        );
+    }
+
+    #[test]
+    fn loop_tracker_should_expire_lost_loop_packets() {
+        let tracker = LoopTracker::new(Duration::from_millis(10));
+        tracker.register(1, ChainPath::direct(Address::default()));
+        tracker.register(2, ChainPath::direct(Address::default()));
+
+        assert_eq!(0, tracker.expire());
+        std::thread::sleep(Duration::from_millis(20));
+        tracker.register(3, ChainPath::direct(Address::default()));
+
+        assert_eq!(2, tracker.expire());
+        assert!(tracker.returned(&1_u64.to_be_bytes()).is_none());
+        assert!(tracker.returned(&3_u64.to_be_bytes()).is_some());
+    }
+}
@@ -11,8 +11,17 @@ use hopr_network_types::{
     prelude::{ResolvedTransportRouting, RoutingOptions},
     types::DestinationRouting,
 };
-use hopr_path::{ChainPath, PathAddressResolver, ValidatedPath, selectors::PathSelector};
+use hopr_path::{
+    ChainPath, ChannelPath, PathAddressResolver, PathConstraints, ValidatedPath,
+    errors::PathError,
+    selectors::{
+        PathSelector,
+        dfs::{DfsPathSelector, DfsPathSelectorConfig, RandomizedEdgeWeighting},
+        k_shortest::{KShortestPathSelector, KShortestPathSelectorConfig},
+    },
+};
 use hopr_primitive_types::{prelude::HoprBalance, primitives::Address};
+use hopr_transport_network::config::NetworkConfig;
 use hopr_transport_protocol::processor::{MsgSender, SendMsgInput};
 use hopr_transport_session::{
     errors::{SessionManagerError, TransportSessionError},
@@ -20,7 +29,10 @@ use hopr_transport_session::{
 };
 use tracing::trace;
 
-use crate::errors::HoprTransportError;
+use crate::{
+    config::{PathSelectionAlgorithm, PathSelectionConfig},
+    errors::HoprTransportError,
+};
 
 #[cfg(all(feature = "prometheus", not(test)))]
 lazy_static::lazy_static! {
@@ -56,6 +68,72 @@ pub struct TicketStatistics {
     pub rejected_value: HoprBalance,
 }
 
+/// [`PathSelector`] chosen according to the [`PathSelectionConfig`].
+#[derive(Clone, Debug)]
+pub(crate) enum ConfiguredPathSelector {
+    Dfs(DfsPathSelector<RandomizedEdgeWeighting>),
+    KShortest(KShortestPathSelector),
+}
+
+impl ConfiguredPathSelector {
+    pub(crate) fn new(
+        channel_graph: Arc<RwLock<hopr_path::channel_graph::ChannelGraph>>,
+        cfg: &PathSelectionConfig,
+        network_cfg: &NetworkConfig,
+    ) -> Self {
+        match cfg.algorithm {
+            PathSelectionAlgorithm::Dfs => Self::Dfs(DfsPathSelector::new(
+                channel_graph,
+                DfsPathSelectorConfig {
+                    node_score_threshold: network_cfg.node_score_auto_path_threshold,
+                    max_first_hop_latency: network_cfg.max_first_hop_latency_threshold,
+                    ..Default::default()
+                },
+            )),
+            PathSelectionAlgorithm::KShortest => Self::KShortest(KShortestPathSelector::new(
+                channel_graph,
+                KShortestPathSelectorConfig {
+                    k: cfg.k,
+                    latency_weight: cfg.latency_weight,
+                    quality_weight: cfg.quality_weight,
+                    balance_weight: cfg.balance_weight,
+                    reference_latency: cfg.reference_latency,
+                    node_score_threshold: network_cfg.node_score_auto_path_threshold,
+                    max_first_hop_latency: network_cfg.max_first_hop_latency_threshold,
+                    excluded_nodes: cfg.excluded_nodes.clone(),
+                    excluded_channels: cfg.excluded_channels.clone(),
+                    ..Default::default()
+                },
+            )),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl PathSelector for ConfiguredPathSelector {
+    async fn select_constrained_path(
+        &self,
+        source: Address,
+        destination: Address,
+        min_hops: usize,
+        max_hops: usize,
+        constraints: &PathConstraints,
+    ) -> hopr_path::errors::Result<ChannelPath> {
+        match self {
+            Self::Dfs(selector) => {
+                selector
+                    .select_constrained_path(source, destination, min_hops, max_hops, constraints)
+                    .await
+            }
+            Self::KShortest(selector) => {
+                selector
+                    .select_constrained_path(source, destination, min_hops, max_hops, constraints)
+                    .await
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct PathPlanner<T, S> {
     db: T,
@@ -93,17 +171,19 @@ where
         source: Address,
         destination: Address,
         options: RoutingOptions,
+        constraints: &PathConstraints,
     ) -> crate::errors::Result<ValidatedPath> {
         let cg = self.channel_graph.read().await;
         let path = match options {
             RoutingOptions::IntermediatePath(path) => {
                 trace!(?path, "resolving a specific path");
 
-                ValidatedPath::new(
+                ValidatedPath::new_with_constraints(
                     source,
                     ChainPath::new(path.into_iter().chain(std::iter::once(destination)))?,
                     &cg,
                     &self.db,
+                    constraints,
                 )
                 .await?
             }
@@ -115,12 +195,33 @@ where
             RoutingOptions::Hops(hops) => {
                 trace!(%hops, "resolving path using hop count");
 
-                let cp = self
-                    .selector
-                    .select_path(source, destination, hops.into(), hops.into())
-                    .await?;
+                let mut attempt = 1;
+                loop {
+                    let cp = self
+                        .selector
+                        .select_constrained_path(source, destination, hops.into(), hops.into(), constraints)
+                        .await?;
 
-                ValidatedPath::new(source, ChainPath::from_channel_path(cp, destination), &cg, &self.db).await?
+                    // Some constraints can be verified only once the path is validated, so try again
+                    // with a different path if the selected one violates them.
+                    match ValidatedPath::new_with_constraints(
+                        source,
+                        ChainPath::from_channel_path(cp, destination),
+                        &cg,
+                        &self.db,
+                        constraints,
+                    )
+                    .await
+                    {
+                        Err(PathError::ConstraintViolation(reason))
+                            if attempt < crate::constants::MAXIMUM_CONSTRAINED_PATH_SELECTION_ATTEMPTS =>
+                        {
+                            trace!(attempt, %reason, "selected path violates routing constraints");
+                            attempt += 1;
+                        }
+                        res => break res?,
+                    }
+                }
             }
         };
 
@@ -135,6 +236,73 @@ where
         Ok(path)
     }
 
+    /// Resolves at most `count` forward routings with fixed paths to the destination of the given `routing`,
+    /// such that no two of them share any relays.
+    ///
+    /// At least one routing is always returned, unless no path to the destination can be resolved.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub(crate) async fn resolve_disjoint_routings(
+        &self,
+        routing: DestinationRouting,
+        count: usize,
+    ) -> crate::errors::Result<Vec<DestinationRouting>> {
+        let DestinationRouting::Forward {
+            destination,
+            pseudonym,
+            forward_options,
+            return_options,
+            path_constraints,
+        } = routing
+        else {
+            // SURB-based routing cannot be split into multiple paths
+            return Ok(vec![routing]);
+        };
+
+        // Direct or explicit paths cannot have alternatives
+        if count == 0
+            || forward_options.count_hops() == 0
+            || matches!(forward_options, RoutingOptions::IntermediatePath(_))
+        {
+            return Ok(vec![DestinationRouting::Forward {
+                destination,
+                pseudonym,
+                forward_options,
+                return_options,
+                path_constraints,
+            }]);
+        }
+
+        let mut used_relays = Vec::new();
+        let mut routings = Vec::with_capacity(count);
+        for _ in 0..count {
+            let constraints = path_constraints.excluding(used_relays.iter().copied());
+            let path = match self
+                .resolve_path(self.me, destination, forward_options.clone(), &constraints)
+                .await
+            {
+                Ok(path) => path,
+                Err(error) if !routings.is_empty() => {
+                    trace!(%error, num_paths = routings.len(), "no more disjoint paths available");
+                    break;
+                }
+                Err(error) => return Err(error),
+            };
+
+            let relays = path.chain_path().clone().into_channel_path();
+            used_relays.extend(relays.iter().copied());
+
+            routings.push(DestinationRouting::Forward {
+                destination,
+                pseudonym,
+                forward_options: RoutingOptions::IntermediatePath(relays.try_into()?),
+                return_options: return_options.clone(),
+                path_constraints: path_constraints.clone(),
+            });
+        }
+
+        Ok(routings)
+    }
+
     #[tracing::instrument(level = "trace", skip(self))]
     pub(crate) async fn resolve_routing(
         &self,
@@ -147,15 +315,26 @@ where
                 pseudonym,
                 forward_options,
                 return_options,
+                path_constraints,
             } => {
-                let forward_path = self.resolve_path(self.me, destination, forward_options).await?;
+                let forward_path = self
+                    .resolve_path(self.me, destination, forward_options, &path_constraints)
+                    .await?;
 
                 let return_paths = if let Some(return_options) = return_options {
                     let num_possible_surbs = HoprPacket::max_surbs_with_message(size_hint);
                     trace!(%destination, %num_possible_surbs, data_len = size_hint, "resolving packet return paths");
 
+                    // Return paths must not use any of the forward path relays if disjoint paths are requested
+                    let return_constraints = if path_constraints.disjoint_paths {
+                        let relays = forward_path.chain_path();
+                        path_constraints.excluding(relays[..relays.len() - 1].iter().copied())
+                    } else {
+                        path_constraints
+                    };
+
                     (0..num_possible_surbs)
-                        .map(|_| self.resolve_path(destination, self.me, return_options.clone()))
+                        .map(|_| self.resolve_path(destination, self.me, return_options.clone(), &return_constraints))
                         .collect::<FuturesUnordered<_>>()
                         .try_collect::<Vec<ValidatedPath>>()
                         .await?
@@ -244,4 +423,19 @@ where
 
         Ok(())
     }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn resolve_disjoint_routings(
+        &self,
+        routing: DestinationRouting,
+        count: usize,
+    ) -> std::result::Result<Vec<DestinationRouting>, TransportSessionError> {
+        self.resolver
+            .resolve_disjoint_routings(routing, count)
+            .await
+            .map_err(|error| {
+                tracing::error!(%error, "failed to resolve disjoint routings");
+                TransportSessionError::Path
+            })
+    }
 }
@@ -17,6 +17,7 @@
 pub mod config;
 /// Constants used and exposed by the crate.
 pub mod constants;
+mod cover_traffic;
 /// Errors used by the crate.
 pub mod errors;
 pub mod helpers;
@@ -53,20 +54,17 @@ pub use hopr_internal_types::prelude::HoprPseudonym;
 use hopr_internal_types::prelude::*;
 pub use hopr_network_types::prelude::RoutingOptions;
 use hopr_network_types::prelude::{DestinationRouting, ResolvedTransportRouting};
-use hopr_path::{
-    PathAddressResolver,
-    selectors::dfs::{DfsPathSelector, DfsPathSelectorConfig, RandomizedEdgeWeighting},
-};
+use hopr_path::PathAddressResolver;
 use hopr_platform::time::native::current_time;
 use hopr_primitive_types::prelude::*;
 use hopr_transport_identity::multiaddrs::strip_p2p_protocol;
 pub use hopr_transport_identity::{Multiaddr, PeerId};
-use hopr_transport_mixer::MixerConfig;
 pub use hopr_transport_network::network::{Health, Network, NetworkTriggeredEvent, PeerOrigin, PeerStatus};
 use hopr_transport_network::{
     heartbeat::Heartbeat,
     ping::{PingConfig, PingQueryReplier, Pinger, Pinging},
 };
+pub use hopr_transport_p2p::swarm::PeerConnectionEvent;
 use hopr_transport_p2p::{
     HoprSwarm,
     swarm::{TicketAggregationRequestType, TicketAggregationResponseType},
@@ -79,9 +77,10 @@ use hopr_transport_protocol::{
 #[cfg(feature = "runtime-tokio")]
 pub use hopr_transport_session::transfer_session;
 pub use hopr_transport_session::{
-    Capability as SessionCapability, IncomingSession, SESSION_PAYLOAD_SIZE, ServiceId, Session, SessionClientConfig,
-    SessionId, SessionTarget, SurbBalancerConfig, USABLE_PAYLOAD_CAPACITY_FOR_SESSION, errors::TransportSessionError,
-    traits::SendMsg,
+    Capability as SessionCapability, IncomingSession, MultipathConfig, RateController, RateLimitExt,
+    SESSION_PAYLOAD_SIZE, ServiceId, Session, SessionClientConfig, SessionId, SessionLifecycleEvent,
+    SessionStatsSnapshot, SessionTarget, SurbBalancerConfig, USABLE_PAYLOAD_CAPACITY_FOR_SESSION,
+    errors::TransportSessionError, traits::SendMsg,
 };
 use hopr_transport_session::{DispatchResult, SessionManager, SessionManagerConfig};
 use hopr_transport_ticket_aggregation::{
@@ -126,6 +125,8 @@ pub enum HoprTransportProcess {
     SessionsManagement(usize),
     #[strum(to_string = "protocol [HOPR [heartbeat]]")]
     Heartbeat,
+    #[strum(to_string = "cover traffic generator")]
+    CoverTraffic,
 }
 
 #[derive(Debug, Clone)]
@@ -180,7 +181,7 @@ where
 }
 
 /// Currently used implementation of [`PathSelector`](hopr_path::selectors::PathSelector).
-type CurrentPathSelector = DfsPathSelector<RandomizedEdgeWeighting>;
+type CurrentPathSelector = helpers::ConfiguredPathSelector;
 
 /// Interface into the physical transport mechanism allowing all off-chain HOPR-related tasks on
 /// the transport, as well as off-chain ticket manipulation.
@@ -200,6 +201,7 @@ where
     process_ticket_aggregate:
         Arc<OnceLock<TicketAggregationActions<TicketAggregationResponseType, TicketAggregationRequestType>>>,
     smgr: SessionManager<helpers::MessageSender<T, CurrentPathSelector>>,
+    cover_traffic: cover_traffic::CoverTraffic<T, CurrentPathSelector>,
 }
 
 impl<T> HoprTransport<T>
@@ -219,30 +221,36 @@ where
         let me_peerid: PeerId = me.into();
         let me_chain_addr = me_onchain.public().to_address();
 
+        let network = Arc::new(Network::new(
+            me_peerid,
+            my_multiaddresses.clone(),
+            cfg.network.clone(),
+            db.clone(),
+        ));
+
+        let path_planner = PathPlanner::new(
+            me_chain_addr,
+            db.clone(),
+            CurrentPathSelector::new(channel_graph.clone(), &cfg.path_selection, &cfg.network),
+            channel_graph.clone(),
+        );
+
+        let cover_traffic = cover_traffic::CoverTraffic::new(
+            cfg.cover_traffic,
+            me_chain_addr,
+            db.clone(),
+            network.clone(),
+            path_planner.clone(),
+            process_packet_send.clone(),
+        );
+
         Self {
             me: me.clone(),
             me_peerid,
             ping: Arc::new(OnceLock::new()),
-            network: Arc::new(Network::new(
-                me_peerid,
-                my_multiaddresses.clone(),
-                cfg.network.clone(),
-                db.clone(),
-            )),
+            network,
             process_packet_send,
-            path_planner: PathPlanner::new(
-                me_chain_addr,
-                db.clone(),
-                CurrentPathSelector::new(
-                    channel_graph.clone(),
-                    DfsPathSelectorConfig {
-                        node_score_threshold: cfg.network.node_score_auto_path_threshold,
-                        max_first_hop_latency: cfg.network.max_first_hop_latency_threshold,
-                        ..Default::default()
-                    },
-                ),
-                channel_graph.clone(),
-            ),
+            path_planner,
             db,
             my_multiaddresses,
             process_ticket_aggregate: Arc::new(OnceLock::new()),
@@ -252,6 +260,7 @@ where
                 idle_timeout: cfg.session.idle_timeout,
                 balancer_sampling_interval: cfg.session.balancer_sampling_interval,
             }),
+            cover_traffic,
             cfg,
         }
     }
@@ -271,6 +280,8 @@ where
         on_incoming_data: UnboundedSender<ApplicationData>,
         discovery_updates: UnboundedReceiver<PeerDiscovery>,
         on_incoming_session: UnboundedSender<IncomingSession>,
+        on_peer_connection: UnboundedSender<PeerConnectionEvent>,
+        on_session_lifecycle: UnboundedSender<SessionLifecycleEvent>,
     ) -> crate::errors::Result<HashMap<HoprTransportProcess, JoinHandle<()>>> {
         let (mut internal_discovery_update_tx, internal_discovery_update_rx) =
             futures::channel::mpsc::unbounded::<PeerDiscovery>();
@@ -440,34 +451,7 @@ where
         );
 
         // initiate the transport layer
-        let mixer_cfg = MixerConfig {
-            min_delay: std::time::Duration::from_millis(
-                std::env::var("HOPR_INTERNAL_MIXER_MINIMUM_DELAY_IN_MS")
-                    .map(|v| {
-                        v.trim()
-                            .parse::<u64>()
-                            .unwrap_or(hopr_transport_mixer::config::HOPR_MIXER_MINIMUM_DEFAULT_DELAY_IN_MS)
-                    })
-                    .unwrap_or(hopr_transport_mixer::config::HOPR_MIXER_MINIMUM_DEFAULT_DELAY_IN_MS),
-            ),
-            delay_range: std::time::Duration::from_millis(
-                std::env::var("HOPR_INTERNAL_MIXER_DELAY_RANGE_IN_MS")
-                    .map(|v| {
-                        v.trim()
-                            .parse::<u64>()
-                            .unwrap_or(hopr_transport_mixer::config::HOPR_MIXER_DEFAULT_DELAY_RANGE_IN_MS)
-                    })
-                    .unwrap_or(hopr_transport_mixer::config::HOPR_MIXER_DEFAULT_DELAY_RANGE_IN_MS),
-            ),
-            capacity: std::env::var("HOPR_INTERNAL_MIXER_CAPACITY")
-                .map(|v| {
-                    v.trim()
-                        .parse::<usize>()
-                        .unwrap_or(hopr_transport_mixer::config::HOPR_MIXER_CAPACITY)
-                })
-                .unwrap_or(hopr_transport_mixer::config::HOPR_MIXER_CAPACITY),
-            ..MixerConfig::default()
-        };
+        let mixer_cfg = crate::config::mixer_config_from_env();
         #[cfg(feature = "mixer-channel")]
         let (mixing_channel_tx, mixing_channel_rx) = hopr_transport_mixer::channel::<(PeerId, Box<[u8]>)>(mixer_cfg);
 
@@ -514,6 +498,8 @@ where
         )
         .await;
 
+        transport_layer.notify_peer_connections(on_peer_connection);
+
         if let Some(port) = self.cfg.protocol.autonat_port {
             transport_layer.run_nat_server(port);
         }
@@ -566,6 +552,9 @@ where
 
         let msg_sender = helpers::MessageSender::new(self.process_packet_send.clone(), self.path_planner.clone());
 
+        self.smgr
+            .notify_session_lifecycle(on_session_lifecycle)
+            .expect("failed to start session manager");
         self.smgr
             .start(msg_sender, on_incoming_session)
             .expect("failed to start session manager")
@@ -577,12 +566,16 @@ where
             });
 
         let smgr = self.smgr.clone();
+        let cover_traffic = self.cover_traffic.clone();
         processes.insert(
             HoprTransportProcess::SessionsManagement(0),
             spawn(async move {
                 let _the_process_should_not_end = StreamExt::filter_map(rx_from_protocol, |(pseudonym, data)| {
                     let smgr = smgr.clone();
+                    // Cover traffic must never reach the Session manager nor the application
+                    let data = cover_traffic.filter_incoming(data);
                     async move {
+                        let data = data?;
                         match smgr.dispatch_message(pseudonym, data).await {
                             Ok(DispatchResult::Processed) => {
                                 trace!("message dispatch completed");
@@ -616,6 +609,14 @@ where
             }),
         );
 
+        let cover_cfg = self.cfg.cover_traffic;
+        if cover_cfg.enabled && (cover_cfg.loop_rate > 0.0 || cover_cfg.drop_rate > 0.0) {
+            processes.insert(
+                HoprTransportProcess::CoverTraffic,
+                spawn(self.cover_traffic.clone().run()),
+            );
+        }
+
         Ok(processes)
     }
 
@@ -695,6 +696,16 @@ where
         Ok(self.smgr.new_session(destination, target, cfg).await?)
     }
 
+    /// Statistics of the given active Session, if it exists.
+    pub fn session_stats(&self, session_id: &SessionId) -> Option<SessionStatsSnapshot> {
+        self.smgr.session_stats(session_id)
+    }
+
+    /// Statistics of all the active Sessions, both incoming and outgoing.
+    pub fn all_session_stats(&self) -> Vec<SessionStatsSnapshot> {
+        self.smgr.all_session_stats()
+    }
+
     #[tracing::instrument(level = "info", skip(self, msg), fields(uuid = uuid::Uuid::new_v4().to_string()))]
     pub async fn send_message(
         &self,
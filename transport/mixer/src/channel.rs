@@ -6,17 +6,20 @@ use std::{
         Arc, Mutex,
         atomic::{AtomicBool, AtomicUsize, Ordering},
     },
-    task::Poll,
+    task::{Poll, Waker},
     time::Duration,
 };
 
 use futures::{FutureExt, SinkExt, Stream, StreamExt};
 use futures_timer::Delay;
 #[cfg(all(feature = "prometheus", not(test)))]
-use hopr_metrics::metrics::SimpleGauge;
+use hopr_metrics::metrics::{SimpleCounter, SimpleGauge};
 use tracing::{error, trace};
 
-use crate::{config::MixerConfig, data::DelayedData};
+use crate::{
+    config::{MixerConfig, MixerOverflowPolicy},
+    data::DelayedData,
+};
 
 #[cfg(all(feature = "prometheus", not(test)))]
 lazy_static::lazy_static! {
@@ -27,6 +30,26 @@ lazy_static::lazy_static! {
         "Average mixer packet delay averaged over a packet window"
     )
     .unwrap();
+    pub static ref METRIC_MIXER_REJECTED: SimpleCounter = SimpleCounter::new(
+        "hopr_mixer_rejected_packet_count",
+        "Number of packets rejected by the full mixer"
+    )
+    .unwrap();
+    pub static ref METRIC_MIXER_DROPPED_NEWEST: SimpleCounter = SimpleCounter::new(
+        "hopr_mixer_dropped_newest_packet_count",
+        "Number of new packets dropped by the full mixer"
+    )
+    .unwrap();
+    pub static ref METRIC_MIXER_DROPPED_OLDEST_RELEASE: SimpleCounter = SimpleCounter::new(
+        "hopr_mixer_dropped_oldest_release_packet_count",
+        "Number of buffered packets with the earliest release dropped by the full mixer"
+    )
+    .unwrap();
+    pub static ref METRIC_MIXER_RANDOM_EARLY_DROPPED: SimpleCounter = SimpleCounter::new(
+        "hopr_mixer_random_early_dropped_packet_count",
+        "Number of new packets dropped by the mixer random early drop policy"
+    )
+    .unwrap();
 }
 
 /// Mixing and delaying channel using random delay function.
@@ -42,16 +65,55 @@ lazy_static::lazy_static! {
 /// 3. The heap maintains ordering so items with earliest release time are at the top
 ///
 /// The channel uses a single timer thread that is instantiated on the first
-/// timer reset and shared across all operations. This channel is **unbounded** by nature
-/// using the `capacity` in the configuration to solely pre-allocate the buffer.
+/// timer reset and shared across all operations. By default, this channel is **unbounded**
+/// using the `capacity` in the configuration to solely pre-allocate the buffer. Any other
+/// [`MixerOverflowPolicy`] makes the `capacity` the hard limit of the buffer.
 struct Channel<T> {
     /// Buffer holding the data with a timestamp ordering to ensure the min heap behavior.
     buffer: BinaryHeap<Reverse<DelayedData<T>>>,
     timer: futures_timer::Delay,
     waker: Option<std::task::Waker>,
+    /// Wakers of the senders waiting for free space in the buffer (back-pressure).
+    sender_wakers: Vec<Waker>,
     cfg: MixerConfig,
 }
 
+impl<T> Channel<T> {
+    fn is_full(&self) -> bool {
+        self.cfg.is_bounded() && self.buffer.len() >= self.cfg.capacity
+    }
+
+    /// Decides whether a new item should be dropped by the random early drop policy.
+    ///
+    /// The drop probability grows linearly from the `min_threshold` to 1 at the full capacity.
+    fn should_drop_early(&self, min_threshold: usize) -> bool {
+        let len = self.buffer.len();
+        if len < min_threshold {
+            return false;
+        }
+
+        let probability =
+            (len - min_threshold + 1) as f64 / (self.cfg.capacity.saturating_sub(min_threshold) + 1) as f64;
+        hopr_crypto_random::random_float() < probability
+    }
+
+    /// Removes the item with the earliest release timestamp and notifies the senders
+    /// waiting for free space.
+    fn pop(&mut self) -> Option<T> {
+        let item = self.buffer.pop()?.0.item;
+
+        #[cfg(all(feature = "prometheus", not(test)))]
+        METRIC_QUEUE_SIZE.decrement(1.0f64);
+
+        self.wake_senders();
+        Some(item)
+    }
+
+    fn wake_senders(&mut self) {
+        self.sender_wakers.drain(..).for_each(Waker::wake);
+    }
+}
+
 /// Channel with sender and receiver counters allowing closure tracking.
 struct TrackedChannel<T> {
     channel: Arc<Mutex<Channel<T>>>,
@@ -79,6 +141,10 @@ pub enum SenderError {
     /// The mutex lock over the channel failed.
     #[error("Channel lock failed")]
     Lock,
+
+    /// The bounded channel reached its capacity.
+    #[error("Channel is full")]
+    Full,
 }
 
 /// Sender object interacting with the mixing channel.
@@ -112,6 +178,10 @@ impl<T> Drop for Sender<T> {
 
 impl<T> Sender<T> {
     /// Send one item to the mixing channel.
+    ///
+    /// Unlike the [`Sink`](futures::sink::Sink) implementation, this method cannot wait
+    /// for free space and returns [`SenderError::Full`] when the bounded channel is full
+    /// and uses the [`MixerOverflowPolicy::BackPressure`].
     pub fn send(&self, item: T) -> Result<(), SenderError> {
         let mut sender = self.clone();
         sender.start_send_unpin(item)
@@ -121,13 +191,28 @@ impl<T> Sender<T> {
 impl<T> futures::sink::Sink<T> for Sender<T> {
     type Error = SenderError;
 
-    fn poll_ready(self: std::pin::Pin<&mut Self>, _cx: &mut std::task::Context<'_>) -> Poll<Result<(), Self::Error>> {
+    fn poll_ready(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Result<(), Self::Error>> {
         let is_active = self.channel.receiver_active.load(Ordering::Relaxed);
-        if is_active {
-            Poll::Ready(Ok(()))
-        } else {
-            Poll::Ready(Err(SenderError::Closed))
+        if !is_active {
+            return Poll::Ready(Err(SenderError::Closed));
+        }
+
+        let mut channel = self.channel.channel.lock().map_err(|_| SenderError::Lock)?;
+        if channel.cfg.overflow_policy == MixerOverflowPolicy::BackPressure && channel.is_full() {
+            // Re-check under the lock, so that the wake-up on receiver drop cannot be missed
+            if !self.channel.receiver_active.load(Ordering::Relaxed) {
+                return Poll::Ready(Err(SenderError::Closed));
+            }
+
+            if !channel.sender_wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                channel.sender_wakers.push(cx.waker().clone());
+            }
+
+            trace!("mixer is full, applying back-pressure");
+            return Poll::Pending;
         }
+
+        Poll::Ready(Ok(()))
     }
 
     #[tracing::instrument(level = "trace", skip(self, item))]
@@ -137,6 +222,47 @@ impl<T> futures::sink::Sink<T> for Sender<T> {
         if is_active {
             let mut channel = self.channel.channel.lock().map_err(|_| SenderError::Lock)?;
 
+            match channel.cfg.overflow_policy {
+                MixerOverflowPolicy::Unbounded => {}
+                MixerOverflowPolicy::Reject | MixerOverflowPolicy::BackPressure if channel.is_full() => {
+                    #[cfg(all(feature = "prometheus", not(test)))]
+                    METRIC_MIXER_REJECTED.increment();
+
+                    return Err(SenderError::Full);
+                }
+                MixerOverflowPolicy::DropNewest if channel.is_full() => {
+                    trace!("mixer is full, dropping the newest item");
+
+                    #[cfg(all(feature = "prometheus", not(test)))]
+                    METRIC_MIXER_DROPPED_NEWEST.increment();
+
+                    return Ok(());
+                }
+                MixerOverflowPolicy::DropOldestRelease if channel.is_full() => {
+                    trace!("mixer is full, dropping the item with the earliest release");
+
+                    if channel.buffer.pop().is_some() {
+                        #[cfg(all(feature = "prometheus", not(test)))]
+                        {
+                            METRIC_QUEUE_SIZE.decrement(1.0f64);
+                            METRIC_MIXER_DROPPED_OLDEST_RELEASE.increment();
+                        }
+                    } else {
+                        // Zero capacity, nothing can be buffered
+                        return Ok(());
+                    }
+                }
+                MixerOverflowPolicy::RandomEarlyDrop { min_threshold } if channel.should_drop_early(min_threshold) => {
+                    trace!("mixer randomly dropped an item");
+
+                    #[cfg(all(feature = "prometheus", not(test)))]
+                    METRIC_MIXER_RANDOM_EARLY_DROPPED.increment();
+
+                    return Ok(());
+                }
+                _ => {}
+            }
+
             let random_delay = channel.cfg.random_delay();
 
             trace!(delay_in_ms = random_delay.as_millis(), "generated mixer delay",);
@@ -208,17 +334,11 @@ impl<T> Stream for Receiver<T> {
 
             if channel.buffer.peek().map(|x| x.0.release_at < now).unwrap_or(false) {
                 let data = channel
-                    .buffer
                     .pop()
-                    .expect("The value should be present within the same locked access")
-                    .0
-                    .item;
+                    .expect("The value should be present within the same locked access");
 
                 trace!(from = "direct", "yield item");
 
-                #[cfg(all(feature = "prometheus", not(test)))]
-                METRIC_QUEUE_SIZE.decrement(1.0f64);
-
                 return Poll::Ready(Some(data));
             }
 
@@ -239,16 +359,10 @@ impl<T> Stream for Receiver<T> {
 
                 trace!(from = "timer", "yield item");
 
-                #[cfg(all(feature = "prometheus", not(test)))]
-                METRIC_QUEUE_SIZE.decrement(1.0f64);
-
                 return Poll::Ready(Some(
                     channel
-                        .buffer
                         .pop()
-                        .expect("The value should be present within the locked access")
-                        .0
-                        .item,
+                        .expect("The value should be present within the locked access"),
                 ));
             }
 
@@ -261,6 +375,19 @@ impl<T> Stream for Receiver<T> {
     }
 }
 
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.channel.receiver_active.store(false, Ordering::Relaxed);
+
+        // Senders waiting on back-pressure must observe the closure
+        let mut channel = self.channel.channel.lock().unwrap_or_else(|e| {
+            self.channel.channel.clear_poison();
+            e.into_inner()
+        });
+        channel.wake_senders();
+    }
+}
+
 impl<T> Receiver<T> {
     /// Receive a single delayed mixed item.
     pub async fn recv(&mut self) -> Option<T> {
@@ -275,6 +402,10 @@ pub fn channel<T>(cfg: crate::config::MixerConfig) -> (Sender<T>, Receiver<T>) {
         // Initialize the lazy statics here
         lazy_static::initialize(&METRIC_QUEUE_SIZE);
         lazy_static::initialize(&METRIC_MIXER_AVERAGE_DELAY);
+        lazy_static::initialize(&METRIC_MIXER_REJECTED);
+        lazy_static::initialize(&METRIC_MIXER_DROPPED_NEWEST);
+        lazy_static::initialize(&METRIC_MIXER_DROPPED_OLDEST_RELEASE);
+        lazy_static::initialize(&METRIC_MIXER_RANDOM_EARLY_DROPPED);
     }
 
     let mut buffer = BinaryHeap::new();
@@ -285,6 +416,7 @@ pub fn channel<T>(cfg: crate::config::MixerConfig) -> (Sender<T>, Receiver<T>) {
             buffer,
             timer: Delay::new(Duration::from_secs(0)),
             waker: None,
+            sender_wakers: Vec::new(),
             cfg,
         })),
         sender_count: Arc::new(AtomicUsize::new(1)),
@@ -488,4 +620,176 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn mixer_channel_should_mix_within_bounds_using_exponential_delay_distribution() -> anyhow::Result<()> {
+        const ITERATIONS: usize = 20;
+
+        let cfg = MixerConfig {
+            delay_distribution: crate::delay::MixerDelayDistribution::Exponential {
+                mean: Duration::from_millis(50),
+            },
+            ..MixerConfig::default()
+        };
+        let (tx, rx) = channel(cfg);
+
+        let start = std::time::SystemTime::now();
+        let input = (0..ITERATIONS).collect::<Vec<_>>();
+
+        for i in input.iter() {
+            tx.send(*i)?;
+        }
+
+        let mixed_output = timeout(
+            2 * MAXIMUM_SINGLE_DELAY_DURATION,
+            rx.take(ITERATIONS).collect::<Vec<_>>(),
+        )
+        .await?;
+
+        assert!(start.elapsed()? < MAXIMUM_SINGLE_DELAY_DURATION + PROCESSING_LEEWAY);
+        assert_ne!(input, mixed_output);
+
+        Ok(())
+    }
+
+    fn bounded_cfg(capacity: usize, overflow_policy: MixerOverflowPolicy) -> MixerConfig {
+        MixerConfig {
+            min_delay: Duration::from_millis(50),
+            delay_range: Duration::from_millis(0),
+            capacity,
+            overflow_policy,
+            ..MixerConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn bounded_mixer_channel_should_reject_items_when_full() -> anyhow::Result<()> {
+        const CAPACITY: usize = 5;
+
+        let (tx, rx) = channel(bounded_cfg(CAPACITY, MixerOverflowPolicy::Reject));
+
+        for i in 0..CAPACITY {
+            tx.send(i)?;
+        }
+        assert!(matches!(tx.send(CAPACITY), Err(SenderError::Full)));
+
+        let mut output = timeout(Duration::from_secs(1), rx.take(CAPACITY).collect::<Vec<_>>()).await?;
+        output.sort();
+        assert_eq!((0..CAPACITY).collect::<Vec<_>>(), output);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn bounded_mixer_channel_should_apply_back_pressure_when_full() -> anyhow::Result<()> {
+        const CAPACITY: usize = 5;
+
+        let (mut tx, mut rx) = channel(bounded_cfg(CAPACITY, MixerOverflowPolicy::BackPressure));
+
+        for i in 0..CAPACITY {
+            SinkExt::send(&mut tx, i).await?;
+        }
+        assert!(matches!(tx.send(CAPACITY), Err(SenderError::Full)));
+
+        // The sink must not be ready until the receiver frees up space
+        let blocked = timeout(Duration::from_millis(10), SinkExt::send(&mut tx, CAPACITY)).await;
+        assert!(blocked.is_err(), "the send must be blocked by back-pressure");
+
+        // Keep a sender alive, so that the receiver does not terminate once the task finishes
+        let _sender = tx.clone();
+        let send_task = tokio::task::spawn(async move { SinkExt::send(&mut tx, CAPACITY).await });
+
+        let mut output = Vec::new();
+        for _ in 0..=CAPACITY {
+            output.push(
+                timeout(Duration::from_secs(1), rx.recv())
+                    .await?
+                    .expect("must receive item"),
+            );
+        }
+        timeout(Duration::from_secs(1), send_task).await???;
+        output.sort();
+
+        assert_eq!((0..=CAPACITY).collect::<Vec<_>>(), output);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn bounded_mixer_channel_should_close_back_pressured_sender_when_receiver_is_dropped() -> anyhow::Result<()> {
+        let (mut tx, rx) = channel(bounded_cfg(1, MixerOverflowPolicy::BackPressure));
+        SinkExt::send(&mut tx, 0).await?;
+
+        let send_task = tokio::task::spawn(async move { SinkExt::send(&mut tx, 1).await });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        drop(rx);
+
+        let res = timeout(Duration::from_secs(1), send_task).await??;
+        assert!(matches!(res, Err(SenderError::Closed)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn bounded_mixer_channel_should_drop_newest_items_when_full() -> anyhow::Result<()> {
+        const CAPACITY: usize = 5;
+
+        let (tx, rx) = channel(bounded_cfg(CAPACITY, MixerOverflowPolicy::DropNewest));
+
+        for i in 0..2 * CAPACITY {
+            tx.send(i)?;
+        }
+
+        let mut output = timeout(Duration::from_secs(1), rx.take(CAPACITY).collect::<Vec<_>>()).await?;
+        output.sort();
+        assert_eq!((0..CAPACITY).collect::<Vec<_>>(), output);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn bounded_mixer_channel_should_drop_items_with_oldest_release_when_full() -> anyhow::Result<()> {
+        const CAPACITY: usize = 5;
+
+        let (tx, rx) = channel(bounded_cfg(CAPACITY, MixerOverflowPolicy::DropOldestRelease));
+
+        for i in 0..2 * CAPACITY {
+            tx.send(i)?;
+        }
+
+        let mut output = timeout(Duration::from_secs(1), rx.take(CAPACITY).collect::<Vec<_>>()).await?;
+        output.sort();
+        assert_eq!((CAPACITY..2 * CAPACITY).collect::<Vec<_>>(), output);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn bounded_mixer_channel_should_randomly_drop_items_past_threshold() -> anyhow::Result<()> {
+        const CAPACITY: usize = 100;
+        const THRESHOLD: usize = 50;
+
+        let (tx, mut rx) = channel(bounded_cfg(
+            CAPACITY,
+            MixerOverflowPolicy::RandomEarlyDrop {
+                min_threshold: THRESHOLD,
+            },
+        ));
+
+        for i in 0..2 * CAPACITY {
+            tx.send(i)?;
+        }
+
+        let mut output = Vec::new();
+        while let Ok(Some(item)) = timeout(Duration::from_millis(200), rx.next()).await {
+            output.push(item);
+        }
+        output.sort();
+
+        assert!(output.len() <= CAPACITY);
+        assert!(output.len() > THRESHOLD);
+        assert_eq!((0..THRESHOLD).collect::<Vec<_>>(), output[..THRESHOLD]);
+
+        Ok(())
+    }
 }
@@ -1,10 +1,80 @@
-use std::time::Duration;
+use std::{str::FromStr, time::Duration};
+
+use crate::delay::{DelayDistribution, MixerDelayDistribution};
 
 pub const HOPR_MIXER_MINIMUM_DEFAULT_DELAY_IN_MS: u64 = 0;
 pub const HOPR_MIXER_DEFAULT_DELAY_RANGE_IN_MS: u64 = 200;
 pub const HOPR_MIXER_DELAY_METRIC_WINDOW: u64 = 100;
 pub const HOPR_MIXER_CAPACITY: usize = 20_000;
 
+/// Policy determining the mixer behavior once its buffer reaches the configured capacity.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum MixerOverflowPolicy {
+    /// The capacity is used solely to pre-allocate the buffer, which grows without limit.
+    #[default]
+    Unbounded,
+    /// New items are rejected with [`SenderError::Full`](crate::channel::SenderError::Full).
+    ///
+    /// Since a forwarding [`Sink`](futures::sink::Sink) pipeline terminates on the first error,
+    /// this policy is meant for callers handling the error of each send individually.
+    Reject,
+    /// The [`Sender`](crate::channel::Sender) sink is not ready until the receiver frees up space.
+    ///
+    /// The synchronous [`Sender::send`](crate::channel::Sender::send) cannot wait and
+    /// returns [`SenderError::Full`](crate::channel::SenderError::Full) instead.
+    BackPressure,
+    /// New items are silently dropped.
+    DropNewest,
+    /// The buffered item with the earliest release timestamp is dropped to make space for the new item.
+    DropOldestRelease,
+    /// Random early drop (RED) of new items.
+    ///
+    /// Once the buffer holds at least `min_threshold` items, new items are dropped with a probability
+    /// linearly increasing up to 1 at the full capacity.
+    RandomEarlyDrop {
+        /// Number of buffered items from which the random dropping starts.
+        ///
+        /// Must not be greater than the capacity of the mixer buffer.
+        min_threshold: usize,
+    },
+}
+
+/// Error returned when parsing the [`MixerOverflowPolicy`] from a string.
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "invalid mixer overflow policy '{0}', expected 'unbounded', 'reject', 'backpressure', 'drop-newest', \
+     'drop-oldest-release' or 'red:<min_threshold>'"
+)]
+pub struct ParseOverflowPolicyError(String);
+
+impl FromStr for MixerOverflowPolicy {
+    type Err = ParseOverflowPolicyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseOverflowPolicyError(s.to_string());
+
+        match s.trim().to_ascii_lowercase().as_str() {
+            "unbounded" => Ok(Self::Unbounded),
+            "reject" => Ok(Self::Reject),
+            "backpressure" => Ok(Self::BackPressure),
+            "drop-newest" => Ok(Self::DropNewest),
+            "drop-oldest-release" => Ok(Self::DropOldestRelease),
+            v => v
+                .strip_prefix("red:")
+                .and_then(|t| t.trim().parse::<usize>().ok())
+                .map(|min_threshold| Self::RandomEarlyDrop { min_threshold })
+                .ok_or_else(err),
+        }
+    }
+}
+
+/// Error returned when the [`MixerConfig`] is inconsistent.
+#[derive(Debug, thiserror::Error)]
+pub enum MixerConfigError {
+    #[error("random early drop threshold {0} is greater than the mixer capacity {1}")]
+    ThresholdAboveCapacity(usize, usize),
+}
+
 /// Mixer configuration.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, smart_default::SmartDefault)]
 pub struct MixerConfig {
@@ -14,28 +84,77 @@ pub struct MixerConfig {
     /// The range from the minimum delay to the maximum possible delay.
     #[default(Duration::from_millis(HOPR_MIXER_DEFAULT_DELAY_RANGE_IN_MS))]
     pub delay_range: Duration,
+    /// The distribution used to draw the delays from the `[min_delay, min_delay + delay_range]` interval.
+    pub delay_distribution: MixerDelayDistribution,
     /// The capacity of the preallocated mixer buffer.
     ///
-    /// It is possible to insert more items past the capacity, triggering
-    /// a possible buffer reallocation.
+    /// With the [`MixerOverflowPolicy::Unbounded`] policy, it is possible to insert more items
+    /// past the capacity, triggering a possible buffer reallocation. All other policies
+    /// treat the capacity as the hard limit of the mixer buffer.
     #[default(HOPR_MIXER_CAPACITY)]
     pub capacity: usize,
+    /// Policy applied when the mixer buffer reaches its `capacity`.
+    pub overflow_policy: MixerOverflowPolicy,
     #[default(HOPR_MIXER_DELAY_METRIC_WINDOW)]
     pub metric_delay_window: u64,
 }
 
 impl MixerConfig {
-    /// Get a random delay duration from the specified minimum and maximum delay available
-    /// inside the configuration.
+    /// Get a random delay duration from the configured delay distribution bounded by
+    /// the minimum and maximum delay available inside the configuration.
     pub fn random_delay(&self) -> Duration {
         let max_delay = self.min_delay.saturating_add(self.delay_range);
+        self.delay_distribution.sample_delay(self.min_delay, max_delay)
+    }
+
+    /// Indicates whether the mixer buffer is limited by its `capacity`.
+    pub fn is_bounded(&self) -> bool {
+        self.overflow_policy != MixerOverflowPolicy::Unbounded
+    }
+
+    /// Checks that the overflow policy is consistent with the `capacity`.
+    pub fn validate(&self) -> Result<(), MixerConfigError> {
+        match self.overflow_policy {
+            MixerOverflowPolicy::RandomEarlyDrop { min_threshold } if min_threshold > self.capacity => {
+                Err(MixerConfigError::ThresholdAboveCapacity(min_threshold, self.capacity))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mixer_overflow_policy_should_be_parseable_from_string() -> anyhow::Result<()> {
+        assert_eq!(MixerOverflowPolicy::Unbounded, "unbounded".parse()?);
+        assert_eq!(MixerOverflowPolicy::Reject, "reject".parse()?);
+        assert_eq!(MixerOverflowPolicy::BackPressure, "backpressure".parse()?);
+        assert_eq!(MixerOverflowPolicy::DropNewest, "drop-newest".parse()?);
+        assert_eq!(MixerOverflowPolicy::DropOldestRelease, "Drop-Oldest-Release".parse()?);
+        assert_eq!(
+            MixerOverflowPolicy::RandomEarlyDrop { min_threshold: 100 },
+            "red:100".parse()?
+        );
+        assert!("red".parse::<MixerOverflowPolicy>().is_err());
+        assert!("drop-all".parse::<MixerOverflowPolicy>().is_err());
+
+        Ok(())
+    }
 
-        let random_delay = if max_delay.as_millis() == 0 {
-            max_delay.as_millis() as u64
-        } else {
-            hopr_crypto_random::random_integer(self.min_delay.as_millis() as u64, Some(max_delay.as_millis() as u64))
+    #[test]
+    fn mixer_config_should_reject_random_early_drop_threshold_above_capacity() {
+        let cfg = |min_threshold| MixerConfig {
+            capacity: 100,
+            overflow_policy: MixerOverflowPolicy::RandomEarlyDrop { min_threshold },
+            ..Default::default()
         };
 
-        Duration::from_millis(random_delay)
+        assert!(cfg(50).validate().is_ok());
+        assert!(cfg(100).validate().is_ok());
+        assert!(cfg(101).validate().is_err());
+        assert!(MixerConfig::default().validate().is_ok());
     }
 }
@@ -0,0 +1,205 @@
+use std::{str::FromStr, time::Duration};
+
+use hopr_crypto_random::Rng;
+use rand_distr::Distribution;
+
+/// Maximum number of rejection sampling attempts before the truncated normal
+/// distribution falls back to clamping the sample into the allowed interval.
+const MAX_REJECTION_SAMPLING_ATTEMPTS: usize = 16;
+
+/// Abstraction over a random distribution used to generate the mixer delays.
+///
+/// Every sample must lie within the closed interval `[min, max]`.
+pub trait DelayDistribution {
+    /// Draw a single random delay from the `[min, max]` interval.
+    fn sample_delay(&self, min: Duration, max: Duration) -> Duration;
+}
+
+/// Delay distribution selection for the mixer.
+///
+/// All distributions are truncated to the interval given by the
+/// [`MixerConfig`](crate::config::MixerConfig) `min_delay` and `delay_range`,
+/// so that a single packet can never be held indefinitely.
+///
+/// The means of the distributions are offsets from the `min_delay`.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum MixerDelayDistribution {
+    /// Uniformly distributed delay in milliseconds.
+    #[default]
+    Uniform,
+    /// Exponentially distributed delay offset by the minimum delay.
+    ///
+    /// This corresponds to the Poisson mixing strategy (as used in Loopix), where
+    /// the memoryless property makes it impossible for an observer to correlate
+    /// packets based on the time they have already spent in the mixer.
+    Exponential {
+        /// Mean of the (non-truncated) exponential distribution on top of the minimum delay.
+        mean: Duration,
+    },
+    /// Normally distributed delay offset by the minimum delay and truncated to the allowed interval.
+    TruncatedNormal {
+        /// Mean of the (non-truncated) normal distribution on top of the minimum delay.
+        mean: Duration,
+        /// Standard deviation of the (non-truncated) normal distribution.
+        std_dev: Duration,
+    },
+}
+
+impl DelayDistribution for MixerDelayDistribution {
+    fn sample_delay(&self, min: Duration, max: Duration) -> Duration {
+        if max <= min {
+            return min;
+        }
+
+        match self {
+            MixerDelayDistribution::Uniform => Duration::from_millis(hopr_crypto_random::random_integer(
+                min.as_millis() as u64,
+                Some(max.as_millis() as u64),
+            )),
+            MixerDelayDistribution::Exponential { mean } => {
+                let mean = mean.as_secs_f64();
+                if mean <= 0.0 {
+                    return min;
+                }
+
+                // Inverse CDF of the exponential distribution truncated to [0, range]
+                let range = (max - min).as_secs_f64();
+                let u = hopr_crypto_random::random_float();
+                let offset = -mean * (1.0 - u * (1.0 - (-range / mean).exp())).ln();
+
+                min + Duration::from_secs_f64(offset.clamp(0.0, range))
+            }
+            MixerDelayDistribution::TruncatedNormal { mean, std_dev } => {
+                let Ok(normal) = rand_distr::Normal::new(mean.as_secs_f64(), std_dev.as_secs_f64()) else {
+                    return min;
+                };
+
+                // Sample the offset from the truncated normal distribution on [0, range]
+                let range = (max - min).as_secs_f64();
+                let mut rng = hopr_crypto_random::rng();
+                let offset = (0..MAX_REJECTION_SAMPLING_ATTEMPTS)
+                    .map(|_| normal.sample(&mut rng))
+                    .find(|v| (0.0..=range).contains(v))
+                    .unwrap_or_else(|| rng.gen_range(0.0..=range));
+
+                min + Duration::from_secs_f64(offset)
+            }
+        }
+    }
+}
+
+/// Error returned when parsing the [`MixerDelayDistribution`] from a string.
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "invalid mixer delay distribution '{0}', expected 'uniform', 'exponential:<mean_ms>' or \
+     'normal:<mean_ms>:<std_dev_ms>', where the means are offsets from the minimum delay"
+)]
+pub struct ParseDelayDistributionError(String);
+
+impl FromStr for MixerDelayDistribution {
+    type Err = ParseDelayDistributionError;
+
+    /// Parses the distribution from one of the following formats (all values in milliseconds,
+    /// the means are offsets from the minimum delay):
+    /// - `uniform`
+    /// - `exponential:<mean>`
+    /// - `normal:<mean>:<std_dev>`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseDelayDistributionError(s.to_string());
+        let parse_ms = |v: &str| v.trim().parse::<u64>().map(Duration::from_millis).map_err(|_| err());
+
+        let parts = s.trim().split(':').collect::<Vec<_>>();
+        match parts.as_slice() {
+            [kind] if kind.eq_ignore_ascii_case("uniform") => Ok(Self::Uniform),
+            [kind, mean] if kind.eq_ignore_ascii_case("exponential") => Ok(Self::Exponential { mean: parse_ms(mean)? }),
+            [kind, mean, std_dev] if kind.eq_ignore_ascii_case("normal") => Ok(Self::TruncatedNormal {
+                mean: parse_ms(mean)?,
+                std_dev: parse_ms(std_dev)?,
+            }),
+            _ => Err(err()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLES: usize = 10_000;
+    const MIN: Duration = Duration::from_millis(10);
+    const MAX: Duration = Duration::from_millis(210);
+
+    fn sample_mean_ms(dist: MixerDelayDistribution) -> f64 {
+        (0..SAMPLES)
+            .map(|_| {
+                let delay = dist.sample_delay(MIN, MAX);
+                assert!(delay >= MIN && delay <= MAX, "delay {delay:?} out of bounds");
+                delay.as_secs_f64() * 1000.0
+            })
+            .sum::<f64>()
+            / SAMPLES as f64
+    }
+
+    #[test]
+    fn uniform_delay_should_be_within_bounds_and_centered() {
+        let mean = sample_mean_ms(MixerDelayDistribution::Uniform);
+        assert!((mean - 110.0).abs() < 10.0, "unexpected mean {mean}");
+    }
+
+    #[test]
+    fn exponential_delay_should_be_within_bounds_and_skewed_towards_minimum() {
+        let mean = sample_mean_ms(MixerDelayDistribution::Exponential {
+            mean: Duration::from_millis(30),
+        });
+        // truncation makes the mean slightly smaller than min + 30 ms
+        assert!(mean > 30.0 && mean < 45.0, "unexpected mean {mean}");
+    }
+
+    #[test]
+    fn truncated_normal_delay_should_be_within_bounds_and_centered_at_mean_above_minimum() {
+        let mean = sample_mean_ms(MixerDelayDistribution::TruncatedNormal {
+            mean: Duration::from_millis(100),
+            std_dev: Duration::from_millis(20),
+        });
+        // the mean is an offset from the minimum delay
+        assert!((mean - 110.0).abs() < 5.0, "unexpected mean {mean}");
+    }
+
+    #[test]
+    fn all_distributions_should_return_minimum_on_empty_interval() {
+        for dist in [
+            MixerDelayDistribution::Uniform,
+            MixerDelayDistribution::Exponential {
+                mean: Duration::from_millis(30),
+            },
+            MixerDelayDistribution::TruncatedNormal {
+                mean: Duration::from_millis(100),
+                std_dev: Duration::from_millis(20),
+            },
+        ] {
+            assert_eq!(dist.sample_delay(MIN, MIN), MIN);
+        }
+    }
+
+    #[test]
+    fn delay_distribution_should_be_parseable_from_string() -> anyhow::Result<()> {
+        assert_eq!(MixerDelayDistribution::Uniform, "uniform".parse()?);
+        assert_eq!(
+            MixerDelayDistribution::Exponential {
+                mean: Duration::from_millis(50)
+            },
+            "exponential:50".parse()?
+        );
+        assert_eq!(
+            MixerDelayDistribution::TruncatedNormal {
+                mean: Duration::from_millis(100),
+                std_dev: Duration::from_millis(25)
+            },
+            "normal:100:25".parse()?
+        );
+        assert!("poisson".parse::<MixerDelayDistribution>().is_err());
+        assert!("exponential:abc".parse::<MixerDelayDistribution>().is_err());
+
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 pub mod channel;
 pub mod config;
 pub mod data;
+pub mod delay;
 
 pub use channel::channel;
-pub use config::MixerConfig;
+pub use config::{MixerConfig, MixerConfigError, MixerOverflowPolicy};
+pub use delay::{DelayDistribution, MixerDelayDistribution};
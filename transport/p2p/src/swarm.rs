@@ -98,8 +98,18 @@ where
         .build())
 }
 
+/// Change of the connectivity to a peer, as observed by the [`HoprSwarm`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeerConnectionEvent {
+    /// The first connection to the peer has been established.
+    Connected(PeerId),
+    /// The last connection to the peer has been closed.
+    Disconnected(PeerId),
+}
+
 pub struct HoprSwarm {
     pub(crate) swarm: libp2p::Swarm<HoprNetworkBehavior>,
+    connection_notifier: Option<futures::channel::mpsc::UnboundedSender<PeerConnectionEvent>>,
 }
 
 impl std::fmt::Debug for HoprSwarm {
@@ -179,7 +189,15 @@ impl HoprSwarm {
         //     "The node failed to listen on at least one of the specified interfaces"
         // );
 
-        Self { swarm }
+        Self {
+            swarm,
+            connection_notifier: None,
+        }
+    }
+
+    /// Sets the channel notified whenever a peer becomes connected or disconnected.
+    pub fn notify_peer_connections(&mut self, notifier: futures::channel::mpsc::UnboundedSender<PeerConnectionEvent>) {
+        self.connection_notifier = Some(notifier);
     }
 
     pub fn build_protocol_control(&self, protocol: &'static str) -> crate::HoprStreamProtocolControl {
@@ -193,6 +211,7 @@ impl HoprSwarm {
     ///
     /// This future can only be resolved by an unrecoverable error or a panic.
     pub async fn run(self, version: String) {
+        let connection_notifier = self.connection_notifier.clone();
         let mut swarm: libp2p::Swarm<HoprNetworkBehavior> = self.into();
 
         // NOTE: an improvement would be a forgetting cache for the active requests
@@ -307,6 +326,12 @@ impl HoprSwarm {
                         {
                             METRIC_TRANSPORT_P2P_OPEN_CONNECTION_COUNT.increment(1.0);
                         }
+
+                        if num_established.get() == 1 {
+                            if let Some(notifier) = &connection_notifier {
+                                let _ = notifier.unbounded_send(PeerConnectionEvent::Connected(peer_id));
+                            }
+                        }
                     }
                     SwarmEvent::ConnectionClosed {
                         peer_id,
@@ -324,6 +349,12 @@ impl HoprSwarm {
                         {
                             METRIC_TRANSPORT_P2P_OPEN_CONNECTION_COUNT.decrement(1.0);
                         }
+
+                        if num_established == 0 {
+                            if let Some(notifier) = &connection_notifier {
+                                let _ = notifier.unbounded_send(PeerConnectionEvent::Disconnected(peer_id));
+                            }
+                        }
                     }
                     SwarmEvent::IncomingConnection {
                         connection_id,
@@ -298,6 +298,10 @@ impl PathAddressResolver for TestResolver {
             .await
             .map_err(|_| PathError::InvalidPeer(key.to_hex()))
     }
+
+    async fn resolve_safe_address(&self, _address: &Address) -> Result<Option<Address>, PathError> {
+        Ok(None)
+    }
 }
 
 pub async fn resolve_mock_path(
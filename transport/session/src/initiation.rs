@@ -50,6 +50,29 @@ pub struct StartEstablished<T> {
     pub orig_challenge: StartChallenge,
     /// Session ID that was selected by the recipient.
     pub session_id: T,
+    /// Capabilities supported by the recipient.
+    ///
+    /// Recipients of older versions do not send their supported capabilities, in which case this set is empty.
+    pub supported_capabilities: HashSet<Capability>,
+}
+
+/// [`StartEstablished`] message as sent by recipients of older versions, without the supported capabilities.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct LegacyStartEstablished<T> {
+    orig_challenge: StartChallenge,
+    session_id: T,
+}
+
+#[cfg(feature = "serde")]
+impl<T> From<LegacyStartEstablished<T>> for StartEstablished<T> {
+    fn from(value: LegacyStartEstablished<T>) -> Self {
+        Self {
+            orig_challenge: value.orig_challenge,
+            session_id: value.session_id,
+            supported_capabilities: HashSet::new(),
+        }
+    }
 }
 
 #[cfg_attr(doc, aquamarine::aquamarine)]
@@ -132,7 +155,14 @@ impl<T: serde::Serialize + for<'de> serde::Deserialize<'de>> StartProtocol<T> {
             )),
             StartProtocolDiscriminants::SessionEstablished => Ok(StartProtocol::SessionEstablished(
                 bincode::serde::borrow_decode_from_slice(data, Self::SESSION_BINCODE_CONFIGURATION)
-                    .map(|(v, _bytes)| v)?,
+                    .map(|(v, _bytes)| v)
+                    .or_else(|_| {
+                        bincode::serde::borrow_decode_from_slice::<LegacyStartEstablished<T>, _>(
+                            data,
+                            Self::SESSION_BINCODE_CONFIGURATION,
+                        )
+                        .map(|(v, _bytes)| v.into())
+                    })?,
             )),
             StartProtocolDiscriminants::SessionError => Ok(StartProtocol::SessionError(
                 bincode::serde::borrow_decode_from_slice(data, Self::SESSION_BINCODE_CONFIGURATION)
@@ -211,6 +241,7 @@ mod tests {
     use hopr_crypto_random::Randomizable;
     use hopr_internal_types::prelude::{HoprPseudonym, Tag};
     use hopr_network_types::prelude::SealedHost;
+    use strum::IntoEnumIterator;
 
     use super::*;
     use crate::SessionId;
@@ -257,6 +288,7 @@ mod tests {
         let msg_1 = StartProtocol::<i32>::SessionEstablished(StartEstablished {
             orig_challenge: 0,
             session_id: 10,
+            supported_capabilities: HashSet::from_iter([Capability::Segmentation, Capability::Multipath]),
         });
 
         let (tag, msg) = msg_1.clone().encode()?;
@@ -268,6 +300,25 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn start_protocol_session_established_message_should_decode_without_supported_capabilities() -> anyhow::Result<()> {
+        let legacy_msg =
+            bincode::serde::encode_to_vec((5_u64, 10_i32), StartProtocol::<i32>::SESSION_BINCODE_CONFIGURATION)?;
+
+        let msg = StartProtocol::<i32>::decode(StartProtocolDiscriminants::SessionEstablished as Tag + 1, &legacy_msg)?;
+
+        assert_eq!(
+            StartProtocol::SessionEstablished(StartEstablished {
+                orig_challenge: 5,
+                session_id: 10,
+                supported_capabilities: HashSet::new(),
+            }),
+            msg
+        );
+        Ok(())
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn start_protocol_session_error_message_should_encode_and_decode() -> anyhow::Result<()> {
@@ -333,6 +384,7 @@ mod tests {
         let msg = StartProtocol::SessionEstablished(StartEstablished {
             orig_challenge: StartChallenge::MAX,
             session_id: SessionId::new(u16::MAX, HoprPseudonym::random()),
+            supported_capabilities: Capability::iter().collect(),
         });
 
         assert!(
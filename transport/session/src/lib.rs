@@ -10,14 +10,20 @@ pub(crate) mod balancer;
 pub mod errors;
 mod initiation;
 mod manager;
+mod multipath;
+mod stats;
 pub mod traits;
 mod types;
 
-pub use balancer::SurbBalancerConfig;
+pub use balancer::{RateController, RateLimitExt, SurbBalancerConfig};
 use hopr_internal_types::prelude::HoprPseudonym;
 use hopr_network_types::prelude::state::{SessionFeature, SessionSocket};
 pub use hopr_network_types::types::*;
-pub use manager::{DispatchResult, MIN_BALANCER_SAMPLING_INTERVAL, SessionManager, SessionManagerConfig};
+pub use manager::{
+    DispatchResult, MIN_BALANCER_SAMPLING_INTERVAL, SessionLifecycleEvent, SessionManager, SessionManagerConfig,
+};
+pub use multipath::MultipathConfig;
+pub use stats::{SessionStats, SessionStatsSnapshot};
 #[cfg(feature = "runtime-tokio")]
 pub use types::transfer_session;
 pub use types::{IncomingSession, ServiceId, Session, SessionId, SessionTarget, USABLE_PAYLOAD_CAPACITY_FOR_SESSION};
@@ -42,6 +48,12 @@ pub enum Capability {
     RetransmissionAckOnly,
     /// Disable packet buffering
     NoDelay,
+    /// Stripe the Session segments across multiple forward paths that do not share any relays.
+    ///
+    /// Implies [`Capability::Segmentation`], so that the segments can be reassembled at the recipient.
+    /// It is used only if the recipient confirms it supports it when the Session is established,
+    /// otherwise the Session uses a single path.
+    Multipath,
 }
 
 impl IntoIterator for Capability {
@@ -60,6 +72,7 @@ impl IntoIterator for Capability {
                 vec![SessionFeature::AcknowledgeFrames, SessionFeature::RetransmitFrames]
             }
             Capability::NoDelay => vec![SessionFeature::NoDelay],
+            Capability::Multipath => vec![],
         }
         .into_iter()
     }
@@ -77,6 +90,8 @@ pub struct SessionClientConfig {
     /// The return path options for the session.
     #[default(RoutingOptions::Hops(hopr_primitive_types::bounded::BoundedSize::MIN))]
     pub return_path_options: RoutingOptions,
+    /// Additional constraints on the relays of both forward and return paths.
+    pub path_constraints: PathConstraints,
     /// Capabilities offered by the session.
     #[default(_code = "vec![Capability::Segmentation]")]
     pub capabilities: Vec<Capability>,
@@ -86,4 +101,6 @@ pub struct SessionClientConfig {
     /// Enable automatic SURB management for the Session.
     #[default(Some(SurbBalancerConfig::default()))]
     pub surb_management: Option<SurbBalancerConfig>,
+    /// Configuration of the multipath mode, used only if [`Capability::Multipath`] is requested.
+    pub multipath: MultipathConfig,
 }
@@ -15,13 +15,15 @@ use hopr_crypto_random::Randomizable;
 use hopr_internal_types::prelude::{ApplicationData, HoprPseudonym, Tag};
 use hopr_network_types::prelude::*;
 use hopr_primitive_types::prelude::Address;
+use strum::IntoEnumIterator;
 use tracing::{debug, error, info, trace, warn};
 
 use crate::{
-    IncomingSession, Session, SessionClientConfig, SessionId, SessionTarget,
+    Capability, IncomingSession, Session, SessionClientConfig, SessionId, SessionTarget,
     balancer::{RateController, RateLimitExt, SurbBalancer, SurbFlowController},
     errors::{SessionManagerError, TransportSessionError},
     initiation::{StartChallenge, StartErrorReason, StartErrorType, StartEstablished, StartInitiation, StartProtocol},
+    stats::{SessionStats, SessionStatsSnapshot},
     traits::SendMsg,
 };
 
@@ -51,6 +53,23 @@ lazy_static::lazy_static! {
     ).unwrap();
 }
 
+/// Lifecycle event of a Session managed by the [`SessionManager`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionLifecycleEvent {
+    /// A new Session has been established.
+    Opened {
+        /// ID of the Session.
+        session_id: SessionId,
+        /// Indicates whether the Session was initiated by the other party.
+        incoming: bool,
+    },
+    /// The Session has been closed, either by any of the parties or due to inactivity.
+    Closed {
+        /// ID of the Session.
+        session_id: SessionId,
+    },
+}
+
 /// Configuration for the [`SessionManager`].
 #[derive(Clone, Debug, PartialEq, Eq, smart_default::SmartDefault)]
 pub struct SessionManagerConfig {
@@ -150,13 +169,14 @@ fn close_session_after_eviction<S: SendMsg + Send + Sync + 'static>(
 /// This function will use the given generator to generate an initial seeding key.
 /// It will check whether the given cache already contains a value for that key, and if not,
 /// calls the generator (with the previous value) to generate a new seeding key and retry.
-/// The function either finds a suitable free slot, inserting the `value` and returns the found key,
-/// or terminates with `None` when `gen` returns the initial seed again.
-async fn insert_into_next_slot<K, V, F>(cache: &moka::future::Cache<K, V>, gen: F, value: V) -> Option<K>
+/// The function either finds a suitable free slot, inserting the value created by `value` for that key
+/// and returns the found key with the value, or terminates with `None` when `gen` returns the initial seed again.
+async fn insert_into_next_slot<K, V, F, G>(cache: &moka::future::Cache<K, V>, gen: F, value: G) -> Option<(K, V)>
 where
     K: Copy + std::hash::Hash + Eq + Send + Sync + 'static,
     V: Clone + Send + Sync + 'static,
     F: Fn(Option<K>) -> K,
+    G: Fn(K) -> V,
 {
     let initial = gen(None);
     let mut next = initial;
@@ -165,7 +185,7 @@ where
             .entry(next)
             .and_try_compute_with(|e| {
                 if e.is_none() {
-                    futures::future::ok::<_, ()>(moka::ops::compute::Op::Put(value.clone()))
+                    futures::future::ok::<_, ()>(moka::ops::compute::Op::Put(value(next)))
                 } else {
                     futures::future::ok::<_, ()>(moka::ops::compute::Op::Nop)
                 }
@@ -173,8 +193,8 @@ where
             .await;
 
         // If we inserted successfully, break the loop and return the insertion key
-        if let Ok(moka::ops::compute::CompResult::Inserted(_)) = insertion_result {
-            return Some(next);
+        if let Ok(moka::ops::compute::CompResult::Inserted(entry)) = insertion_result {
+            return Some((next, entry.into_value()));
         }
 
         // Otherwise, generate the next key
@@ -206,6 +226,7 @@ struct CachedSession {
     session_tx: Arc<UnboundedSender<Box<[u8]>>>,
     routing_opts: DestinationRouting,
     abort_handles: Vec<AbortHandle>,
+    stats: Arc<SessionStats>,
 }
 
 /// Indicates the result of processing a message.
@@ -272,6 +293,7 @@ impl<T: SendMsg + Send + Sync> SendMsg for CountingSendMsg<T> {
 pub struct SessionManager<S> {
     session_initiations: SessionInitiationCache,
     session_notifiers: Arc<OnceLock<(UnboundedSender<IncomingSession>, UnboundedSender<SessionId>)>>,
+    lifecycle_notifier: Arc<OnceLock<UnboundedSender<SessionLifecycleEvent>>>,
     sessions: moka::future::Cache<SessionId, CachedSession>,
     msg_sender: Arc<OnceLock<S>>,
     cfg: SessionManagerConfig,
@@ -282,6 +304,7 @@ impl<S> Clone for SessionManager<S> {
         Self {
             session_initiations: self.session_initiations.clone(),
             session_notifiers: self.session_notifiers.clone(),
+            lifecycle_notifier: self.lifecycle_notifier.clone(),
             sessions: self.sessions.clone(),
             cfg: self.cfg.clone(),
             msg_sender: self.msg_sender.clone(),
@@ -312,6 +335,8 @@ impl<S: SendMsg + Clone + Send + Sync + 'static> SessionManager<S> {
         METRIC_ACTIVE_SESSIONS.set(0.0);
 
         let msg_sender = Arc::new(OnceLock::new());
+        let lifecycle_notifier = Arc::new(OnceLock::<UnboundedSender<SessionLifecycleEvent>>::new());
+        let lifecycle_notifier_clone = lifecycle_notifier.clone();
         Self {
             msg_sender: msg_sender.clone(),
             session_initiations: moka::future::Cache::builder()
@@ -327,15 +352,39 @@ impl<S: SendMsg + Clone + Send + Sync + 'static> SessionManager<S> {
                 .max_capacity(u16::MAX as u64)
                 .time_to_idle(cfg.idle_timeout)
                 .async_eviction_listener(move |k, v, c| {
+                    if c != moka::notification::RemovalCause::Replaced {
+                        if let Some(notifier) = lifecycle_notifier_clone.get() {
+                            let _ = notifier.unbounded_send(SessionLifecycleEvent::Closed { session_id: *k });
+                        }
+                    }
                     let msg_sender = msg_sender.clone();
                     close_session_after_eviction(msg_sender, *k, v, c)
                 })
                 .build(),
             session_notifiers: Arc::new(OnceLock::new()),
+            lifecycle_notifier,
             cfg,
         }
     }
 
+    /// Sets the channel notified whenever a Session is opened or closed.
+    ///
+    /// This can be done only once.
+    pub fn notify_session_lifecycle(
+        &self,
+        notifier: UnboundedSender<SessionLifecycleEvent>,
+    ) -> crate::errors::Result<()> {
+        self.lifecycle_notifier
+            .set(notifier)
+            .map_err(|_| SessionManagerError::AlreadyStarted.into())
+    }
+
+    fn notify_session_opened(&self, session_id: SessionId, incoming: bool) {
+        if let Some(notifier) = self.lifecycle_notifier.get() {
+            let _ = notifier.unbounded_send(SessionLifecycleEvent::Opened { session_id, incoming });
+        }
+    }
+
     /// Starts the instance with the given [transport](SendMsg) implementation
     /// and a channel that is used to notify when a new incoming session is opened to us.
     ///
@@ -413,6 +462,25 @@ impl<S: SendMsg + Clone + Send + Sync + 'static> SessionManager<S> {
         self.session_notifiers.get().is_some()
     }
 
+    /// Returns the [statistics](SessionStatsSnapshot) of an active Session.
+    ///
+    /// Observing the statistics does not count as a Session activity, so it does not prevent
+    /// the Session from being closed when idle.
+    pub fn session_stats(&self, session_id: &SessionId) -> Option<SessionStatsSnapshot> {
+        self.sessions
+            .iter()
+            .find(|(id, _)| id.as_ref() == session_id)
+            .map(|(_, session)| session.stats.snapshot())
+    }
+
+    /// Returns the [statistics](SessionStatsSnapshot) of all active Sessions.
+    pub fn all_session_stats(&self) -> Vec<SessionStatsSnapshot> {
+        self.sessions
+            .iter()
+            .map(|(_, session)| session.stats.snapshot())
+            .collect()
+    }
+
     fn spawn_keep_alive_stream(
         &self,
         session_id: SessionId,
@@ -461,10 +529,15 @@ impl<S: SendMsg + Clone + Send + Sync + 'static> SessionManager<S> {
         &self,
         destination: Address,
         target: SessionTarget,
-        cfg: SessionClientConfig,
+        mut cfg: SessionClientConfig,
     ) -> crate::errors::Result<Session> {
         let msg_sender = self.msg_sender.get().ok_or(SessionManagerError::NotStarted)?;
 
+        // Segments striped across multiple paths must be reassembled at the counterparty
+        if cfg.capabilities.contains(&Capability::Multipath) && !cfg.capabilities.contains(&Capability::Segmentation) {
+            cfg.capabilities.push(Capability::Segmentation);
+        }
+
         let (tx_initiation_done, rx_initiation_done) = futures::channel::mpsc::unbounded();
         let challenge = insert_into_next_slot(
             &self.session_initiations,
@@ -475,17 +548,24 @@ impl<S: SendMsg + Clone + Send + Sync + 'static> SessionManager<S> {
                     hopr_crypto_random::random_integer(MIN_CHALLENGE, None)
                 }
             },
-            tx_initiation_done,
+            |_| tx_initiation_done.clone(),
         )
         .await
+        .map(|(challenge, _)| challenge)
         .ok_or(SessionManagerError::NoChallengeSlots)?; // almost impossible with u64
 
         // Prepare the session initiation message in the Start protocol
         trace!(challenge, ?cfg, "initiating session with config");
+        // Multipath is a mode of the sender only, it is enabled once the counterparty confirms it supports it
         let start_session_msg = StartProtocol::<SessionId>::StartSession(StartInitiation {
             challenge,
             target,
-            capabilities: cfg.capabilities.iter().copied().collect(),
+            capabilities: cfg
+                .capabilities
+                .iter()
+                .copied()
+                .filter(|capability| *capability != Capability::Multipath)
+                .collect(),
         });
 
         let pseudonym = cfg.pseudonym.unwrap_or(HoprPseudonym::random());
@@ -494,6 +574,7 @@ impl<S: SendMsg + Clone + Send + Sync + 'static> SessionManager<S> {
             pseudonym: Some(pseudonym), // Session must use a fixed pseudonym already
             forward_options: cfg.forward_path_options.clone(),
             return_options: cfg.return_path_options.clone().into(),
+            path_constraints: cfg.path_constraints.clone(),
         };
 
         // Send the Session initiation message
@@ -520,7 +601,36 @@ impl<S: SendMsg + Clone + Send + Sync + 'static> SessionManager<S> {
                 let session_id = est.session_id;
                 debug!(challenge = est.orig_challenge, ?session_id, "started a new session");
 
+                if cfg.capabilities.contains(&Capability::Multipath)
+                    && !est.supported_capabilities.contains(&Capability::Multipath)
+                {
+                    warn!(%session_id, "counterparty does not support multipath sessions, using a single path");
+                    cfg.capabilities
+                        .retain(|capability| *capability != Capability::Multipath);
+                }
+
+                // Multipath Sessions stripe the data across multiple forward paths with the same pseudonym
+                let forward_routings = if cfg.capabilities.contains(&Capability::Multipath) {
+                    match msg_sender
+                        .resolve_disjoint_routings(forward_routing.clone(), cfg.multipath.paths)
+                        .await
+                    {
+                        Ok(routings) if !routings.is_empty() => {
+                            debug!(%session_id, num_paths = routings.len(), "resolved multipath session routings");
+                            routings
+                        }
+                        Ok(_) => vec![forward_routing.clone()],
+                        Err(error) => {
+                            warn!(%session_id, %error, "failed to resolve multiple session paths, using a single one");
+                            vec![forward_routing.clone()]
+                        }
+                    }
+                } else {
+                    vec![forward_routing.clone()]
+                };
+
                 let (tx, rx) = futures::channel::mpsc::unbounded::<Box<[u8]>>();
+                let stats = Arc::new(SessionStats::new(session_id));
                 let mut abort_handles = Vec::new();
                 let notifier = self
                     .session_notifiers
@@ -559,6 +669,8 @@ impl<S: SendMsg + Clone + Send + Sync + 'static> SessionManager<S> {
                         ka_controller,
                         balancer_config,
                     );
+                    let balancer_stats = stats.clone();
+                    let surbs_consumed = surb_consumption_counter.clone();
 
                     let (surbs_ready_tx, surbs_ready_rx) = futures::channel::oneshot::channel();
                     let mut surbs_ready_tx = Some(surbs_ready_tx);
@@ -575,6 +687,8 @@ impl<S: SendMsg + Clone + Send + Sync + 'static> SessionManager<S> {
                         )
                         .for_each(move |_| {
                             let level = balancer.update();
+                            balancer_stats
+                                .surb_estimates(level, surbs_consumed.load(std::sync::atomic::Ordering::Relaxed));
                             // We will wait until at least half of the target buffer has been sent
                             if surbs_ready_tx.is_some() && level >= balancer_config.target_surb_buffer_size / 2 {
                                 let _ = surbs_ready_tx.take().unwrap().send(level);
@@ -606,9 +720,10 @@ impl<S: SendMsg + Clone + Send + Sync + 'static> SessionManager<S> {
                         }
                     }
 
-                    Session::new(
+                    Session::new_multipath(
                         session_id,
-                        forward_routing.clone(),
+                        forward_routings,
+                        cfg.multipath,
                         cfg.capabilities.into_iter().collect(),
                         sender,
                         Box::pin(rx.inspect(move |_| {
@@ -617,16 +732,19 @@ impl<S: SendMsg + Clone + Send + Sync + 'static> SessionManager<S> {
                         })),
                         Some(notifier),
                     )
+                    .with_stats(stats.clone())
                 } else {
                     warn!(%session_id, "session ready without SURB balancing");
-                    Session::new(
+                    Session::new_multipath(
                         session_id,
-                        forward_routing.clone(),
+                        forward_routings,
+                        cfg.multipath,
                         cfg.capabilities.into_iter().collect(),
                         Arc::new(msg_sender.clone()),
                         Box::pin(rx),
                         Some(notifier),
                     )
+                    .with_stats(stats.clone())
                 };
 
                 // We currently do not support loopback Sessions on ourselves.
@@ -639,6 +757,7 @@ impl<S: SendMsg + Clone + Send + Sync + 'static> SessionManager<S> {
                                 session_tx: Arc::new(tx),
                                 routing_opts: forward_routing,
                                 abort_handles,
+                                stats,
                             })
                         } else {
                             moka::ops::compute::Op::Nop
@@ -652,6 +771,8 @@ impl<S: SendMsg + Clone + Send + Sync + 'static> SessionManager<S> {
                         METRIC_ACTIVE_SESSIONS.increment(1.0);
                     }
 
+                    self.notify_session_opened(session_id, false);
+
                     Ok(session)
                 } else {
                     // Session already exists; it means it is most likely a loopback attempt
@@ -745,7 +866,8 @@ impl<S: SendMsg + Clone + Send + Sync + 'static> SessionManager<S> {
 
                 // Construct the session
                 let (tx_session_data, rx_session_data) = futures::channel::mpsc::unbounded::<Box<[u8]>>();
-                if let Some(session_id) = insert_into_next_slot(
+                let tx_session_data = Arc::new(tx_session_data);
+                if let Some((session_id, cached)) = insert_into_next_slot(
                     &self.sessions,
                     |sid| {
                         // NOTE: It is allowed to insert sessions using the same tag
@@ -760,10 +882,11 @@ impl<S: SendMsg + Clone + Send + Sync + 'static> SessionManager<S> {
                         };
                         SessionId::new(next_tag, pseudonym)
                     },
-                    CachedSession {
-                        session_tx: Arc::new(tx_session_data),
+                    |session_id| CachedSession {
+                        session_tx: tx_session_data.clone(),
                         routing_opts: reply_routing.clone(),
                         abort_handles: vec![],
+                        stats: Arc::new(SessionStats::new(session_id)),
                     },
                 )
                 .await
@@ -781,7 +904,8 @@ impl<S: SendMsg + Clone + Send + Sync + 'static> SessionManager<S> {
                                 error!(%session_id, %error, "failed to notify session closure");
                             }
                         })),
-                    );
+                    )
+                    .with_stats(cached.stats);
 
                     // Extract useful information about the session from the Start protocol message
                     let incoming_session = IncomingSession {
@@ -801,6 +925,7 @@ impl<S: SendMsg + Clone + Send + Sync + 'static> SessionManager<S> {
                     let data = StartProtocol::SessionEstablished(StartEstablished {
                         orig_challenge: session_req.challenge,
                         session_id,
+                        supported_capabilities: Capability::iter().collect(),
                     });
 
                     msg_sender
@@ -817,6 +942,8 @@ impl<S: SendMsg + Clone + Send + Sync + 'static> SessionManager<S> {
                         METRIC_NUM_ESTABLISHED_SESSIONS.increment();
                         METRIC_ACTIVE_SESSIONS.increment(1.0);
                     }
+
+                    self.notify_session_opened(session_id, true);
                 } else {
                     error!(
                         %pseudonym,
@@ -965,9 +1092,7 @@ mod tests {
     use tokio::time::timeout;
 
     use super::*;
-    use crate::{
-        Capability, balancer::SurbBalancerConfig, initiation::StartProtocolDiscriminants, types::SessionTarget,
-    };
+    use crate::{balancer::SurbBalancerConfig, initiation::StartProtocolDiscriminants, types::SessionTarget};
 
     mockall::mock! {
         MsgSender {}
@@ -1001,17 +1126,25 @@ mod tests {
         let cache = moka::future::Cache::new(10);
 
         for i in 0..5 {
-            let v = insert_into_next_slot(&cache, |prev| prev.map(|v| (v + 1) % 5).unwrap_or(0), "foo".to_string())
-                .await
-                .ok_or(anyhow!("should insert"))?;
+            let (v, _) = insert_into_next_slot(
+                &cache,
+                |prev| prev.map(|v| (v + 1) % 5).unwrap_or(0),
+                |_| "foo".to_string(),
+            )
+            .await
+            .ok_or(anyhow!("should insert"))?;
             assert_eq!(v, i);
             assert_eq!(Some("foo".to_string()), cache.get(&i).await);
         }
 
         assert!(
-            insert_into_next_slot(&cache, |prev| prev.map(|v| (v + 1) % 5).unwrap_or(0), "foo".to_string())
-                .await
-                .is_none(),
+            insert_into_next_slot(
+                &cache,
+                |prev| prev.map(|v| (v + 1) % 5).unwrap_or(0),
+                |_| { "foo".to_string() }
+            )
+            .await
+            .is_none(),
             "must not find slot when full"
         );
 
@@ -1345,6 +1478,7 @@ mod tests {
                     session_tx: Arc::new(dummy_tx),
                     routing_opts: DestinationRouting::Return(SurbMatcher::Pseudonym(alice_pseudonym)),
                     abort_handles: Vec::new(),
+                    stats: Arc::new(SessionStats::new(SessionId::new(16, alice_pseudonym))),
                 },
             )
             .await;
@@ -1716,6 +1850,13 @@ mod tests {
 
         // Let the Surb balancer send enough KeepAlive messages
         tokio::time::sleep(Duration::from_millis(3000)).await;
+
+        let alice_stats = alice_mgr
+            .session_stats(alice_session.id())
+            .ok_or(anyhow!("alice must have session stats"))?;
+        assert!(alice_stats.surb_buffer_estimate.is_some_and(|level| level > 0));
+        assert_eq!(1, bob_mgr.all_session_stats().len());
+
         alice_session.close().await?;
 
         tokio::time::sleep(Duration::from_millis(300)).await;
@@ -0,0 +1,444 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        Mutex, PoisonError,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+};
+
+use hopr_network_types::{
+    prelude::{DestinationRouting, RoutingOptions},
+    session::SegmentId,
+};
+use tracing::{debug, warn};
+
+use crate::types::SessionId;
+
+/// Configuration of the multipath Session mode.
+///
+/// See [`Capability::Multipath`](crate::Capability::Multipath).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, smart_default::SmartDefault)]
+pub struct MultipathConfig {
+    /// Number of forward paths (not sharing any relays) to stripe the Session segments across.
+    ///
+    /// Fewer paths are used if not enough such paths can be found.
+    #[default(2)]
+    pub paths: usize,
+    /// Number of consecutive failed packet deliveries after which a path is no longer used
+    /// and a replacement path is looked up.
+    ///
+    /// A segment is considered lost on a path when it has to be retransmitted, which happens
+    /// only if [`Capability::Retransmission`](crate::Capability::Retransmission) or
+    /// [`Capability::RetransmissionAckOnly`](crate::Capability::RetransmissionAckOnly) is enabled.
+    /// The last remaining path is never dropped.
+    #[default(5)]
+    pub max_consecutive_failures: usize,
+}
+
+/// Number of the most recently sent segments, whose retransmissions are attributed to the path they were sent over.
+///
+/// A segment that leaves this window without being retransmitted counts as delivered.
+const SENT_SEGMENTS_WINDOW: usize = 2048;
+
+#[derive(Debug)]
+struct StripedPath {
+    routing: Mutex<DestinationRouting>,
+    consecutive_failures: AtomicUsize,
+    dropped: AtomicBool,
+    replacing: AtomicBool,
+}
+
+/// Segments sent recently, along with the index of the path each was last sent over.
+#[derive(Debug, Default)]
+struct SentSegments {
+    paths: HashMap<SegmentId, usize>,
+    order: VecDeque<SegmentId>,
+}
+
+/// Distributes outgoing Session packets across multiple routings in a round-robin fashion
+/// and keeps track of delivery failures on each of them.
+#[derive(Debug)]
+pub(crate) struct PathStriping {
+    session_id: SessionId,
+    paths: Vec<StripedPath>,
+    next: AtomicUsize,
+    active: AtomicUsize,
+    max_consecutive_failures: usize,
+    sent_segments: Mutex<SentSegments>,
+}
+
+impl PathStriping {
+    /// Creates striping over the given non-empty list of routings.
+    pub fn new(session_id: SessionId, routings: Vec<DestinationRouting>, max_consecutive_failures: usize) -> Self {
+        assert!(!routings.is_empty(), "at least one routing must be given");
+
+        Self {
+            session_id,
+            active: AtomicUsize::new(routings.len()),
+            paths: routings
+                .into_iter()
+                .map(|routing| StripedPath {
+                    routing: Mutex::new(routing),
+                    consecutive_failures: AtomicUsize::new(0),
+                    dropped: AtomicBool::new(false),
+                    replacing: AtomicBool::new(false),
+                })
+                .collect(),
+            next: AtomicUsize::new(0),
+            max_consecutive_failures: max_consecutive_failures.max(1),
+            sent_segments: Default::default(),
+        }
+    }
+
+    /// Creates a trivial striping over a single routing.
+    pub fn single(session_id: SessionId, routing: DestinationRouting) -> Self {
+        Self::new(session_id, vec![routing], usize::MAX)
+    }
+
+    /// Number of paths that have not been dropped.
+    pub fn num_active(&self) -> usize {
+        self.active.load(Ordering::Acquire)
+    }
+
+    /// Indicates whether the striping is done over more than one path.
+    pub fn is_multipath(&self) -> bool {
+        self.paths.len() > 1
+    }
+
+    fn routing(&self, idx: usize) -> DestinationRouting {
+        self.paths[idx]
+            .routing
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Returns the index and routing of the next path that has not been dropped.
+    pub fn next_path(&self) -> (usize, DestinationRouting) {
+        let num_paths = self.paths.len();
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+
+        let idx = (0..num_paths)
+            .map(|i| (start + i) % num_paths)
+            .find(|&idx| !self.paths[idx].dropped.load(Ordering::Acquire))
+            .unwrap_or(start % num_paths);
+
+        (idx, self.routing(idx))
+    }
+
+    /// Records successful delivery over the path with the given index.
+    pub fn record_success(&self, idx: usize) {
+        self.paths[idx].consecutive_failures.store(0, Ordering::Release);
+    }
+
+    /// Records failed delivery over the path with the given index.
+    ///
+    /// Drops the path if it failed too many times in a row, unless it is the last active path.
+    /// Returns `true` if the failure can be tolerated, because other active paths remain.
+    pub fn record_failure(&self, idx: usize) -> bool {
+        let path = &self.paths[idx];
+        let failures = path.consecutive_failures.fetch_add(1, Ordering::AcqRel) + 1;
+
+        if failures >= self.max_consecutive_failures
+            && !path.dropped.load(Ordering::Acquire)
+            && self
+                .active
+                .fetch_update(Ordering::AcqRel, Ordering::Acquire, |active| {
+                    (active > 1).then(|| active - 1)
+                })
+                .is_ok()
+        {
+            path.dropped.store(true, Ordering::Release);
+            warn!(session_id = %self.session_id, path = idx, failures, "dropping unreliable session path");
+        }
+
+        if path.dropped.load(Ordering::Acquire) {
+            self.num_active() > 0
+        } else {
+            self.num_active() > 1
+        }
+    }
+
+    /// Records that the given segments are being sent over the path with the given index.
+    ///
+    /// A segment that is sent again is a retransmission, which means that the previous transmission
+    /// or its acknowledgement got lost. This counts as a failure of the path the segment was previously
+    /// sent over.
+    pub fn record_sent_segments<I: IntoIterator<Item = SegmentId>>(&self, idx: usize, segments: I) {
+        let mut failed = Vec::new();
+        let mut delivered = Vec::new();
+        {
+            let mut sent = self.sent_segments.lock().unwrap_or_else(PoisonError::into_inner);
+            for segment in segments {
+                match sent.paths.insert(segment, idx) {
+                    Some(prev_idx) => failed.push(prev_idx),
+                    None => sent.order.push_back(segment),
+                }
+            }
+
+            while sent.order.len() > SENT_SEGMENTS_WINDOW {
+                if let Some(prev_idx) = sent.order.pop_front().and_then(|segment| sent.paths.remove(&segment)) {
+                    delivered.push(prev_idx);
+                }
+            }
+        }
+
+        for prev_idx in failed {
+            debug!(session_id = %self.session_id, path = prev_idx, "segment retransmitted, counting as lost on the path");
+            self.record_failure(prev_idx);
+        }
+        for prev_idx in delivered {
+            self.record_success(prev_idx);
+        }
+    }
+
+    /// Marks a dropped path as being replaced and returns its index together with the routing
+    /// to resolve the replacement path from.
+    ///
+    /// The returned routing asks for the same number of hops as the dropped path, but excludes the relays
+    /// of all the paths of the striping, including the dropped one.
+    /// Only paths with a fixed intermediate path can be replaced.
+    pub fn take_replaceable(&self) -> Option<(usize, DestinationRouting)> {
+        let idx = self.paths.iter().position(|path| {
+            path.dropped.load(Ordering::Acquire)
+                && path
+                    .replacing
+                    .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+                    .is_ok()
+        })?;
+
+        let used_relays = (0..self.paths.len())
+            .filter_map(|i| match self.routing(i) {
+                DestinationRouting::Forward {
+                    forward_options: RoutingOptions::IntermediatePath(relays),
+                    ..
+                } => Some(relays.into_iter()),
+                _ => None,
+            })
+            .flatten()
+            .collect::<Vec<_>>();
+
+        match self.routing(idx) {
+            DestinationRouting::Forward {
+                destination,
+                pseudonym,
+                forward_options: RoutingOptions::IntermediatePath(relays),
+                return_options,
+                path_constraints,
+            } => Some((
+                idx,
+                DestinationRouting::Forward {
+                    destination,
+                    pseudonym,
+                    forward_options: RoutingOptions::Hops(relays.as_ref().len().try_into().ok()?),
+                    return_options,
+                    path_constraints: path_constraints.excluding(used_relays),
+                },
+            )),
+            _ => None,
+        }
+    }
+
+    /// Replaces the forward path of the dropped path with the given index and starts using it again.
+    ///
+    /// If `forward_options` is `None`, no replacement was found and the path can be taken
+    /// for replacement again.
+    pub fn replace(&self, idx: usize, forward_options: Option<RoutingOptions>) {
+        let path = &self.paths[idx];
+        let Some(new_options) = forward_options else {
+            path.replacing.store(false, Ordering::Release);
+            return;
+        };
+
+        if let DestinationRouting::Forward { forward_options, .. } =
+            &mut *path.routing.lock().unwrap_or_else(PoisonError::into_inner)
+        {
+            *forward_options = new_options;
+        }
+
+        // Retransmissions of the segments sent over the old path must not count against the new one
+        {
+            let mut sent = self.sent_segments.lock().unwrap_or_else(PoisonError::into_inner);
+            sent.paths.retain(|_, path_idx| *path_idx != idx);
+            let SentSegments { paths, order } = &mut *sent;
+            order.retain(|segment| paths.contains_key(segment));
+        }
+
+        path.consecutive_failures.store(0, Ordering::Release);
+        path.replacing.store(false, Ordering::Release);
+        if path.dropped.swap(false, Ordering::AcqRel) {
+            self.active.fetch_add(1, Ordering::AcqRel);
+        }
+        debug!(session_id = %self.session_id, path = idx, "replaced dropped session path");
+    }
+
+    /// The routing of the first path of the striping.
+    pub fn primary_routing(&self) -> DestinationRouting {
+        self.routing(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hopr_crypto_random::Randomizable;
+    use hopr_crypto_types::keypairs::{ChainKeypair, Keypair};
+    use hopr_internal_types::prelude::HoprPseudonym;
+    use hopr_network_types::prelude::RoutingOptions;
+    use hopr_primitive_types::prelude::Address;
+
+    use super::*;
+
+    fn routings(count: usize) -> anyhow::Result<Vec<DestinationRouting>> {
+        let addr: Address = (&ChainKeypair::random()).into();
+        (0..count)
+            .map(|i| {
+                let relay: Address = (&ChainKeypair::random()).into();
+                Ok(DestinationRouting::forward_only(
+                    addr,
+                    if i % 2 == 0 {
+                        RoutingOptions::IntermediatePath(vec![relay].try_into()?)
+                    } else {
+                        RoutingOptions::Hops(1_u32.try_into()?)
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn path_striping_should_distribute_packets_in_round_robin() -> anyhow::Result<()> {
+        let routings = routings(3)?;
+        let striping = PathStriping::new(SessionId::new(1, HoprPseudonym::random()), routings.clone(), 5);
+
+        for i in 0..9 {
+            let (idx, routing) = striping.next_path();
+            assert_eq!(i % 3, idx);
+            assert_eq!(routings[idx], routing);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn path_striping_should_drop_path_after_consecutive_failures() -> anyhow::Result<()> {
+        let striping = PathStriping::new(SessionId::new(1, HoprPseudonym::random()), routings(2)?, 2);
+
+        assert!(striping.record_failure(0), "failure must be tolerated");
+        striping.record_success(0);
+        assert!(striping.record_failure(0), "failure must be tolerated");
+        assert_eq!(2, striping.num_active(), "path must not be dropped after a success");
+
+        assert!(striping.record_failure(0), "failure must be tolerated");
+        assert_eq!(1, striping.num_active(), "path must be dropped");
+
+        for _ in 0..4 {
+            assert_eq!(1, striping.next_path().0, "dropped path must not be used");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn path_striping_should_never_drop_the_last_path() -> anyhow::Result<()> {
+        let striping = PathStriping::new(SessionId::new(1, HoprPseudonym::random()), routings(2)?, 1);
+
+        assert!(striping.record_failure(0), "failure must be tolerated");
+        assert_eq!(1, striping.num_active());
+
+        assert!(
+            !striping.record_failure(1),
+            "failure on the last path must not be tolerated"
+        );
+        assert!(
+            !striping.record_failure(1),
+            "failure on the last path must not be tolerated"
+        );
+        assert_eq!(1, striping.num_active(), "last path must not be dropped");
+        assert_eq!(1, striping.next_path().0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn path_striping_should_count_retransmitted_segments_as_failures() -> anyhow::Result<()> {
+        let striping = PathStriping::new(SessionId::new(1, HoprPseudonym::random()), routings(2)?, 2);
+
+        striping.record_sent_segments(0, [SegmentId(1, 0), SegmentId(1, 1)]);
+        striping.record_sent_segments(1, [SegmentId(1, 0)]);
+        assert_eq!(2, striping.num_active(), "path must not be dropped after a single loss");
+
+        striping.record_sent_segments(1, [SegmentId(1, 1)]);
+        assert_eq!(
+            1,
+            striping.num_active(),
+            "path must be dropped after consecutive losses"
+        );
+        assert_eq!(1, striping.next_path().0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn path_striping_should_count_segments_leaving_the_window_as_delivered() -> anyhow::Result<()> {
+        let striping = PathStriping::new(SessionId::new(1, HoprPseudonym::random()), routings(2)?, 2);
+
+        striping.record_sent_segments(0, [SegmentId(1, 0)]);
+        striping.record_sent_segments(1, [SegmentId(1, 0)]);
+        striping.record_sent_segments(0, (0..SENT_SEGMENTS_WINDOW as u32).map(|i| SegmentId(i + 2, 0)));
+        striping.record_sent_segments(0, [SegmentId(1, 0)]);
+
+        assert_eq!(2, striping.num_active(), "path must not be dropped after a delivery");
+
+        Ok(())
+    }
+
+    #[test]
+    fn path_striping_should_replace_dropped_path() -> anyhow::Result<()> {
+        let routings = routings(2)?;
+        let striping = PathStriping::new(SessionId::new(1, HoprPseudonym::random()), routings.clone(), 1);
+        assert!(striping.take_replaceable().is_none(), "no path must be replaceable");
+
+        striping.record_failure(0);
+        assert_eq!(1, striping.num_active());
+
+        let (idx, routing) = striping
+            .take_replaceable()
+            .ok_or_else(|| anyhow::anyhow!("dropped path must be replaceable"))?;
+        assert_eq!(0, idx);
+        let DestinationRouting::Forward {
+            forward_options,
+            path_constraints,
+            ..
+        } = routing
+        else {
+            anyhow::bail!("replacement routing must be a forward routing");
+        };
+        assert_eq!(RoutingOptions::Hops(1_u32.try_into()?), forward_options);
+        let DestinationRouting::Forward {
+            forward_options: RoutingOptions::IntermediatePath(relays),
+            ..
+        } = &routings[0]
+        else {
+            anyhow::bail!("dropped routing must have a fixed path");
+        };
+        assert!(
+            relays
+                .as_ref()
+                .iter()
+                .all(|relay| path_constraints.excluded_relays.contains(relay)),
+            "replacement must not use the relays of the dropped path"
+        );
+        assert!(striping.take_replaceable().is_none(), "path must not be replaced twice");
+
+        let relay: Address = (&ChainKeypair::random()).into();
+        let new_options = RoutingOptions::IntermediatePath(vec![relay].try_into()?);
+        striping.replace(idx, Some(new_options.clone()));
+
+        assert_eq!(2, striping.num_active(), "replaced path must be used again");
+        assert!(
+            matches!(striping.routing(0), DestinationRouting::Forward { forward_options, .. } if forward_options == new_options)
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,186 @@
+use std::{
+    sync::{
+        Arc, OnceLock,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use hopr_network_types::prelude::SessionSocketStats;
+
+use crate::SessionId;
+
+#[cfg(all(feature = "prometheus", not(test)))]
+lazy_static::lazy_static! {
+    static ref METRIC_SESSION_BYTES: hopr_metrics::MultiCounter =
+        hopr_metrics::MultiCounter::new(
+            "hopr_session_bytes",
+            "Number of bytes written to or read from a Session",
+            &["direction"]
+    ).unwrap();
+    static ref METRIC_SURB_BUFFER_ESTIMATE: hopr_metrics::SimpleHistogram =
+        hopr_metrics::SimpleHistogram::new(
+            "hopr_session_surb_buffer_estimate",
+            "Estimated numbers of SURBs available to the Session counterparties",
+            vec![0.0, 100.0, 500.0, 1000.0, 2500.0, 5000.0, 10000.0, 25000.0, 50000.0],
+    ).unwrap();
+}
+
+/// Counters of a single [`Session`](crate::Session).
+///
+/// The counters are shared between the Session and the [`SessionManager`](crate::SessionManager),
+/// so that they can be observed while the Session is in use.
+#[derive(Debug)]
+pub struct SessionStats {
+    session_id: SessionId,
+    created_at: Instant,
+    last_activity_ms: AtomicU64,
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+    surb_balancing: AtomicBool,
+    surb_buffer_estimate: AtomicU64,
+    surbs_consumed: AtomicU64,
+    socket: OnceLock<Arc<SessionSocketStats>>,
+}
+
+impl SessionStats {
+    pub(crate) fn new(session_id: SessionId) -> Self {
+        Self {
+            session_id,
+            created_at: Instant::now(),
+            last_activity_ms: AtomicU64::new(0),
+            bytes_sent: AtomicU64::new(0),
+            bytes_received: AtomicU64::new(0),
+            surb_balancing: AtomicBool::new(false),
+            surb_buffer_estimate: AtomicU64::new(0),
+            surbs_consumed: AtomicU64::new(0),
+            socket: OnceLock::new(),
+        }
+    }
+
+    pub(crate) fn attach_socket(&self, socket: Arc<SessionSocketStats>) {
+        let _ = self.socket.set(socket);
+    }
+
+    pub(crate) fn socket(&self) -> Option<&Arc<SessionSocketStats>> {
+        self.socket.get()
+    }
+
+    fn touch(&self) {
+        self.last_activity_ms
+            .store(self.created_at.elapsed().as_millis() as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn bytes_sent(&self, count: usize) {
+        self.bytes_sent.fetch_add(count as u64, Ordering::Relaxed);
+        self.touch();
+
+        #[cfg(all(feature = "prometheus", not(test)))]
+        METRIC_SESSION_BYTES.increment_by(&["sent"], count as u64);
+    }
+
+    pub(crate) fn bytes_received(&self, count: usize) {
+        self.bytes_received.fetch_add(count as u64, Ordering::Relaxed);
+        self.touch();
+
+        #[cfg(all(feature = "prometheus", not(test)))]
+        METRIC_SESSION_BYTES.increment_by(&["received"], count as u64);
+    }
+
+    /// Records the latest estimates of the [SURB balancer](crate::SurbBalancerConfig).
+    pub(crate) fn surb_estimates(&self, buffer_estimate: u64, consumed: u64) {
+        self.surb_balancing.store(true, Ordering::Relaxed);
+        self.surb_buffer_estimate.store(buffer_estimate, Ordering::Relaxed);
+        self.surbs_consumed.store(consumed, Ordering::Relaxed);
+
+        #[cfg(all(feature = "prometheus", not(test)))]
+        METRIC_SURB_BUFFER_ESTIMATE.observe(buffer_estimate as f64);
+    }
+
+    /// Takes a consistent-enough snapshot of all the counters.
+    pub fn snapshot(&self) -> SessionStatsSnapshot {
+        let age = self.created_at.elapsed();
+        let surb_balancing = self.surb_balancing.load(Ordering::Relaxed);
+        let socket = self.socket.get();
+
+        SessionStatsSnapshot {
+            session_id: self.session_id,
+            age,
+            idle: age.saturating_sub(Duration::from_millis(self.last_activity_ms.load(Ordering::Relaxed))),
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+            bytes_received: self.bytes_received.load(Ordering::Relaxed),
+            frames_sent: socket.map(|s| s.frames_sent()).unwrap_or_default(),
+            frames_received: socket.map(|s| s.frames_received()).unwrap_or_default(),
+            frames_skipped: socket.map(|s| s.frames_skipped()).unwrap_or_default(),
+            segments_retransmitted: socket.map(|s| s.segments_retransmitted()).unwrap_or_default(),
+            retransmission_requests: socket.map(|s| s.retransmission_requests()).unwrap_or_default(),
+            rtt: socket.and_then(|s| s.smoothed_rtt()),
+            surb_buffer_estimate: surb_balancing.then(|| self.surb_buffer_estimate.load(Ordering::Relaxed)),
+            surbs_consumed: surb_balancing.then(|| self.surbs_consumed.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+/// Point-in-time values of [`SessionStats`].
+///
+/// The frame-related counters are always zero for Sessions that do not use the Session protocol
+/// (that is, Sessions without any [capabilities](crate::Capability)).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionStatsSnapshot {
+    /// ID of the Session.
+    pub session_id: SessionId,
+    /// Time since the Session has been created.
+    pub age: Duration,
+    /// Time since data were last written to or read from the Session.
+    pub idle: Duration,
+    /// Number of bytes written to the Session.
+    pub bytes_sent: u64,
+    /// Number of bytes read from the Session.
+    pub bytes_received: u64,
+    /// Number of frames sent.
+    pub frames_sent: u64,
+    /// Number of frames received.
+    pub frames_received: u64,
+    /// Number of incoming frames that were discarded or evicted before they were complete.
+    pub frames_skipped: u64,
+    /// Number of segments retransmitted to the counterparty.
+    pub segments_retransmitted: u64,
+    /// Number of requests for missing segments sent to the counterparty.
+    pub retransmission_requests: u64,
+    /// Smoothed round-trip time estimate, if any frame has been acknowledged yet.
+    pub rtt: Option<Duration>,
+    /// Estimated number of SURBs available to the counterparty, if SURB balancing is enabled.
+    pub surb_buffer_estimate: Option<u64>,
+    /// Estimated number of SURBs consumed by the counterparty, if SURB balancing is enabled.
+    pub surbs_consumed: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use hopr_crypto_random::Randomizable;
+    use hopr_internal_types::prelude::HoprPseudonym;
+
+    use super::*;
+
+    #[test]
+    fn session_stats_snapshot_should_reflect_the_counters() {
+        let stats = SessionStats::new(SessionId::new(1234, HoprPseudonym::random()));
+
+        let snapshot = stats.snapshot();
+        assert_eq!(0, snapshot.bytes_sent);
+        assert_eq!(None, snapshot.surb_buffer_estimate);
+        assert_eq!(None, snapshot.rtt);
+
+        stats.bytes_sent(100);
+        stats.bytes_received(50);
+        stats.bytes_received(25);
+        stats.surb_estimates(1000, 20);
+
+        let snapshot = stats.snapshot();
+        assert_eq!(100, snapshot.bytes_sent);
+        assert_eq!(75, snapshot.bytes_received);
+        assert_eq!(Some(1000), snapshot.surb_buffer_estimate);
+        assert_eq!(Some(20), snapshot.surbs_consumed);
+        assert!(snapshot.idle <= snapshot.age);
+    }
+}
@@ -11,4 +11,16 @@ pub trait SendMsg {
         data: ApplicationData,
         destination: DestinationRouting,
     ) -> std::result::Result<(), TransportSessionError>;
+
+    /// Resolves at most `count` forward routings to the destination of the given `routing`,
+    /// such that no two of them share any relays.
+    ///
+    /// The default implementation does not support multiple routings and returns just the given one.
+    async fn resolve_disjoint_routings(
+        &self,
+        routing: DestinationRouting,
+        _count: usize,
+    ) -> std::result::Result<Vec<DestinationRouting>, TransportSessionError> {
+        Ok(vec![routing])
+    }
 }
@@ -4,7 +4,7 @@ use std::{
     hash::{Hash, Hasher},
     io::{Error, ErrorKind},
     pin::Pin,
-    sync::Arc,
+    sync::{Arc, Weak},
     task::{Context, Poll},
     time::Duration,
 };
@@ -17,12 +17,19 @@ use hopr_internal_types::{
 };
 use hopr_network_types::{
     prelude::{DestinationRouting, SealedHost},
-    session::state::{SessionConfig, SessionSocket},
+    session::{
+        SegmentId,
+        protocol::SessionMessageIter,
+        state::{SessionConfig, SessionSocket},
+    },
 };
 use hopr_primitive_types::prelude::BytesRepresentable;
-use tracing::{debug, error};
+use tracing::{debug, error, warn};
 
-use crate::{Capability, errors::TransportSessionError, traits::SendMsg};
+use crate::{
+    Capability, MultipathConfig, errors::TransportSessionError, multipath::PathStriping, stats::SessionStats,
+    traits::SendMsg,
+};
 
 #[cfg(all(feature = "prometheus", not(test)))]
 lazy_static::lazy_static! {
@@ -250,6 +257,7 @@ pub struct Session {
     inner: Pin<Box<dyn AsyncReadWrite>>,
     routing: DestinationRouting,
     capabilities: HashSet<Capability>,
+    stats: Arc<SessionStats>,
     on_close: Option<Box<dyn FnOnce(SessionId) + Send + Sync>>,
 }
 
@@ -262,7 +270,43 @@ impl Session {
         rx: Pin<Box<dyn futures::Stream<Item = Box<[u8]>> + Send + Sync>>,
         on_close: Option<Box<dyn FnOnce(SessionId) + Send + Sync>>,
     ) -> Self {
-        let inner_session = InnerSession::new(id, routing.clone(), tx, rx);
+        Self::with_striping(id, PathStriping::single(id, routing), capabilities, tx, rx, on_close)
+    }
+
+    /// Creates a Session that stripes the outgoing data across all the given `routings`.
+    ///
+    /// The `routings` must not be empty, and the first one is considered the primary routing
+    /// of the Session.
+    pub fn new_multipath(
+        id: SessionId,
+        routings: Vec<DestinationRouting>,
+        cfg: MultipathConfig,
+        capabilities: HashSet<Capability>,
+        tx: Arc<dyn SendMsg + Send + Sync>,
+        rx: Pin<Box<dyn futures::Stream<Item = Box<[u8]>> + Send + Sync>>,
+        on_close: Option<Box<dyn FnOnce(SessionId) + Send + Sync>>,
+    ) -> Self {
+        Self::with_striping(
+            id,
+            PathStriping::new(id, routings, cfg.max_consecutive_failures),
+            capabilities,
+            tx,
+            rx,
+            on_close,
+        )
+    }
+
+    fn with_striping(
+        id: SessionId,
+        paths: PathStriping,
+        capabilities: HashSet<Capability>,
+        tx: Arc<dyn SendMsg + Send + Sync>,
+        rx: Pin<Box<dyn futures::Stream<Item = Box<[u8]>> + Send + Sync>>,
+        on_close: Option<Box<dyn FnOnce(SessionId) + Send + Sync>>,
+    ) -> Self {
+        let routing = paths.primary_routing();
+        let inner_session = InnerSession::with_striping(id, Arc::new(paths), tx, rx);
+        let stats = Arc::new(SessionStats::new(id));
 
         // If we request any capability, we need to use Session protocol
         if !capabilities.is_empty() {
@@ -294,15 +338,15 @@ impl Session {
                 "opening new session socket"
             );
 
+            let socket = SessionSocket::<USABLE_PAYLOAD_CAPACITY_FOR_SESSION>::new(id, inner_session, cfg);
+            stats.attach_socket(socket.state().stats().clone());
+
             Self {
                 id,
-                inner: Box::pin(SessionSocket::<USABLE_PAYLOAD_CAPACITY_FOR_SESSION>::new(
-                    id,
-                    inner_session,
-                    cfg,
-                )),
+                inner: Box::pin(socket),
                 routing,
                 capabilities,
+                stats,
                 on_close,
             }
         } else {
@@ -312,6 +356,7 @@ impl Session {
                 inner: Box::pin(inner_session),
                 routing,
                 capabilities,
+                stats,
                 on_close,
             }
         }
@@ -323,6 +368,8 @@ impl Session {
     }
 
     /// Routing options used to deliver data.
+    ///
+    /// For multipath Sessions, this is the primary routing.
     pub fn routing(&self) -> &DestinationRouting {
         &self.routing
     }
@@ -331,6 +378,21 @@ impl Session {
     pub fn capabilities(&self) -> &HashSet<Capability> {
         &self.capabilities
     }
+
+    /// Counters of this Session.
+    pub fn stats(&self) -> &Arc<SessionStats> {
+        &self.stats
+    }
+
+    /// Replaces the counters of this Session with the given ones, which are shared
+    /// with the [`SessionManager`](crate::SessionManager).
+    pub(crate) fn with_stats(mut self, stats: Arc<SessionStats>) -> Self {
+        if let Some(socket) = self.stats.socket() {
+            stats.attach_socket(socket.clone());
+        }
+        self.stats = stats;
+        self
+    }
 }
 
 impl std::fmt::Debug for Session {
@@ -346,7 +408,11 @@ impl futures::AsyncRead for Session {
     fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<std::io::Result<usize>> {
         let inner = self.inner.as_mut();
         pin_mut!(inner);
-        inner.poll_read(cx, buf)
+        let res = inner.poll_read(cx, buf);
+        if let Poll::Ready(Ok(read)) = &res {
+            self.stats.bytes_received(*read);
+        }
+        res
     }
 }
 
@@ -354,7 +420,11 @@ impl futures::AsyncWrite for Session {
     fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
         let inner = &mut self.inner;
         pin_mut!(inner);
-        inner.poll_write(cx, buf)
+        let res = inner.poll_write(cx, buf);
+        if let Poll::Ready(Ok(written)) = &res {
+            self.stats.bytes_sent(*written);
+        }
+        res
     }
 
     fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
@@ -413,7 +483,7 @@ type FuturesBuffer = futures::stream::FuturesUnordered<
 >;
 struct InnerSession {
     id: SessionId,
-    routing: DestinationRouting,
+    paths: Arc<PathStriping>,
     rx: Pin<Box<dyn futures::Stream<Item = Box<[u8]>> + Send + Sync>>,
     tx: Arc<dyn SendMsg + Send + Sync>,
     tx_bytes: usize,
@@ -424,15 +494,25 @@ struct InnerSession {
 }
 
 impl InnerSession {
+    #[cfg(test)]
     pub fn new(
         id: SessionId,
         routing: DestinationRouting,
         tx: Arc<dyn SendMsg + Send + Sync>,
         rx: Pin<Box<dyn futures::Stream<Item = Box<[u8]>> + Send + Sync>>,
+    ) -> Self {
+        Self::with_striping(id, Arc::new(PathStriping::single(id, routing)), tx, rx)
+    }
+
+    pub fn with_striping(
+        id: SessionId,
+        paths: Arc<PathStriping>,
+        tx: Arc<dyn SendMsg + Send + Sync>,
+        rx: Pin<Box<dyn futures::Stream<Item = Box<[u8]>> + Send + Sync>>,
     ) -> Self {
         Self {
             id,
-            routing,
+            paths,
             rx,
             tx,
             tx_bytes: 0,
@@ -444,6 +524,47 @@ impl InnerSession {
     }
 }
 
+/// Delay after a failed lookup of a replacement of a dropped Session path, before it is looked up again.
+const PATH_REPLACEMENT_RETRY_DELAY: Duration = Duration::from_secs(10);
+
+/// Identifiers of all the Session protocol segments contained in the given chunk of data.
+fn segment_ids(chunk: &[u8]) -> impl Iterator<Item = SegmentId> + '_ {
+    SessionMessageIter::<USABLE_PAYLOAD_CAPACITY_FOR_SESSION>::from(chunk)
+        .map_while(Result::ok)
+        .filter_map(|message| message.try_as_segment().map(|segment| segment.id()))
+}
+
+/// Looks up a replacement of the dropped path with the given index in the background,
+/// using the given `routing` which excludes the relays of all the Session paths.
+fn replace_path(
+    session_id: SessionId,
+    paths: Weak<PathStriping>,
+    tx: Arc<dyn SendMsg + Send + Sync>,
+    path: usize,
+    routing: DestinationRouting,
+) {
+    hopr_async_runtime::prelude::spawn(async move {
+        let replacement = match tx.resolve_disjoint_routings(routing, 1).await {
+            Ok(routings) => routings.into_iter().find_map(|routing| match routing {
+                DestinationRouting::Forward { forward_options, .. } => Some(forward_options),
+                DestinationRouting::Return(_) => None,
+            }),
+            Err(error) => {
+                warn!(%session_id, path, %error, "failed to find a replacement of a dropped session path");
+                None
+            }
+        };
+
+        if replacement.is_none() {
+            hopr_async_runtime::prelude::sleep(PATH_REPLACEMENT_RETRY_DELAY).await;
+        }
+
+        if let Some(paths) = paths.upgrade() {
+            paths.replace(path, replacement);
+        }
+    });
+}
+
 impl futures::AsyncWrite for InnerSession {
     fn poll_write(
         mut self: std::pin::Pin<&mut Self>,
@@ -496,14 +617,34 @@ impl futures::AsyncWrite for InnerSession {
 
             let payload = ApplicationData::new(tag, &buf[start..end]);
             let sender = self.tx.clone();
-            let routing = self.routing.clone();
+            let paths = self.paths.clone();
+            let session_id = self.id;
+            let (path, routing) = paths.next_path();
+            if paths.is_multipath() {
+                paths.record_sent_segments(path, segment_ids(&buf[start..end]));
+            }
 
-            self.tx_buffer
-                .push(Box::pin(async move { sender.send_message(payload, routing).await }));
+            self.tx_buffer.push(Box::pin(async move {
+                match sender.send_message(payload, routing).await {
+                    // The delivery over the path is confirmed only if the segments do not need to be retransmitted
+                    Ok(()) => Ok(()),
+                    Err(TransportSessionError::OutOfSurbs) => Err(TransportSessionError::OutOfSurbs),
+                    // Failure on one of multiple paths is not fatal, the chunk is considered lost
+                    Err(error) if paths.record_failure(path) => {
+                        warn!(%session_id, path, %error, "failed to send the message chunk over a session path");
+                        Ok(())
+                    }
+                    Err(error) => Err(error),
+                }
+            }));
 
             self.tx_bytes += end - start;
         }
 
+        if let Some((path, routing)) = self.paths.take_replaceable() {
+            replace_path(self.id, Arc::downgrade(&self.paths), self.tx.clone(), path, routing);
+        }
+
         loop {
             match self.tx_buffer.poll_next_unpin(cx) {
                 Poll::Ready(Some(Ok(_))) => {
@@ -844,4 +985,84 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn multipath_session_should_stripe_data_across_paths() -> anyhow::Result<()> {
+        const TO_SEND: usize = USABLE_PAYLOAD_CAPACITY_FOR_SESSION * 3;
+
+        let addr: Address = (&ChainKeypair::random()).into();
+        let id = SessionId::new(1, HoprPseudonym::random());
+        let (_tx, rx) = futures::channel::mpsc::unbounded();
+        let mut mock = MockSendMsg::new();
+
+        let routings = vec![
+            DestinationRouting::forward_only(addr, RoutingOptions::IntermediatePath(vec![addr].try_into()?)),
+            DestinationRouting::forward_only(addr, RoutingOptions::Hops(1_u32.try_into()?)),
+        ];
+
+        let used_routings = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let used_routings_clone = used_routings.clone();
+        mock.expect_send_message().times(3).returning(move |_, routing| {
+            used_routings_clone.lock().unwrap().push(routing);
+            Ok(())
+        });
+
+        let mut session = InnerSession::with_striping(
+            id,
+            Arc::new(PathStriping::new(id, routings.clone(), 5)),
+            Arc::new(mock),
+            Box::pin(rx),
+        );
+
+        let data = hopr_crypto_random::random_bytes::<TO_SEND>();
+        let bytes_written = session.write(data.as_ref()).await?;
+        assert_eq!(bytes_written, TO_SEND);
+
+        let used_routings = used_routings.lock().unwrap().clone();
+        assert_eq!(2, used_routings.iter().filter(|r| *r == &routings[0]).count());
+        assert_eq!(1, used_routings.iter().filter(|r| *r == &routings[1]).count());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn multipath_session_should_tolerate_failure_on_one_of_the_paths() -> anyhow::Result<()> {
+        const TO_SEND: usize = USABLE_PAYLOAD_CAPACITY_FOR_SESSION * 2;
+
+        let addr: Address = (&ChainKeypair::random()).into();
+        let id = SessionId::new(1, HoprPseudonym::random());
+        let (_tx, rx) = futures::channel::mpsc::unbounded();
+        let mut mock = MockSendMsg::new();
+
+        let failing_routing =
+            DestinationRouting::forward_only(addr, RoutingOptions::IntermediatePath(vec![addr].try_into()?));
+        let routings = vec![
+            failing_routing.clone(),
+            DestinationRouting::forward_only(addr, RoutingOptions::Hops(1_u32.try_into()?)),
+        ];
+
+        mock.expect_send_message().times(2).returning(move |_, routing| {
+            if routing == failing_routing {
+                Err(TransportSessionError::Path)
+            } else {
+                Ok(())
+            }
+        });
+        mock.expect_resolve_disjoint_routings()
+            .returning(|routing, _| Ok(vec![routing]));
+
+        let mut session = InnerSession::with_striping(
+            id,
+            Arc::new(PathStriping::new(id, routings, 1)),
+            Arc::new(mock),
+            Box::pin(rx),
+        );
+
+        let data = hopr_crypto_random::random_bytes::<TO_SEND>();
+        let bytes_written = session.write(data.as_ref()).await?;
+        assert_eq!(bytes_written, TO_SEND);
+        assert_eq!(1, session.paths.num_active(), "failing path must be dropped");
+
+        Ok(())
+    }
 }